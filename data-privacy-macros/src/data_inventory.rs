@@ -0,0 +1,104 @@
+use crate::{SynResult, data_privacy_path};
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::{ToTokens, quote};
+use syn::{Data, DeriveInput, parse2};
+
+pub fn data_inventory_impl(item: TokenStream) -> SynResult<TokenStream> {
+    let input: DeriveInput = parse2(item)?;
+
+    let Data::Struct(struct_data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input,
+            "DataInventory can only be derived for structs",
+        ));
+    };
+
+    let data_privacy_path = data_privacy_path(&input)?;
+    let type_name = &input.ident;
+    let type_name_str = type_name.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let type_params: Vec<_> = input
+        .generics
+        .type_params()
+        .map(|param| &param.ident)
+        .collect();
+
+    let fields = struct_data.fields.iter().enumerate().map(|(index, field)| {
+        let name = field
+            .ident
+            .as_ref()
+            .map_or_else(|| index.to_string(), ToString::to_string);
+        let ty = &field.ty;
+
+        // the probe can't see through type parameters, so such fields can't be told apart from plain ones
+        if mentions_any(ty.to_token_stream(), &type_params) {
+            return quote!(#data_privacy_path::FieldInventory::unknown(#name));
+        }
+
+        quote! {
+            #data_privacy_path::FieldInventory::new(#name, {
+                #[allow(unused_imports, reason = "Only one of the probe traits applies to a given field")]
+                use #data_privacy_path::__private::{ProbeClassified as _, ProbePlain as _};
+                (&&#data_privacy_path::__private::InventoryProbe::<#ty>::new()).probe_data_class()
+            })
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics #data_privacy_path::DataInventory for #type_name #ty_generics #where_clause {
+            fn data_inventory() -> #data_privacy_path::TypeInventory {
+                #data_privacy_path::TypeInventory::new(
+                    #type_name_str,
+                    ::std::vec::Vec::from([#(#fields),*]),
+                )
+            }
+        }
+    })
+}
+
+/// Returns whether the tokens mention any of the given identifiers.
+fn mentions_any(tokens: TokenStream, idents: &[&Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => idents.contains(&&ident),
+        TokenTree::Group(group) => mentions_any(group.stream(), idents),
+        TokenTree::Punct(_) | TokenTree::Literal(_) => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+
+    #[test]
+    fn test_data_inventory_impl_enum() {
+        let input = quote! {
+            enum MyEnum {
+                VariantOne,
+            }
+        };
+
+        let err = data_inventory_impl(input).unwrap_err();
+        assert_eq!(
+            "DataInventory can only be derived for structs",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_data_inventory_impl_success() {
+        let input = quote! {
+            struct Employee<T> {
+                name: Sensitive<String>,
+                age: u32,
+                extra: Option<T>,
+            }
+        };
+
+        let result = data_inventory_impl(input);
+        let result_file = syn::parse_file(&result.unwrap().to_string()).unwrap();
+        let pretty = prettyplease::unparse(&result_file);
+
+        assert_snapshot!(pretty);
+    }
+}
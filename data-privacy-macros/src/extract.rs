@@ -0,0 +1,356 @@
+use crate::{SynResult, data_privacy_path};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Field, Fields, Index, Path, parse2};
+
+/// Returns the data class named by a field's `#[classified(Path)]` attribute, if any.
+pub fn classified_attr(field: &Field) -> SynResult<Option<Path>> {
+    let mut class = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("classified"))
+    {
+        if class.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "duplicate `classified` attribute",
+            ));
+        }
+
+        class = Some(attr.parse_args::<Path>()?);
+    }
+
+    Ok(class)
+}
+
+/// Indicates whether a field is excluded from extraction by an `#[extract(skip)]` attribute.
+fn skip_attr(field: &Field) -> SynResult<bool> {
+    let mut skip = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("extract"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `skip`"))
+            }
+        })?;
+    }
+
+    Ok(skip)
+}
+
+/// Generates the statements extracting a set of fields, laid out like the output of `#[derive(Debug)]`.
+///
+/// The accessor returns, for each field, an expression of the field's value and an expression of a
+/// reference to it.
+fn fields_statements(
+    data_privacy_path: &TokenStream,
+    name: &str,
+    fields: &Fields,
+    access: impl Fn(usize, &Field) -> (TokenStream, TokenStream),
+) -> SynResult<Vec<TokenStream>> {
+    let (open, close) = match fields {
+        Fields::Named(_) => (" { ", " }"),
+        Fields::Unnamed(_) => ("(", ")"),
+        Fields::Unit => ("", ""),
+    };
+
+    let mut statements = Vec::new();
+    let mut extracted = 0;
+    for (index, field) in fields.iter().enumerate() {
+        if skip_attr(field)? {
+            continue;
+        }
+
+        let (value, reference) = access(index, field);
+
+        let mut plain = if extracted == 0 {
+            format!("{name}{open}")
+        } else {
+            ", ".to_string()
+        };
+
+        let field_name = field
+            .ident
+            .as_ref()
+            .map_or_else(|| index.to_string(), ToString::to_string);
+
+        if field.ident.is_some() {
+            plain.push_str(&field_name);
+            plain.push_str(": ");
+        }
+
+        statements.push(quote! {
+            extractor.write_plain(#plain);
+        });
+
+        let extract = classified_attr(field)?.map_or_else(
+            || {
+                quote! {
+                    #data_privacy_path::Extract::extract(#reference, &mut #data_privacy_path::FieldExtractor::new::<Self>(extractor, #field_name));
+                }
+            },
+            |class| {
+                quote! {
+                    #data_privacy_path::__private::with_formatted(::core::format_args!("{}", #value), |s| {
+                        extractor.write_field(&#data_privacy_path::FieldPath::new::<Self>(#field_name), &#class.data_class(), s);
+                    });
+                }
+            },
+        );
+        statements.push(extract);
+        extracted += 1;
+    }
+
+    let end = if statements.is_empty() {
+        name.to_string()
+    } else {
+        close.to_string()
+    };
+
+    statements.push(quote! {
+        extractor.write_plain(#end);
+    });
+
+    Ok(statements)
+}
+
+pub fn extract_impl(item: TokenStream) -> SynResult<TokenStream> {
+    let input: DeriveInput = parse2(item)?;
+
+    let data_privacy_path = data_privacy_path(&input)?;
+    let type_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(struct_data) => {
+            let statements = fields_statements(
+                &data_privacy_path,
+                &type_name.to_string(),
+                &struct_data.fields,
+                |index, field| {
+                    let member = field.ident.as_ref().map_or_else(
+                        || {
+                            let index = Index::from(index);
+                            quote!(#index)
+                        },
+                        |ident| quote!(#ident),
+                    );
+                    (quote!(self.#member), quote!(&self.#member))
+                },
+            )?;
+
+            quote! {
+                #(#statements)*
+            }
+        }
+        Data::Enum(enum_data) => {
+            let mut arms = Vec::new();
+            for variant in &enum_data.variants {
+                let variant_name = &variant.ident;
+                let bindings: Vec<_> = (0..variant.fields.len())
+                    .map(|index| format_ident!("__field{index}"))
+                    .collect();
+
+                // skipped fields aren't bound, so they don't trigger unused variable warnings
+                let patterns = variant
+                    .fields
+                    .iter()
+                    .zip(&bindings)
+                    .map(|(field, binding)| {
+                        Ok(if skip_attr(field)? {
+                            quote!(_)
+                        } else {
+                            quote!(#binding)
+                        })
+                    })
+                    .collect::<SynResult<Vec<_>>>()?;
+
+                let pattern = match &variant.fields {
+                    Fields::Named(fields) => {
+                        let names = fields.named.iter().map(|field| &field.ident);
+                        quote!(Self::#variant_name { #(#names: #patterns),* })
+                    }
+                    Fields::Unnamed(_) => quote!(Self::#variant_name(#(#patterns),*)),
+                    Fields::Unit => quote!(Self::#variant_name),
+                };
+
+                let statements = fields_statements(
+                    &data_privacy_path,
+                    &variant_name.to_string(),
+                    &variant.fields,
+                    |index, _| {
+                        let binding = &bindings[index];
+                        (quote!(#binding), quote!(#binding))
+                    },
+                )?;
+
+                arms.push(quote! {
+                    #pattern => {
+                        #(#statements)*
+                    }
+                });
+            }
+
+            if arms.is_empty() {
+                quote! {
+                    match *self {}
+                }
+            } else {
+                quote! {
+                    match self {
+                        #(#arms)*
+                    }
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input,
+                "Extract can only be derived for structs and enums",
+            ));
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics #data_privacy_path::Extract for #type_name #ty_generics #where_clause {
+            fn extract(&self, extractor: &mut dyn #data_privacy_path::Extractor) {
+                #body
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+
+    fn pretty(input: TokenStream) -> String {
+        let result = extract_impl(input).unwrap();
+        prettyplease::unparse(&syn::parse_file(&result.to_string()).unwrap())
+    }
+
+    #[test]
+    fn test_extract_impl_union() {
+        let input = quote! {
+            union MyUnion {
+                a: u32,
+            }
+        };
+
+        let err = extract_impl(input).unwrap_err();
+        assert_eq!(
+            "Extract can only be derived for structs and enums",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_extract_impl_enum() {
+        let input = quote! {
+            enum Contact {
+                Email(#[classified(ExampleTaxonomy::Pii)] String),
+                Phone { country: u16, number: Sensitive<String> },
+                Unknown,
+            }
+        };
+
+        assert_snapshot!(pretty(input));
+    }
+
+    #[test]
+    fn test_extract_impl_duplicate_attribute() {
+        let input = quote! {
+            struct Employee {
+                #[classified(CoreTaxonomy::Sensitive)]
+                #[classified(CoreTaxonomy::Insensitive)]
+                name: String,
+            }
+        };
+
+        let err = extract_impl(input).unwrap_err();
+        assert_eq!("duplicate `classified` attribute", err.to_string());
+    }
+
+    #[test]
+    fn test_extract_impl_invalid_attribute() {
+        let input = quote! {
+            struct Employee {
+                #[classified("sensitive")]
+                name: String,
+            }
+        };
+
+        assert!(extract_impl(input).is_err());
+    }
+
+    #[test]
+    fn test_extract_impl_skip() {
+        let output = pretty(quote! {
+            struct Employee {
+                #[extract(skip)]
+                id: u64,
+                #[classified(CoreTaxonomy::Sensitive)]
+                name: String,
+                #[extract(skip)]
+                age: u32,
+            }
+        });
+        assert!(output.contains("\"Employee { name: \""));
+        assert!(!output.contains("self.id"));
+        assert!(!output.contains("self.age"));
+
+        let output = pretty(quote! {
+            enum Contact {
+                Phone { #[extract(skip)] country: u16, number: Sensitive<String> },
+            }
+        });
+        assert!(output.contains("Self::Phone { country: _, number: __field1 }"));
+        assert!(output.contains("\"Phone { number: \""));
+
+        let err = extract_impl(quote! {
+            struct Employee {
+                #[extract(hide)]
+                id: u64,
+            }
+        })
+        .unwrap_err();
+        assert_eq!("expected `skip`", err.to_string());
+    }
+
+    #[test]
+    fn test_extract_impl_tuple_and_unit() {
+        let output = pretty(quote! {
+            struct Pair(#[classified(CoreTaxonomy::Sensitive)] String, u32);
+        });
+        assert!(output.contains("\"Pair(\""));
+        assert!(output.contains("self.0"));
+        assert!(output.contains("self.1"));
+
+        let output = pretty(quote! {
+            struct Marker;
+        });
+        assert!(output.contains("extractor.write_plain(\"Marker\")"));
+    }
+
+    #[test]
+    fn test_extract_impl_success() {
+        let input = quote! {
+            struct Employee {
+                #[classified(ExampleTaxonomy::Pii)]
+                email: String,
+                name: Sensitive<String>,
+                age: u32,
+            }
+        };
+
+        assert_snapshot!(pretty(input));
+    }
+}
@@ -1,16 +1,91 @@
 //! Exposes a macro to generate data taxonomies.
 
 use proc_macro2::{Ident, TokenStream};
-use quote::quote;
+use quote::{quote, quote_spanned};
 use syn::parse::Parse;
 use syn::spanned::Spanned;
 use syn::{Data, DeriveInput, Fields, parse2};
 
+mod data_inventory;
+mod extract;
+mod redacted_debug;
+
 type SynResult<T> = Result<T, syn::Error>;
 
+/// Which serde traits are implemented by the generated containers.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SerdeMode {
+    None,
+    Serialize,
+    Deserialize,
+    Both,
+    Strict,
+}
+
+impl SerdeMode {
+    const fn serialize(self) -> bool {
+        matches!(self, Self::Serialize | Self::Both | Self::Strict)
+    }
+
+    const fn deserialize(self) -> bool {
+        matches!(self, Self::Deserialize | Self::Both | Self::Strict)
+    }
+}
+
+impl Parse for SerdeMode {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::LitBool) {
+            return Ok(if input.parse::<syn::LitBool>()?.value {
+                Self::Both
+            } else {
+                Self::None
+            });
+        }
+
+        let mode = input.parse::<syn::LitStr>().map_err(|e| {
+            syn::Error::new(
+                e.span(),
+                "expected `true`, `false`, `\"serialize\"`, `\"deserialize\"`, or `\"strict\"`",
+            )
+        })?;
+
+        match mode.value().as_str() {
+            "serialize" => Ok(Self::Serialize),
+            "deserialize" => Ok(Self::Deserialize),
+            "strict" => Ok(Self::Strict),
+            _ => Err(syn::Error::new(
+                mode.span(),
+                "expected `true`, `false`, `\"serialize\"`, `\"deserialize\"`, or `\"strict\"`",
+            )),
+        }
+    }
+}
+
+/// Parses a `cfg!(predicate)` argument value, if there is one, into the attribute which applies the
+/// predicate to the generated items.
+fn parse_cfg(input: syn::parse::ParseStream) -> syn::Result<Option<TokenStream>> {
+    if !(input.peek(syn::Ident) && input.peek2(syn::token::Not)) {
+        return Ok(None);
+    }
+
+    let mac = input.parse::<syn::Macro>()?;
+    if !mac.path.is_ident("cfg") {
+        return Err(syn::Error::new_spanned(&mac.path, "expected `cfg!`"));
+    }
+
+    let predicate = mac.tokens;
+    Ok(Some(quote!(#[cfg(#predicate)])))
+}
+
 struct MacroArgs {
     taxonomy_name: Ident,
-    generate_serde: bool,
+    serde_mode: SerdeMode,
+    serde_cfg: Option<TokenStream>,
+    zeroize: bool,
+    defmt: bool,
+    defmt_cfg: Option<TokenStream>,
+    generic: bool,
+    extends: Vec<syn::Path>,
 }
 
 impl MacroArgs {
@@ -30,26 +105,280 @@ impl Parse for MacroArgs {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let taxonomy_name: Ident = input.parse()?;
 
-        let generate_serde = if input.peek(syn::token::Comma) {
+        let mut serde_mode = SerdeMode::Both;
+        let mut serde_cfg = None;
+        let mut zeroize = false;
+        let mut defmt = false;
+        let mut defmt_cfg = None;
+        let mut generic = None;
+        let mut extends = Vec::new();
+
+        let mut seen = std::collections::HashSet::new();
+        while input.peek(syn::token::Comma) {
             _ = input.parse::<syn::token::Comma>()?;
             let ident = input.parse::<Ident>()?;
-            if ident != "serde" {
-                return Err(syn::Error::new(input.span(), "expected `serde`"));
+            if ident == "serde" {
+                _ = input.parse::<syn::token::Eq>()?;
+                serde_cfg = parse_cfg(input)?;
+                if serde_cfg.is_none() {
+                    serde_mode = input.parse::<SerdeMode>()?;
+                }
+            } else if ident == "zeroize" {
+                _ = input.parse::<syn::token::Eq>()?;
+                zeroize = input.parse::<syn::LitBool>()?.value;
+            } else if ident == "defmt" {
+                _ = input.parse::<syn::token::Eq>()?;
+                defmt_cfg = parse_cfg(input)?;
+                defmt = defmt_cfg.is_some() || input.parse::<syn::LitBool>()?.value;
+            } else if ident == "generic" {
+                _ = input.parse::<syn::token::Eq>()?;
+                generic = input.parse::<syn::LitBool>()?.value.then(|| ident.clone());
+            } else if ident == "extends" {
+                let content;
+                _ = syn::parenthesized!(content in input);
+                extends.extend(
+                    content.parse_terminated(syn::Path::parse_mod_style, syn::token::Comma)?,
+                );
+            } else {
+                return Err(syn::Error::new_spanned(
+                    &ident,
+                    "expected `serde`, `zeroize`, `defmt`, `generic`, or `extends`",
+                ));
             }
 
-            _ = input.parse::<syn::token::Eq>()?;
-            input.parse::<syn::LitBool>()?.value
-        } else {
-            true
-        };
+            if !seen.insert(ident.to_string()) {
+                return Err(syn::Error::new_spanned(
+                    &ident,
+                    format!("duplicate `{ident}` argument"),
+                ));
+            }
+        }
+
+        // generic containers are implemented once in the data-privacy crate, so they can't be customized
+        if let Some(generic) = &generic {
+            let conflict = if zeroize {
+                Some("`generic` can't be combined with `zeroize`")
+            } else if defmt {
+                Some(
+                    "`generic` can't be combined with `defmt`, generic containers implement `defmt::Format` whenever the `defmt` feature is enabled",
+                )
+            } else if serde_mode != SerdeMode::Both || serde_cfg.is_some() {
+                Some(
+                    "`generic` can't be combined with a `serde` mode, generic containers implement serde traits whenever the `serde` feature is enabled",
+                )
+            } else {
+                None
+            };
+
+            if let Some(message) = conflict {
+                return Err(syn::Error::new_spanned(generic, message));
+            }
+        }
 
         Ok(Self {
             taxonomy_name,
-            generate_serde,
+            serde_mode,
+            serde_cfg,
+            zeroize,
+            defmt,
+            defmt_cfg,
+            generic: generic.is_some(),
+            extends,
         })
     }
 }
 
+/// Per-class settings, given with a `#[class(...)]` attribute on an enum variant.
+#[derive(Default)]
+struct ClassArgs {
+    level: Option<syn::LitInt>,
+    name: Option<syn::LitStr>,
+    id: Option<syn::LitInt>,
+    retention_days: Option<syn::LitInt>,
+    never_log: Option<syn::LitBool>,
+    upcasts: Vec<syn::Path>,
+}
+
+impl ClassArgs {
+    fn parse(variant: &syn::Variant) -> SynResult<Self> {
+        let mut args = Self::default();
+
+        for attr in variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("class"))
+        {
+            attr.parse_nested_meta(|meta| {
+                let duplicate = |is_set: bool| {
+                    is_set.then(|| {
+                        let name = meta.path.get_ident().map(ToString::to_string).unwrap_or_default();
+                        meta.error(format!("duplicate `{name}` argument"))
+                    })
+                };
+
+                if meta.path.is_ident("level") {
+                    if let Some(error) = duplicate(args.level.is_some()) {
+                        return Err(error);
+                    }
+                    args.level = Some(parse_int::<u8>(meta.value()?, "expected a level between 0 and 255")?);
+                    Ok(())
+                } else if meta.path.is_ident("name") {
+                    if let Some(error) = duplicate(args.name.is_some()) {
+                        return Err(error);
+                    }
+                    let name: syn::LitStr = meta.value()?.parse()?;
+                    if !is_class_name(&name.value()) {
+                        return Err(syn::Error::new_spanned(
+                            &name,
+                            "class names must be non-empty and only contain ASCII letters, digits, `_`, `.`, or `-`",
+                        ));
+                    }
+                    args.name = Some(name);
+                    Ok(())
+                } else if meta.path.is_ident("id") {
+                    if let Some(error) = duplicate(args.id.is_some()) {
+                        return Err(error);
+                    }
+                    args.id = Some(parse_int::<u32>(meta.value()?, "expected a class id between 0 and 4294967295")?);
+                    Ok(())
+                } else if meta.path.is_ident("retention_days") {
+                    if let Some(error) = duplicate(args.retention_days.is_some()) {
+                        return Err(error);
+                    }
+                    args.retention_days = Some(parse_int::<u32>(
+                        meta.value()?,
+                        "expected a number of days between 0 and 4294967295",
+                    )?);
+                    Ok(())
+                } else if meta.path.is_ident("never_log") {
+                    if let Some(error) = duplicate(args.never_log.is_some()) {
+                        return Err(error);
+                    }
+                    let never_log = if meta.input.peek(syn::Token![=]) {
+                        meta.value()?.parse()?
+                    } else {
+                        syn::LitBool::new(true, meta.path.span())
+                    };
+                    args.never_log = Some(never_log);
+                    Ok(())
+                } else if meta.path.is_ident("upcast") {
+                    let value = meta.value()?;
+                    let message = "expected the path of a classified container, such as `data_privacy::core_taxonomy::Sensitive`";
+                    let target = if value.peek(syn::LitStr) {
+                        let path = value.parse::<syn::LitStr>()?;
+                        path.parse()
+                            .map_err(|_invalid| syn::Error::new_spanned(&path, message))?
+                    } else {
+                        value
+                            .parse()
+                            .map_err(|e| syn::Error::new(e.span(), message))?
+                    };
+                    args.upcasts.push(target);
+                    Ok(())
+                } else {
+                    Err(meta.error(
+                        "expected `level`, `name`, `id`, `retention_days`, `never_log`, or `upcast`",
+                    ))
+                }
+            })?;
+        }
+
+        Ok(args)
+    }
+}
+
+/// Parses an integer literal which must fit in `T`, failing with the given message otherwise.
+fn parse_int<T>(input: syn::parse::ParseStream, message: &str) -> syn::Result<syn::LitInt>
+where
+    T: core::str::FromStr,
+    T::Err: core::fmt::Display,
+{
+    let value = input
+        .parse::<syn::LitInt>()
+        .map_err(|e| syn::Error::new(e.span(), message))?;
+    _ = value
+        .base10_parse::<T>()
+        .map_err(|_overflow| syn::Error::new_spanned(&value, message))?;
+    Ok(value)
+}
+
+/// Checks the variants of a taxonomy enum, returning the settings and name of each variant's class.
+///
+/// Every variant is checked before failing, so that all the errors of a taxonomy are reported at once,
+/// each pointing at the offending variant or argument.
+fn parse_variants(
+    variants: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+) -> SynResult<Vec<(ClassArgs, String)>> {
+    let mut classes = Vec::new();
+    let mut errors: Option<syn::Error> = None;
+    let mut push_error = |error: syn::Error| match &mut errors {
+        Some(errors) => errors.combine(error),
+        None => errors = Some(error),
+    };
+
+    let mut class_names = std::collections::HashSet::new();
+    let mut class_ids = std::collections::HashSet::new();
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            push_error(syn::Error::new_spanned(
+                &variant.fields,
+                "the taxonomy attribute only supports unit variants",
+            ));
+        }
+
+        let class_args = match ClassArgs::parse(variant) {
+            Ok(class_args) => class_args,
+            Err(error) => {
+                push_error(error);
+                continue;
+            }
+        };
+
+        let class_name = class_args.name.as_ref().map_or_else(
+            || pascal_to_snake_case(&variant.ident.to_string()),
+            syn::LitStr::value,
+        );
+        if !class_names.insert(class_name.clone()) {
+            let message = format!("duplicate class name `{class_name}`");
+            push_error(class_args.name.as_ref().map_or_else(
+                || syn::Error::new_spanned(&variant.ident, &message),
+                |name| syn::Error::new_spanned(name, &message),
+            ));
+        }
+
+        if let Some(id) = &class_args.id {
+            // ids are validated when parsed, so this can't fail
+            if !class_ids.insert(id.base10_parse::<u32>().unwrap_or_default()) {
+                push_error(syn::Error::new_spanned(
+                    id,
+                    format!("duplicate class id `{id}`"),
+                ));
+            }
+        }
+
+        classes.push((class_args, class_name));
+    }
+
+    errors.map_or(Ok(classes), Err)
+}
+
+/// Computes the code of a data class, like `ClassCode::of` does.
+fn class_code(taxonomy: &str, name: &str) -> u64 {
+    [taxonomy.as_bytes(), &[0xff], name.as_bytes()]
+        .concat()
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+}
+
+/// Checks that a custom class name can be used within classification tags.
+fn is_class_name(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'.' | b'-'))
+}
+
 /// Convert `PascalCase` to `snake_case`
 fn pascal_to_snake_case(s: &str) -> String {
     let mut result = String::new();
@@ -69,6 +398,72 @@ fn pascal_to_snake_case(s: &str) -> String {
     result
 }
 
+/// Generates operator forwarding impls, letting classified values be combined without declassification.
+fn arithmetic_impls(variant_name: &Ident) -> TokenStream {
+    let binary_ops = [
+        ("Add", "add", "AddAssign", "add_assign"),
+        ("Sub", "sub", "SubAssign", "sub_assign"),
+        ("Mul", "mul", "MulAssign", "mul_assign"),
+        ("Div", "div", "DivAssign", "div_assign"),
+        ("Rem", "rem", "RemAssign", "rem_assign"),
+    ];
+
+    let binary_impls = binary_ops
+        .iter()
+        .map(|(op, method, assign_op, assign_method)| {
+            let op = Ident::new(op, variant_name.span());
+            let method = Ident::new(method, variant_name.span());
+            let assign_op = Ident::new(assign_op, variant_name.span());
+            let assign_method = Ident::new(assign_method, variant_name.span());
+
+            quote! {
+                impl<T, U> core::ops::#op<#variant_name<U>> for #variant_name<T>
+                where
+                    T: core::ops::#op<U>,
+                {
+                    type Output = #variant_name<<T as core::ops::#op<U>>::Output>;
+
+                    fn #method(self, rhs: #variant_name<U>) -> Self::Output {
+                        #variant_name::new(core::ops::#op::#method(self.payload, rhs.payload))
+                    }
+                }
+
+                impl<T, U> core::ops::#assign_op<#variant_name<U>> for #variant_name<T>
+                where
+                    T: core::ops::#assign_op<U>,
+                {
+                    fn #assign_method(&mut self, rhs: #variant_name<U>) {
+                        core::ops::#assign_op::#assign_method(&mut self.payload, rhs.payload);
+                    }
+                }
+            }
+        });
+
+    quote! {
+        #(#binary_impls)*
+
+        impl<T> core::ops::Neg for #variant_name<T>
+        where
+            T: core::ops::Neg,
+        {
+            type Output = #variant_name<<T as core::ops::Neg>::Output>;
+
+            fn neg(self) -> Self::Output {
+                #variant_name::new(core::ops::Neg::neg(self.payload))
+            }
+        }
+
+        impl<T> core::iter::Sum for #variant_name<T>
+        where
+            T: core::iter::Sum,
+        {
+            fn sum<I: core::iter::Iterator<Item = Self>>(iter: I) -> Self {
+                Self::new(iter.map(|value| value.payload).sum())
+            }
+        }
+    }
+}
+
 /// Determine the path to the `data-privacy` crate
 #[cfg(not(test))]
 #[cfg_attr(test, mutants::skip)]
@@ -77,7 +472,8 @@ fn find_crate(input: &DeriveInput) -> Result<TokenStream, syn::Error> {
         .map_err(|e| syn::Error::new(input.ident.span(), e))?;
 
     Ok(match found_crate {
-        proc_macro_crate::FoundCrate::Itself => quote!(crate),
+        // the crate names itself with `extern crate self`, so this works in its examples, doc tests, and integration tests alike
+        proc_macro_crate::FoundCrate::Itself => quote!(::data_privacy),
         proc_macro_crate::FoundCrate::Name(name) => {
             let ident = quote::format_ident!("{}", name);
             quote!(::#ident)
@@ -85,178 +481,955 @@ fn find_crate(input: &DeriveInput) -> Result<TokenStream, syn::Error> {
     })
 }
 
-#[allow(clippy::too_many_lines, reason = "Yeah, it's a bit much...")]
-fn taxonomy_impl(attr_args: TokenStream, item: TokenStream) -> SynResult<TokenStream> {
-    let macro_args = MacroArgs::parse(attr_args)?;
-    let input: DeriveInput = parse2(item)?;
+/// Determine the path to the `data-privacy` crate, for use in generated code
+#[cfg_attr(
+    test,
+    expect(clippy::unnecessary_wraps, reason = "Tests always use the crate path")
+)]
+fn data_privacy_path(input: &DeriveInput) -> SynResult<TokenStream> {
+    #[cfg(test)]
+    {
+        _ = input;
+        Ok(quote!(crate))
+    }
+
+    #[cfg(not(test))]
+    find_crate(input)
+}
+
+/// A class of a taxonomy, along with the taxonomy settings needed to generate its container.
+struct TaxonomyClass<'a> {
+    data_privacy_path: &'a TokenStream,
+    macro_args: &'a MacroArgs,
+    vis: &'a syn::Visibility,
+    taxonomy_name: &'a str,
+    variant: &'a syn::Variant,
+    class_name: &'a str,
+    class_expr: &'a TokenStream,
+    upcasts: &'a [syn::Path],
+
+    /// Whether the name of the borrowed container alias isn't taken by another variant.
+    ref_alias: bool,
+}
+
+impl TaxonomyClass<'_> {
+    /// Returns the doc comments of the variant of the class.
+    fn docs(&self) -> impl Iterator<Item = &syn::Attribute> {
+        self.variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"))
+    }
+
+    /// Generates an expression moving the payload out of a container, without declassifying it.
+    fn take_payload(&self, container: &TokenStream) -> TokenStream {
+        if self.macro_args.zeroize {
+            // The container scrubs its payload on drop, so it is never dropped and its payload is moved out instead.
+            quote!({
+                let this = ::core::mem::ManuallyDrop::new(#container);
+
+                // SAFETY: The container is owned and wrapped in `ManuallyDrop`, so its destructor never runs and
+                // the payload read out of it is only ever dropped once, by the caller.
+                unsafe { ::core::ptr::read(&this.payload) }
+            })
+        } else {
+            quote!(#container.payload)
+        }
+    }
+}
+
+/// The pieces of the generated taxonomy methods, gathered from the classes and extended taxonomies.
+#[derive(Default)]
+struct TaxonomyParts {
+    match_arms: Vec<TokenStream>,
+    code_arms: Vec<TokenStream>,
+    codes: Vec<TokenStream>,
+    class_exprs: Vec<TokenStream>,
+    variant_names: Vec<Ident>,
+    from_str_arms: Vec<TokenStream>,
+    extension_impls: Vec<TokenStream>,
+    extension_parsers: Vec<TokenStream>,
+}
+
+/// Generates the expression of the data class of a class, along with its metadata.
+fn class_expr(
+    data_privacy_path: &TokenStream,
+    taxonomy_name: &str,
+    class_name: &str,
+    class_args: &ClassArgs,
+) -> TokenStream {
+    let level = class_args
+        .level
+        .as_ref()
+        .map_or_else(|| quote!(0), |level| quote!(#level));
+    let class_id = class_args.id.as_ref().map(|id| quote!(.with_id(#id)));
+    let retention_days = class_args
+        .retention_days
+        .as_ref()
+        .map(|days| quote!(.with_retention_days(#days)));
+    let never_log = class_args
+        .never_log
+        .as_ref()
+        .map(|never_log| quote!(.with_never_log(#never_log)));
+
+    quote! {
+        #data_privacy_path::DataClass::new(#taxonomy_name, #class_name).with_level(#level) #class_id #retention_days #never_log
+    }
+}
 
-    let Data::Enum(enum_data) = &input.data else {
+/// Generates the marker type and aliases of a class of a generic taxonomy, whose classes share a container.
+fn marker_items(class: &TaxonomyClass<'_>) -> SynResult<TokenStream> {
+    if let Some(upcast) = class.upcasts.first() {
         return Err(syn::Error::new_spanned(
-            &input,
-            "the taxonomy attribute can only be applied to enums",
+            upcast,
+            "upcasts aren't supported by taxonomies created with `generic = true`",
         ));
+    }
+
+    let TaxonomyClass {
+        data_privacy_path,
+        vis,
+        taxonomy_name,
+        class_name,
+        class_expr,
+        ..
+    } = class;
+    let variant_name = &class.variant.ident;
+    let variant_docs = class.docs();
+
+    let marker_name = quote::format_ident!("{}Class", variant_name);
+    let ref_name = quote::format_ident!("{}Ref", variant_name);
+    let ref_alias = class.ref_alias.then(|| {
+        quote! {
+            #[doc = concat!("A classified data container for the `", #class_name, "` class of the `", #taxonomy_name, "` taxonomy, borrowing its payload.")]
+            #vis type #ref_name<'a, T> = #data_privacy_path::ClassifiedAs<&'a T, #marker_name>;
+        }
+    });
+
+    Ok(quote! {
+        #[doc = concat!("The marker type of the `", #class_name, "` class of the `", #taxonomy_name, "` taxonomy.")]
+        #[derive(Clone, Copy, Debug)]
+        #vis struct #marker_name;
+
+        impl #data_privacy_path::ClassMarker for #marker_name {
+            const DATA_CLASS: #data_privacy_path::DataClass = #class_expr;
+            const CODE: #data_privacy_path::ClassCode = #data_privacy_path::ClassCode::of(#taxonomy_name, #class_name);
+        }
+
+        #[doc = concat!("A classified data container for the `", #class_name, "` class of the `", #taxonomy_name, "` taxonomy.")]
+        #[doc = ""]
+        #(
+            #variant_docs
+        )*
+        #vis type #variant_name<T> = #data_privacy_path::ClassifiedAs<T, #marker_name>;
+
+        #ref_alias
+    })
+}
+
+/// Generates the container of a class, along with the implementations of its traits.
+fn container_items(class: &TaxonomyClass<'_>) -> TokenStream {
+    let data_privacy_path = class.data_privacy_path;
+    let macro_args = class.macro_args;
+    let variant_name = &class.variant.ident;
+
+    // zeroizing containers scrub their payload on drop, so they can't be copied and their payload can
+    // only be moved out through declassification
+    let payload_bound = if macro_args.zeroize {
+        quote!(: #data_privacy_path::__private::zeroize::Zeroize)
+    } else {
+        quote!()
     };
 
-    if !input.generics.params.is_empty() {
-        return Err(syn::Error::new_spanned(
-            &input.generics,
-            "the taxonomy attribute cannot be applied to generic enums",
-        ));
+    let (combinators, conversion_impls, arithmetic_impls) = if macro_args.zeroize {
+        (
+            zeroizing_combinators(data_privacy_path),
+            zeroize_impls(data_privacy_path, variant_name, &payload_bound),
+            quote!(),
+        )
+    } else {
+        (
+            combinators(data_privacy_path, variant_name),
+            conversion_impls(class),
+            arithmetic_impls(variant_name),
+        )
+    };
+
+    let declaration = container_declaration(class, &payload_bound, &combinators);
+    let trait_impls = container_trait_impls(class, &payload_bound);
+    let upcast_impls = upcast_impls(class, &payload_bound);
+    let serde_impls = serde_impls(macro_args, data_privacy_path, variant_name, &payload_bound);
+    let defmt_impl = defmt_impl(macro_args, data_privacy_path, variant_name, &payload_bound);
+
+    quote! {
+        #declaration
+
+        #trait_impls
+
+        #conversion_impls
+
+        #upcast_impls
+
+        #arithmetic_impls
+
+        #serde_impls
+
+        #defmt_impl
     }
+}
 
-    #[cfg(test)]
-    let data_privacy_path = quote!(crate);
+/// Generates the declaration of the container of a class, along with its inherent methods.
+fn container_declaration(
+    class: &TaxonomyClass<'_>,
+    payload_bound: &TokenStream,
+    combinators: &TokenStream,
+) -> TokenStream {
+    let TaxonomyClass {
+        data_privacy_path,
+        vis,
+        taxonomy_name,
+        class_name,
+        class_expr,
+        ..
+    } = class;
+    let variant_name = &class.variant.ident;
+    let variant_docs = class.docs();
+    let take_payload = class.take_payload(&quote!(self));
+
+    let derives = if class.macro_args.zeroize {
+        quote!(#[derive(Default, Eq, PartialEq, Ord, PartialOrd, Hash)])
+    } else {
+        quote!(#[derive(Clone, Default, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)])
+    };
 
-    #[cfg(not(test))]
-    let data_privacy_path = find_crate(&input)?;
+    quote! {
+        #[doc = concat!("A classified data container for the `", #class_name, "` class of the `", #taxonomy_name, "` taxonomy.")]
+        #[doc = ""]
+        #(
+            #variant_docs
+        )*
 
-    let enum_name = &input.ident;
-    let enum_vis = &input.vis;
+        #derives
+        #vis struct #variant_name<T #payload_bound> {
+            payload: T,
+        }
 
-    let mut variant_structs = Vec::new();
-    let mut match_arms = Vec::new();
+        impl<T #payload_bound> #variant_name<T> {
+            /// Creates a new instance of the classified data container.
+            #[must_use]
+            pub fn new(payload: T) -> Self {
+                Self { payload }
+            }
 
-    for variant in &enum_data.variants {
-        match &variant.fields {
-            Fields::Unit => {}
-            _ => {
-                return Err(syn::Error::new_spanned(
-                    variant,
-                    "the taxonomy attribute only supports unit variants",
-                ));
+            /// Exfiltrates the payload, allowing it to be used outside the classified context.
+            ///
+            /// Exfiltration should be done with caution, as it may expose sensitive information.
+            ///
+            /// # Returns
+            /// The original payload.
+            #[must_use]
+            #[track_caller]
+            pub fn declassify(self) -> T {
+                #data_privacy_path::__private::record_declassification(Self::data_class);
+                #take_payload
+            }
+
+            /// Declassifies the payload for the given purpose, if the process-wide declassification policy allows it.
+            ///
+            /// See the `policy` module of `data_privacy` for details.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the policy denies declassifying the payload for this purpose, or
+            /// requires a justification. The payload is dropped in that case.
+            #[track_caller]
+            pub fn declassify_for(
+                self,
+                purpose: #data_privacy_path::policy::Purpose,
+            ) -> core::result::Result<T, #data_privacy_path::policy::PolicyError> {
+                #data_privacy_path::__private::check_declassification(Self::data_class, purpose, None)?;
+                ::core::result::Result::Ok(self.declassify())
             }
+
+            /// Declassifies the payload for the given purpose and justification, if the process-wide
+            /// declassification policy allows it.
+            ///
+            /// See the `policy` module of `data_privacy` for details.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the policy denies declassifying the payload for this purpose. The payload
+            /// is dropped in that case.
+            #[track_caller]
+            pub fn declassify_with_justification(
+                self,
+                purpose: #data_privacy_path::policy::Purpose,
+                justification: &str,
+            ) -> core::result::Result<T, #data_privacy_path::policy::PolicyError> {
+                #data_privacy_path::__private::check_declassification(Self::data_class, purpose, Some(justification))?;
+                ::core::result::Result::Ok(self.declassify())
+            }
+
+            #combinators
+
+            /// Returns the data class of the payload.
+            #[must_use]
+            pub const fn data_class() -> #data_privacy_path::DataClass {
+                #class_expr
+            }
+
+            /// The compact code of the data class of the payload, usable as a `match` pattern.
+            pub const CODE: #data_privacy_path::ClassCode = #data_privacy_path::ClassCode::of(#taxonomy_name, #class_name);
         }
+    }
+}
 
-        let variant_name = &variant.ident;
-        let variant_name_str = variant_name.to_string();
-        let snake_case_variant_name = pascal_to_snake_case(&variant_name_str);
-        let variant_docs = variant
-            .attrs
-            .iter()
-            .filter(|attr| attr.path().is_ident("doc"));
+/// Generates the implementations of the traits every container implements.
+fn container_trait_impls(class: &TaxonomyClass<'_>, payload_bound: &TokenStream) -> TokenStream {
+    let TaxonomyClass {
+        data_privacy_path,
+        taxonomy_name,
+        class_name,
+        ..
+    } = class;
+    let variant_name = &class.variant.ident;
+    let take_payload = class.take_payload(&quote!(self));
+
+    quote! {
+        impl<T #payload_bound> #data_privacy_path::Classified<T> for #variant_name<T> {
+            #[track_caller]
+            fn declassify(self) -> T {
+                #variant_name::declassify(self)
+            }
 
-        let serde_impls = if macro_args.generate_serde {
-            quote! {
-                impl<'a, T> serde::Deserialize<'a> for #variant_name<T>
+            fn take_payload(self, _token: #data_privacy_path::__private::PayloadToken) -> T {
+                #take_payload
+            }
+
+            fn visit(&self, operation: impl FnOnce(&T)) {
+                operation(&self.payload);
+            }
+
+            fn visit_mut(&mut self, operation: impl FnOnce(&mut T)) {
+                operation(&mut self.payload);
+            }
+
+            fn data_class(&self) -> #data_privacy_path::DataClass {
+                Self::data_class()
+            }
+        }
+
+        impl<T #payload_bound, Q: ?Sized> core::borrow::Borrow<#data_privacy_path::ClassifiedKey<Q>> for #variant_name<T>
+        where
+            T: core::borrow::Borrow<Q>,
+        {
+            fn borrow(&self) -> &#data_privacy_path::ClassifiedKey<Q> {
+                #data_privacy_path::ClassifiedKey::new(self.payload.borrow())
+            }
+        }
+
+        impl<T #payload_bound> #data_privacy_path::ClassifiedType for #variant_name<T> {
+            const DATA_CLASS: #data_privacy_path::DataClass = Self::data_class();
+        }
+
+        impl<T #payload_bound> core::fmt::Debug for #variant_name<T>
+        where
+            T: core::fmt::Debug,
+        {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_fmt(::core::format_args!("<{}/{}:REDACTED>", #taxonomy_name, #class_name))
+            }
+        }
+
+        impl<T #payload_bound> #data_privacy_path::Extract for #variant_name<T>
+        where
+            T: core::fmt::Display,
+        {
+            fn extract(&self, extractor: &mut dyn #data_privacy_path::Extractor) {
+                #data_privacy_path::__private::with_formatted(::core::format_args!("{}", self.payload), |s| {
+                    extractor.write_classified(&Self::data_class(), s);
+                });
+            }
+        }
+
+        impl<T #payload_bound> core::convert::From<T> for #variant_name<T> {
+            fn from(payload: T) -> Self {
+                Self::new(payload)
+            }
+        }
+
+        impl<T #payload_bound> #variant_name<#variant_name<T>> {
+            /// Removes a redundant level of classification, returning the inner container.
+            ///
+            /// Wrapping a container in another one of the same data class adds no protection, and makes
+            /// the payload unreachable to redaction, since the outer container only sees the inner one.
+            #[must_use]
+            pub fn flatten(self) -> #variant_name<T> {
+                #take_payload
+            }
+        }
+
+        impl<T #payload_bound> core::convert::From<#variant_name<#variant_name<T>>> for #variant_name<T> {
+            fn from(value: #variant_name<#variant_name<T>>) -> Self {
+                value.flatten()
+            }
+        }
+    }
+}
+
+/// Generates the combinators transforming the payload of containers which aren't zeroizing.
+fn combinators(data_privacy_path: &TokenStream, variant_name: &Ident) -> TokenStream {
+    // doc strings aren't interpolated, so links to the crate's items are spelled out here
+    let zip_same_doc = format!(
+        " Unlike [`Classified::zip`]({}::Classified::zip), which accepts containers of",
+        data_privacy_path.to_string().replace(' ', "")
+    );
+
+    quote! {
+        /// Transforms the payload without declassifying it, keeping the data class.
+        #[must_use]
+        pub fn map<U>(self, operation: impl FnOnce(T) -> U) -> #variant_name<U> {
+            #variant_name::new(operation(self.payload))
+        }
+
+        /// Borrows the payload, producing a container of the same data class holding a reference to it.
+        #[must_use]
+        pub const fn as_ref(&self) -> #variant_name<&T> {
+            #variant_name { payload: &self.payload }
+        }
+
+        /// Mutably borrows the payload, producing a container of the same data class holding a reference to it.
+        #[must_use]
+        pub const fn as_mut(&mut self) -> #variant_name<&mut T> {
+            #variant_name { payload: &mut self.payload }
+        }
+
+        /// Combines two containers of this data class into one holding both payloads.
+        ///
+        #[doc = #zip_same_doc]
+        /// any class, this keeps the static type of the container and doesn't declassify the payloads.
+        #[must_use]
+        pub fn zip_same<U>(self, other: #variant_name<U>) -> #variant_name<(T, U)> {
+            #variant_name::new((self.payload, other.payload))
+        }
+
+        /// Adds another data class to the payload, producing a container carrying both classes.
+        #[must_use]
+        pub fn with_class(self, data_class: #data_privacy_path::DataClass) -> #data_privacy_path::MultiClassified<T> {
+            #data_privacy_path::MultiClassified::new(self.payload, Self::data_class()).with_class(data_class)
+        }
+    }
+}
+
+/// Generates the conversions of containers which aren't zeroizing, and their borrowed container alias.
+fn conversion_impls(class: &TaxonomyClass<'_>) -> TokenStream {
+    let TaxonomyClass {
+        data_privacy_path,
+        vis,
+        taxonomy_name,
+        class_name,
+        ..
+    } = class;
+    let variant_name = &class.variant.ident;
+
+    // borrowed containers are aliases of containers holding references, unless the name is taken by another variant
+    let ref_name = quote::format_ident!("{}Ref", variant_name);
+    let ref_alias = class.ref_alias.then(|| {
+        quote! {
+            #[doc = concat!("A classified data container for the `", #class_name, "` class of the `", #taxonomy_name, "` taxonomy, borrowing its payload.")]
+            #[doc = ""]
+            #[doc = concat!("This classifies data borrowed from elsewhere, such as from a parser or a database row, without copying it. It is a [`", stringify!(#variant_name), "`] holding a reference, so it has the same data class and is redacted the same way, and the payload can be unsized, as in `", stringify!(#ref_name), "<'_, str>`.")]
+            #vis type #ref_name<'a, T> = #variant_name<&'a T>;
+        }
+    });
+
+    quote! {
+        #ref_alias
+
+        impl<T> core::convert::From<#variant_name<T>> for #data_privacy_path::MultiClassified<T> {
+            fn from(value: #variant_name<T>) -> Self {
+                Self::new(value.payload, #variant_name::<T>::data_class())
+            }
+        }
+
+        impl<T> #variant_name<core::option::Option<T>> {
+            /// Turns a container of an optional payload into an optional container, keeping the data class.
+            #[must_use]
+            pub fn transpose(self) -> core::option::Option<#variant_name<T>> {
+                self.payload.map(#variant_name::new)
+            }
+        }
+
+        impl<T, E> #variant_name<core::result::Result<T, E>> {
+            /// Turns a container of a result into a result of containers, keeping the data class of both
+            /// the value and the error.
+            ///
+            /// # Errors
+            ///
+            /// Returns the error of the payload, still classified, if the payload is an error.
+            pub fn transpose(self) -> core::result::Result<#variant_name<T>, #variant_name<E>> {
+                self.payload.map(#variant_name::new).map_err(#variant_name::new)
+            }
+        }
+
+        impl<T, C: core::iter::FromIterator<T>> core::iter::FromIterator<#variant_name<T>> for #variant_name<C> {
+            fn from_iter<I: core::iter::IntoIterator<Item = #variant_name<T>>>(iter: I) -> Self {
+                #variant_name::new(iter.into_iter().map(|item| item.payload).collect())
+            }
+        }
+    }
+}
+
+/// Generates the combinators of zeroizing containers.
+fn zeroizing_combinators(data_privacy_path: &TokenStream) -> TokenStream {
+    let zeroize_path = quote!(#data_privacy_path::__private::zeroize);
+
+    quote! {
+        /// Declassifies the payload, keeping it in a wrapper which scrubs it when dropped.
+        ///
+        /// Unlike [`declassify`](Self::declassify), this keeps the payload protected against lingering
+        /// in memory after it leaves its container.
+        #[must_use]
+        #[track_caller]
+        pub fn declassify_zeroizing(self) -> #zeroize_path::Zeroizing<T> {
+            #zeroize_path::Zeroizing::new(self.declassify())
+        }
+    }
+}
+
+/// Generates the impls letting zeroizing containers scrub their payload, including on drop.
+fn zeroize_impls(
+    data_privacy_path: &TokenStream,
+    variant_name: &Ident,
+    payload_bound: &TokenStream,
+) -> TokenStream {
+    let zeroize_path = quote!(#data_privacy_path::__private::zeroize);
+
+    quote! {
+        impl<T #payload_bound> #zeroize_path::Zeroize for #variant_name<T> {
+            fn zeroize(&mut self) {
+                #zeroize_path::Zeroize::zeroize(&mut self.payload);
+            }
+        }
+
+        impl<T #payload_bound> #zeroize_path::ZeroizeOnDrop for #variant_name<T> {}
+
+        impl<T #payload_bound> core::ops::Drop for #variant_name<T> {
+            fn drop(&mut self) {
+                #zeroize_path::Zeroize::zeroize(&mut self.payload);
+            }
+        }
+    }
+}
+
+/// Generates the conversions of a container into the containers of the classes it can be upcast to.
+fn upcast_impls(class: &TaxonomyClass<'_>, payload_bound: &TokenStream) -> TokenStream {
+    let data_privacy_path = class.data_privacy_path;
+    let class_expr = class.class_expr;
+    let variant_name = &class.variant.ident;
+
+    // upcasts move the payload into a container of another class without declassifying it
+    let take_value_payload = class.take_payload(&quote!(value));
+    let upcast_impls = class.upcasts.iter().map(|target| {
+        // the target class must protect the payload at least as well as its current class
+        let target_class = quote!(<#target<u8> as #data_privacy_path::ClassifiedType>::DATA_CLASS);
+        let check = quote_spanned! {target.span()=>
+            const _: () = {
+                let source = #class_expr;
+                let target = #target_class;
+                assert!(
+                    target.level() >= source.level(),
+                    "the target of an upcast must have at least the level of the upcast class",
+                );
+                assert!(
+                    target.never_log() || !source.never_log(),
+                    "the target of an upcast must never be logged if the upcast class must never be logged",
+                );
+
+                // data classes can't be dropped in constants
+                core::mem::forget(source);
+                core::mem::forget(target);
+            };
+        };
+
+        quote! {
+            #check
+
+            impl<T #payload_bound> core::convert::From<#variant_name<T>> for #target<T>
+            where
+                #target<T>: core::convert::From<T>,
+            {
+                fn from(value: #variant_name<T>) -> Self {
+                    core::convert::From::from(#take_value_payload)
+                }
+            }
+        }
+    });
+
+    quote!(#(#upcast_impls)*)
+}
+
+/// Generates the serde impls of a container, as selected by the `serde` argument of the taxonomy.
+fn serde_impls(
+    macro_args: &MacroArgs,
+    data_privacy_path: &TokenStream,
+    variant_name: &Ident,
+    payload_bound: &TokenStream,
+) -> TokenStream {
+    // in strict mode, payloads are wrapped in an envelope which names their data class
+    let (deserialize_payload, serialize_payload) = if macro_args.serde_mode == SerdeMode::Strict {
+        (
+            quote!(#data_privacy_path::__private::deserialize_enveloped(deserializer, &Self::data_class())?),
+            quote!(#data_privacy_path::__private::serialize_enveloped(&self.payload, &Self::data_class(), serializer)),
+        )
+    } else {
+        (
+            quote!(T::deserialize(deserializer)?),
+            quote!(#data_privacy_path::__private::serialize_classified(&self.payload, &Self::data_class(), serializer)),
+        )
+    };
+
+    let serde_cfg = &macro_args.serde_cfg;
+    let deserialize_impl = macro_args.serde_mode.deserialize().then(|| {
+        quote! {
+            #serde_cfg
+            impl<'a, T #payload_bound> #data_privacy_path::__serde::Deserialize<'a> for #variant_name<T>
+            where
+                T: #data_privacy_path::__serde::Deserialize<'a>,
+            {
+                fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
                 where
-                    T: serde::Deserialize<'a>,
+                    D: #data_privacy_path::__serde::Deserializer<'a>,
                 {
-                    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-                    where
-                        D: serde::Deserializer<'a>,
-                    {
-                        let payload = T::deserialize(deserializer)?;
-                        core::result::Result::Ok(Self::new(payload))
-                    }
+                    let payload = #deserialize_payload;
+                    core::result::Result::Ok(Self::new(payload))
                 }
+            }
+        }
+    });
 
-                impl<T> serde::Serialize for #variant_name<T>
+    let serialize_impl = macro_args.serde_mode.serialize().then(|| {
+        quote! {
+            #serde_cfg
+            impl<T #payload_bound> #data_privacy_path::__serde::Serialize for #variant_name<T>
+            where
+                T: #data_privacy_path::__serde::Serialize,
+            {
+                fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
                 where
-                    T: serde::Serialize,
+                    S: #data_privacy_path::__serde::Serializer,
                 {
-                    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-                    where
-                        S: serde::Serializer,
-                    {
-                        self.payload.serialize(serializer)
-                    }
+                    #serialize_payload
                 }
             }
-        } else {
-            quote! {}
-        };
+        }
+    });
 
-        let taxonomy_name = macro_args.taxonomy_name.to_string();
-        variant_structs.push(quote! {
-            #[doc = concat!("A classified data container for the `", #snake_case_variant_name, "` class of the `", #taxonomy_name, "` taxonomy.")]
-            #[doc = ""]
-            #(
-                #variant_docs
-            )*
+    quote! {
+        #deserialize_impl
 
-            #[derive(Clone, Default, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
-            #enum_vis struct #variant_name<T> {
-                payload: T,
-            }
+        #serialize_impl
+    }
+}
 
-            impl<T> #variant_name<T> {
-                /// Creates a new instance of the classified data container.
-                #[must_use]
-                pub fn new(payload: T) -> Self {
-                    Self { payload }
+/// Generates the `defmt::Format` impl of a container, if the taxonomy asks for one.
+fn defmt_impl(
+    macro_args: &MacroArgs,
+    data_privacy_path: &TokenStream,
+    variant_name: &Ident,
+    payload_bound: &TokenStream,
+) -> Option<TokenStream> {
+    let defmt_cfg = &macro_args.defmt_cfg;
+    macro_args.defmt.then(|| {
+        quote! {
+            #defmt_cfg
+            impl<T #payload_bound> #data_privacy_path::__private::defmt::Format for #variant_name<T> {
+                fn format(&self, f: #data_privacy_path::__private::defmt::Formatter<'_>) {
+                    #data_privacy_path::__private::format_masked(&Self::data_class(), f);
                 }
+            }
+        }
+    })
+}
 
-                /// Exfiltrates the payload, allowing it to be used outside the classified context.
-                ///
-                /// Exfiltration should be done with caution, as it may expose sensitive information.
-                ///
-                /// # Returns
-                /// The original payload.
-                #[must_use]
-                pub fn declassify(self) -> T {
-                    self.payload
-                }
+/// Gathers the pieces of the taxonomy methods handling the extended taxonomies, returning the variants
+/// embedding them.
+///
+/// Each extended taxonomy is embedded in a variant named after it.
+fn extensions(
+    enum_name: &Ident,
+    bases: &[syn::Path],
+    parts: &mut TaxonomyParts,
+) -> SynResult<Vec<syn::Variant>> {
+    let mut extension_variants = Vec::new();
+    for base in bases {
+        let Some(segment) = base.segments.last() else {
+            return Err(syn::Error::new_spanned(base, "expected a taxonomy path"));
+        };
 
-                /// Returns the data class of the payload.
-                #[must_use]
-                pub const fn data_class() -> #data_privacy_path::DataClass {
-                    #data_privacy_path::DataClass::new(#taxonomy_name, #snake_case_variant_name)
+        let variant_name = &segment.ident;
+        extension_variants.push(syn::parse_quote! {
+            #[doc = concat!("The classes of the [`", stringify!(#variant_name), "`] taxonomy.")]
+            #variant_name(#base)
+        });
+
+        parts.match_arms.push(quote! {
+            #enum_name::#variant_name(base) => base.data_class()
+        });
+        parts.code_arms.push(quote! {
+            #enum_name::#variant_name(base) => base.code()
+        });
+
+        parts.extension_impls.push(quote! {
+            impl core::convert::From<#base> for #enum_name {
+                fn from(base: #base) -> Self {
+                    Self::#variant_name(base)
                 }
             }
+        });
 
-            impl<T> #data_privacy_path::Classified<T> for #variant_name<T> {
-                fn declassify(self) -> T {
-                    #variant_name::declassify(self)
-                }
+        parts.extension_parsers.push(quote! {
+            if let ::core::result::Result::Ok(base) = <#base as ::core::str::FromStr>::from_str(s) {
+                return ::core::result::Result::Ok(Self::#variant_name(base));
+            }
+        });
+    }
 
-                fn visit(&self, operation: impl FnOnce(&T)) {
-                    operation(&self.payload);
-                }
+    Ok(extension_variants)
+}
 
-                fn visit_mut(&mut self, operation: impl FnOnce(&mut T)) {
-                    operation(&mut self.payload);
+/// Generates the methods of the taxonomy enum, and its `FromStr` impl.
+fn taxonomy_methods(
+    data_privacy_path: &TokenStream,
+    enum_name: &Ident,
+    taxonomy_name: &str,
+    parts: &TaxonomyParts,
+    bases: &[syn::Path],
+) -> TokenStream {
+    let TaxonomyParts {
+        match_arms,
+        code_arms,
+        codes,
+        class_exprs,
+        variant_names,
+        from_str_arms,
+        extension_parsers,
+        ..
+    } = parts;
+
+    quote! {
+        impl #enum_name {
+            /// Returns the data class associated with the current variant.
+            #[must_use]
+            pub fn data_class(&self) -> #data_privacy_path::DataClass {
+                match self {
+                    #(#match_arms),*
                 }
+            }
 
-                fn data_class(&self) -> #data_privacy_path::DataClass {
-                    Self::data_class()
+            /// Returns the compact code of the data class associated with the current variant.
+            #[must_use]
+            pub const fn code(&self) -> #data_privacy_path::ClassCode {
+                match self {
+                    #(#code_arms),*
                 }
             }
 
-            impl<T> core::fmt::Debug for #variant_name<T>
-            where
-                T: core::fmt::Debug,
-            {
-                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                    f.write_fmt(::core::format_args!("<{}/{}:REDACTED>", #taxonomy_name, #snake_case_variant_name))
-                }
+            /// Returns whether a code is the code of the data class of a variant, including the variants
+            /// embedding the classes of extended taxonomies.
+            #[must_use]
+            pub const fn has_code(code: #data_privacy_path::ClassCode) -> bool {
+                const CODES: &[#data_privacy_path::ClassCode] = &[#(#codes),*];
+                #data_privacy_path::__private::contains_code(CODES, code) #(|| #bases::has_code(code))*
+            }
+
+            /// Returns the data classes of all the variants, in declaration order.
+            ///
+            /// The classes of extended taxonomies aren't included, they're returned by the `classes`
+            /// function of each of these taxonomies.
+            #[must_use]
+            pub const fn classes() -> &'static [#data_privacy_path::DataClass] {
+                const CLASSES: &[#data_privacy_path::DataClass] = &[#(#class_exprs),*];
+                CLASSES
+            }
+
+            /// Returns all the variants, in declaration order.
+            ///
+            /// The variants embedding the classes of extended taxonomies aren't included.
+            #[must_use]
+            pub const fn variants() -> &'static [#enum_name] {
+                const VARIANTS: &[#enum_name] = &[#(#enum_name::#variant_names),*];
+                VARIANTS
+            }
+
+            /// Returns the data classes of all the variants, followed by the classes of the extended taxonomies.
+            pub fn all_data_classes() -> impl ::core::iter::Iterator<Item = #data_privacy_path::DataClass> {
+                Self::classes().iter().cloned() #(.chain(#bases::all_data_classes()))*
             }
+        }
+
+        impl ::core::str::FromStr for #enum_name {
+            type Err = #data_privacy_path::UnknownClassError;
 
-            impl<T> core::convert::From<T> for #variant_name<T> {
-                fn from(payload: T) -> Self {
-                    Self::new(payload)
+            /// Parses a data class name of the form `taxonomy/class` into the variant of that class.
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                if let ::core::option::Option::Some((#taxonomy_name, name)) = s.split_once('/') {
+                    match name {
+                        #(#from_str_arms)*
+                        _ => {}
+                    }
                 }
+
+                #(#extension_parsers)*
+
+                ::core::result::Result::Err(#data_privacy_path::UnknownClassError::new())
             }
+        }
+    }
+}
+
+/// Generates a compile-time check that no class has the code of a class of an extended taxonomy.
+///
+/// Codes within the taxonomy are checked by the macro, while the codes of extended taxonomies are only
+/// known to the compiler.
+fn code_check(
+    data_privacy_path: &TokenStream,
+    taxonomy_name: &str,
+    codes: &[TokenStream],
+    bases: &[syn::Path],
+) -> Option<TokenStream> {
+    (!bases.is_empty()).then(|| {
+        let message = format!(
+            "a class of the `{taxonomy_name}` taxonomy has the same code as a class of an extended taxonomy"
+        );
+        quote! {
+            const _: () = {
+                const CODES: &[#data_privacy_path::ClassCode] = &[#(#codes),*];
+                let mut index = 0;
+                while index < CODES.len() {
+                    assert!(!(#(#bases::has_code(CODES[index]))||*), #message);
+                    index += 1;
+                }
+            };
+        }
+    })
+}
+
+fn taxonomy_impl(attr_args: TokenStream, item: TokenStream) -> SynResult<TokenStream> {
+    let macro_args = MacroArgs::parse(attr_args)?;
+    let mut input: DeriveInput = parse2(item)?;
+
+    let enum_data = match &input.data {
+        Data::Enum(enum_data) => enum_data,
+        Data::Struct(data) => {
+            return Err(syn::Error::new(
+                data.struct_token.span,
+                "the taxonomy attribute can only be applied to enums",
+            ));
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span,
+                "the taxonomy attribute can only be applied to enums",
+            ));
+        }
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "the taxonomy attribute cannot be applied to generic enums",
+        ));
+    }
+
+    let data_privacy_path = data_privacy_path(&input)?;
+    let enum_name = &input.ident;
 
-            #serde_impls
+    let taxonomy_name = macro_args.taxonomy_name.to_string();
+    let classes = parse_variants(&enum_data.variants)?;
+    let mut parts = TaxonomyParts::default();
+    let mut variant_structs = Vec::new();
+    let mut class_codes = std::collections::HashMap::new();
+    let variant_idents: std::collections::HashSet<String> = enum_data
+        .variants
+        .iter()
+        .map(|variant| variant.ident.to_string())
+        .collect();
+
+    for (variant, (class_args, class_name)) in enum_data.variants.iter().zip(classes) {
+        let variant_name = &variant.ident;
+        if let Some(other) =
+            class_codes.insert(class_code(&taxonomy_name, &class_name), class_name.clone())
+        {
+            return Err(syn::Error::new_spanned(
+                variant_name,
+                format!(
+                    "class `{class_name}` has the same code as class `{other}`, rename one of them"
+                ),
+            ));
+        }
+
+        let class_expr = class_expr(&data_privacy_path, &taxonomy_name, &class_name, &class_args);
+        parts.match_arms.push(quote! {
+            #enum_name::#variant_name => #class_expr
         });
+        parts.code_arms.push(quote! {
+            #enum_name::#variant_name => #data_privacy_path::ClassCode::of(#taxonomy_name, #class_name)
+        });
+        parts
+            .codes
+            .push(quote!(#data_privacy_path::ClassCode::of(#taxonomy_name, #class_name)));
+        parts.class_exprs.push(class_expr.clone());
+        parts.from_str_arms.push(quote! {
+            #class_name => return ::core::result::Result::Ok(Self::#variant_name),
+        });
+        parts.variant_names.push(variant_name.clone());
+
+        let class = TaxonomyClass {
+            data_privacy_path: &data_privacy_path,
+            macro_args: &macro_args,
+            vis: &input.vis,
+            taxonomy_name: &taxonomy_name,
+            variant,
+            class_name: &class_name,
+            class_expr: &class_expr,
+            upcasts: &class_args.upcasts,
+            ref_alias: !variant_idents.contains(&format!("{variant_name}Ref")),
+        };
 
-        match_arms.push(quote! {
-            #enum_name::#variant_name => #data_privacy_path::DataClass::new(#taxonomy_name, #snake_case_variant_name)
+        // generic taxonomies share a single container, so each class only needs a marker type and aliases
+        variant_structs.push(if macro_args.generic {
+            marker_items(&class)?
+        } else {
+            container_items(&class)
         });
     }
 
+    let bases = &macro_args.extends;
+    let extension_variants = extensions(enum_name, bases, &mut parts)?;
+    let methods = taxonomy_methods(&data_privacy_path, enum_name, &taxonomy_name, &parts, bases);
+    let code_check = code_check(&data_privacy_path, &taxonomy_name, &parts.codes, bases);
+    let extension_impls = &parts.extension_impls;
+
+    // the class attributes are consumed by this macro, so remove them from the enum
+    if let Data::Enum(enum_data) = &mut input.data {
+        for variant in &mut enum_data.variants {
+            variant.attrs.retain(|attr| !attr.path().is_ident("class"));
+        }
+
+        enum_data.variants.extend(extension_variants);
+    }
+
     Ok(quote! {
         #input
 
-        impl #enum_name {
-            /// Returns the data class associated with the current variant.
-            #[must_use]
-            pub fn data_class(&self) -> #data_privacy_path::DataClass {
-                match self {
-                    #(#match_arms),*
-                }
-            }
-        }
+        #methods
+
+        #(#extension_impls)*
+
+        #code_check
+
+        #data_privacy_path::__register_taxonomy!(#enum_name::classes(), #enum_name);
 
         #(#variant_structs)*
     })
@@ -277,11 +1450,53 @@ pub fn taxonomy(
         .into()
 }
 
+#[expect(
+    missing_docs,
+    reason = "this is documented in the data-privacy reexport"
+)]
+#[proc_macro_derive(DataInventory)]
+#[cfg_attr(test, mutants::skip)]
+pub fn derive_data_inventory(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    data_inventory::data_inventory_impl(item.into())
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+#[expect(
+    missing_docs,
+    reason = "this is documented in the data-privacy reexport"
+)]
+#[proc_macro_derive(Extract, attributes(classified, extract))]
+#[cfg_attr(test, mutants::skip)]
+pub fn derive_extract(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    extract::extract_impl(item.into())
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+#[expect(
+    missing_docs,
+    reason = "this is documented in the data-privacy reexport"
+)]
+#[proc_macro_derive(RedactedDebug, attributes(classified))]
+#[cfg_attr(test, mutants::skip)]
+pub fn derive_redacted_debug(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    redacted_debug::redacted_debug_impl(item.into())
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use insta::assert_snapshot;
 
+    #[test]
+    fn test_class_code() {
+        assert_eq!(class_code("core", "sensitive"), 0xc5b9_d3c3_191d_60a1);
+        assert_ne!(class_code("ab", "c"), class_code("a", "bc"));
+    }
+
     #[test]
     fn test_pascal_to_snake_case() {
         assert_eq!(pascal_to_snake_case("PascalCase"), "pascal_case");
@@ -358,7 +1573,10 @@ mod tests {
 
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert_eq!("expected `serde`", err.to_string());
+        assert_eq!(
+            "expected `serde`, `zeroize`, `defmt`, `generic`, or `extends`",
+            err.to_string()
+        );
     }
 
     #[test]
@@ -457,6 +1675,194 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_taxonomy_impl_invalid_serde_mode() {
+        let input = quote! {
+            pub enum MyEnum {
+                VariantOne,
+            }
+        };
+
+        let attr_args = quote! { MyTaxonomy, serde = "both" };
+        let err = taxonomy_impl(attr_args, input).unwrap_err();
+        assert_eq!(
+            "expected `true`, `false`, `\"serialize\"`, `\"deserialize\"`, or `\"strict\"`",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_taxonomy_impl_directional_serde() {
+        let input = quote! {
+            pub enum MyEnum {
+                VariantOne,
+            }
+        };
+
+        let output = taxonomy_impl(quote! { tax, serde = "deserialize" }, input.clone())
+            .unwrap()
+            .to_string();
+        assert!(output.contains("Deserialize"));
+        assert!(!output.contains("Serialize for"));
+
+        let output = taxonomy_impl(quote! { tax, serde = "serialize" }, input.clone())
+            .unwrap()
+            .to_string();
+        assert!(!output.contains("Deserialize"));
+        assert!(output.contains("Serialize for"));
+
+        let output = taxonomy_impl(quote! { tax, serde = "strict" }, input.clone())
+            .unwrap()
+            .to_string();
+        assert!(output.contains("deserialize_enveloped"));
+        assert!(output.contains("serialize_enveloped"));
+
+        let output = taxonomy_impl(quote! { tax, serde = false }, input)
+            .unwrap()
+            .to_string();
+        assert!(!output.contains("serde"));
+    }
+
+    #[test]
+    fn test_taxonomy_impl_zeroize() {
+        let input = quote! {
+            pub enum MyEnum {
+                VariantOne,
+            }
+        };
+
+        let output = taxonomy_impl(quote! { tax, serde = false, zeroize = true }, input.clone())
+            .unwrap()
+            .to_string();
+        assert!(output.contains("ZeroizeOnDrop"));
+        assert!(!output.contains("Copy"));
+        assert!(!output.contains("fn map"));
+
+        let output = taxonomy_impl(quote! { tax, zeroize = false }, input.clone())
+            .unwrap()
+            .to_string();
+        assert!(!output.contains("zeroize"));
+        assert!(output.contains("Copy"));
+
+        let err = taxonomy_impl(quote! { tax, zeroize = "yes" }, input).unwrap_err();
+        assert_eq!("expected boolean literal", err.to_string());
+    }
+
+    #[test]
+    fn test_taxonomy_impl_defmt() {
+        let input = quote! {
+            pub enum MyEnum {
+                VariantOne,
+            }
+        };
+
+        let output = taxonomy_impl(quote! { tax, defmt = true }, input.clone())
+            .unwrap()
+            .to_string();
+        assert!(output.contains("defmt :: Format for VariantOne"));
+        assert!(output.contains("format_masked (& Self :: data_class () , f)"));
+
+        let output = taxonomy_impl(quote! { tax, defmt = false }, input.clone())
+            .unwrap()
+            .to_string();
+        assert!(!output.contains("defmt"));
+
+        let output = taxonomy_impl(
+            quote! { tax, defmt = cfg!(feature = "defmt") },
+            input.clone(),
+        )
+        .unwrap()
+        .to_string();
+        assert!(output.contains(
+            "# [cfg (feature = \"defmt\")] impl < T > crate :: __private :: defmt :: Format for VariantOne"
+        ));
+
+        let err = taxonomy_impl(quote! { tax, defmt = dbg!(true) }, input.clone()).unwrap_err();
+        assert_eq!("expected `cfg!`", err.to_string());
+
+        let err = taxonomy_impl(quote! { tax, defmt }, input).unwrap_err();
+        assert_eq!("expected `=`", err.to_string());
+    }
+
+    #[test]
+    fn test_taxonomy_impl_generic() {
+        let input = quote! {
+            pub enum MyEnum {
+                VariantOne,
+                #[class(level = 2)]
+                VariantTwo,
+            }
+        };
+
+        let output = taxonomy_impl(quote! { tax, generic = true }, input.clone())
+            .unwrap()
+            .to_string();
+        assert!(
+            output.contains(
+                "pub type VariantOne < T > = crate :: ClassifiedAs < T , VariantOneClass >"
+            )
+        );
+        assert!(output.contains("pub struct VariantTwoClass ;"));
+        assert!(output.contains("type VariantTwoRef <"));
+        assert!(output.contains("fn classes ()"));
+        assert!(!output.contains("struct VariantOne <"));
+        assert!(!output.contains("Serialize"));
+
+        let output = taxonomy_impl(quote! { tax, generic = false }, input.clone())
+            .unwrap()
+            .to_string();
+        assert!(output.contains("struct VariantOne <"));
+
+        for (args, message) in [
+            (
+                quote! { tax, generic = true, zeroize = true },
+                "`generic` can't be combined with `zeroize`",
+            ),
+            (
+                quote! { tax, serde = false, generic = true },
+                "`generic` can't be combined with a `serde` mode, generic containers implement serde traits whenever the `serde` feature is enabled",
+            ),
+        ] {
+            let err = taxonomy_impl(args, input.clone()).unwrap_err();
+            assert_eq!(message, err.to_string());
+        }
+
+        let input = quote! {
+            pub enum MyEnum {
+                #[class(upcast = base::Sensitive)]
+                VariantOne,
+            }
+        };
+        let err = taxonomy_impl(quote! { tax, generic = true }, input).unwrap_err();
+        assert_eq!(
+            "upcasts aren't supported by taxonomies created with `generic = true`",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_taxonomy_impl_extends() {
+        let input = quote! {
+            pub enum MyEnum {
+                VariantOne,
+            }
+        };
+
+        let output = taxonomy_impl(
+            quote! { tax, extends(base::BaseTaxonomy, Other) },
+            input.clone(),
+        )
+        .unwrap()
+        .to_string();
+        assert!(output.contains("BaseTaxonomy (base :: BaseTaxonomy)"));
+        assert!(output.contains("MyEnum :: BaseTaxonomy (base) => base . data_class ()"));
+        assert!(output.contains("From < base :: BaseTaxonomy > for MyEnum"));
+        assert!(output.contains("Other (Other)"));
+
+        let err = taxonomy_impl(quote! { tax, extends = base::BaseTaxonomy }, input).unwrap_err();
+        assert_eq!("expected parentheses", err.to_string());
+    }
+
     #[test]
     fn test_taxonomy_impl_serde_without_value() {
         let input = quote! {
@@ -474,6 +1880,224 @@ mod tests {
         assert_eq!("expected `=`", err.to_string());
     }
 
+    #[test]
+    fn test_taxonomy_impl_invalid_class_level() {
+        let input = quote! {
+            pub enum MyEnum {
+                #[class(level = 256)]
+                VariantOne,
+            }
+        };
+
+        let attr_args = quote! { tax };
+        let result = taxonomy_impl(attr_args, input);
+
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert_eq!("expected a level between 0 and 255", err.to_string());
+    }
+
+    #[test]
+    fn test_taxonomy_impl_unknown_class_parameter() {
+        let input = quote! {
+            pub enum MyEnum {
+                #[class(secret = 1)]
+                VariantOne,
+            }
+        };
+
+        let attr_args = quote! { tax };
+        let result = taxonomy_impl(attr_args, input);
+
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert_eq!(
+            "expected `level`, `name`, `id`, `retention_days`, `never_log`, or `upcast`",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_taxonomy_impl_class_policies() {
+        let input = quote! {
+            pub enum MyEnum {
+                #[class(retention_days = 30, never_log)]
+                Password,
+                #[class(never_log = false)]
+                Phone,
+            }
+        };
+
+        let output = taxonomy_impl(quote! { tax }, input).unwrap().to_string();
+        assert!(output.contains(
+            "DataClass :: new (\"tax\" , \"password\") . with_level (0) . with_retention_days (30) . with_never_log (true)"
+        ));
+        assert!(output.contains(
+            "DataClass :: new (\"tax\" , \"phone\") . with_level (0) . with_never_log (false)"
+        ));
+
+        let input = quote! {
+            pub enum MyEnum {
+                #[class(retention_days = -1)]
+                Password,
+            }
+        };
+        assert!(taxonomy_impl(quote! { tax }, input).is_err());
+    }
+
+    #[test]
+    fn test_taxonomy_impl_upcast() {
+        let input = quote! {
+            pub enum MyEnum {
+                #[class(upcast = "data_privacy::core_taxonomy::Sensitive", upcast = other::Secret)]
+                Email,
+                Phone,
+            }
+        };
+
+        let output = taxonomy_impl(quote! { tax }, input).unwrap().to_string();
+        assert!(output.contains(
+            "impl < T > core :: convert :: From < Email < T >> for data_privacy :: core_taxonomy :: Sensitive < T >"
+        ));
+        assert!(output.contains("for other :: Secret < T >"));
+        assert!(!output.contains("From < Phone < T >> for data_privacy"));
+
+        let input = quote! {
+            pub enum MyEnum {
+                #[class(upcast = "not a path")]
+                Email,
+            }
+        };
+        assert!(taxonomy_impl(quote! { tax }, input).is_err());
+    }
+
+    #[test]
+    fn test_taxonomy_impl_borrowed_aliases() {
+        let input = quote! {
+            pub enum MyEnum {
+                Email,
+                Phone,
+                PhoneRef,
+            }
+        };
+
+        let output = taxonomy_impl(quote! { tax }, input).unwrap().to_string();
+        assert!(output.contains("pub type EmailRef < 'a , T > = Email < & 'a T >"));
+        assert!(!output.contains("type PhoneRef <"));
+        assert!(output.contains("pub type PhoneRefRef < 'a , T > = PhoneRef < & 'a T >"));
+    }
+
+    #[test]
+    fn test_taxonomy_impl_duplicate_arguments() {
+        let input = quote! {
+            pub enum MyEnum {
+                Email,
+            }
+        };
+        let err =
+            taxonomy_impl(quote! { tax, zeroize = false, zeroize = true }, input).unwrap_err();
+        assert_eq!("duplicate `zeroize` argument", err.to_string());
+
+        let input = quote! {
+            pub enum MyEnum {
+                #[class(never_log, name = "email")]
+                #[class(never_log = false)]
+                Email,
+            }
+        };
+        let err = taxonomy_impl(quote! { tax }, input).unwrap_err();
+        assert_eq!("duplicate `never_log` argument", err.to_string());
+    }
+
+    #[test]
+    fn test_taxonomy_impl_reports_every_invalid_variant() {
+        let input = quote! {
+            pub enum MyEnum {
+                #[class(level = 300)]
+                Email,
+                Phone(String),
+                #[class(name = "email")]
+                Address,
+            }
+        };
+
+        let messages: Vec<_> = taxonomy_impl(quote! { tax }, input)
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "expected a level between 0 and 255",
+                "the taxonomy attribute only supports unit variants",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_taxonomy_impl_custom_class_name_and_id() {
+        let input = quote! {
+            pub enum MyEnum {
+                #[class(name = "pii.email", id = 17)]
+                Email,
+                Phone,
+            }
+        };
+
+        let output = taxonomy_impl(quote! { tax }, input).unwrap().to_string();
+        assert!(output.contains(
+            "DataClass :: new (\"tax\" , \"pii.email\") . with_level (0) . with_id (17)"
+        ));
+        assert!(output.contains("DataClass :: new (\"tax\" , \"phone\") . with_level (0) }"));
+        assert!(!output.contains("\"email\""));
+    }
+
+    #[test]
+    fn test_taxonomy_impl_invalid_class_name() {
+        let input = quote! {
+            pub enum MyEnum {
+                #[class(name = "pii/email")]
+                Email,
+            }
+        };
+
+        let err = taxonomy_impl(quote! { tax }, input).unwrap_err();
+        assert_eq!(
+            "class names must be non-empty and only contain ASCII letters, digits, `_`, `.`, or `-`",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_taxonomy_impl_duplicate_class_name() {
+        let input = quote! {
+            pub enum MyEnum {
+                #[class(name = "phone")]
+                Email,
+                Phone,
+            }
+        };
+
+        let err = taxonomy_impl(quote! { tax }, input).unwrap_err();
+        assert_eq!("duplicate class name `phone`", err.to_string());
+    }
+
+    #[test]
+    fn test_taxonomy_impl_duplicate_class_id() {
+        let input = quote! {
+            pub enum MyEnum {
+                #[class(id = 3)]
+                Email,
+                #[class(id = 3)]
+                Phone,
+            }
+        };
+
+        let err = taxonomy_impl(quote! { tax }, input).unwrap_err();
+        assert_eq!("duplicate class id `3`", err.to_string());
+    }
+
     #[test]
     fn test_success() {
         let args = quote! { tax, serde = true };
@@ -482,6 +2106,7 @@ mod tests {
                 #[doc("Really secret data")]
                 #[doc("More secret data")]
                 Confidential,
+                #[class(level = 2)]
                 TopSecret,
             }
         };
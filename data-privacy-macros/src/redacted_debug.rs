@@ -0,0 +1,197 @@
+use crate::extract::classified_attr;
+use crate::{SynResult, data_privacy_path};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Field, Fields, Index, parse2};
+
+/// Generates an expression formatting a set of fields with the formatter's debug builders.
+///
+/// The accessor returns, for each field, an expression of a reference to the field's value.
+fn fields_expr(
+    data_privacy_path: &TokenStream,
+    name: &str,
+    fields: &Fields,
+    access: impl Fn(usize, &Field) -> TokenStream,
+) -> SynResult<TokenStream> {
+    let mut values = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let reference = access(index, field);
+        values.push(classified_attr(field)?.map_or_else(
+            || reference,
+            |class| quote!(&#data_privacy_path::__private::RedactedField(#class.data_class())),
+        ));
+    }
+
+    Ok(match fields {
+        Fields::Named(fields) => {
+            let names = fields
+                .named
+                .iter()
+                .filter_map(|field| field.ident.as_ref().map(ToString::to_string));
+            quote! {
+                f.debug_struct(#name)
+                    #(.field(#names, #values))*
+                    .finish()
+            }
+        }
+        Fields::Unnamed(_) => quote! {
+            f.debug_tuple(#name)
+                #(.field(#values))*
+                .finish()
+        },
+        Fields::Unit => quote! {
+            f.write_str(#name)
+        },
+    })
+}
+
+pub fn redacted_debug_impl(item: TokenStream) -> SynResult<TokenStream> {
+    let mut input: DeriveInput = parse2(item)?;
+
+    let data_privacy_path = data_privacy_path(&input)?;
+
+    // like `#[derive(Debug)]`, require each type parameter to implement `Debug`
+    let type_params: Vec<_> = input
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = input.generics.make_where_clause();
+    for param in type_params {
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#param: core::fmt::Debug));
+    }
+
+    let type_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(struct_data) => fields_expr(
+            &data_privacy_path,
+            &type_name.to_string(),
+            &struct_data.fields,
+            |index, field| {
+                let member = field.ident.as_ref().map_or_else(
+                    || {
+                        let index = Index::from(index);
+                        quote!(#index)
+                    },
+                    |ident| quote!(#ident),
+                );
+                quote!(&self.#member)
+            },
+        )?,
+        Data::Enum(enum_data) => {
+            let mut arms = Vec::new();
+            for variant in &enum_data.variants {
+                let variant_name = &variant.ident;
+                let bindings: Vec<_> = (0..variant.fields.len())
+                    .map(|index| format_ident!("__field{index}"))
+                    .collect();
+
+                let pattern = match &variant.fields {
+                    Fields::Named(fields) => {
+                        let names = fields.named.iter().map(|field| &field.ident);
+                        quote!(Self::#variant_name { #(#names: #bindings),* })
+                    }
+                    Fields::Unnamed(_) => quote!(Self::#variant_name(#(#bindings),*)),
+                    Fields::Unit => quote!(Self::#variant_name),
+                };
+
+                let expr = fields_expr(
+                    &data_privacy_path,
+                    &variant_name.to_string(),
+                    &variant.fields,
+                    |index, _| {
+                        let binding = &bindings[index];
+                        quote!(#binding)
+                    },
+                )?;
+
+                arms.push(quote! {
+                    #pattern => #expr,
+                });
+            }
+
+            if arms.is_empty() {
+                quote! {
+                    match *self {}
+                }
+            } else {
+                quote! {
+                    match self {
+                        #(#arms)*
+                    }
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input,
+                "RedactedDebug can only be derived for structs and enums",
+            ));
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics core::fmt::Debug for #type_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                #body
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+
+    fn pretty(input: TokenStream) -> String {
+        let result = redacted_debug_impl(input).unwrap();
+        prettyplease::unparse(&syn::parse_file(&result.to_string()).unwrap())
+    }
+
+    #[test]
+    fn test_redacted_debug_impl_union() {
+        let input = quote! {
+            union MyUnion {
+                a: u32,
+            }
+        };
+
+        let err = redacted_debug_impl(input).unwrap_err();
+        assert_eq!(
+            "RedactedDebug can only be derived for structs and enums",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_redacted_debug_impl_enum() {
+        let input = quote! {
+            enum Contact<T> {
+                Email(#[classified(ExampleTaxonomy::Pii)] String),
+                Phone { country: u16, number: T },
+                Unknown,
+            }
+        };
+
+        assert_snapshot!(pretty(input));
+    }
+
+    #[test]
+    fn test_redacted_debug_impl_success() {
+        let input = quote! {
+            struct Employee {
+                #[classified(ExampleTaxonomy::Pii)]
+                email: String,
+                name: Sensitive<String>,
+                age: u32,
+            }
+        };
+
+        assert_snapshot!(pretty(input));
+    }
+}
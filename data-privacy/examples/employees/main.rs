@@ -35,18 +35,17 @@ mod employee;
 mod example_taxonomy;
 mod logging;
 
-use data_privacy::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+use data_privacy::{
+    RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode, set_global_redaction_engine,
+};
 use employee::Employee;
 use example_taxonomy::{
     ExampleTaxonomy, OrganizationallyIdentifiableInformation, PersonallyIdentifiableInformation,
 };
-use logging::{log, set_redaction_engine_for_logging};
+use logging::log;
 use std::fs::{File, OpenOptions};
 use std::io::BufReader;
 
-use data_privacy::Classified;
-use data_privacy::DataClass;
-
 fn main() {
     // First step, we create a redaction engine that prescribes how to redact individual data classes.
     // Normally, the specific algorithm to adopt for a given data class would be controlled by external configuration,
@@ -66,7 +65,7 @@ fn main() {
         .build();
 
     // now configure the logging system to use the redaction engine
-    set_redaction_engine_for_logging(engine);
+    set_global_redaction_engine(engine).unwrap();
 
     // now go run the app's business logic
     app_loop();
@@ -108,13 +107,16 @@ fn app_loop() {
     // Here we log the employee creation event. Our little logging framework takes as input a set of name/value pairs that provide
     // a structured log record.
     //
-    // For each value, you can control which trait is used to format the value into a string:
-    //   `name` - formats the value with the `Display` trait.
-    //   `name:?` - formats the value with the `Debug` trait.
-    //   `mame:@` - formats the value with the `Display` trait and redacts it.
-    log!(event:? = "Employee created",
-         name:@ = employee.name,
-         address:@ = employee.address,
-         employee_id:@ = employee.id,
-         age = employee.age);
+    // For each value, you can control how the value is formatted into a string:
+    //   `display` - formats the value with the `Display` trait.
+    //   `debug` - formats the value with the `Debug` trait.
+    //   `classified` - formats the value with the `Display` trait and redacts it.
+    log(|record| {
+        record
+            .display("event", &"Employee created")
+            .classified("name", &employee.name)
+            .classified("address", &employee.address)
+            .classified("employee_id", &employee.id)
+            .display("age", &employee.age)
+    });
 }
@@ -0,0 +1,339 @@
+use crate::RedactionEngine;
+use crate::tag_scanner::{ScanState, complete_len};
+use core::fmt::{Debug, Formatter};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_io::AsyncWrite;
+use std::io;
+use std::sync::Arc;
+
+/// Redacts classification tags in text as it's written to an asynchronous sink.
+///
+/// Text written to this adapter is scanned for tags in the engine's
+/// [tag format](RedactionEngine::tag_format), such as `<taxonomy/class:value>` by default, as produced by
+/// the tagging modes of [`SimpleRedactor`](crate::SimpleRedactor), and each tag is replaced with the
+/// output of the redactor registered for its data class, exactly like
+/// [`RedactionEngine::redact_tags`]. Large outputs, such as batches of NDJSON telemetry, can therefore be
+/// redacted as they stream to their destination rather than being redacted in memory all at once.
+///
+/// Writes may split tags or UTF-8 sequences at any point. Text which might be the start of a tag is
+/// held back until the tag is complete, so a tag's value never reaches the sink in plain text. As a
+/// result, flushing only writes out text up to the start of any tag still open. Closing the writer
+/// writes out everything, redacting an unterminated tag up to the end of the text. Invalid UTF-8 is
+/// replaced with `U+FFFD`.
+///
+/// The text held back is limited to [`DEFAULT_MAX_PENDING`](Self::DEFAULT_MAX_PENDING) bytes by default,
+/// which can be changed with [`with_max_pending`](Self::with_max_pending). A tag which exceeds the limit
+/// fails the write with an [`InvalidData`](io::ErrorKind::InvalidData) error, and the text held back is
+/// discarded rather than written out. The writer can't be written to after that, as the rest of the
+/// tag's value would otherwise reach the sink in plain text.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::CoreTaxonomy;
+/// use data_privacy::{AsyncRedactedWriter, RedactionEngineBuilder, SimpleRedactor};
+/// use futures::AsyncWriteExt;
+///
+/// let engine = RedactionEngineBuilder::new()
+///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+///     .build()
+///     .into_shared();
+///
+/// let mut writer = AsyncRedactedWriter::new(engine, Vec::new());
+/// futures::executor::block_on(async {
+///     writer.write_all(b"{\"user\":\"<core/sensi").await.unwrap();
+///     writer.write_all(b"tive:jdoe>\"}\n").await.unwrap();
+///     writer.close().await.unwrap();
+/// });
+///
+/// assert_eq!(writer.into_inner(), b"{\"user\":\"****\"}\n");
+/// ```
+pub struct AsyncRedactedWriter<W> {
+    engine: Arc<RedactionEngine>,
+    writer: W,
+    undecoded: Vec<u8>,
+    pending: String,
+    scan: ScanState,
+    max_pending: usize,
+    overflowed: bool,
+    redacted: Vec<u8>,
+}
+
+impl<W> AsyncRedactedWriter<W> {
+    /// The default limit on the length of the text held back while waiting for the end of a tag.
+    pub const DEFAULT_MAX_PENDING: usize = 64 * 1024;
+
+    /// Creates a new writer which redacts the text written to it before passing it on to the given writer.
+    #[must_use]
+    pub const fn new(engine: Arc<RedactionEngine>, writer: W) -> Self {
+        Self {
+            engine,
+            writer,
+            undecoded: Vec::new(),
+            pending: String::new(),
+            scan: ScanState::new(),
+            max_pending: Self::DEFAULT_MAX_PENDING,
+            overflowed: false,
+            redacted: Vec::new(),
+        }
+    }
+
+    /// Sets the limit on the length in bytes of the text held back while waiting for the end of a tag.
+    #[must_use]
+    pub const fn with_max_pending(mut self, max_pending: usize) -> Self {
+        self.max_pending = max_pending;
+        self
+    }
+
+    /// Returns a reference to the underlying writer.
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Consumes the adapter, returning the underlying writer.
+    ///
+    /// Any text which hasn't been written to the underlying writer yet is lost, so the adapter should
+    /// be closed first.
+    #[must_use]
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Decodes the bytes written so far, keeping back an incomplete UTF-8 sequence unless `finish` is set.
+    fn decode(&mut self, buf: &[u8], finish: bool) {
+        self.undecoded.extend_from_slice(buf);
+
+        let mut consumed = 0;
+        let mut chunks = self.undecoded.utf8_chunks().peekable();
+        while let Some(chunk) = chunks.next() {
+            let invalid = chunk.invalid();
+            let incomplete = !finish
+                && chunks.peek().is_none()
+                && matches!(core::str::from_utf8(invalid), Err(e) if e.error_len().is_none());
+
+            self.pending.push_str(chunk.valid());
+            consumed += chunk.valid().len();
+            if !invalid.is_empty() && !incomplete {
+                self.pending.push(char::REPLACEMENT_CHARACTER);
+                consumed += invalid.len();
+            }
+        }
+
+        _ = self.undecoded.drain(..consumed);
+    }
+
+    /// Redacts the pending text, leaving out any open tag unless `finish` is set.
+    #[expect(
+        clippy::string_slice,
+        reason = "complete_len returns the position of a character, which is always a character boundary"
+    )]
+    fn redact_pending(&mut self, finish: bool) -> io::Result<()> {
+        let complete = if finish {
+            self.pending.len()
+        } else {
+            complete_len(&self.pending, self.engine.tag_format(), &mut self.scan)
+        };
+
+        let redacted = &mut self.redacted;
+        self.engine.redact_tags(&self.pending[..complete], |s| {
+            redacted.extend_from_slice(s.as_bytes());
+        });
+        _ = self.pending.drain(..complete);
+
+        if self.pending.len() + self.undecoded.len() > self.max_pending {
+            self.overflowed = true;
+            self.pending = String::new();
+            self.undecoded = Vec::new();
+            return Err(overflow_error());
+        }
+
+        Ok(())
+    }
+}
+
+fn overflow_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "a classification tag exceeds the maximum pending length",
+    )
+}
+
+impl<W: AsyncWrite + Unpin> AsyncRedactedWriter<W> {
+    /// Writes the redacted text to the underlying writer.
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while !self.redacted.is_empty() {
+            match Pin::new(&mut self.writer).poll_write(cx, &self.redacted) {
+                Poll::Ready(Ok(0)) => return Poll::Ready(Err(io::ErrorKind::WriteZero.into())),
+                Poll::Ready(Ok(len)) => _ = self.redacted.drain(..len),
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for AsyncRedactedWriter<W> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = &mut *self;
+        if let Poll::Ready(Err(e)) = this.poll_drain(cx) {
+            return Poll::Ready(Err(e));
+        }
+
+        if !this.redacted.is_empty() {
+            return Poll::Pending;
+        }
+
+        if this.overflowed {
+            return Poll::Ready(Err(overflow_error()));
+        }
+
+        this.decode(buf, false);
+        Poll::Ready(this.redact_pending(false).map(|()| buf.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        match this.poll_drain(cx) {
+            Poll::Ready(Ok(())) => Pin::new(&mut this.writer).poll_flush(cx),
+            other => other,
+        }
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        if !(this.overflowed || this.pending.is_empty() && this.undecoded.is_empty()) {
+            this.decode(&[], true);
+            this.redact_pending(true)?;
+        }
+
+        match this.poll_drain(cx) {
+            Poll::Ready(Ok(())) => Pin::new(&mut this.writer).poll_close(cx),
+            other => other,
+        }
+    }
+}
+
+impl<W: Debug> Debug for AsyncRedactedWriter<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AsyncRedactedWriter")
+            .field("writer", &self.writer)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::CoreTaxonomy;
+    use crate::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+    use futures::AsyncWriteExt;
+    use futures::executor::block_on;
+
+    fn create_writer() -> AsyncRedactedWriter<Vec<u8>> {
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')),
+            )
+            .build()
+            .into_shared();
+
+        AsyncRedactedWriter::new(engine, Vec::new())
+    }
+
+    fn write_chunks(chunks: &[&[u8]]) -> Vec<u8> {
+        let mut writer = create_writer();
+        block_on(async {
+            for chunk in chunks {
+                writer.write_all(chunk).await.unwrap();
+            }
+            writer.close().await.unwrap();
+        });
+        writer.into_inner()
+    }
+
+    #[test]
+    fn tags_should_be_redacted_across_writes() {
+        let text = "a <core/sensitive:héllo> b <core/sensitive:x>\n".as_bytes();
+
+        for split in 0..=text.len() {
+            let chunks: [&[u8]; 2] = text.split_at(split).into();
+            assert_eq!(
+                String::from_utf8(write_chunks(&chunks)).unwrap(),
+                "a ###### b #\n",
+                "split at {split}"
+            );
+        }
+    }
+
+    #[test]
+    fn flush_should_hold_back_open_tags() {
+        let mut writer = create_writer();
+        block_on(async {
+            writer.write_all(b"id=1 <core/sensitive:ab").await.unwrap();
+            writer.flush().await.unwrap();
+            assert_eq!(writer.get_ref(), b"id=1 ");
+
+            writer.write_all(b"c> done").await.unwrap();
+            writer.flush().await.unwrap();
+            assert_eq!(writer.get_ref(), b"id=1 ### done");
+        });
+    }
+
+    #[test]
+    fn close_should_redact_unterminated_tags() {
+        assert_eq!(
+            write_chunks(&[b"1 < 2 <core/sensitive:oops"]),
+            b"1 < 2 ####"
+        );
+        assert_eq!(
+            write_chunks(&[b"bad \xFF utf8"]),
+            "bad \u{FFFD} utf8".as_bytes()
+        );
+    }
+
+    #[test]
+    fn long_open_tags_should_fail_closed() {
+        let mut writer = create_writer().with_max_pending(16);
+        block_on(async {
+            writer.write_all(b"ok <core/sensitive:").await.unwrap();
+            let err = writer.write_all(b"0123456789").await.unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+            let err = writer.write_all(b"> rest").await.unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            writer.close().await.unwrap();
+        });
+        assert_eq!(writer.get_ref(), b"ok ");
+    }
+
+    #[test]
+    fn long_complete_text_should_not_be_held_back() {
+        let text = "x".repeat(100);
+        let mut writer = create_writer().with_max_pending(16);
+        block_on(async {
+            for _ in 0..10 {
+                writer.write_all(text.as_bytes()).await.unwrap();
+            }
+            writer.write_all(b"<core/sensitive:abc>").await.unwrap();
+            writer.close().await.unwrap();
+        });
+        assert_eq!(writer.get_ref().len(), 1003);
+    }
+
+    #[test]
+    fn debug_should_show_writer() {
+        assert_eq!(
+            format!("{:?}", create_writer()),
+            "AsyncRedactedWriter { writer: [], .. }"
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use super::{AuditEvent, AuditSink};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::UNIX_EPOCH;
+
+/// An audit sink which appends events to a file, rotating it once it reaches a size limit.
+///
+/// Each event is written as a single line holding the time of the event in seconds since the Unix
+/// epoch, the data class, and the source location of the declassification:
+///
+/// ```text
+/// 1718035200.125 core/sensitive src/main.rs:42:17
+/// ```
+///
+/// When writing an event would grow the file beyond its size limit, the file is renamed by appending
+/// `.1` to its name, previously rotated files are shifted to `.2`, `.3`, and so on, and a new file is
+/// started. Only a limited number of rotated files are kept, one by default. Write errors are ignored,
+/// since there is nowhere to report them from within a declassification.
+#[derive(Debug)]
+pub struct FileAuditSink {
+    path: PathBuf,
+    max_size: u64,
+    max_backups: usize,
+    state: Mutex<FileState>,
+}
+
+#[derive(Debug)]
+struct FileState {
+    file: File,
+    size: u64,
+}
+
+impl FileAuditSink {
+    /// Creates a new sink appending to the file at the given path, which is created if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be opened.
+    pub fn new(path: impl AsRef<Path>, max_size: u64) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = open(&path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            path,
+            max_size,
+            max_backups: 1,
+            state: Mutex::new(FileState { file, size }),
+        })
+    }
+
+    /// Sets how many rotated files are kept around.
+    ///
+    /// With a value of 0, the file is truncated instead of being rotated.
+    #[must_use]
+    pub const fn with_max_backups(mut self, max_backups: usize) -> Self {
+        self.max_backups = max_backups;
+        self
+    }
+
+    fn rotate(&self, state: &mut FileState) -> io::Result<()> {
+        if self.max_backups > 0 {
+            for index in (1..self.max_backups).rev() {
+                let from = self.backup_path(index);
+                if from.exists() {
+                    fs::rename(&from, self.backup_path(index + 1))?;
+                }
+            }
+
+            fs::rename(&self.path, self.backup_path(1))?;
+        } else {
+            fs::remove_file(&self.path)?;
+        }
+
+        state.file = open(&self.path)?;
+        state.size = 0;
+        Ok(())
+    }
+
+    fn backup_path(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{index}"));
+        path.into()
+    }
+}
+
+impl AuditSink for FileAuditSink {
+    fn record(&self, event: &AuditEvent) {
+        let timestamp = event
+            .timestamp()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        let line = format!(
+            "{}.{:03} {} {}\n",
+            timestamp.as_secs(),
+            timestamp.subsec_millis(),
+            event.data_class(),
+            event.location()
+        );
+
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let len = line.len() as u64;
+
+        if state.size > 0 && state.size + len > self.max_size {
+            _ = self.rotate(&mut state);
+        }
+
+        if state.file.write_all(line.as_bytes()).is_ok() {
+            state.size += len;
+        }
+    }
+}
+
+fn open(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DataClass;
+    use core::panic::Location;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("data_privacy_audit_{name}_{}", std::process::id()));
+        _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn event() -> AuditEvent {
+        AuditEvent::new(DataClass::new("taxonomy", "class"), Location::caller())
+    }
+
+    #[test]
+    fn sink_should_append_lines() {
+        let dir = test_dir("append");
+        let path = dir.join("audit.log");
+
+        let sink = FileAuditSink::new(&path, 1024 * 1024).unwrap();
+        sink.record(&event());
+        sink.record(&event());
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains(" taxonomy/class "));
+        assert!(lines[0].contains(file!()));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn sink_should_rotate_when_full() {
+        let dir = test_dir("rotate");
+        let path = dir.join("audit.log");
+
+        // small enough that each event needs its own file
+        let sink = FileAuditSink::new(&path, 10).unwrap().with_max_backups(2);
+
+        for _ in 0..4 {
+            sink.record(&event());
+        }
+
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 1);
+        assert_eq!(
+            fs::read_to_string(sink.backup_path(1))
+                .unwrap()
+                .lines()
+                .count(),
+            1
+        );
+        assert_eq!(
+            fs::read_to_string(sink.backup_path(2))
+                .unwrap()
+                .lines()
+                .count(),
+            1
+        );
+        assert!(!sink.backup_path(3).exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn sink_should_truncate_without_backups() {
+        let dir = test_dir("truncate");
+        let path = dir.join("audit.log");
+
+        let sink = FileAuditSink::new(&path, 10).unwrap().with_max_backups(0);
+
+        sink.record(&event());
+        sink.record(&event());
+
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 1);
+        assert!(!sink.backup_path(1).exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
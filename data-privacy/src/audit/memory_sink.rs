@@ -0,0 +1,70 @@
+use super::{AuditEvent, AuditSink};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// An audit sink which captures events in memory.
+///
+/// Clones of this sink share the same storage, so a clone can be installed as the
+/// process-wide sink while the original is kept around to inspect the captured events.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryAuditSink {
+    events: Arc<Mutex<Vec<AuditEvent>>>,
+}
+
+impl MemoryAuditSink {
+    /// Creates a new empty sink.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a copy of the events captured so far.
+    #[must_use]
+    pub fn events(&self) -> Vec<AuditEvent> {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Discards all captured events.
+    pub fn clear(&self) {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+impl AuditSink for MemoryAuditSink {
+    fn record(&self, event: &AuditEvent) {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(event.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DataClass;
+    use core::panic::Location;
+
+    #[test]
+    fn sink_should_capture_events() {
+        let sink = MemoryAuditSink::new();
+        let clone = sink.clone();
+
+        clone.record(&AuditEvent::new(
+            DataClass::new("taxonomy", "class"),
+            Location::caller(),
+        ));
+
+        let events = sink.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data_class(), &DataClass::new("taxonomy", "class"));
+
+        sink.clear();
+        assert!(clone.events().is_empty());
+    }
+}
@@ -0,0 +1,291 @@
+//! An audit trail of the places where classified data is declassified.
+//!
+//! Declassifying data is the one point where sensitive information leaves the protection of
+//! its classified container. To make these points reviewable, every call to `declassify` on a
+//! container generated by the [`taxonomy`](crate::taxonomy) attribute is reported to the process-wide
+//! [`AuditSink`], along with the data class involved and the source location of the call.
+//!
+//! Audit sinks require the `std` feature. Without it, declassification can still be observed with a
+//! lightweight hook installed by [`set_declassify_observer`], which is invoked in addition to the sink.
+//!
+//! No sink is installed by default, in which case declassification isn't audited. Install a sink once
+//! at startup with [`set_audit_sink`]. This module provides a few ready-made sinks:
+//!
+//! * [`MemoryAuditSink`] captures events in memory, which is mostly useful in tests.
+//! * [`FileAuditSink`] appends events to a file, rotating it when it grows too large.
+//! * [`ThresholdAuditSink`] counts declassifications per class and raises alerts on unusual activity.
+//! * `TracingAuditSink` emits events through the `tracing` crate, when the `tracing` feature is enabled.
+//!
+//! # Example
+//!
+#![cfg_attr(feature = "std", doc = "```rust")]
+#![cfg_attr(not(feature = "std"), doc = "```ignore")]
+//! use data_privacy::audit::{set_audit_sink, MemoryAuditSink};
+//! use data_privacy::core_taxonomy::Sensitive;
+//!
+//! let sink = MemoryAuditSink::new();
+//! assert!(set_audit_sink(Box::new(sink.clone())).is_ok());
+//!
+//! let _name = Sensitive::new("John Doe").declassify();
+//!
+//! assert_eq!(sink.events().len(), 1);
+//! assert_eq!(sink.events()[0].data_class().name(), "sensitive");
+//! ```
+
+#[cfg(feature = "std")]
+mod file_sink;
+#[cfg(feature = "std")]
+mod memory_sink;
+#[cfg(feature = "std")]
+mod threshold_sink;
+#[cfg(feature = "tracing")]
+mod tracing_sink;
+
+#[cfg(feature = "std")]
+pub use file_sink::FileAuditSink;
+#[cfg(feature = "std")]
+pub use memory_sink::MemoryAuditSink;
+#[cfg(feature = "std")]
+pub use threshold_sink::ThresholdAuditSink;
+#[cfg(feature = "tracing")]
+pub use tracing_sink::TracingAuditSink;
+
+use crate::DataClass;
+use core::panic::Location;
+use core::sync::atomic::{AtomicPtr, Ordering};
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+#[cfg(feature = "std")]
+static AUDIT_SINK: OnceLock<Box<dyn AuditSink>> = OnceLock::new();
+
+/// The installed [`DeclassifyObserver`], stored as a raw pointer so it can be set without `std`.
+static DECLASSIFY_OBSERVER: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// A function invoked whenever classified data is declassified.
+///
+/// The function receives the data class of the declassified data and the source location of the call
+/// to `declassify`. Like [sinks](AuditSink), observers are invoked synchronously on the thread doing
+/// the declassification, so they should be quick and must not panic.
+pub type DeclassifyObserver = fn(&DataClass, &'static Location<'static>);
+
+/// A record of classified data being declassified.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEvent {
+    data_class: DataClass,
+    location: &'static Location<'static>,
+    timestamp: SystemTime,
+}
+
+#[cfg(feature = "std")]
+impl AuditEvent {
+    /// Creates a new event for data of the given class declassified at the given location.
+    #[must_use]
+    pub fn new(data_class: DataClass, location: &'static Location<'static>) -> Self {
+        Self {
+            data_class,
+            location,
+            timestamp: SystemTime::now(),
+        }
+    }
+
+    /// Sets the time at which the data was declassified, which defaults to the time the event was created.
+    #[must_use]
+    pub const fn with_timestamp(mut self, timestamp: SystemTime) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Returns the data class of the declassified data.
+    #[must_use]
+    pub const fn data_class(&self) -> &DataClass {
+        &self.data_class
+    }
+
+    /// Returns the source location where the data was declassified.
+    #[must_use]
+    pub const fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Returns the time at which the data was declassified.
+    #[must_use]
+    pub const fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+}
+
+/// Receives audit events whenever classified data is declassified.
+///
+/// Sinks are invoked synchronously on the thread doing the declassification, so they
+/// should be quick and must not panic.
+#[cfg(feature = "std")]
+pub trait AuditSink: Send + Sync {
+    /// Records a single audit event.
+    fn record(&self, event: &AuditEvent);
+}
+
+/// Installs the process-wide audit sink.
+///
+/// The sink can only be set once, typically when the application starts.
+///
+/// # Errors
+///
+/// Returns the given sink back if a sink has already been set.
+#[cfg(feature = "std")]
+pub fn set_audit_sink(sink: Box<dyn AuditSink>) -> Result<(), Box<dyn AuditSink>> {
+    AUDIT_SINK.set(sink)
+}
+
+/// Installs the process-wide declassification observer.
+///
+/// Unlike [`set_audit_sink`], this doesn't require the `std` feature, which makes it suitable for
+/// auditing in `no_std` environments. When both are installed, the observer is invoked before the sink.
+///
+/// The observer can only be set once, typically when the application starts.
+///
+/// # Errors
+///
+/// Returns the given observer back if an observer has already been set.
+///
+/// # Example
+///
+/// ```rust
+/// use core::panic::Location;
+/// use data_privacy::DataClass;
+/// use data_privacy::audit::set_declassify_observer;
+/// use data_privacy::core_taxonomy::Sensitive;
+///
+/// fn observe(data_class: &DataClass, location: &'static Location<'static>) {
+///     println!("{data_class} declassified at {location}");
+/// }
+///
+/// assert!(set_declassify_observer(observe).is_ok());
+/// let _name = Sensitive::new("John Doe").declassify();
+/// ```
+#[expect(
+    clippy::fn_to_numeric_cast_any,
+    reason = "The observer is stored as a raw pointer, which is converted back when invoked"
+)]
+pub fn set_declassify_observer(observer: DeclassifyObserver) -> Result<(), DeclassifyObserver> {
+    DECLASSIFY_OBSERVER
+        .compare_exchange(
+            core::ptr::null_mut(),
+            observer as *mut (),
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        )
+        .map(|_| ())
+        .map_err(|_e| observer)
+}
+
+/// Returns the installed declassification observer, if any.
+fn declassify_observer() -> Option<DeclassifyObserver> {
+    let observer = DECLASSIFY_OBSERVER.load(Ordering::Acquire);
+    if observer.is_null() {
+        return None;
+    }
+
+    // SAFETY: The pointer is only ever set from a `DeclassifyObserver` in `set_declassify_observer`.
+    Some(unsafe { core::mem::transmute::<*mut (), DeclassifyObserver>(observer) })
+}
+
+/// Reports a declassification to the installed observer and audit sink, if any.
+#[doc(hidden)]
+#[cfg(feature = "std")]
+#[track_caller]
+pub fn record_declassification(data_class: impl FnOnce() -> DataClass) {
+    let observer = declassify_observer();
+    let sink = AUDIT_SINK.get();
+    if observer.is_none() && sink.is_none() {
+        return;
+    }
+
+    let data_class = data_class();
+    let location = Location::caller();
+    if let Some(observer) = observer {
+        observer(&data_class, location);
+    }
+
+    if let Some(sink) = sink {
+        sink.record(&AuditEvent::new(data_class, location));
+    }
+}
+
+/// Reports a declassification to the installed observer, if any, since audit sinks require `std`.
+#[doc(hidden)]
+#[cfg(not(feature = "std"))]
+#[track_caller]
+pub fn record_declassification(data_class: impl FnOnce() -> DataClass) {
+    if let Some(observer) = declassify_observer() {
+        observer(&data_class(), Location::caller());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::taxonomy;
+    use core::sync::atomic::AtomicU32;
+
+    #[taxonomy(audit_test, serde = false)]
+    enum AuditTestTaxonomy {
+        Audited,
+        Observed,
+    }
+
+    /// The line of the last declassification of an observed value.
+    static OBSERVED_LINE: AtomicU32 = AtomicU32::new(0);
+
+    fn observe(data_class: &DataClass, location: &'static Location<'static>) {
+        if data_class == &AuditTestTaxonomy::Observed.data_class() {
+            OBSERVED_LINE.store(location.line(), Ordering::Relaxed);
+        }
+    }
+
+    // This is the only test that installs the process-wide sink.
+    #[test]
+    #[cfg(feature = "std")]
+    fn declassify_should_report_to_installed_sink() {
+        use crate::Classified;
+
+        let sink = MemoryAuditSink::new();
+        assert!(set_audit_sink(Box::new(sink.clone())).is_ok());
+        assert!(set_audit_sink(Box::new(MemoryAuditSink::new())).is_err());
+
+        let line = line!() + 1;
+        let value = Audited::new(42).declassify();
+        assert_eq!(value, 42);
+
+        // combinators keep the payloads classified, so they aren't declassifications
+        let flat = Classified::flatten(Observed::new(Audited::new(1)));
+        let zipped = Classified::zip(flat, Audited::new(2));
+        zipped.visit(|payload| assert_eq!(*payload, (1, 2)));
+
+        let events: Vec<_> = sink
+            .events()
+            .into_iter()
+            .filter(|event| event.data_class() == &AuditTestTaxonomy::Audited.data_class())
+            .collect();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].location().file(), file!());
+        assert_eq!(events[0].location().line(), line);
+    }
+
+    // This is the only test that installs the process-wide observer.
+    #[test]
+    fn declassify_should_report_to_installed_observer() {
+        assert!(set_declassify_observer(observe).is_ok());
+        assert!(set_declassify_observer(|_, _| {}).is_err());
+
+        let line = line!() + 1;
+        let value = Observed::new(42).declassify();
+        assert_eq!(value, 42);
+
+        assert_eq!(OBSERVED_LINE.load(Ordering::Relaxed), line);
+    }
+}
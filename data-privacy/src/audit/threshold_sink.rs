@@ -0,0 +1,250 @@
+use super::{AuditEvent, AuditSink};
+use crate::DataClass;
+use core::fmt::Debug;
+use core::time::Duration;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, PoisonError};
+use std::time::SystemTime;
+
+type ThresholdCallback = Box<dyn Fn(&DataClass, usize) + Send + Sync>;
+
+/// An audit sink which counts declassifications per data class and raises alerts on unusual activity.
+///
+/// Every event is forwarded to an inner sink, so this can be layered on top of any other sink. In addition,
+/// the sink keeps a running count of declassifications for each data class, and lets you register thresholds.
+/// A threshold invokes a callback once more than a given number of declassifications of a class occur within
+/// a sliding time window, such as more than 100 declassifications of `corp/ssn` in a minute. This turns the
+/// audit trail into an active leak-detection control.
+///
+/// A callback fires when the threshold is first exceeded, and is re-armed once the number of declassifications
+/// within the window drops back to the limit. Callbacks are invoked synchronously on the thread doing the
+/// declassification, so they should be quick, for example by signaling another thread.
+///
+/// # Example
+///
+/// ```rust
+/// use core::time::Duration;
+/// use data_privacy::audit::{AuditSink, MemoryAuditSink, ThresholdAuditSink};
+/// use data_privacy::core_taxonomy::CoreTaxonomy;
+///
+/// let sink = ThresholdAuditSink::new(MemoryAuditSink::new()).add_threshold(
+///     &CoreTaxonomy::Sensitive.data_class(),
+///     100,
+///     Duration::from_secs(60),
+///     |data_class, count| eprintln!("{count} declassifications of {data_class} in the last minute"),
+/// );
+/// ```
+pub struct ThresholdAuditSink<S> {
+    inner: S,
+    thresholds: HashMap<DataClass, Vec<Threshold>>,
+    state: Mutex<State>,
+}
+
+struct Threshold {
+    limit: usize,
+    window: Duration,
+    callback: ThresholdCallback,
+}
+
+#[derive(Default)]
+struct State {
+    totals: HashMap<DataClass, u64>,
+
+    // per class, per threshold: the times of recent events, and whether the alert has fired
+    recent: HashMap<DataClass, Vec<(VecDeque<SystemTime>, bool)>>,
+}
+
+impl<S: AuditSink> ThresholdAuditSink<S> {
+    /// Creates a new sink which forwards events to the given sink.
+    #[must_use]
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            thresholds: HashMap::new(),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Adds a threshold on the number of declassifications of a data class within a time window.
+    ///
+    /// The callback receives the data class and the number of declassifications within the window
+    /// when more than `limit` declassifications occur within `window`.
+    #[must_use]
+    pub fn add_threshold(
+        mut self,
+        data_class: &DataClass,
+        limit: usize,
+        window: Duration,
+        callback: impl Fn(&DataClass, usize) + Send + Sync + 'static,
+    ) -> Self {
+        self.thresholds
+            .entry(data_class.clone())
+            .or_default()
+            .push(Threshold {
+                limit,
+                window,
+                callback: Box::new(callback),
+            });
+        self
+    }
+
+    /// Returns the total number of declassifications recorded for a data class.
+    #[must_use]
+    pub fn count(&self, data_class: &DataClass) -> u64 {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .totals
+            .get(data_class)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns a reference to the inner sink.
+    #[must_use]
+    pub const fn inner(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S: AuditSink> AuditSink for ThresholdAuditSink<S> {
+    fn record(&self, event: &AuditEvent) {
+        self.inner.record(event);
+
+        let data_class = event.data_class();
+        let mut alerts = Vec::new();
+
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        *state.totals.entry(data_class.clone()).or_default() += 1;
+
+        if let Some(thresholds) = self.thresholds.get(data_class) {
+            let recent = state.recent.entry(data_class.clone()).or_insert_with(|| {
+                thresholds
+                    .iter()
+                    .map(|_| (VecDeque::new(), false))
+                    .collect()
+            });
+
+            for (threshold, (times, fired)) in thresholds.iter().zip(recent.iter_mut()) {
+                let now = event.timestamp();
+                while times.front().is_some_and(|time| {
+                    now.duration_since(*time).unwrap_or_default() >= threshold.window
+                }) {
+                    _ = times.pop_front();
+                }
+
+                // the most recent `limit + 1` events are enough to tell whether the limit is exceeded
+                times.push_back(now);
+                if times.len() > threshold.limit.saturating_add(1) {
+                    _ = times.pop_front();
+                }
+
+                if times.len() <= threshold.limit {
+                    *fired = false;
+                } else if !*fired {
+                    *fired = true;
+                    alerts.push((threshold, times.len()));
+                }
+            }
+        }
+
+        drop(state);
+
+        // callbacks run outside the lock, so they're free to declassify data themselves
+        for (threshold, count) in alerts {
+            (threshold.callback)(data_class, count);
+        }
+    }
+}
+
+impl<S: Debug> Debug for ThresholdAuditSink<S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ThresholdAuditSink")
+            .field("inner", &self.inner)
+            .field("thresholds", &self.thresholds.keys())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::MemoryAuditSink;
+    use core::panic::Location;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    const SSN: DataClass = DataClass::new("corp", "ssn");
+    const NAME: DataClass = DataClass::new("corp", "name");
+
+    fn event_at(data_class: &DataClass, seconds: u64) -> AuditEvent {
+        AuditEvent::new(data_class.clone(), Location::caller())
+            .with_timestamp(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+    }
+
+    #[test]
+    fn sink_should_count_and_forward_events() {
+        let sink = ThresholdAuditSink::new(MemoryAuditSink::new());
+        sink.record(&event_at(&SSN, 0));
+        sink.record(&event_at(&SSN, 1));
+        sink.record(&event_at(&NAME, 2));
+
+        assert_eq!(sink.count(&SSN), 2);
+        assert_eq!(sink.count(&NAME), 1);
+        assert_eq!(sink.count(&DataClass::new("corp", "other")), 0);
+        assert_eq!(sink.inner().events().len(), 3);
+    }
+
+    #[test]
+    fn threshold_should_fire_once_when_exceeded_within_window() {
+        let alerts = Arc::new(AtomicUsize::new(0));
+        let alerts_clone = Arc::clone(&alerts);
+
+        let sink = ThresholdAuditSink::new(MemoryAuditSink::new()).add_threshold(
+            &SSN,
+            2,
+            Duration::from_secs(60),
+            move |data_class, count| {
+                assert_eq!(data_class, &SSN);
+                assert_eq!(count, 3);
+                _ = alerts_clone.fetch_add(1, Ordering::SeqCst);
+            },
+        );
+
+        sink.record(&event_at(&SSN, 0));
+        sink.record(&event_at(&SSN, 10));
+        sink.record(&event_at(&NAME, 15));
+        assert_eq!(alerts.load(Ordering::SeqCst), 0);
+
+        sink.record(&event_at(&SSN, 20));
+        assert_eq!(alerts.load(Ordering::SeqCst), 1);
+
+        // still above the limit, so no new alert
+        sink.record(&event_at(&SSN, 30));
+        sink.record(&event_at(&SSN, 40));
+        assert_eq!(alerts.load(Ordering::SeqCst), 1);
+
+        // the window has moved on, re-arming the alert
+        sink.record(&event_at(&SSN, 200));
+        sink.record(&event_at(&SSN, 201));
+        assert_eq!(alerts.load(Ordering::SeqCst), 1);
+        sink.record(&event_at(&SSN, 202));
+        assert_eq!(alerts.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn recent_events_should_be_bounded_by_the_limit() {
+        let sink = ThresholdAuditSink::new(MemoryAuditSink::new()).add_threshold(
+            &SSN,
+            2,
+            Duration::from_secs(60),
+            |_, _| {},
+        );
+
+        for second in 0..50 {
+            sink.record(&event_at(&SSN, second));
+        }
+
+        assert_eq!(sink.state.lock().unwrap().recent[&SSN][0].0.len(), 3);
+    }
+}
@@ -0,0 +1,42 @@
+use super::{AuditEvent, AuditSink};
+
+/// An audit sink which emits events through the `tracing` crate.
+///
+/// Events are emitted at the `INFO` level with the `data_privacy::audit` target, and carry
+/// the data class and source location of the declassification as fields.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingAuditSink;
+
+impl TracingAuditSink {
+    /// Creates a new sink.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl AuditSink for TracingAuditSink {
+    fn record(&self, event: &AuditEvent) {
+        tracing::info!(
+            target: "data_privacy::audit",
+            data_class = %event.data_class(),
+            location = %event.location(),
+            "classified data declassified"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DataClass;
+    use core::panic::Location;
+
+    #[test]
+    fn sink_should_emit_without_subscriber() {
+        TracingAuditSink::new().record(&AuditEvent::new(
+            DataClass::new("taxonomy", "class"),
+            Location::caller(),
+        ));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::DataClass;
+use core::fmt::Display;
+
+/// Errors reported while configuring a [`RedactionEngineBuilder`](crate::RedactionEngineBuilder).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BuilderError {
+    /// A redactor was already registered for the given data class.
+    DuplicateClassRedactor(DataClass),
+
+    /// A data class name wasn't of the form `taxonomy/class`.
+    InvalidDataClass(String),
+
+    /// No redactor of the given kind is registered.
+    UnknownRedactor(String),
+
+    /// A required option of a redactor wasn't set.
+    MissingRedactorOption {
+        /// The kind of redactor.
+        redactor: String,
+
+        /// The name of the option.
+        option: String,
+    },
+
+    /// An option the redactor doesn't support was set.
+    UnknownRedactorOption {
+        /// The kind of redactor.
+        redactor: String,
+
+        /// The name of the option.
+        option: String,
+    },
+
+    /// An option of a redactor had an invalid value.
+    InvalidRedactorOption {
+        /// The kind of redactor.
+        redactor: String,
+
+        /// The name of the option.
+        option: String,
+    },
+
+    /// Data classes expected to be handled explicitly would be redacted by the fallback redactor.
+    MissingClassRedactors(Vec<DataClass>),
+
+    /// A data class which must never be logged would be redacted by a redactor which may reveal its input.
+    RevealingRedactor(DataClass),
+}
+
+impl Display for BuilderError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::DuplicateClassRedactor(data_class) => {
+                write!(
+                    f,
+                    "a redactor is already registered for data class {data_class}"
+                )
+            }
+            Self::InvalidDataClass(name) => {
+                write!(f, "invalid data class `{name}`, expected `taxonomy/class`")
+            }
+            Self::UnknownRedactor(kind) => write!(f, "unknown redactor `{kind}`"),
+            Self::MissingRedactorOption { redactor, option } => {
+                write!(f, "redactor `{redactor}` requires option `{option}`")
+            }
+            Self::UnknownRedactorOption { redactor, option } => {
+                write!(f, "redactor `{redactor}` has no option `{option}`")
+            }
+            Self::InvalidRedactorOption { redactor, option } => {
+                write!(
+                    f,
+                    "invalid value for option `{option}` of redactor `{redactor}`"
+                )
+            }
+            Self::MissingClassRedactors(data_classes) => {
+                f.write_str("no redactor is registered for data classes ")?;
+                for (index, data_class) in data_classes.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{data_class}")?;
+                }
+                Ok(())
+            }
+            Self::RevealingRedactor(data_class) => {
+                write!(
+                    f,
+                    "data class {data_class} must never be logged, but its redactor may reveal its input"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for BuilderError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_should_name_the_data_class() {
+        let error = BuilderError::DuplicateClassRedactor(DataClass::new("taxonomy", "class"));
+        assert_eq!(
+            error.to_string(),
+            "a redactor is already registered for data class taxonomy/class"
+        );
+    }
+
+    #[test]
+    fn display_should_describe_policy_errors() {
+        assert_eq!(
+            BuilderError::InvalidDataClass("core".to_string()).to_string(),
+            "invalid data class `core`, expected `taxonomy/class`"
+        );
+        assert_eq!(
+            BuilderError::UnknownRedactor("shred".to_string()).to_string(),
+            "unknown redactor `shred`"
+        );
+        assert_eq!(
+            BuilderError::MissingRedactorOption {
+                redactor: "insert".to_string(),
+                option: "text".to_string()
+            }
+            .to_string(),
+            "redactor `insert` requires option `text`"
+        );
+        assert_eq!(
+            BuilderError::UnknownRedactorOption {
+                redactor: "replace".to_string(),
+                option: "chars".to_string()
+            }
+            .to_string(),
+            "redactor `replace` has no option `chars`"
+        );
+        assert_eq!(
+            BuilderError::InvalidRedactorOption {
+                redactor: "replace".to_string(),
+                option: "char".to_string()
+            }
+            .to_string(),
+            "invalid value for option `char` of redactor `replace`"
+        );
+        assert_eq!(
+            BuilderError::MissingClassRedactors(vec![
+                DataClass::new("core", "sensitive"),
+                DataClass::new("corp", "secret")
+            ])
+            .to_string(),
+            "no redactor is registered for data classes core/sensitive, corp/secret"
+        );
+        assert_eq!(
+            BuilderError::RevealingRedactor(DataClass::new("corp", "secret")).to_string(),
+            "data class corp/secret must never be logged, but its redactor may reveal its input"
+        );
+    }
+}
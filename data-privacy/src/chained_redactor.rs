@@ -0,0 +1,176 @@
+use crate::{DataClass, RedactionContext, Redactor};
+use alloc::borrow::Cow;
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::fmt::Debug;
+
+/// A redactor which composes other redactors, each transforming the output of the previous one.
+///
+/// Chaining lets simple redactors be combined instead of writing a custom redactor for every combination.
+/// For example, long values can be [truncated](crate::TruncatingRedactor) before being hashed, with the
+/// hash then tagged with the data class.
+///
+/// Every stage is given the same data class and [`RedactionContext`], including its
+/// [`TagFormat`](crate::TagFormat). Only the output of the last stage is sent to the output callback,
+/// so a chain is only as safe as its last stage. A chain without any stages passes values through
+/// unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::{ChainedRedactor, DataClass, Redactor, SimpleRedactor, SimpleRedactorMode, TruncatingRedactor};
+///
+/// let redactor = ChainedRedactor::new()
+///     .then(TruncatingRedactor::new(4))
+///     .then(SimpleRedactor::with_mode(SimpleRedactorMode::ReplaceAndTag('*')));
+///
+/// let mut output = String::new();
+/// redactor.redact(&DataClass::new("corp", "note"), "a very long note", &mut |s| output.push_str(s));
+/// assert_eq!(output, "<corp/note:****>");
+/// ```
+#[derive(Default)]
+pub struct ChainedRedactor {
+    stages: Vec<Box<dyn Redactor>>,
+}
+
+impl ChainedRedactor {
+    /// Creates a new chain without any stages.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a stage to the end of the chain.
+    #[must_use]
+    pub fn then(self, redactor: impl Redactor + 'static) -> Self {
+        self.then_boxed(Box::new(redactor))
+    }
+
+    /// Adds a boxed stage to the end of the chain.
+    #[must_use]
+    pub fn then_boxed(mut self, redactor: Box<dyn Redactor>) -> Self {
+        self.stages.push(redactor);
+        self
+    }
+}
+
+impl Redactor for ChainedRedactor {
+    fn redact(&self, data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
+        self.redact_ctx(data_class, value, &RedactionContext::new(), output);
+    }
+
+    fn redact_ctx(
+        &self,
+        data_class: &DataClass,
+        value: &str,
+        context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) {
+        let Some((last, stages)) = self.stages.split_last() else {
+            output(value);
+            return;
+        };
+
+        let mut input = Cow::Borrowed(value);
+        for stage in stages {
+            let mut next = String::new();
+            stage.redact_ctx(data_class, &input, context, &mut |s| {
+                next.push_str(s);
+            });
+            input = Cow::Owned(next);
+        }
+
+        last.redact_ctx(data_class, &input, context, output);
+    }
+
+    fn exact_len(&self) -> Option<usize> {
+        self.stages.last()?.exact_len()
+    }
+
+    fn reveals_input(&self) -> bool {
+        self.stages.last().is_none_or(Redactor::reveals_input)
+    }
+}
+
+impl Debug for ChainedRedactor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ChainedRedactor")
+            .field("stages", &self.stages.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::CoreTaxonomy;
+    use crate::{SimpleRedactor, SimpleRedactorMode, TagFormat, TruncatingRedactor};
+    #[cfg(not(feature = "std"))]
+    use alloc::format;
+
+    fn redact_to_string(redactor: &ChainedRedactor, value: &str) -> String {
+        let mut output = String::new();
+        redactor.redact(&CoreTaxonomy::Sensitive.data_class(), value, &mut |s| {
+            output.push_str(s);
+        });
+        output
+    }
+
+    #[test]
+    fn stages_should_apply_in_order() {
+        let redactor = ChainedRedactor::new()
+            .then(TruncatingRedactor::new(3))
+            .then(SimpleRedactor::with_mode(
+                SimpleRedactorMode::ReplaceAndTag('#'),
+            ));
+
+        assert_eq!(
+            redact_to_string(&redactor, "secret"),
+            "<core/sensitive:###>"
+        );
+        assert_eq!(redact_to_string(&redactor, "ab"), "<core/sensitive:##>");
+        assert_eq!(redactor.exact_len(), None);
+    }
+
+    #[test]
+    fn stages_should_use_tag_format() {
+        let redactor = ChainedRedactor::new()
+            .then(SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')))
+            .then(SimpleRedactor::with_mode(
+                SimpleRedactorMode::PassthroughAndTag,
+            ));
+
+        let mut output = String::new();
+        redactor.redact_ctx(
+            &CoreTaxonomy::Sensitive.data_class(),
+            "abc",
+            &RedactionContext::new().with_tag_format(
+                TagFormat::new()
+                    .with_delimiters("[", "]")
+                    .with_separator("|"),
+            ),
+            &mut |s| output.push_str(s),
+        );
+        assert_eq!(output, "[core/sensitive|***]");
+    }
+
+    #[test]
+    fn exact_len_should_come_from_last_stage() {
+        let redactor = ChainedRedactor::new()
+            .then(SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')))
+            .then_boxed(Box::new(SimpleRedactor::with_mode(
+                SimpleRedactorMode::Erase,
+            )));
+
+        assert_eq!(redact_to_string(&redactor, "secret"), "");
+        assert_eq!(redactor.exact_len(), Some(0));
+    }
+
+    #[test]
+    fn empty_chain_should_pass_through() {
+        let redactor = ChainedRedactor::new();
+        assert_eq!(redact_to_string(&redactor, "value"), "value");
+        assert_eq!(redactor.exact_len(), None);
+        assert_eq!(format!("{redactor:?}"), "ChainedRedactor { stages: 0 }");
+    }
+}
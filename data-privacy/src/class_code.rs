@@ -0,0 +1,129 @@
+use core::fmt::Display;
+
+/// A compact code identifying a data class, computed at compile time from its taxonomy and class names.
+///
+/// Comparing codes is a single integer comparison, and since codes of well-known classes are constants,
+/// they can be used as patterns in `match` expressions, letting hot paths dispatch on a data class without
+/// comparing names. Containers generated by the [`taxonomy`](crate::taxonomy) attribute expose the code
+/// of their class as a `CODE` constant, and every [`DataClass`](crate::DataClass) has a
+/// [`code`](crate::DataClass::code).
+///
+/// Codes are a 64-bit FNV-1a hash of the names. They're the identity a [`DataClass`](crate::DataClass)
+/// carries for lookups, and are checked to be unique at compile time: the [`taxonomy`](crate::taxonomy)
+/// attribute rejects taxonomies in which two classes, including those of the taxonomies they extend,
+/// share a code, so matching on the codes of a taxonomy's classes is exact. Classes of unrelated
+/// taxonomies, or classes created from names at runtime, are extremely unlikely to share a code with
+/// them, but code which must be exact in that case should compare the data classes themselves once the
+/// codes match.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::{CoreTaxonomy, Insensitive, Sensitive};
+/// use data_privacy::DataClass;
+///
+/// fn is_public(data_class: &DataClass) -> bool {
+///     match data_class.code() {
+///         Insensitive::<()>::CODE => true,
+///         Sensitive::<()>::CODE => false,
+///         _ => false,
+///     }
+/// }
+///
+/// assert!(is_public(&CoreTaxonomy::Insensitive.data_class()));
+/// assert!(!is_public(&CoreTaxonomy::Sensitive.data_class()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ClassCode(u64);
+
+impl ClassCode {
+    /// Computes the code of the data class with the given taxonomy and class names.
+    #[must_use]
+    pub const fn of(taxonomy: &str, name: &str) -> Self {
+        // the names are separated by a byte which can't appear in UTF-8 text, so that distinct pairs of
+        // names don't produce the same input
+        let hash = fnv1a(0xcbf2_9ce4_8422_2325, taxonomy.as_bytes());
+        let hash = fnv1a(hash, &[0xff]);
+        Self(fnv1a(hash, name.as_bytes()))
+    }
+
+    /// Creates a code from its numeric value.
+    #[must_use]
+    pub const fn from_u64(value: u64) -> Self {
+        Self(value)
+    }
+
+    /// Returns the numeric value of the code.
+    #[must_use]
+    pub const fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+/// Returns whether a list of codes contains a code, for use in constants.
+#[doc(hidden)]
+#[must_use]
+pub const fn contains_code(codes: &[ClassCode], code: ClassCode) -> bool {
+    let mut index = 0;
+    while index < codes.len() {
+        if codes[index].0 == code.0 {
+            return true;
+        }
+        index += 1;
+    }
+
+    false
+}
+
+/// Computes the FNV-1a hash of some bytes, starting from the given hash.
+const fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    let mut index = 0;
+    while index < bytes.len() {
+        hash ^= bytes[index] as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        index += 1;
+    }
+
+    hash
+}
+
+impl Display for ClassCode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DataClass;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    const CODE: ClassCode = ClassCode::of("taxonomy", "class");
+
+    #[test]
+    fn codes_should_match_data_classes() {
+        assert_eq!(DataClass::new("taxonomy", "class").code(), CODE);
+        assert_eq!(
+            DataClass::from_parts("taxonomy".to_string(), "class".to_string()).code(),
+            CODE
+        );
+        assert_ne!(ClassCode::of("taxonomy", "other"), CODE);
+        assert_ne!(ClassCode::of("taxonomyc", "lass"), CODE);
+    }
+
+    #[test]
+    fn contains_code_should_find_codes() {
+        let codes = [ClassCode::of("taxonomy", "other"), CODE];
+        assert!(contains_code(&codes, CODE));
+        assert!(!contains_code(&codes[..1], CODE));
+        assert!(!contains_code(&[], CODE));
+    }
+
+    #[test]
+    fn codes_should_round_trip_through_u64() {
+        assert_eq!(ClassCode::from_u64(CODE.as_u64()), CODE);
+        assert_eq!(ClassCode::from_u64(0xab).to_string(), "00000000000000ab");
+    }
+}
@@ -0,0 +1,15 @@
+use crate::{ClassCode, DataClass};
+
+/// Identifies a data class at the type level.
+///
+/// Marker types implementing this trait parameterize [`ClassifiedAs`](crate::ClassifiedAs), the container
+/// shared by all the classes of taxonomies created with `generic = true`. The [`taxonomy`](crate::taxonomy)
+/// attribute generates one marker type per class of such taxonomies, named after the class's variant with
+/// a `Class` suffix, as in `SensitiveClass`.
+pub trait ClassMarker {
+    /// The data class identified by the marker.
+    const DATA_CLASS: DataClass;
+
+    /// The compact code of the data class identified by the marker.
+    const CODE: ClassCode;
+}
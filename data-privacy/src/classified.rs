@@ -1,4 +1,5 @@
 use crate::DataClass;
+use crate::DynamicClassified;
 
 /// Represents a container that holds classified state.
 ///
@@ -51,10 +52,22 @@ use crate::DataClass;
 ///     }
 /// }
 ///  ```
+///
+/// # Nested Containers
+///
+/// Generic code can end up wrapping a classified container within another, as in `Sensitive<Pii<T>>`.
+/// The outer container treats the inner one as an opaque payload: formatting the inner container only
+/// ever produces a redacted placeholder, so its data can't reach the outer class's redactor.
+///
+/// To work with the innermost payload, use [`flatten`](Self::flatten) or the nested redaction methods of
+/// [`RedactionEngine`](crate::RedactionEngine). These resolve the nested classes to a single effective
+/// class, the [most restrictive](DataClass::most_restrictive) of the two, with the outer class winning
+/// if the levels are equal. The redactor of the other class is never applied.
 pub trait Classified<T> {
     /// Exfiltrates the payload, allowing it to be used outside the classified context.
     ///
     /// Exfiltration should be done with caution, as it may expose sensitive information.
+    /// Implementations are expected to report each declassification to the [audit trail](crate::audit).
     ///
     /// # Returns
     /// The original payload.
@@ -70,4 +83,247 @@ pub trait Classified<T> {
     /// Returns the data class of the classified data.
     #[must_use]
     fn data_class(&self) -> DataClass;
+
+    /// Moves the payload out of the container, to be placed into another classified container.
+    ///
+    /// Unlike [`declassify`](Self::declassify), this isn't reported to the audit trail, since the payload
+    /// remains classified. The token can only be created by this crate, so this can't be used to bypass
+    /// the audit trail. The default implementation declassifies the payload.
+    #[doc(hidden)]
+    #[must_use]
+    #[track_caller]
+    fn take_payload(self, _token: PayloadToken) -> T
+    where
+        Self: Sized,
+    {
+        self.declassify()
+    }
+
+    /// Returns the effective data class of a nested container.
+    ///
+    /// See [Nested Containers](Self#nested-containers) for how the classes are resolved.
+    #[must_use]
+    fn nested_data_class<U>(&self) -> DataClass
+    where
+        T: Classified<U>,
+    {
+        let outer = self.data_class();
+        let mut data_class = None;
+        self.visit(|inner| {
+            data_class = Some(DataClass::most_restrictive(&outer, &inner.data_class()).clone());
+        });
+        data_class.unwrap_or(outer)
+    }
+
+    /// Collapses a nested container into a single container holding the innermost payload.
+    ///
+    /// The resulting container has the effective class of the nested containers, as described
+    /// in [Nested Containers](Self#nested-containers). The payload remains classified, so this isn't
+    /// reported to the [audit trail](crate::audit).
+    #[must_use]
+    #[track_caller]
+    fn flatten<U>(self) -> DynamicClassified<U>
+    where
+        Self: Sized,
+        T: Classified<U>,
+    {
+        let outer = self.data_class();
+        let inner = self.take_payload(PayloadToken(()));
+        let data_class = DataClass::most_restrictive(&outer, &inner.data_class()).clone();
+        DynamicClassified::new(inner.take_payload(PayloadToken(())), data_class)
+    }
+
+    /// Combines two classified values into a single container holding both payloads.
+    ///
+    /// The resulting container has the [most restrictive](DataClass::most_restrictive) class of the two values.
+    /// The payloads remain classified, so this isn't reported to the [audit trail](crate::audit).
+    #[must_use]
+    #[track_caller]
+    fn zip<U, C>(self, other: C) -> DynamicClassified<(T, U)>
+    where
+        Self: Sized,
+        C: Classified<U>,
+    {
+        let data_class =
+            DataClass::most_restrictive(&self.data_class(), &other.data_class()).clone();
+        DynamicClassified::new(
+            (
+                self.take_payload(PayloadToken(())),
+                other.take_payload(PayloadToken(())),
+            ),
+            data_class,
+        )
+    }
+
+    /// Returns whether the payload contains the given substring.
+    ///
+    /// The check runs against the payload in place, so only the outcome is revealed.
+    #[must_use]
+    fn contains(&self, pattern: &str) -> bool
+    where
+        T: AsRef<str>,
+    {
+        let mut result = false;
+        self.visit(|payload| result = payload.as_ref().contains(pattern));
+        result
+    }
+
+    /// Returns whether the payload is equal to the given string, ignoring ASCII case.
+    ///
+    /// The check runs against the payload in place, so only the outcome is revealed.
+    #[must_use]
+    fn eq_ignore_ascii_case(&self, other: &str) -> bool
+    where
+        T: AsRef<str>,
+    {
+        let mut result = false;
+        self.visit(|payload| result = payload.as_ref().eq_ignore_ascii_case(other));
+        result
+    }
+
+    /// Returns whether the payload matches the given regular expression.
+    ///
+    /// The check runs against the payload in place, so only the outcome is revealed.
+    #[cfg(feature = "regex")]
+    #[must_use]
+    fn matches(&self, regex: &regex::Regex) -> bool
+    where
+        T: AsRef<str>,
+    {
+        let mut result = false;
+        self.visit(|payload| result = regex.is_match(payload.as_ref()));
+        result
+    }
+}
+
+/// Grants access to [`Classified::take_payload`], which only this crate may call.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct PayloadToken(());
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::{CoreTaxonomy, Insensitive, Sensitive};
+    use crate::taxonomy;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    #[test]
+    fn flatten_should_use_outer_class_for_equal_levels() {
+        let value = Sensitive::new(Insensitive::new(42));
+        assert_eq!(
+            value.nested_data_class(),
+            CoreTaxonomy::Sensitive.data_class()
+        );
+
+        let flat = value.flatten();
+        assert_eq!(flat.data_class(), CoreTaxonomy::Sensitive.data_class());
+        assert_eq!(flat.declassify(), 42);
+    }
+
+    #[test]
+    fn flatten_should_use_most_restrictive_class() {
+        struct Leveled<T>(T, DataClass);
+
+        impl<T> Classified<T> for Leveled<T> {
+            fn declassify(self) -> T {
+                self.0
+            }
+
+            fn visit(&self, operation: impl FnOnce(&T)) {
+                operation(&self.0);
+            }
+
+            fn visit_mut(&mut self, operation: impl FnOnce(&mut T)) {
+                operation(&mut self.0);
+            }
+
+            fn data_class(&self) -> DataClass {
+                self.1.clone()
+            }
+        }
+
+        let low = DataClass::new("nested", "low").with_level(1);
+        let high = DataClass::new("nested", "high").with_level(2);
+
+        let value = Leveled(Leveled("x", high.clone()), low.clone());
+        assert_eq!(value.nested_data_class(), high);
+        assert_eq!(value.flatten().data_class(), high);
+
+        let value = Leveled(Leveled("x", low), high.clone());
+        assert_eq!(value.flatten().data_class(), high);
+    }
+
+    #[test]
+    fn zip_should_use_most_restrictive_class() {
+        let low = DynamicClassified::new(1, DataClass::new("zip", "low").with_level(1));
+        let high = DynamicClassified::new("a", DataClass::new("zip", "high").with_level(2));
+
+        let zipped = low.zip(high);
+        assert_eq!(zipped.data_class(), DataClass::new("zip", "high"));
+        assert_eq!(zipped.declassify(), (1, "a"));
+
+        let zipped = Sensitive::new(1).zip(Insensitive::new(2));
+        assert_eq!(zipped.data_class(), CoreTaxonomy::Sensitive.data_class());
+    }
+
+    #[test]
+    fn taxonomy_levels_should_drive_resolution() {
+        #[taxonomy(leveled, serde = false)]
+        enum LeveledTaxonomy {
+            Public,
+            #[class(level = 2)]
+            Secret,
+        }
+
+        assert_eq!(LeveledTaxonomy::Secret.data_class().level(), 2);
+        assert_eq!(LeveledTaxonomy::Public.data_class().level(), 0);
+
+        let zipped = Public::new(1).zip(Secret::new(2));
+        assert_eq!(zipped.data_class(), LeveledTaxonomy::Secret.data_class());
+
+        let nested = Public::new(Secret::new("x"));
+        assert_eq!(nested.flatten().data_class(), Secret::<()>::data_class());
+    }
+
+    #[test]
+    fn upcasts_should_keep_payloads_classified() {
+        #[taxonomy(corp, serde = false)]
+        enum CorpTaxonomy {
+            #[class(upcast = "crate::core_taxonomy::Sensitive", upcast = Vault)]
+            CustomerEmail,
+            Vault,
+        }
+
+        let sensitive: Sensitive<&str> = CustomerEmail::new("jdoe").into();
+        assert_eq!(sensitive.data_class(), CoreTaxonomy::Sensitive.data_class());
+        assert_eq!(sensitive.declassify(), "jdoe");
+
+        let vault: Vault<i32> = CustomerEmail::new(7).into();
+        assert_eq!(vault.data_class(), CorpTaxonomy::Vault.data_class());
+        assert_eq!(vault.declassify(), 7);
+    }
+
+    #[test]
+    fn contains_should_check_payload() {
+        let email = Sensitive::new("john@example.com".to_string());
+        assert!(email.contains("@example.com"));
+        assert!(!email.contains("@contoso.com"));
+    }
+
+    #[test]
+    fn eq_ignore_ascii_case_should_check_payload() {
+        let region = Sensitive::new("West-US");
+        assert!(region.eq_ignore_ascii_case("west-us"));
+        assert!(!region.eq_ignore_ascii_case("east-us"));
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn matches_should_check_payload() {
+        let regex = regex::Regex::new(r"^\d{3}-\d{2}-\d{4}$").unwrap();
+        assert!(Sensitive::new("123-45-6789").matches(&regex));
+        assert!(!Sensitive::new("not an ssn").matches(&regex));
+    }
 }
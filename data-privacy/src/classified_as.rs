@@ -0,0 +1,458 @@
+use crate::policy::{PolicyError, Purpose};
+use crate::{
+    ClassCode, ClassMarker, Classified, ClassifiedKey, ClassifiedType, DataClass, Extract,
+    Extractor, MultiClassified,
+};
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+
+/// A classified data container whose data class is given by a [marker type](ClassMarker).
+///
+/// By default, the [`taxonomy`](crate::taxonomy) attribute generates a distinct container type for each
+/// class, along with all of its trait implementations, which adds up for taxonomies with many classes.
+/// Taxonomies created with `generic = true` instead share this single container, and the attribute only
+/// generates a marker type and a type alias per class, as in:
+///
+/// ```rust,ignore
+/// pub struct SensitiveClass;
+/// pub type Sensitive<T> = ClassifiedAs<T, SensitiveClass>;
+/// ```
+///
+/// The aliases are used exactly like the containers generated by default, and behave the same: their
+/// `Debug` output is redacted, declassifying them is audited, they're redacted by
+/// [`RedactionEngine`](crate::RedactionEngine)s, and they forward the arithmetic operators to their payloads.
+/// They implement `Serialize` and `Deserialize` when the `serde` feature is enabled, and `defmt::Format`
+/// when the `defmt` feature is enabled.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::{taxonomy, ClassifiedAs, ClassifiedType};
+///
+/// #[taxonomy(contoso, generic = true)]
+/// enum ContosoTaxonomy {
+///     CustomerContent,
+///     CustomerIdentifier,
+/// }
+///
+/// let id: CustomerIdentifier<u32> = CustomerIdentifier::new(42);
+/// assert_eq!(format!("{id:?}"), "<contoso/customer_identifier:REDACTED>");
+/// assert_eq!(
+///     <ClassifiedAs<u32, CustomerIdentifierClass> as ClassifiedType>::DATA_CLASS,
+///     ContosoTaxonomy::CustomerIdentifier.data_class()
+/// );
+/// assert_eq!(id.declassify(), 42);
+/// ```
+pub struct ClassifiedAs<T, C> {
+    payload: T,
+    class: PhantomData<fn() -> C>,
+}
+
+impl<T, C: ClassMarker> ClassifiedAs<T, C> {
+    /// The compact code of the data class of the payload, usable as a `match` pattern.
+    pub const CODE: ClassCode = C::CODE;
+
+    /// Creates a new instance of the classified data container.
+    #[must_use]
+    pub const fn new(payload: T) -> Self {
+        Self {
+            payload,
+            class: PhantomData,
+        }
+    }
+
+    /// Exfiltrates the payload, allowing it to be used outside the classified context.
+    ///
+    /// Exfiltration should be done with caution, as it may expose sensitive information.
+    ///
+    /// # Returns
+    /// The original payload.
+    #[must_use]
+    #[track_caller]
+    pub fn declassify(self) -> T {
+        crate::audit::record_declassification(Self::data_class);
+        self.payload
+    }
+
+    /// Declassifies the payload for the given purpose, if the process-wide declassification policy allows it.
+    ///
+    /// See the [`policy`](crate::policy) module for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the policy denies declassifying the payload for this purpose, or
+    /// requires a justification. The payload is dropped in that case.
+    #[track_caller]
+    pub fn declassify_for(self, purpose: Purpose) -> Result<T, PolicyError> {
+        crate::policy::check_declassification(Self::data_class, purpose, None)?;
+        Ok(self.declassify())
+    }
+
+    /// Declassifies the payload for the given purpose and justification, if the process-wide
+    /// declassification policy allows it.
+    ///
+    /// See the [`policy`](crate::policy) module for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the policy denies declassifying the payload for this purpose. The payload
+    /// is dropped in that case.
+    #[track_caller]
+    pub fn declassify_with_justification(
+        self,
+        purpose: Purpose,
+        justification: &str,
+    ) -> Result<T, PolicyError> {
+        crate::policy::check_declassification(Self::data_class, purpose, Some(justification))?;
+        Ok(self.declassify())
+    }
+
+    /// Transforms the payload without declassifying it, keeping the data class.
+    #[must_use]
+    pub fn map<U>(self, operation: impl FnOnce(T) -> U) -> ClassifiedAs<U, C> {
+        ClassifiedAs::new(operation(self.payload))
+    }
+
+    /// Borrows the payload, producing a container of the same data class holding a reference to it.
+    #[must_use]
+    pub const fn as_ref(&self) -> ClassifiedAs<&T, C> {
+        ClassifiedAs::new(&self.payload)
+    }
+
+    /// Mutably borrows the payload, producing a container of the same data class holding a reference to it.
+    #[must_use]
+    pub const fn as_mut(&mut self) -> ClassifiedAs<&mut T, C> {
+        ClassifiedAs::new(&mut self.payload)
+    }
+
+    /// Combines two containers of this data class into one holding both payloads.
+    ///
+    /// Unlike [`Classified::zip`], which accepts containers of any class, this keeps the static type of
+    /// the container and doesn't declassify the payloads.
+    #[must_use]
+    pub fn zip_same<U>(self, other: ClassifiedAs<U, C>) -> ClassifiedAs<(T, U), C> {
+        ClassifiedAs::new((self.payload, other.payload))
+    }
+
+    /// Adds another data class to the payload, producing a container carrying both classes.
+    #[must_use]
+    pub fn with_class(self, data_class: DataClass) -> MultiClassified<T> {
+        MultiClassified::new(self.payload, Self::data_class()).with_class(data_class)
+    }
+
+    /// Returns the data class of the payload.
+    #[must_use]
+    pub const fn data_class() -> DataClass {
+        C::DATA_CLASS
+    }
+}
+
+impl<T, C: ClassMarker> ClassifiedAs<Option<T>, C> {
+    /// Turns a container of an optional payload into an optional container, keeping the data class.
+    #[must_use]
+    pub fn transpose(self) -> Option<ClassifiedAs<T, C>> {
+        self.payload.map(ClassifiedAs::new)
+    }
+}
+
+impl<T, E, C: ClassMarker> ClassifiedAs<Result<T, E>, C> {
+    /// Turns a container of a result into a result of containers, keeping the data class of both
+    /// the value and the error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the payload, still classified, if the payload is an error.
+    pub fn transpose(self) -> Result<ClassifiedAs<T, C>, ClassifiedAs<E, C>> {
+        self.payload
+            .map(ClassifiedAs::new)
+            .map_err(ClassifiedAs::new)
+    }
+}
+
+impl<T, C: ClassMarker> ClassifiedAs<ClassifiedAs<T, C>, C> {
+    /// Removes a redundant level of classification, returning the inner container.
+    ///
+    /// Wrapping a container in another one of the same data class adds no protection, and makes
+    /// the payload unreachable to redaction, since the outer container only sees the inner one.
+    #[must_use]
+    pub fn flatten(self) -> ClassifiedAs<T, C> {
+        self.payload
+    }
+}
+
+impl<T, C: ClassMarker> Classified<T> for ClassifiedAs<T, C> {
+    #[track_caller]
+    fn declassify(self) -> T {
+        Self::declassify(self)
+    }
+
+    fn take_payload(self, _token: crate::__private::PayloadToken) -> T {
+        self.payload
+    }
+
+    fn visit(&self, operation: impl FnOnce(&T)) {
+        operation(&self.payload);
+    }
+
+    fn visit_mut(&mut self, operation: impl FnOnce(&mut T)) {
+        operation(&mut self.payload);
+    }
+
+    fn data_class(&self) -> DataClass {
+        Self::data_class()
+    }
+}
+
+impl<T, C: ClassMarker> ClassifiedType for ClassifiedAs<T, C> {
+    const DATA_CLASS: DataClass = C::DATA_CLASS;
+}
+
+impl<T, C: ClassMarker, Q: ?Sized> Borrow<ClassifiedKey<Q>> for ClassifiedAs<T, C>
+where
+    T: Borrow<Q>,
+{
+    fn borrow(&self) -> &ClassifiedKey<Q> {
+        ClassifiedKey::new(self.payload.borrow())
+    }
+}
+
+impl<T: Debug, C: ClassMarker> Debug for ClassifiedAs<T, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "<{}:REDACTED>", C::DATA_CLASS)
+    }
+}
+
+impl<T: Display, C: ClassMarker> Extract for ClassifiedAs<T, C> {
+    fn extract(&self, extractor: &mut dyn Extractor) {
+        crate::formatting::with_formatted(format_args!("{}", self.payload), |s| {
+            extractor.write_classified(&C::DATA_CLASS, s);
+        });
+    }
+}
+
+impl<T, C: ClassMarker> From<T> for ClassifiedAs<T, C> {
+    fn from(payload: T) -> Self {
+        Self::new(payload)
+    }
+}
+
+impl<T, C: ClassMarker> From<ClassifiedAs<Self, C>> for ClassifiedAs<T, C> {
+    fn from(value: ClassifiedAs<Self, C>) -> Self {
+        value.flatten()
+    }
+}
+
+impl<T, C: ClassMarker> From<ClassifiedAs<T, C>> for MultiClassified<T> {
+    fn from(value: ClassifiedAs<T, C>) -> Self {
+        Self::new(value.payload, C::DATA_CLASS)
+    }
+}
+
+impl<T, U: FromIterator<T>, C: ClassMarker> FromIterator<ClassifiedAs<T, C>>
+    for ClassifiedAs<U, C>
+{
+    fn from_iter<I: IntoIterator<Item = ClassifiedAs<T, C>>>(iter: I) -> Self {
+        Self::new(iter.into_iter().map(|item| item.payload).collect())
+    }
+}
+
+// the standard traits are implemented by hand, so they don't require anything of the marker type
+
+impl<T: Clone, C> Clone for ClassifiedAs<T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            payload: self.payload.clone(),
+            class: PhantomData,
+        }
+    }
+}
+
+impl<T: Copy, C> Copy for ClassifiedAs<T, C> {}
+
+impl<T: Default, C> Default for ClassifiedAs<T, C> {
+    fn default() -> Self {
+        Self {
+            payload: T::default(),
+            class: PhantomData,
+        }
+    }
+}
+
+impl<T: PartialEq, C> PartialEq for ClassifiedAs<T, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.payload == other.payload
+    }
+}
+
+impl<T: Eq, C> Eq for ClassifiedAs<T, C> {}
+
+impl<T: PartialOrd, C> PartialOrd for ClassifiedAs<T, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.payload.partial_cmp(&other.payload)
+    }
+}
+
+impl<T: Ord, C> Ord for ClassifiedAs<T, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.payload.cmp(&other.payload)
+    }
+}
+
+impl<T: Hash, C> Hash for ClassifiedAs<T, C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.payload.hash(state);
+    }
+}
+
+macro_rules! binary_ops {
+    ($($op:ident $method:ident $assign_op:ident $assign_method:ident),*) => {
+        $(
+            impl<T: core::ops::$op<U>, U, C: ClassMarker> core::ops::$op<ClassifiedAs<U, C>> for ClassifiedAs<T, C> {
+                type Output = ClassifiedAs<T::Output, C>;
+
+                fn $method(self, rhs: ClassifiedAs<U, C>) -> Self::Output {
+                    ClassifiedAs::new(core::ops::$op::$method(self.payload, rhs.payload))
+                }
+            }
+
+            impl<T: core::ops::$assign_op<U>, U, C: ClassMarker> core::ops::$assign_op<ClassifiedAs<U, C>> for ClassifiedAs<T, C> {
+                fn $assign_method(&mut self, rhs: ClassifiedAs<U, C>) {
+                    core::ops::$assign_op::$assign_method(&mut self.payload, rhs.payload);
+                }
+            }
+        )*
+    };
+}
+
+binary_ops!(
+    Add add AddAssign add_assign,
+    Sub sub SubAssign sub_assign,
+    Mul mul MulAssign mul_assign,
+    Div div DivAssign div_assign,
+    Rem rem RemAssign rem_assign
+);
+
+impl<T: core::ops::Neg, C: ClassMarker> core::ops::Neg for ClassifiedAs<T, C> {
+    type Output = ClassifiedAs<T::Output, C>;
+
+    fn neg(self) -> Self::Output {
+        ClassifiedAs::new(-self.payload)
+    }
+}
+
+impl<T: core::iter::Sum, C: ClassMarker> core::iter::Sum for ClassifiedAs<T, C> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self::new(iter.map(|value| value.payload).sum())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, C: ClassMarker> serde::Serialize for ClassifiedAs<T, C> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::__private::serialize_classified(&self.payload, &C::DATA_CLASS, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>, C: ClassMarker> serde::Deserialize<'de>
+    for ClassifiedAs<T, C>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T, C: ClassMarker> defmt::Format for ClassifiedAs<T, C> {
+    fn format(&self, fmt: defmt::Formatter<'_>) {
+        crate::formatting::format_masked(&C::DATA_CLASS, fmt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::taxonomy;
+    #[cfg(not(feature = "std"))]
+    use alloc::{
+        format,
+        string::{String, ToString},
+        vec::Vec,
+    };
+
+    #[taxonomy(generic_test, generic = true)]
+    enum GenericTestTaxonomy {
+        Personal,
+
+        #[class(level = 2, id = 7)]
+        Financial,
+    }
+
+    #[test]
+    fn generic_containers_should_behave_like_generated_ones() {
+        let amount: Financial<i64> = Financial::new(40);
+        assert_eq!(format!("{amount:?}"), "<generic_test/financial:REDACTED>");
+        assert_eq!(
+            Financial::<()>::data_class(),
+            GenericTestTaxonomy::Financial.data_class()
+        );
+        assert_eq!(Financial::<()>::data_class().id(), Some(7));
+        assert_eq!(Financial::<()>::CODE, GenericTestTaxonomy::Financial.code());
+
+        let mut total = amount + Financial::new(2);
+        total -= Financial::new(1);
+        assert_eq!((-total).declassify(), -41);
+
+        let sum: Financial<i64> = [1, 2, 3].into_iter().map(Financial::new).sum();
+        assert_eq!(sum, Financial::new(6));
+        assert!(Financial::new(1) < Financial::new(2));
+
+        let name = Personal::new("jdoe").map(str::to_string);
+        assert_eq!(name.as_ref().map(String::len).declassify(), 4);
+        assert_eq!(
+            Classified::data_class(&name),
+            GenericTestTaxonomy::Personal.data_class()
+        );
+
+        let names: Personal<Vec<_>> = ["a", "b"].into_iter().map(Personal::new).collect();
+        assert_eq!(names.declassify(), ["a", "b"]);
+        assert_eq!(Personal::new(Some(1)).transpose(), Some(Personal::new(1)));
+        assert_eq!(Personal::new(Personal::new(1)).flatten(), Personal::new(1));
+
+        let mut keys = alloc::collections::BTreeSet::new();
+        _ = keys.insert(Personal::new("x".to_string()));
+        assert!(keys.contains(ClassifiedKey::new("x")));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn generic_containers_should_be_redacted() {
+        use crate::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &GenericTestTaxonomy::Personal.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')),
+            )
+            .build();
+
+        assert_eq!(engine.redact_to_string(&Personal::new("jdoe")), "####");
+        assert_eq!(
+            engine.redact_to_string(&PersonalRef::<str>::new("ab")),
+            "##"
+        );
+    }
+
+    #[cfg(all(feature = "serde", feature = "std"))]
+    #[test]
+    fn generic_containers_should_serialize_payload() {
+        let json = serde_json::to_string(&Personal::new("jdoe")).unwrap();
+        assert_eq!(json, r#""jdoe""#);
+
+        let value: Personal<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(value.declassify(), "jdoe");
+    }
+}
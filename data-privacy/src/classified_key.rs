@@ -0,0 +1,133 @@
+use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::hash::{Hash, Hasher};
+
+/// An opaque view of a key, used to look up classified keys in maps and sets.
+///
+/// Maps and sets keyed by classified containers, such as `HashMap<Sensitive<String>, V>`, can't be queried
+/// with a plain `&str` directly, as letting containers [borrow](core::borrow::Borrow) as their payload would
+/// expose the payload without declassifying it. Containers generated by the [`taxonomy`](crate::taxonomy)
+/// attribute instead borrow as a `ClassifiedKey`, which compares and hashes like the payload but doesn't
+/// give access to it. Wrapping the plain key in a `ClassifiedKey` with [`new`](Self::new) lets it be used
+/// to query the collection, without building a container or copying the key.
+///
+/// A container with a payload of type `T` borrows as a `ClassifiedKey<Q>` for any `Q` that `T` borrows as,
+/// so a `Sensitive<String>` key can be looked up with a `ClassifiedKey<str>` or a `ClassifiedKey<String>`.
+///
+/// # Examples
+///
+/// Looking up a map keyed by classified data:
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use data_privacy::ClassifiedKey;
+/// use data_privacy::core_taxonomy::Sensitive;
+///
+/// let mut logins = HashMap::new();
+/// logins.insert(Sensitive::new("jdoe@contoso.com".to_string()), 3);
+///
+/// assert_eq!(logins.get(ClassifiedKey::new("jdoe@contoso.com")), Some(&3));
+/// assert!(logins.remove(ClassifiedKey::new("jsmith@contoso.com")).is_none());
+/// ```
+///
+/// Checking membership in an ordered set of classified byte strings:
+///
+/// ```rust
+/// use std::collections::BTreeSet;
+/// use data_privacy::ClassifiedKey;
+/// use data_privacy::core_taxonomy::Sensitive;
+///
+/// let blocked: BTreeSet<_> = [Sensitive::new(vec![1u8, 2, 3])].into_iter().collect();
+///
+/// let incoming: &[u8] = &[1, 2, 3];
+/// assert!(blocked.contains(ClassifiedKey::new(incoming)));
+/// ```
+#[repr(transparent)]
+pub struct ClassifiedKey<Q: ?Sized>(Q);
+
+impl<Q: ?Sized> ClassifiedKey<Q> {
+    /// Wraps a reference to a key, so that it can be used to query a collection of classified keys.
+    #[must_use]
+    pub const fn new(key: &Q) -> &Self {
+        // SAFETY: `ClassifiedKey` is a transparent wrapper, so it has the same layout and pointer metadata as `Q`.
+        unsafe { &*(core::ptr::from_ref(key) as *const Self) }
+    }
+}
+
+impl<Q: PartialEq + ?Sized> PartialEq for ClassifiedKey<Q> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<Q: Eq + ?Sized> Eq for ClassifiedKey<Q> {}
+
+impl<Q: PartialOrd + ?Sized> PartialOrd for ClassifiedKey<Q> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<Q: Ord + ?Sized> Ord for ClassifiedKey<Q> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<Q: Hash + ?Sized> Hash for ClassifiedKey<Q> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<Q: ?Sized> Debug for ClassifiedKey<Q> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("ClassifiedKey(REDACTED)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::{Insensitive, Sensitive};
+    use alloc::collections::BTreeMap;
+    #[cfg(not(feature = "std"))]
+    use alloc::{
+        format,
+        string::{String, ToString},
+    };
+    use core::borrow::Borrow;
+
+    #[test]
+    fn keys_should_behave_like_payloads() {
+        let key = Sensitive::new("jdoe".to_string());
+        let borrowed: &ClassifiedKey<str> = key.borrow();
+        assert_eq!(borrowed, ClassifiedKey::new("jdoe"));
+        assert!(borrowed < ClassifiedKey::new("jsmith"));
+        assert_eq!(format!("{borrowed:?}"), "ClassifiedKey(REDACTED)");
+
+        let mut map = BTreeMap::new();
+        _ = map.insert(key, 1);
+        _ = map.insert(Sensitive::new("alice".to_string()), 2);
+        assert_eq!(map.get(ClassifiedKey::new("jdoe")), Some(&1));
+        assert_eq!(
+            map.get(ClassifiedKey::new(&String::from("alice"))),
+            Some(&2)
+        );
+        assert_eq!(map.get(ClassifiedKey::new("bob")), None);
+
+        let mut counts = BTreeMap::new();
+        _ = counts.insert(Insensitive::new(7), "seven");
+        assert_eq!(counts.get(ClassifiedKey::new(&7)), Some(&"seven"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn keys_should_hash_like_payloads() {
+        let mut map = std::collections::HashMap::new();
+        _ = map.insert(Sensitive::new("jdoe".to_string()), 1);
+        assert_eq!(map.get(ClassifiedKey::new("jdoe")), Some(&1));
+        assert!(map.contains_key(ClassifiedKey::new(&"jdoe".to_string())));
+        assert!(!map.contains_key(ClassifiedKey::new("bob")));
+    }
+}
@@ -0,0 +1,318 @@
+use crate::{DataClass, DynamicClassified, RedactionEngine};
+use core::fmt::{Debug, Formatter};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::Stream;
+use futures_io::AsyncRead;
+use std::io;
+use std::sync::Arc;
+
+type Inspector = Box<dyn FnMut(&str) + Send>;
+
+/// Classifies the items of a stream as they flow through it.
+///
+/// Each item produced by the underlying stream is wrapped in a [`DynamicClassified`] container of the given
+/// data class, so data streamed through a service, such as a proxy, is protected without ever being held
+/// in memory all at once.
+///
+/// Items can also be inspected as they pass through, in redacted form, which is useful to log the traffic
+/// of a stream without exposing its data.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::CoreTaxonomy;
+/// use data_privacy::{Classified, ClassifiedStream, RedactionEngineBuilder, SimpleRedactor};
+/// use futures::StreamExt;
+/// use std::sync::{Arc, Mutex};
+///
+/// let engine = RedactionEngineBuilder::new()
+///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+///     .build()
+///     .into_shared();
+///
+/// let log = Arc::new(Mutex::new(Vec::new()));
+/// let log_clone = Arc::clone(&log);
+///
+/// let stream = futures::stream::iter(["alice", "bob"]);
+/// let classified = ClassifiedStream::new(stream, CoreTaxonomy::Sensitive.data_class())
+///     .inspect_redacted(engine, move |s| log_clone.lock().unwrap().push(s.to_string()));
+///
+/// let items: Vec<_> = futures::executor::block_on(classified.collect());
+/// assert_eq!(items[0].data_class(), CoreTaxonomy::Sensitive.data_class());
+/// assert_eq!(*log.lock().unwrap(), ["*******", "*****"]);
+/// ```
+pub struct ClassifiedStream<S> {
+    stream: S,
+    data_class: DataClass,
+    inspector: Option<(Arc<RedactionEngine>, Inspector)>,
+}
+
+impl<S> ClassifiedStream<S> {
+    /// Creates a new stream which classifies the items of the given stream with a data class.
+    #[must_use]
+    pub const fn new(stream: S, data_class: DataClass) -> Self {
+        Self {
+            stream,
+            data_class,
+            inspector: None,
+        }
+    }
+
+    /// Hands the redacted [`Debug`] output of each item to a callback as it passes through.
+    #[must_use]
+    pub fn inspect_redacted(
+        mut self,
+        engine: Arc<RedactionEngine>,
+        inspector: impl FnMut(&str) + Send + 'static,
+    ) -> Self {
+        self.inspector = Some((engine, Box::new(inspector)));
+        self
+    }
+
+    /// Returns the data class applied to the items of the stream.
+    #[must_use]
+    pub const fn data_class(&self) -> &DataClass {
+        &self.data_class
+    }
+
+    /// Consumes the adapter, returning the underlying stream.
+    #[must_use]
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S> Stream for ClassifiedStream<S>
+where
+    S: Stream + Unpin,
+    S::Item: Debug,
+{
+    type Item = DynamicClassified<S::Item>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        Pin::new(&mut this.stream).poll_next(cx).map(|item| {
+            item.map(|item| {
+                let item = DynamicClassified::new(item, this.data_class.clone());
+                if let Some((engine, inspector)) = &mut this.inspector {
+                    inspect(engine, inspector, &item);
+                }
+
+                item
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<S: Debug> Debug for ClassifiedStream<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ClassifiedStream")
+            .field("stream", &self.stream)
+            .field("data_class", &self.data_class)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Reads an asynchronous byte source as a stream of classified chunks.
+///
+/// Rather than exposing the bytes it reads, this adapter is a [`Stream`] of chunks of at most a given
+/// size, each wrapped in a [`DynamicClassified`] container of the given data class. Chunks can be
+/// inspected in redacted form as they are read, in which case they are decoded as UTF-8, replacing
+/// invalid sequences, before being redacted.
+pub struct ClassifiedRead<R> {
+    reader: R,
+    data_class: DataClass,
+    chunk_size: usize,
+    inspector: Option<(Arc<RedactionEngine>, Inspector)>,
+
+    // reused across polls, so that pending reads don't allocate
+    buf: Vec<u8>,
+}
+
+impl<R> ClassifiedRead<R> {
+    /// Creates a new stream of classified chunks read from the given reader.
+    ///
+    /// Chunks are at most 8 KiB by default.
+    #[must_use]
+    pub const fn new(reader: R, data_class: DataClass) -> Self {
+        Self {
+            reader,
+            data_class,
+            chunk_size: 8 * 1024,
+            inspector: None,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Sets the maximum size of the chunks produced by the stream.
+    ///
+    /// A chunk size of 0 is treated as 1.
+    #[must_use]
+    pub const fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = if chunk_size == 0 { 1 } else { chunk_size };
+        self
+    }
+
+    /// Hands the redacted content of each chunk to a callback as it's read.
+    #[must_use]
+    pub fn inspect_redacted(
+        mut self,
+        engine: Arc<RedactionEngine>,
+        inspector: impl FnMut(&str) + Send + 'static,
+    ) -> Self {
+        self.inspector = Some((engine, Box::new(inspector)));
+        self
+    }
+
+    /// Returns the data class applied to the chunks of the stream.
+    #[must_use]
+    pub const fn data_class(&self) -> &DataClass {
+        &self.data_class
+    }
+
+    /// Consumes the adapter, returning the underlying reader.
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: AsyncRead + Unpin> Stream for ClassifiedRead<R> {
+    type Item = io::Result<DynamicClassified<Vec<u8>>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        this.buf.resize(this.chunk_size, 0);
+
+        match Pin::new(&mut this.reader).poll_read(cx, &mut this.buf) {
+            Poll::Ready(Ok(0)) => Poll::Ready(None),
+            Poll::Ready(Ok(len)) => {
+                let buf = this.buf[..len].to_vec();
+                if let Some((engine, inspector)) = &mut this.inspector {
+                    engine.redact(&this.data_class, String::from_utf8_lossy(&buf), inspector);
+                }
+
+                Poll::Ready(Some(Ok(DynamicClassified::new(
+                    buf,
+                    this.data_class.clone(),
+                ))))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Some(Err(e))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<R: Debug> Debug for ClassifiedRead<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ClassifiedRead")
+            .field("reader", &self.reader)
+            .field("data_class", &self.data_class)
+            .field("chunk_size", &self.chunk_size)
+            .finish_non_exhaustive()
+    }
+}
+
+fn inspect<T: Debug>(
+    engine: &RedactionEngine,
+    inspector: &mut Inspector,
+    item: &DynamicClassified<T>,
+) {
+    let mut redacted = String::new();
+    engine.debug_redacted(item, |s| redacted.push_str(s));
+    inspector(&redacted);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::CoreTaxonomy;
+    use crate::{Classified, RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+    use futures::StreamExt;
+    use futures::executor::block_on;
+    use std::sync::Mutex;
+
+    fn create_engine() -> Arc<RedactionEngine> {
+        RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')),
+            )
+            .build()
+            .into_shared()
+    }
+
+    #[test]
+    fn stream_should_classify_items() {
+        let stream = ClassifiedStream::new(
+            futures::stream::iter([1, 22, 333]),
+            CoreTaxonomy::Sensitive.data_class(),
+        );
+
+        assert_eq!(stream.data_class(), &CoreTaxonomy::Sensitive.data_class());
+        assert_eq!(stream.size_hint(), (3, Some(3)));
+
+        let items: Vec<_> = block_on(stream.collect());
+        assert_eq!(items.len(), 3);
+        assert!(
+            items
+                .iter()
+                .all(|item| item.data_class() == CoreTaxonomy::Sensitive.data_class())
+        );
+
+        let payloads: Vec<_> = items.into_iter().map(Classified::declassify).collect();
+        assert_eq!(payloads, [1, 22, 333]);
+    }
+
+    #[test]
+    fn stream_should_inspect_redacted_items() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let log_clone = Arc::clone(&log);
+
+        let stream = ClassifiedStream::new(
+            futures::stream::iter(["a", "bb"]),
+            CoreTaxonomy::Sensitive.data_class(),
+        )
+        .inspect_redacted(create_engine(), move |s| {
+            log_clone.lock().unwrap().push(s.to_string());
+        });
+
+        let count = block_on(stream.count());
+        assert_eq!(count, 2);
+        assert_eq!(*log.lock().unwrap(), ["###", "####"]);
+    }
+
+    #[test]
+    fn read_should_produce_classified_chunks() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let log_clone = Arc::clone(&log);
+
+        let reader = futures::io::Cursor::new(b"hello world".to_vec());
+        let stream = ClassifiedRead::new(reader, CoreTaxonomy::Sensitive.data_class())
+            .with_chunk_size(4)
+            .inspect_redacted(create_engine(), move |s| {
+                log_clone.lock().unwrap().push(s.to_string());
+            });
+
+        let chunks: Vec<_> = block_on(stream.collect());
+        let chunks: Vec<_> = chunks
+            .into_iter()
+            .map(|chunk| {
+                let chunk = chunk.unwrap();
+                assert_eq!(chunk.data_class(), CoreTaxonomy::Sensitive.data_class());
+                chunk.declassify()
+            })
+            .collect();
+
+        assert_eq!(
+            chunks,
+            [b"hell".to_vec(), b"o wo".to_vec(), b"rld".to_vec()]
+        );
+        assert_eq!(*log.lock().unwrap(), ["####", "####", "###"]);
+    }
+}
@@ -0,0 +1,26 @@
+use crate::DataClass;
+
+/// Describes classified container types whose data class is known statically.
+///
+/// Containers generated by the [`taxonomy`](crate::taxonomy) attribute implement this trait, which lets
+/// tooling discover the data class of a container from its type alone, without needing a value.
+pub trait ClassifiedType {
+    /// The data class of every value of this type.
+    const DATA_CLASS: DataClass;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::{CoreTaxonomy, Sensitive};
+    #[cfg(not(feature = "std"))]
+    use alloc::string::String;
+
+    #[test]
+    fn generated_containers_should_expose_their_class() {
+        assert_eq!(
+            <Sensitive<String> as ClassifiedType>::DATA_CLASS,
+            CoreTaxonomy::Sensitive.data_class()
+        );
+    }
+}
@@ -0,0 +1,137 @@
+/// Converts a plain struct into its classified counterpart in a single expression.
+///
+/// This is intended for the boundary where external data first enters a system, such as right after
+/// deserializing a request body. You provide the source value, the target struct, and the list of
+/// target fields. The target struct can be named by a path, as in `api::Employee`. Fields followed by a classified container type are wrapped in that container,
+/// while fields listed without a type are moved over unchanged.
+///
+/// Every field of the target struct must be listed, which ensures that newly added fields must be
+/// explicitly considered for classification.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::classify_fields;
+/// use data_privacy::core_taxonomy::{Insensitive, Sensitive};
+///
+/// struct RawEmployee {
+///     name: String,
+///     team: String,
+///     age: u32,
+/// }
+///
+/// struct Employee {
+///     name: Sensitive<String>,
+///     team: Insensitive<String>,
+///     age: u32,
+/// }
+///
+/// let raw = RawEmployee {
+///     name: "John Doe".to_string(),
+///     team: "Accounting".to_string(),
+///     age: 33,
+/// };
+///
+/// let employee = classify_fields!(raw => Employee {
+///     name: Sensitive,
+///     team: Insensitive,
+///     age,
+/// });
+///
+/// assert_eq!(employee.age, 33);
+/// assert_eq!(employee.name.declassify(), "John Doe");
+/// ```
+#[macro_export]
+macro_rules! classify_fields {
+    ($source:expr => $($target:ident)::+ { $($field:ident $(: $($class:ident)::+)?),* $(,)? }) => {{
+        let source = $source;
+        $($target)::+ {
+            $(
+                $field: $crate::classify_fields!(@field source.$field $(, $($class)::+)?),
+            )*
+        }
+    }};
+
+    (@field $value:expr) => {
+        $value
+    };
+
+    (@field $value:expr, $($class:ident)::+) => {
+        $($class)::+::new($value)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Classified;
+    use crate::core_taxonomy::{CoreTaxonomy, Sensitive, UnknownSensitivity};
+    #[cfg(not(feature = "std"))]
+    use alloc::string::{String, ToString};
+
+    struct RawRecord {
+        user: String,
+        notes: String,
+        visits: u32,
+    }
+
+    struct Record {
+        user: Sensitive<String>,
+        notes: UnknownSensitivity<String>,
+        visits: u32,
+    }
+
+    mod api {
+        use crate::core_taxonomy::Sensitive;
+        #[cfg(not(feature = "std"))]
+        use alloc::string::String;
+
+        pub struct Record {
+            pub user: Sensitive<String>,
+            pub visits: u32,
+        }
+    }
+
+    #[test]
+    fn classify_fields_should_wrap_listed_fields() {
+        let raw = RawRecord {
+            user: "jdoe".to_string(),
+            notes: "likes tea".to_string(),
+            visits: 3,
+        };
+
+        let record = classify_fields!(raw => Record {
+            user: Sensitive,
+            notes: crate::core_taxonomy::UnknownSensitivity,
+            visits,
+        });
+
+        assert_eq!(record.visits, 3);
+        assert_eq!(
+            record.user.data_class(),
+            CoreTaxonomy::Sensitive.data_class()
+        );
+        assert_eq!(
+            record.notes.data_class(),
+            CoreTaxonomy::UnknownSensitivity.data_class()
+        );
+        assert_eq!(record.user.declassify(), "jdoe");
+        assert_eq!(record.notes.declassify(), "likes tea");
+    }
+
+    #[test]
+    fn classify_fields_should_accept_target_paths() {
+        let raw = RawRecord {
+            user: "jdoe".to_string(),
+            notes: String::new(),
+            visits: 3,
+        };
+
+        let record = classify_fields!(raw => api::Record {
+            user: Sensitive,
+            visits,
+        });
+
+        assert_eq!(record.visits, 3);
+        assert_eq!(record.user.declassify(), "jdoe");
+    }
+}
@@ -0,0 +1,296 @@
+use crate::{DataClass, Redactor};
+
+/// Generates tests checking that a redactor meets the baseline guarantees expected of all redactors.
+///
+/// The generated tests check that the redactor:
+///
+/// * Is deterministic, producing the same output when given the same input twice.
+/// * Handles empty input without panicking.
+/// * Handles multi-byte UTF-8 input without panicking.
+/// * Handles arbitrary Unicode input, including combining marks, control characters, and characters
+///   outside of the Basic Multilingual Plane, deterministically and without panicking.
+/// * Produces output of the length reported by [`Redactor::exact_len`], when it reports one.
+/// * Never echoes its input back, even when tagging it.
+///
+/// The last check means the harness isn't suitable for redactors that intentionally pass data through.
+///
+/// The macro is given an expression producing the redactor to test, and generates a set of `#[test]`
+/// functions in the current module. To test several redactors in the same module, give each a
+/// name, which puts its tests in a sub-module of that name.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::{redactor_conformance_tests, SimpleRedactor, SimpleRedactorMode};
+///
+/// #[cfg(test)]
+/// mod tests {
+///     use super::*;
+///
+///     redactor_conformance_tests!(SimpleRedactor::new());
+///     redactor_conformance_tests!(erase => SimpleRedactor::with_mode(SimpleRedactorMode::Erase));
+/// }
+/// ```
+#[macro_export]
+macro_rules! redactor_conformance_tests {
+    ($name:ident => $redactor:expr) => {
+        mod $name {
+            #[allow(
+                unused_imports,
+                reason = "The redactor expression may rely on the parent's imports"
+            )]
+            use super::*;
+
+            $crate::redactor_conformance_tests!($redactor);
+        }
+    };
+
+    ($redactor:expr) => {
+        #[test]
+        fn redactor_is_deterministic() {
+            $crate::__private::check_determinism(&$redactor);
+        }
+
+        #[test]
+        fn redactor_handles_empty_input() {
+            $crate::__private::check_empty_input(&$redactor);
+        }
+
+        #[test]
+        fn redactor_is_utf8_safe() {
+            $crate::__private::check_utf8_safety(&$redactor);
+        }
+
+        #[test]
+        fn redactor_handles_arbitrary_unicode() {
+            $crate::__private::check_arbitrary_unicode(&$redactor);
+        }
+
+        #[test]
+        fn redactor_exact_len_is_consistent() {
+            $crate::__private::check_exact_len(&$redactor);
+        }
+
+        #[test]
+        fn redactor_does_not_echo_input() {
+            $crate::__private::check_no_echo(&$redactor);
+        }
+    };
+}
+
+const CLASSES: [DataClass; 2] = [
+    DataClass::new("conformance", "first"),
+    DataClass::new("conformance", "second"),
+];
+
+const INPUTS: [&str; 8] = [
+    "a",
+    "s3cr3t",
+    "John Doe",
+    "john.doe@example.com",
+    "héllo wörld",
+    "日本語のテキスト",
+    "emoji 🦀🔒",
+    "a much longer input which exceeds the small buffers some redactors use internally, to exercise any slow paths",
+];
+
+/// The number of arbitrary inputs generated for each data class.
+const ARBITRARY_INPUTS: usize = 256;
+
+/// Generates pseudo-random strings made of arbitrary Unicode characters.
+///
+/// The strings are reproducible, so failures can be replayed, and favor characters which commonly trip
+/// up text handling: multi-byte characters of every encoded length, combining marks, control characters,
+/// and the delimiters of classification tags.
+pub fn arbitrary_inputs() -> impl Iterator<Item = String> {
+    const INTERESTING: [char; 10] = [
+        '\0', '\n', '\u{7f}', '<', '>', ':', '/', '\u{301}', '\u{200d}', '\u{feff}',
+    ];
+
+    // xorshift64, seeded with a fixed value
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    (0..ARBITRARY_INPUTS).map(move |_| {
+        let len = next() % 40;
+        (0..len)
+            .map(|_| {
+                let random = next();
+                #[expect(
+                    clippy::cast_possible_truncation,
+                    reason = "Only the low bits of the random values are needed"
+                )]
+                let code = match random % 6 {
+                    0 => (random >> 8) as u32 % 0x5f + 0x20,
+                    1 => u32::from(INTERESTING[(random >> 8) as usize % INTERESTING.len()]),
+                    2 => (random >> 8) as u32 % 0x780 + 0x80,
+                    3 => (random >> 8) as u32 % 0xf800 + 0x800,
+                    4 => (random >> 8) as u32 % 0x10_0000 + 0x1_0000,
+                    _ => (random >> 8) as u32 % 0x11_0000,
+                };
+
+                // surrogates aren't characters, so they map to the replacement character
+                char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+            })
+            .collect()
+    })
+}
+
+fn redact(redactor: &impl Redactor, data_class: &DataClass, value: &str) -> String {
+    let mut output = String::new();
+    redactor.redact(data_class, value, &mut |s| output.push_str(s));
+    output
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn check_determinism(redactor: &impl Redactor) {
+    for data_class in &CLASSES {
+        for input in INPUTS {
+            let first = redact(redactor, data_class, input);
+            let second = redact(redactor, data_class, input);
+            assert_eq!(
+                first, second,
+                "redacting {input:?} as {data_class} produced different outputs"
+            );
+        }
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn check_empty_input(redactor: &impl Redactor) {
+    for data_class in &CLASSES {
+        let first = redact(redactor, data_class, "");
+        let second = redact(redactor, data_class, "");
+        assert_eq!(
+            first, second,
+            "redacting empty input as {data_class} produced different outputs"
+        );
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn check_utf8_safety(redactor: &impl Redactor) {
+    for data_class in &CLASSES {
+        for input in INPUTS {
+            // every prefix of the input, so some inputs end with a multi-byte character
+            for prefix in input
+                .char_indices()
+                .filter_map(|(index, c)| input.get(..index + c.len_utf8()))
+            {
+                _ = redact(redactor, data_class, prefix);
+            }
+        }
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn check_arbitrary_unicode(redactor: &impl Redactor) {
+    let exact_len = redactor.exact_len();
+    for data_class in &CLASSES {
+        for input in arbitrary_inputs() {
+            let output = redact(redactor, data_class, &input);
+            assert_eq!(
+                output,
+                redact(redactor, data_class, &input),
+                "redacting {input:?} as {data_class} produced different outputs"
+            );
+
+            if let Some(exact_len) = exact_len {
+                assert_eq!(
+                    output.len(),
+                    exact_len,
+                    "redacting {input:?} as {data_class} produced {output:?}, but exact_len is {exact_len}"
+                );
+            }
+        }
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn check_exact_len(redactor: &impl Redactor) {
+    let Some(exact_len) = redactor.exact_len() else {
+        return;
+    };
+
+    for data_class in &CLASSES {
+        for input in INPUTS.iter().copied().chain([""]) {
+            let output = redact(redactor, data_class, input);
+            assert_eq!(
+                output.len(),
+                exact_len,
+                "redacting {input:?} as {data_class} produced {output:?}, but exact_len is {exact_len}"
+            );
+        }
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn check_no_echo(redactor: &impl Redactor) {
+    for data_class in &CLASSES {
+        for input in INPUTS.iter().filter(|input| input.len() > 1) {
+            let output = redact(redactor, data_class, input);
+            assert!(
+                !output.contains(input),
+                "redacting {input:?} as {data_class} echoed the input in {output:?}"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{SimpleRedactor, SimpleRedactorMode};
+
+    redactor_conformance_tests!(SimpleRedactor::new());
+    redactor_conformance_tests!(erase => SimpleRedactor::with_mode(SimpleRedactorMode::Erase));
+    redactor_conformance_tests!(tag => SimpleRedactor::with_mode(SimpleRedactorMode::ReplaceBucketedAndTag('#')));
+    redactor_conformance_tests!(insert => SimpleRedactor::with_mode(SimpleRedactorMode::Insert("[redacted]".to_string())));
+
+    #[cfg(feature = "xxh3")]
+    redactor_conformance_tests!(xxh3 => crate::xxH3Redactor::with_secret([7_u8; 192]).with_per_class_keys());
+    #[cfg(feature = "xxh3")]
+    redactor_conformance_tests!(xxh3_encoded => crate::xxH3Redactor::with_secret([7_u8; 192])
+        .with_128_bit_output()
+        .with_encoding(crate::HashEncoding::Base64)
+        .with_truncation(16)
+        .with_prefix("xxh3:"));
+
+    #[cfg(feature = "uuid")]
+    redactor_conformance_tests!(uuid => crate::UuidRedactor::with_namespace([1; 16]));
+
+    #[test]
+    fn arbitrary_inputs_should_be_reproducible_and_varied() {
+        let inputs: Vec<_> = super::arbitrary_inputs().collect();
+        assert_eq!(inputs, super::arbitrary_inputs().collect::<Vec<_>>());
+        assert_eq!(inputs.len(), super::ARBITRARY_INPUTS);
+
+        for len in 1..=4 {
+            assert!(
+                inputs
+                    .iter()
+                    .flat_map(|input| input.chars())
+                    .any(|c| c.len_utf8() == len),
+                "no character takes {len} bytes"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "echoed the input")]
+    fn passthrough_should_fail_no_echo_check() {
+        crate::__private::check_no_echo(&SimpleRedactor::with_mode(
+            SimpleRedactorMode::Passthrough,
+        ));
+    }
+}
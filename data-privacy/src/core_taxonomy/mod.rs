@@ -3,8 +3,11 @@
 use data_privacy_macros::taxonomy;
 
 /// A simple data taxonomy with universal data classes.
-#[cfg_attr(feature = "serde", taxonomy(core, serde = true))]
-#[cfg_attr(not(feature = "serde"), taxonomy(core, serde = false))]
+#[taxonomy(
+    core,
+    serde = cfg!(feature = "serde"),
+    defmt = cfg!(feature = "defmt")
+)]
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum CoreTaxonomy {
     /// The `sensitive` data class indicates data must be treated carefully.
@@ -24,6 +27,49 @@ pub enum CoreTaxonomy {
 mod tests {
     use super::*;
     use crate::DataClass;
+    #[cfg(not(feature = "std"))]
+    use alloc::{
+        format,
+        string::{String, ToString},
+        vec::Vec,
+    };
+
+    #[test]
+    fn test_core_taxonomy_variants_round_trip() {
+        assert_eq!(
+            CoreTaxonomy::variants(),
+            [
+                CoreTaxonomy::Sensitive,
+                CoreTaxonomy::Insensitive,
+                CoreTaxonomy::UnknownSensitivity
+            ]
+        );
+        assert!(CoreTaxonomy::all_data_classes().eq(CoreTaxonomy::classes().iter().cloned()));
+
+        for variant in CoreTaxonomy::variants() {
+            assert_eq!(
+                variant.data_class().to_string().parse::<CoreTaxonomy>(),
+                Ok(variant.clone())
+            );
+        }
+
+        for invalid in [
+            "core",
+            "sensitive",
+            "core/",
+            "corp/sensitive",
+            "core/sensitive/x",
+        ] {
+            assert_eq!(
+                invalid.parse::<CoreTaxonomy>(),
+                Err(crate::UnknownClassError::new())
+            );
+        }
+        assert_eq!(
+            crate::UnknownClassError::new().to_string(),
+            "unknown data class, expected `taxonomy/class` naming a class of the taxonomy"
+        );
+    }
 
     #[test]
     fn test_core_taxonomy() {
@@ -39,6 +85,14 @@ mod tests {
             CoreTaxonomy::UnknownSensitivity.data_class(),
             DataClass::new("core", "unknown_sensitivity")
         );
+        assert_eq!(
+            CoreTaxonomy::classes(),
+            [
+                CoreTaxonomy::Sensitive.data_class(),
+                CoreTaxonomy::Insensitive.data_class(),
+                CoreTaxonomy::UnknownSensitivity.data_class(),
+            ]
+        );
     }
 
     #[test]
@@ -56,4 +110,308 @@ mod tests {
             "<core/unknown_sensitivity:REDACTED>"
         );
     }
+
+    #[test]
+    fn borrowed_containers_should_classify_without_copying() {
+        let row = String::from("john@example.com");
+        let email: SensitiveRef<'_, str> = SensitiveRef::new(row.as_str());
+        assert_eq!(format!("{email:?}"), "<core/sensitive:REDACTED>");
+        assert!(core::ptr::eq(email.declassify(), row.as_str()));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn borrowed_containers_should_be_redacted() {
+        let engine = crate::RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                crate::SimpleRedactor::new(),
+            )
+            .build();
+
+        let row = String::from("john@example.com");
+        let email = SensitiveRef::new(row.as_str());
+        assert_eq!(engine.display(&email).to_string(), "****************");
+    }
+
+    #[test]
+    fn test_arithmetic_preserves_classification() {
+        let total = Sensitive::new(10) + Sensitive::new(5);
+        assert_eq!(total.declassify(), 15);
+
+        let difference = Sensitive::new(10) - Sensitive::new(5);
+        assert_eq!(difference.declassify(), 5);
+
+        let product = Sensitive::new(10) * Sensitive::new(5);
+        assert_eq!(product.declassify(), 50);
+
+        let quotient = Sensitive::new(10) / Sensitive::new(4);
+        assert_eq!(quotient.declassify(), 2);
+
+        let remainder = Sensitive::new(10) % Sensitive::new(4);
+        assert_eq!(remainder.declassify(), 2);
+
+        let negated = -Sensitive::new(10);
+        assert_eq!(negated.declassify(), -10);
+
+        let mut counter = Sensitive::new(1);
+        counter += Sensitive::new(2);
+        counter *= Sensitive::new(3);
+        counter -= Sensitive::new(1);
+        counter /= Sensitive::new(2);
+        counter %= Sensitive::new(3);
+        assert_eq!(counter.declassify(), 1);
+
+        let sum: Sensitive<u32> = [1, 2, 3].into_iter().map(Sensitive::new).sum();
+        assert_eq!(sum.declassify(), 6);
+    }
+
+    #[test]
+    fn test_combinators_preserve_classification() {
+        let name = Sensitive::new("John".to_string());
+
+        let len: Sensitive<usize> = name.as_ref().map(String::len);
+        assert_eq!(len.declassify(), 4);
+
+        let mut upper = name.map(|s| s.to_uppercase());
+        _ = upper.as_mut().map(|s| s.push('!'));
+
+        let pair = upper.zip_same(Sensitive::new(33));
+        assert_eq!(
+            Sensitive::<(String, i32)>::data_class(),
+            CoreTaxonomy::Sensitive.data_class()
+        );
+        assert_eq!(pair.declassify(), ("JOHN!".to_string(), 33));
+    }
+
+    #[test]
+    fn test_transpose_and_collect_preserve_classification() {
+        let some = Sensitive::new(Some(42)).transpose();
+        assert_eq!(some.map(Sensitive::declassify), Some(42));
+        assert!(Sensitive::new(None::<i32>).transpose().is_none());
+
+        let ok: Result<Sensitive<i32>, Sensitive<&str>> = Sensitive::new(Ok(1)).transpose();
+        assert_eq!(ok.map(Sensitive::declassify), Ok(1));
+        let err: Result<Sensitive<i32>, Sensitive<&str>> = Sensitive::new(Err("bad")).transpose();
+        assert_eq!(err.map_err(Sensitive::declassify), Err("bad"));
+
+        let names: Sensitive<Vec<&str>> = ["a", "b"].into_iter().map(Sensitive::new).collect();
+        assert_eq!(names.declassify(), ["a", "b"]);
+    }
+
+    #[test]
+    fn test_flatten_removes_redundant_classification() {
+        let nested = Sensitive::new(Sensitive::new("John".to_string()));
+        let flat = nested.flatten();
+        assert_eq!(flat.declassify(), "John");
+
+        let flat: Sensitive<u32> = Sensitive::new(Sensitive::new(7)).into();
+        assert_eq!(flat.declassify(), 7);
+    }
+
+    #[test]
+    fn test_with_class_combines_classes() {
+        let export = DataClass::new("corp", "export_controlled").with_level(1);
+
+        let value = Sensitive::new(7).with_class(export.clone());
+        assert_eq!(
+            value.data_classes(),
+            [CoreTaxonomy::Sensitive.data_class(), export.clone()]
+        );
+        assert_eq!(crate::Classified::data_class(&value), export);
+
+        let value: crate::MultiClassified<_> = Insensitive::new(7).into();
+        assert_eq!(
+            value.data_classes(),
+            [CoreTaxonomy::Insensitive.data_class()]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_deserialize_only_taxonomy() {
+        #[taxonomy(storage, serde = "deserialize")]
+        enum StorageTaxonomy {
+            Stored,
+        }
+
+        let value: Stored<String> = serde_json::from_str("\"secret\"").unwrap();
+        assert_eq!(value.declassify(), "secret");
+        assert_eq!(
+            StorageTaxonomy::Stored.data_class(),
+            DataClass::new("storage", "stored")
+        );
+    }
+
+    mod extended {
+        use super::CoreTaxonomy;
+        use crate::ClassCode;
+        use crate::taxonomy;
+        #[cfg(not(feature = "std"))]
+        use alloc::{string::ToString, vec::Vec};
+
+        #[taxonomy(corp, serde = false, extends(CoreTaxonomy))]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        enum CorpTaxonomy {
+            EmployeeId,
+        }
+
+        #[test]
+        fn test_extended_taxonomy_resolves_base_classes() {
+            assert_eq!(
+                CorpTaxonomy::from(CoreTaxonomy::Sensitive).data_class(),
+                CoreTaxonomy::Sensitive.data_class()
+            );
+            assert_eq!(
+                CorpTaxonomy::CoreTaxonomy(CoreTaxonomy::Insensitive).data_class(),
+                CoreTaxonomy::Insensitive.data_class()
+            );
+            assert_eq!(
+                CorpTaxonomy::EmployeeId.data_class().to_string(),
+                "corp/employee_id"
+            );
+            assert_eq!(EmployeeId::new(7).declassify(), 7);
+            assert_eq!(
+                CorpTaxonomy::classes(),
+                [CorpTaxonomy::EmployeeId.data_class()]
+            );
+        }
+
+        #[test]
+        fn test_extended_taxonomy_codes_match_classes() {
+            for class in [
+                CorpTaxonomy::EmployeeId,
+                CorpTaxonomy::CoreTaxonomy(CoreTaxonomy::Sensitive),
+            ] {
+                assert_eq!(class.code(), class.data_class().code());
+            }
+
+            assert_eq!(
+                CorpTaxonomy::CoreTaxonomy(CoreTaxonomy::Sensitive).code(),
+                crate::core_taxonomy::Sensitive::<()>::CODE
+            );
+            assert_eq!(EmployeeId::<()>::CODE, CorpTaxonomy::EmployeeId.code());
+
+            assert!(CorpTaxonomy::has_code(EmployeeId::<()>::CODE));
+            assert!(CorpTaxonomy::has_code(
+                crate::core_taxonomy::Sensitive::<()>::CODE
+            ));
+            assert!(!CoreTaxonomy::has_code(EmployeeId::<()>::CODE));
+            assert!(!CorpTaxonomy::has_code(ClassCode::of("corp", "missing")));
+        }
+
+        #[test]
+        fn test_extended_taxonomy_enumerates_and_parses_base_classes() {
+            assert_eq!(CorpTaxonomy::variants(), [CorpTaxonomy::EmployeeId]);
+            assert_eq!(
+                CorpTaxonomy::all_data_classes().collect::<Vec<_>>(),
+                [
+                    CorpTaxonomy::EmployeeId.data_class(),
+                    CoreTaxonomy::Sensitive.data_class(),
+                    CoreTaxonomy::Insensitive.data_class(),
+                    CoreTaxonomy::UnknownSensitivity.data_class(),
+                ]
+            );
+            assert_eq!(
+                "corp/employee_id".parse::<CorpTaxonomy>(),
+                Ok(CorpTaxonomy::EmployeeId)
+            );
+            assert_eq!(
+                "core/sensitive".parse::<CorpTaxonomy>(),
+                Ok(CorpTaxonomy::CoreTaxonomy(CoreTaxonomy::Sensitive))
+            );
+            assert!("corp/sensitive".parse::<CorpTaxonomy>().is_err());
+        }
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn containers_should_implement_defmt_format() {
+        fn assert_format<T: defmt::Format + ?Sized>(_value: &T) {}
+
+        // the payload needn't implement `Format`, as only the masked representation is emitted
+        struct Opaque;
+
+        assert_format(&Sensitive::new(Opaque));
+        assert_format(&Insensitive::new(Opaque));
+        assert_format(&UnknownSensitivity::new(Opaque));
+    }
+
+    #[cfg(feature = "zeroize")]
+    mod zeroizing {
+        use crate::Classified;
+        use crate::taxonomy;
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        use zeroize::Zeroize;
+
+        #[taxonomy(secrets, serde = false, zeroize = true)]
+        enum SecretTaxonomy {
+            Credential,
+        }
+
+        static SCRUBBED: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Debug, Default, PartialEq, Eq)]
+        struct Key(Vec<u8>);
+
+        impl Zeroize for Key {
+            fn zeroize(&mut self) {
+                self.0.zeroize();
+                _ = SCRUBBED.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        #[test]
+        fn test_zeroizing_containers_scrub_payload() {
+            let mut password = Credential::new("hunter2".to_string());
+            password.zeroize();
+            password.visit(|s| assert!(s.is_empty()));
+            assert_eq!(
+                password.data_class(),
+                SecretTaxonomy::Credential.data_class()
+            );
+
+            let before = SCRUBBED.load(Ordering::Relaxed);
+            drop(Credential::new(Key(vec![1, 2, 3])));
+            assert_eq!(SCRUBBED.load(Ordering::Relaxed), before + 1);
+
+            // declassified payloads are moved out without being scrubbed
+            let key = Credential::new(Key(vec![1, 2, 3])).declassify();
+            assert_eq!(SCRUBBED.load(Ordering::Relaxed), before + 1);
+            assert_eq!(key, Key(vec![1, 2, 3]));
+        }
+
+        /// A payload counting its own scrubs, so tests running in parallel don't disturb each other.
+        #[derive(Debug, Default)]
+        struct Tracked(std::sync::Arc<AtomicUsize>);
+
+        impl Zeroize for Tracked {
+            fn zeroize(&mut self) {
+                _ = self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        #[test]
+        fn test_declassify_zeroizing_scrubs_payload_when_dropped() {
+            let scrubbed = std::sync::Arc::new(AtomicUsize::new(0));
+            let payload =
+                Credential::new(Tracked(std::sync::Arc::clone(&scrubbed))).declassify_zeroizing();
+            assert_eq!(scrubbed.load(Ordering::Relaxed), 0);
+
+            drop(payload);
+            assert_eq!(scrubbed.load(Ordering::Relaxed), 1);
+        }
+
+        #[test]
+        fn test_flattening_zeroizing_containers_does_not_scrub_payload() {
+            let scrubbed = std::sync::Arc::new(AtomicUsize::new(0));
+            let nested =
+                Credential::new(Credential::new(Tracked(std::sync::Arc::clone(&scrubbed))));
+            let flat: Credential<Tracked> = nested.into();
+            assert_eq!(scrubbed.load(Ordering::Relaxed), 0);
+
+            drop(flat);
+            assert_eq!(scrubbed.load(Ordering::Relaxed), 1);
+        }
+    }
 }
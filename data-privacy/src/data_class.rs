@@ -1,14 +1,52 @@
+use crate::ClassCode;
+use alloc::borrow::Cow;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
 use core::fmt::Display;
-use std::borrow::Cow;
+#[cfg(feature = "std")]
+use core::hash::BuildHasherDefault;
+use core::hash::{Hash, Hasher};
 
 /// The identity of a well-known data class.
 ///
 /// Each data class has a name, which is unique in the context of a specific named taxonomy.
-#[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+///
+/// A data class also carries a restriction level, which indicates how carefully its data must be
+/// handled relative to other classes, optionally a stable numeric identifier, and handling policies
+/// such as a retention period and whether its data must never be logged. These are metadata:
+/// they don't participate in equality, ordering, or hashing, which depend only on the taxonomy and
+/// class names.
+///
+/// The [`ClassCode`] of the names is computed once when a data class is created, at compile time for
+/// data classes created with [`new`](Self::new) in a constant, and serves as the hash of the data class,
+/// so looking up a data class in a collection doesn't hash its names again. Collections built with a
+/// [`BuildClassHasher`](crate::BuildClassHasher) use the code as is, turning lookups into an integer
+/// hash followed by a comparison of the names.
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(from = "DataClassRepr"))]
 pub struct DataClass {
     taxonomy: Cow<'static, str>,
     name: Cow<'static, str>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    code: ClassCode,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "is_default_level")
+    )]
+    level: u8,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    id: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    retention_days: Option<u32>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "core::ops::Not::not")
+    )]
+    never_log: bool,
 }
 
 impl DataClass {
@@ -18,9 +56,155 @@ impl DataClass {
         Self {
             taxonomy: Cow::Borrowed(taxonomy),
             name: Cow::Borrowed(name),
+            code: ClassCode::of(taxonomy, name),
+            level: 0,
+            id: None,
+            retention_days: None,
+            never_log: false,
         }
     }
 
+    /// Creates a new data class instance from names which may be computed at runtime.
+    ///
+    /// This is useful when the names of a data class come from external input, such as a configuration
+    /// file or a classification tag embedded in text.
+    #[must_use]
+    pub fn from_parts(
+        taxonomy: impl Into<Cow<'static, str>>,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        let taxonomy = taxonomy.into();
+        let name = name.into();
+
+        Self {
+            code: ClassCode::of(&taxonomy, &name),
+            taxonomy,
+            name,
+            level: 0,
+            id: None,
+            retention_days: None,
+            never_log: false,
+        }
+    }
+
+    /// Sets the restriction level of the data class.
+    ///
+    /// Higher levels indicate data that must be handled more carefully. When a classified container
+    /// holds another classified container, the class with the higher level takes precedence.
+    /// Data classes have a level of 0 by default. The level is serialized along with the names, so data
+    /// classes keep their precedence when they are read back.
+    #[must_use]
+    pub const fn with_level(mut self, level: u8) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Returns the restriction level of the data class.
+    #[must_use]
+    pub const fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// Sets the numeric identifier of the data class.
+    ///
+    /// Identifiers are stable codes assigned by a taxonomy, which let external tooling refer to a class
+    /// independently of its name. They are set with the [`taxonomy`](crate::taxonomy) attribute.
+    #[must_use]
+    pub const fn with_id(mut self, id: u32) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Returns the numeric identifier of the data class, if it has one.
+    #[must_use]
+    pub const fn id(&self) -> Option<u32> {
+        self.id
+    }
+
+    /// Returns the compact code of the data class, computed from its names.
+    ///
+    /// See [`ClassCode`] for details.
+    #[must_use]
+    pub const fn code(&self) -> ClassCode {
+        self.code
+    }
+
+    /// Sets the number of days data of this class may be retained.
+    ///
+    /// The retention period is advisory: it's set with the [`taxonomy`](crate::taxonomy) attribute so that
+    /// storage and telemetry pipelines can look it up, but nothing in this crate enforces it.
+    #[must_use]
+    pub const fn with_retention_days(mut self, days: u32) -> Self {
+        self.retention_days = Some(days);
+        self
+    }
+
+    /// Returns the number of days data of this class may be retained, if limited.
+    #[must_use]
+    pub const fn retention_days(&self) -> Option<u32> {
+        self.retention_days
+    }
+
+    /// Sets whether data of this class must never be logged in the clear.
+    ///
+    /// A [`RedactionEngine`](crate::RedactionEngine) drops the output of a redactor for such a class when
+    /// the redactor [may reveal its input](crate::Redactor::reveals_input), so that a misconfigured
+    /// passthrough redactor can't leak the data.
+    /// [`RedactionEngineBuilder::try_build`](crate::RedactionEngineBuilder::try_build) rejects such a
+    /// redactor registered for the class.
+    ///
+    /// This flag is serialized along with the names, level, and identifier of the class, while the
+    /// retention period isn't.
+    #[must_use]
+    pub const fn with_never_log(mut self, never_log: bool) -> Self {
+        self.never_log = never_log;
+        self
+    }
+
+    /// Returns whether data of this class must never be logged in the clear.
+    #[must_use]
+    pub const fn never_log(&self) -> bool {
+        self.never_log
+    }
+
+    /// Returns the more restrictive of two data classes.
+    ///
+    /// This defines how classes combine when values of different classes are merged, such as when
+    /// [zipping](crate::Classified::zip) two classified values or when one classified container holds
+    /// another. The class with the higher [level](Self::level) wins. When both classes have the same
+    /// level, the first class wins.
+    ///
+    /// Levels come from the taxonomy definition, see the [`taxonomy`](crate::taxonomy) attribute.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use data_privacy::DataClass;
+    ///
+    /// let internal = DataClass::new("corp", "internal").with_level(1);
+    /// let secret = DataClass::new("corp", "secret").with_level(2);
+    ///
+    /// assert_eq!(DataClass::most_restrictive(&internal, &secret), &secret);
+    /// assert_eq!(DataClass::most_restrictive(&secret, &internal), &secret);
+    /// ```
+    #[must_use]
+    pub const fn most_restrictive<'a>(a: &'a Self, b: &'a Self) -> &'a Self {
+        if b.level > a.level { b } else { a }
+    }
+
+    /// Returns the most restrictive of several data classes, or `None` if there are none.
+    ///
+    /// Among data classes of the same level, the earliest one wins. This is the rule used for values
+    /// falling under several data classes, such as a [`MultiClassified`](crate::MultiClassified), by
+    /// both their [`Classified::data_class`](crate::Classified::data_class) and the
+    /// [`RedactionEngine`](crate::RedactionEngine).
+    #[must_use]
+    pub fn most_restrictive_of<'a>(
+        data_classes: impl IntoIterator<Item = &'a Self>,
+    ) -> Option<&'a Self> {
+        data_classes.into_iter().reduce(Self::most_restrictive)
+    }
+
     /// Returns the taxonomy of the data class.
     #[must_use]
     pub fn taxonomy(&self) -> &str {
@@ -34,6 +218,166 @@ impl DataClass {
     }
 }
 
+/// The serialized form of a data class, from which the code of its names is computed again.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "DataClass")]
+struct DataClassRepr {
+    taxonomy: Cow<'static, str>,
+    name: Cow<'static, str>,
+    #[serde(default)]
+    level: u8,
+    #[serde(default)]
+    id: Option<u32>,
+    #[serde(default)]
+    never_log: bool,
+}
+
+#[cfg(feature = "serde")]
+#[expect(
+    clippy::trivially_copy_pass_by_ref,
+    reason = "serde passes fields by reference"
+)]
+const fn is_default_level(level: &u8) -> bool {
+    *level == 0
+}
+
+#[cfg(feature = "serde")]
+impl From<DataClassRepr> for DataClass {
+    fn from(repr: DataClassRepr) -> Self {
+        let data_class = Self::from_parts(repr.taxonomy, repr.name)
+            .with_level(repr.level)
+            .with_never_log(repr.never_log);
+        match repr.id {
+            Some(id) => data_class.with_id(id),
+            None => data_class,
+        }
+    }
+}
+
+/// A hasher for collections keyed by [`DataClass`] or [`ClassCode`], which uses the code computed when
+/// each data class was created as the hash.
+///
+/// Class codes aren't keyed, so collections using this hasher shouldn't be filled from untrusted input,
+/// which could pick names whose codes collide. The [`RedactionEngine`](crate::RedactionEngine) uses it
+/// for its collections, which are only filled from configuration.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::CoreTaxonomy;
+/// use data_privacy::BuildClassHasher;
+/// use std::collections::HashMap;
+///
+/// let mut limits = HashMap::with_hasher(BuildClassHasher::default());
+/// limits.insert(CoreTaxonomy::Sensitive.data_class(), 10);
+/// assert_eq!(limits.get(&CoreTaxonomy::Sensitive.data_class()), Some(&10));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ClassHasher(u64);
+
+/// Builds [`ClassHasher`] instances.
+#[cfg(feature = "std")]
+pub type BuildClassHasher = BuildHasherDefault<ClassHasher>;
+
+#[cfg(feature = "std")]
+impl Hasher for ClassHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.0 ^= i;
+    }
+}
+
+/// The identity of a data class, as a pair of taxonomy and class names.
+///
+/// This lets collections keyed by [`DataClass`] be queried with borrowed names, without
+/// constructing a `DataClass`. Implementations must hash and compare the same way `DataClass` does.
+pub trait ClassKey {
+    /// Returns the name of the taxonomy.
+    fn taxonomy(&self) -> &str;
+
+    /// Returns the name of the class.
+    fn name(&self) -> &str;
+}
+
+impl ClassKey for DataClass {
+    fn taxonomy(&self) -> &str {
+        &self.taxonomy
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl ClassKey for (&str, &str) {
+    fn taxonomy(&self) -> &str {
+        self.0
+    }
+
+    fn name(&self) -> &str {
+        self.1
+    }
+}
+
+impl<'a> Borrow<dyn ClassKey + 'a> for DataClass {
+    fn borrow(&self) -> &(dyn ClassKey + 'a) {
+        self
+    }
+}
+
+impl PartialEq for dyn ClassKey + '_ {
+    fn eq(&self, other: &Self) -> bool {
+        self.taxonomy() == other.taxonomy() && self.name() == other.name()
+    }
+}
+
+impl Eq for dyn ClassKey + '_ {}
+
+impl Hash for dyn ClassKey + '_ {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ClassCode::of(self.taxonomy(), self.name()).hash(state);
+    }
+}
+
+impl PartialEq for DataClass {
+    fn eq(&self, other: &Self) -> bool {
+        self.code == other.code && self.taxonomy == other.taxonomy && self.name == other.name
+    }
+}
+
+impl Eq for DataClass {}
+
+impl PartialOrd for DataClass {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DataClass {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.taxonomy
+            .cmp(&other.taxonomy)
+            .then_with(|| self.name.cmp(&other.name))
+    }
+}
+
+impl Hash for DataClass {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.code.hash(state);
+    }
+}
+
 impl Display for DataClass {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}/{}", self.taxonomy, self.name)
@@ -42,9 +386,17 @@ impl Display for DataClass {
 
 #[cfg(test)]
 mod tests {
+    // the test harness links `std` anyway, so its hasher is usable without the `std` feature
+    #[cfg(not(feature = "std"))]
+    extern crate std;
+
     use super::*;
-    use core::hash::{Hash, Hasher};
-    use std::collections::hash_map::DefaultHasher;
+    #[cfg(not(feature = "std"))]
+    use alloc::{
+        format,
+        string::{String, ToString},
+    };
+    use std::hash::DefaultHasher;
 
     #[test]
     fn new_should_create_data_class() {
@@ -56,6 +408,188 @@ mod tests {
         assert_eq!(data_class.name(), "class");
     }
 
+    #[test]
+    fn from_parts_should_match_static_data_class() {
+        let data_class = DataClass::from_parts("taxonomy".to_string(), String::from("class"));
+        assert_eq!(data_class, DataClass::new("taxonomy", "class"));
+    }
+
+    #[test]
+    fn policies_should_not_affect_identity() {
+        let plain = DataClass::new("taxonomy", "class");
+        let governed = DataClass::new("taxonomy", "class")
+            .with_retention_days(30)
+            .with_never_log(true);
+
+        assert_eq!(plain.retention_days(), None);
+        assert!(!plain.never_log());
+        assert_eq!(governed.retention_days(), Some(30));
+        assert!(governed.never_log());
+        assert_eq!(plain, governed);
+    }
+
+    #[test]
+    fn level_should_not_affect_identity() {
+        let plain = DataClass::new("taxonomy", "class");
+        let leveled = DataClass::new("taxonomy", "class").with_level(3);
+
+        assert_eq!(plain.level(), 0);
+        assert_eq!(leveled.level(), 3);
+        assert_eq!(plain, leveled);
+        assert_eq!(plain.cmp(&leveled), Ordering::Equal);
+
+        let mut hasher1 = DefaultHasher::new();
+        plain.hash(&mut hasher1);
+        let mut hasher2 = DefaultHasher::new();
+        leveled.hash(&mut hasher2);
+        assert_eq!(hasher1.finish(), hasher2.finish());
+    }
+
+    #[test]
+    fn id_should_not_affect_identity() {
+        let plain = DataClass::new("taxonomy", "class");
+        let numbered = DataClass::new("taxonomy", "class").with_id(17);
+
+        assert_eq!(plain.id(), None);
+        assert_eq!(numbered.id(), Some(17));
+        assert_eq!(plain, numbered);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_should_round_trip_id() {
+        let data_class = DataClass::new("taxonomy", "class").with_id(17);
+        let serialized = serde_json::to_string(&data_class).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"taxonomy":"taxonomy","name":"class","id":17}"#
+        );
+
+        let deserialized: DataClass = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.id(), Some(17));
+
+        let serialized = serde_json::to_string(&DataClass::new("taxonomy", "class")).unwrap();
+        assert_eq!(serialized, r#"{"taxonomy":"taxonomy","name":"class"}"#);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_should_round_trip_never_log() {
+        let data_class = DataClass::new("taxonomy", "class").with_never_log(true);
+        let serialized = serde_json::to_string(&data_class).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"taxonomy":"taxonomy","name":"class","never_log":true}"#
+        );
+
+        let deserialized: DataClass = serde_json::from_str(&serialized).unwrap();
+        assert!(deserialized.never_log());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_should_round_trip_level() {
+        let data_class = DataClass::new("taxonomy", "class").with_level(2);
+        let serialized = serde_json::to_string(&data_class).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"taxonomy":"taxonomy","name":"class","level":2}"#
+        );
+
+        let deserialized: DataClass = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.level(), 2);
+
+        let low = DataClass::new("taxonomy", "low").with_level(1);
+        assert_eq!(
+            DataClass::most_restrictive(&deserialized, &low),
+            &deserialized
+        );
+
+        let deserialized: DataClass =
+            serde_json::from_str(r#"{"taxonomy":"taxonomy","name":"class"}"#).unwrap();
+        assert_eq!(deserialized.level(), 0);
+    }
+
+    #[test]
+    fn most_restrictive_should_prefer_higher_level() {
+        let low = DataClass::new("taxonomy", "low").with_level(1);
+        let high = DataClass::new("taxonomy", "high").with_level(2);
+        let other = DataClass::new("taxonomy", "other").with_level(1);
+
+        assert_eq!(DataClass::most_restrictive(&low, &high), &high);
+        assert_eq!(DataClass::most_restrictive(&high, &low), &high);
+        assert_eq!(DataClass::most_restrictive(&low, &other), &low);
+        assert_eq!(DataClass::most_restrictive(&other, &low), &other);
+
+        assert_eq!(
+            DataClass::most_restrictive_of([&low, &high, &other]),
+            Some(&high)
+        );
+        assert_eq!(DataClass::most_restrictive_of([&other, &low]), Some(&other));
+        assert_eq!(DataClass::most_restrictive_of([]), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn class_key_should_find_data_classes() {
+        let mut map = std::collections::HashMap::new();
+        _ = map.insert(DataClass::new("taxonomy", "class"), 1);
+        _ = map.insert(DataClass::new("taxonomy", "other"), 2);
+
+        let taxonomy = String::from("taxonomy");
+        assert_eq!(
+            map.get(&(taxonomy.as_str(), "class") as &dyn ClassKey),
+            Some(&1)
+        );
+        assert_eq!(map.get(&("taxonomy", "other") as &dyn ClassKey), Some(&2));
+        assert_eq!(map.get(&("other", "class") as &dyn ClassKey), None);
+    }
+
+    #[test]
+    fn class_keys_should_hash_like_data_classes() {
+        fn hash(value: &(impl Hash + ?Sized)) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        const DATA_CLASS: DataClass = DataClass::new("taxonomy", "class");
+        let key: &dyn ClassKey = &("taxonomy", "class");
+
+        assert_eq!(hash(&DATA_CLASS), hash(key));
+        assert_eq!(
+            hash(&DATA_CLASS),
+            hash(&DataClass::from_parts("taxonomy".to_string(), "class"))
+        );
+        assert_ne!(
+            hash(&DataClass::new("ab", "c")),
+            hash(&DataClass::new("a", "bc"))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn class_hasher_should_find_data_classes() {
+        let mut map = std::collections::HashMap::with_hasher(BuildClassHasher::default());
+        _ = map.insert(DataClass::new("taxonomy", "class"), 1);
+        _ = map.insert(DataClass::new("taxonomy", "other"), 2);
+
+        assert_eq!(map.get(&DataClass::new("taxonomy", "class")), Some(&1));
+        assert_eq!(map.get(&("taxonomy", "other") as &dyn ClassKey), Some(&2));
+        assert_eq!(map.get(&DataClass::new("other", "class")), None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "std"))]
+    fn deserialized_data_classes_should_hash_like_others() {
+        let data_class: DataClass =
+            serde_json::from_str(r#"{"taxonomy":"taxonomy","name":"class"}"#).unwrap();
+
+        let mut map = std::collections::HashMap::with_hasher(BuildClassHasher::default());
+        _ = map.insert(DataClass::new("taxonomy", "class"), 1);
+        assert_eq!(map.get(&data_class), Some(&1));
+    }
+
     #[test]
     fn display_should_format_correctly() {
         let data_class = DataClass::new("taxonomy", "class");
@@ -81,7 +615,7 @@ mod tests {
         assert!(data_class1 < data_class3);
         assert!(data_class1 > data_class4);
         assert!(data_class3 > data_class4);
-        assert_eq!(data_class1.cmp(&data_class2), core::cmp::Ordering::Equal);
+        assert_eq!(data_class1.cmp(&data_class2), Ordering::Equal);
 
         // Hash
         let mut hasher1 = DefaultHasher::new();
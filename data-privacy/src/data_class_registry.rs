@@ -0,0 +1,209 @@
+use crate::{DataClass, DuplicateClass};
+use std::sync::OnceLock;
+
+/// The data classes of a taxonomy, as registered by the [`taxonomy`](crate::taxonomy) attribute.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct TaxonomyRegistration {
+    classes: &'static [DataClass],
+    source: &'static str,
+}
+
+impl TaxonomyRegistration {
+    /// Creates a registration for the given data classes, defined by the given taxonomy enum.
+    #[must_use]
+    pub const fn new(classes: &'static [DataClass], source: &'static str) -> Self {
+        Self { classes, source }
+    }
+}
+
+inventory::collect!(TaxonomyRegistration);
+
+/// Registers the data classes of a taxonomy with the [`DataClassRegistry`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_taxonomy {
+    ($classes:expr, $source:ident) => {
+        $crate::__private::inventory::submit! {
+            $crate::__private::TaxonomyRegistration::new(
+                $classes,
+                ::core::concat!(::core::module_path!(), "::", ::core::stringify!($source)),
+            )
+        }
+    };
+}
+
+/// The data classes of all the taxonomies linked into the binary.
+///
+/// Every taxonomy defined with the [`taxonomy`](crate::taxonomy) attribute registers its data classes
+/// when the `registry` feature is enabled, in whichever crate it's defined. This lets applications
+/// enumerate the known data classes, or check that the data classes named in configuration, such as a
+/// [`RedactionPolicy`](crate::RedactionPolicy), actually exist.
+///
+/// Since taxonomies are identified by name only, two enums can define the same data class. The registry
+/// keeps the first definition it finds and reports the conflict through [`duplicates`](Self::duplicates),
+/// which applications can check at startup.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::DataClassRegistry;
+/// use data_privacy::core_taxonomy::CoreTaxonomy;
+///
+/// let registry = DataClassRegistry::global();
+/// assert_eq!(registry.lookup("core/sensitive"), Some(&CoreTaxonomy::Sensitive.data_class()));
+/// assert_eq!(registry.lookup("core/missing"), None);
+/// assert!(registry.duplicates().is_empty());
+/// ```
+#[derive(Debug)]
+pub struct DataClassRegistry {
+    classes: Vec<DataClass>,
+    duplicates: Vec<DuplicateClass>,
+}
+
+impl DataClassRegistry {
+    /// Returns the registry of the data classes linked into the binary.
+    #[must_use]
+    pub fn global() -> &'static Self {
+        static GLOBAL: OnceLock<DataClassRegistry> = OnceLock::new();
+
+        GLOBAL.get_or_init(|| Self::from_registrations(inventory::iter::<TaxonomyRegistration>))
+    }
+
+    fn from_registrations(
+        registrations: impl IntoIterator<Item = &'static TaxonomyRegistration>,
+    ) -> Self {
+        let mut definitions: Vec<_> = registrations
+            .into_iter()
+            .flat_map(|registration| {
+                registration
+                    .classes
+                    .iter()
+                    .map(|data_class| (data_class, registration.source))
+            })
+            .collect();
+
+        definitions.sort_unstable();
+        definitions.dedup();
+
+        let mut classes: Vec<DataClass> = Vec::with_capacity(definitions.len());
+        let mut duplicates = Vec::new();
+        for group in definitions.chunk_by(|a, b| a.0 == b.0) {
+            classes.push(group[0].0.clone());
+            if group.len() > 1 {
+                let sources = group.iter().map(|(_, source)| *source).collect();
+                duplicates.push(DuplicateClass::new(group[0].0.clone(), sources));
+            }
+        }
+
+        Self {
+            classes,
+            duplicates,
+        }
+    }
+
+    /// Finds a data class given as `taxonomy/class`.
+    #[must_use]
+    pub fn lookup(&self, name: &str) -> Option<&DataClass> {
+        let (taxonomy, name) = name.split_once('/')?;
+        self.get(taxonomy, name)
+    }
+
+    /// Finds a data class by its taxonomy and class names.
+    #[must_use]
+    pub fn get(&self, taxonomy: &str, name: &str) -> Option<&DataClass> {
+        self.classes
+            .binary_search_by(|data_class| {
+                (data_class.taxonomy(), data_class.name()).cmp(&(taxonomy, name))
+            })
+            .ok()
+            .map(|index| &self.classes[index])
+    }
+
+    /// Returns all the registered data classes, ordered by taxonomy and class name.
+    pub fn classes(&self) -> impl Iterator<Item = &DataClass> {
+        self.classes.iter()
+    }
+
+    /// Returns the data classes defined by more than one taxonomy enum, ordered by taxonomy and class name.
+    #[must_use]
+    pub fn duplicates(&self) -> &[DuplicateClass] {
+        &self.duplicates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::CoreTaxonomy;
+    use crate::taxonomy;
+
+    #[taxonomy(registry_test, serde = false)]
+    enum RegistryTestTaxonomy {
+        #[class(name = "pii.email", id = 3)]
+        Email,
+    }
+
+    #[test]
+    fn registry_should_hold_all_taxonomies() {
+        let registry = DataClassRegistry::global();
+
+        for data_class in CoreTaxonomy::classes() {
+            assert!(registry.classes().any(|c| c == data_class));
+        }
+
+        let email = registry.lookup("registry_test/pii.email").unwrap();
+        assert_eq!(email, &RegistryTestTaxonomy::Email.data_class());
+        assert_eq!(email.id(), Some(3));
+        assert_eq!(registry.get("registry_test", "pii.email"), Some(email));
+    }
+
+    #[test]
+    fn lookup_should_reject_unknown_names() {
+        let registry = DataClassRegistry::global();
+        assert_eq!(registry.lookup("core"), None);
+        assert_eq!(registry.lookup("core/unknown"), None);
+        assert_eq!(registry.lookup("unknown/sensitive"), None);
+    }
+
+    #[test]
+    fn duplicates_should_report_every_source() {
+        static FIRST: [DataClass; 2] = [
+            DataClass::new("dup_test", "email"),
+            DataClass::new("dup_test", "phone"),
+        ];
+        static SECOND: [DataClass; 1] = [DataClass::new("dup_test", "email").with_level(2)];
+
+        static REGISTRATIONS: [TaxonomyRegistration; 3] = [
+            TaxonomyRegistration::new(&SECOND, "b::Second"),
+            TaxonomyRegistration::new(&FIRST, "a::First"),
+            TaxonomyRegistration::new(&FIRST, "a::First"),
+        ];
+
+        let registry = DataClassRegistry::from_registrations(&REGISTRATIONS);
+
+        assert_eq!(registry.classes().count(), 2);
+        assert_eq!(
+            registry.duplicates(),
+            [DuplicateClass::new(
+                DataClass::new("dup_test", "email"),
+                vec!["a::First", "b::Second"]
+            )]
+        );
+        assert_eq!(
+            registry.duplicates()[0].sources(),
+            ["a::First", "b::Second"]
+        );
+    }
+
+    #[test]
+    fn global_registry_should_have_no_duplicates() {
+        assert_eq!(DataClassRegistry::global().duplicates(), []);
+    }
+
+    #[test]
+    fn classes_should_be_sorted() {
+        let classes: Vec<_> = DataClassRegistry::global().classes().collect();
+        assert!(classes.is_sorted());
+    }
+}
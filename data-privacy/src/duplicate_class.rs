@@ -0,0 +1,33 @@
+use crate::DataClass;
+
+/// A data class defined by more than one taxonomy.
+///
+/// Taxonomies are identified by name only, so nothing prevents two enums, possibly in different crates,
+/// from defining the same `taxonomy/class` with different meanings. Such conflicts are reported by
+/// [`DataClassRegistry::duplicates`](crate::DataClassRegistry::duplicates).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DuplicateClass {
+    data_class: DataClass,
+    sources: Vec<&'static str>,
+}
+
+impl DuplicateClass {
+    pub(crate) const fn new(data_class: DataClass, sources: Vec<&'static str>) -> Self {
+        Self {
+            data_class,
+            sources,
+        }
+    }
+
+    /// Returns the data class defined more than once.
+    #[must_use]
+    pub const fn data_class(&self) -> &DataClass {
+        &self.data_class
+    }
+
+    /// Returns the paths of the taxonomy enums defining the data class, in alphabetical order.
+    #[must_use]
+    pub fn sources(&self) -> &[&'static str] {
+        &self.sources
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{Classified, DataClass};
+use core::fmt::Debug;
+
+/// A classified container whose data class is determined at runtime.
+///
+/// Containers generated by the [`taxonomy`](crate::taxonomy) attribute carry their data class in their type.
+/// This container instead stores the data class alongside the payload, which is useful when the class is only
+/// known at runtime, such as when [flattening](Classified::flatten) nested containers.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::{Classified, DataClass, DynamicClassified};
+///
+/// let value = DynamicClassified::new("John", DataClass::new("corp", "name"));
+/// assert_eq!(value.data_class(), DataClass::new("corp", "name"));
+/// assert_eq!(format!("{value:?}"), "<corp/name:REDACTED>");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct DynamicClassified<T> {
+    payload: T,
+    data_class: DataClass,
+}
+
+impl<T> DynamicClassified<T> {
+    /// Creates a new instance of the classified data container.
+    #[must_use]
+    pub const fn new(payload: T, data_class: DataClass) -> Self {
+        Self {
+            payload,
+            data_class,
+        }
+    }
+
+    /// Transforms the payload without declassifying it, keeping the data class.
+    #[must_use]
+    pub fn map<U>(self, operation: impl FnOnce(T) -> U) -> DynamicClassified<U> {
+        DynamicClassified::new(operation(self.payload), self.data_class)
+    }
+
+    /// Borrows the payload, producing a container of the same data class holding a reference to it.
+    #[must_use]
+    pub fn as_ref(&self) -> DynamicClassified<&T> {
+        DynamicClassified::new(&self.payload, self.data_class.clone())
+    }
+}
+
+impl<T> DynamicClassified<Option<T>> {
+    /// Turns a container of an optional payload into an optional container, keeping the data class.
+    #[must_use]
+    pub fn transpose(self) -> Option<DynamicClassified<T>> {
+        let data_class = self.data_class;
+        self.payload
+            .map(|payload| DynamicClassified::new(payload, data_class))
+    }
+}
+
+impl<T> Classified<T> for DynamicClassified<T> {
+    #[track_caller]
+    fn declassify(self) -> T {
+        let data_class = self.data_class;
+        crate::audit::record_declassification(|| data_class);
+        self.payload
+    }
+
+    fn take_payload(self, _token: crate::__private::PayloadToken) -> T {
+        self.payload
+    }
+
+    fn visit(&self, operation: impl FnOnce(&T)) {
+        operation(&self.payload);
+    }
+
+    fn visit_mut(&mut self, operation: impl FnOnce(&mut T)) {
+        operation(&mut self.payload);
+    }
+
+    fn data_class(&self) -> DataClass {
+        self.data_class.clone()
+    }
+}
+
+impl<T> Debug for DynamicClassified<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "<{}:REDACTED>", self.data_class)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::format;
+
+    #[test]
+    fn container_should_hold_payload_and_class() {
+        let data_class = DataClass::new("taxonomy", "class");
+        let mut value = DynamicClassified::new(1, data_class.clone());
+
+        value.visit_mut(|payload| *payload += 1);
+        value.visit(|payload| assert_eq!(*payload, 2));
+        assert_eq!(value.data_class(), data_class);
+        assert_eq!(format!("{value:?}"), "<taxonomy/class:REDACTED>");
+        assert_eq!(value.declassify(), 2);
+    }
+
+    #[test]
+    fn combinators_should_keep_class() {
+        let data_class = DataClass::new("taxonomy", "class");
+        let value = DynamicClassified::new(Some("abc"), data_class.clone());
+
+        let len = value.as_ref().map(|payload| payload.map_or(0, str::len));
+        assert_eq!(len.data_class(), data_class);
+        assert_eq!(len.declassify(), 3);
+
+        let inner = value.transpose().unwrap();
+        assert_eq!(inner.data_class(), data_class);
+        assert_eq!(inner.declassify(), "abc");
+        assert!(
+            DynamicClassified::new(None::<i32>, data_class)
+                .transpose()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use crate::SecretProvider;
+use std::borrow::Cow;
+use std::env::{self, VarError};
+use std::io;
+
+/// A [`SecretProvider`] reading the secret from an environment variable.
+///
+/// The secret is the UTF-8 value of the variable, read each time the secret is fetched.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::{EnvSecretProvider, SecretProvider};
+///
+/// let provider = EnvSecretProvider::new("MY_APP_REDACTION_SECRET");
+/// assert!(provider.secret().is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct EnvSecretProvider {
+    var: Cow<'static, str>,
+}
+
+impl EnvSecretProvider {
+    /// Creates a new provider reading the given environment variable.
+    #[must_use]
+    pub fn new(var: impl Into<Cow<'static, str>>) -> Self {
+        Self { var: var.into() }
+    }
+}
+
+impl SecretProvider for EnvSecretProvider {
+    fn secret(&self) -> io::Result<Vec<u8>> {
+        env::var(self.var.as_ref())
+            .map(String::into_bytes)
+            .map_err(|e| match e {
+                VarError::NotPresent => io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("environment variable `{}` is not set", self.var),
+                ),
+                VarError::NotUnicode(_) => io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("environment variable `{}` is not valid UTF-8", self.var),
+                ),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_should_come_from_variable() {
+        // cargo sets this variable when running tests
+        let provider = EnvSecretProvider::new("CARGO_PKG_NAME");
+        assert_eq!(provider.secret().unwrap(), b"data-privacy");
+
+        let error = EnvSecretProvider::new("DATA_PRIVACY_UNSET_SECRET")
+            .secret()
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(error.to_string().contains("DATA_PRIVACY_UNSET_SECRET"));
+    }
+}
@@ -0,0 +1,210 @@
+use crate::DataClass;
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+use core::fmt::Formatter;
+use core::marker::PhantomData;
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// The fields of an envelope, in the order they're serialized.
+const FIELDS: &[&str] = &["class", "value"];
+
+/// Serializes the payload of a classified container within an envelope naming its data class.
+///
+/// The envelope has the form `{"class": "taxonomy/class", "value": ...}`. The payload is serialized
+/// like that of any other container, so it's redacted within a
+/// [`SerializeRedacted`](crate::SerializeRedacted).
+///
+/// # Errors
+///
+/// Returns any error produced by the serializer.
+pub fn serialize_enveloped<T, S>(
+    payload: &T,
+    data_class: &DataClass,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize + ?Sized,
+    S: Serializer,
+{
+    /// Serializes the payload the way containers which aren't strict do.
+    struct Value<'a, T: ?Sized> {
+        payload: &'a T,
+        data_class: &'a DataClass,
+    }
+
+    impl<T: Serialize + ?Sized> Serialize for Value<'_, T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            crate::__private::serialize_classified(self.payload, self.data_class, serializer)
+        }
+    }
+
+    let mut envelope = serializer.serialize_struct("Classified", FIELDS.len())?;
+    envelope.serialize_field("class", &format_args!("{data_class}"))?;
+    envelope.serialize_field(
+        "value",
+        &Value {
+            payload,
+            data_class,
+        },
+    )?;
+    envelope.end()
+}
+
+/// Deserializes the payload of a classified container from an envelope naming its data class.
+///
+/// # Errors
+///
+/// Returns an error if the input isn't an envelope, or if the envelope doesn't name the given data class.
+pub fn deserialize_enveloped<'de, T, D>(
+    deserializer: D,
+    data_class: &DataClass,
+) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_struct(
+        "Classified",
+        FIELDS,
+        EnvelopeVisitor {
+            data_class,
+            payload: PhantomData,
+        },
+    )
+}
+
+#[derive(serde::Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum Field {
+    Class,
+    Value,
+}
+
+/// Checks that the class named by an envelope is the expected one.
+fn check_class<E: de::Error>(data_class: &DataClass, class: &str) -> Result<(), E> {
+    if class.split_once('/') == Some((data_class.taxonomy(), data_class.name())) {
+        Ok(())
+    } else {
+        Err(E::custom(format_args!(
+            "expected data class `{data_class}`, found `{class}`"
+        )))
+    }
+}
+
+struct EnvelopeVisitor<'a, T> {
+    data_class: &'a DataClass,
+    payload: PhantomData<T>,
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for EnvelopeVisitor<'_, T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            formatter,
+            "an envelope holding data of class `{}`",
+            self.data_class
+        )
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        let class: String = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        check_class(self.data_class, &class)?;
+
+        seq.next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<T, A::Error> {
+        let mut class: Option<String> = None;
+        let mut value = None;
+
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Class if class.is_some() => return Err(de::Error::duplicate_field("class")),
+                Field::Class => class = Some(map.next_value()?),
+                Field::Value if value.is_some() => return Err(de::Error::duplicate_field("value")),
+                Field::Value => value = Some(map.next_value()?),
+            }
+        }
+
+        let class = class.ok_or_else(|| de::Error::missing_field("class"))?;
+        check_class(self.data_class, &class)?;
+        value.ok_or_else(|| de::Error::missing_field("value"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::taxonomy;
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, string::ToString};
+
+    #[taxonomy(strict_test, serde = "strict")]
+    enum StrictTaxonomy {
+        Secret,
+        Other,
+    }
+
+    #[test]
+    fn containers_should_round_trip_through_envelope() {
+        let json = serde_json::to_string(&Secret::new(42)).unwrap();
+        assert_eq!(json, r#"{"class":"strict_test/secret","value":42}"#);
+
+        let value: Secret<u32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(value.declassify(), 42);
+
+        let value: Secret<u32> =
+            serde_json::from_str(r#"{"value":7,"class":"strict_test/secret"}"#).unwrap();
+        assert_eq!(value.declassify(), 7);
+    }
+
+    #[test]
+    fn cleartext_should_be_refused() {
+        let err = serde_json::from_str::<Secret<u32>>("42").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("an envelope holding data of class `strict_test/secret`")
+        );
+
+        let err = serde_json::from_str::<Secret<u32>>(r#"{"value":42}"#).unwrap_err();
+        assert!(err.to_string().contains("missing field `class`"));
+
+        let err =
+            serde_json::from_str::<Secret<u32>>(r#"{"class":"strict_test/secret"}"#).unwrap_err();
+        assert!(err.to_string().contains("missing field `value`"));
+    }
+
+    #[test]
+    fn mismatched_class_should_be_refused() {
+        let json = serde_json::to_string(&Other::new(42)).unwrap();
+        let err = serde_json::from_str::<Secret<u32>>(&json).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("expected data class `strict_test/secret`, found `strict_test/other`")
+        );
+
+        let err = serde_json::from_str::<Secret<u32>>(r#"["strict_test/other",42]"#).unwrap_err();
+        let other = StrictTaxonomy::Other.data_class();
+        assert!(err.to_string().contains(&format!("found `{other}`")));
+
+        let value: Secret<u32> = serde_json::from_str(r#"["strict_test/secret",42]"#).unwrap();
+        assert_eq!(value.declassify(), 42);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn value_should_be_redacted_within_serialize_redacted() {
+        use crate::{RedactionEngineBuilder, SimpleRedactor};
+
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(&StrictTaxonomy::Secret.data_class(), SimpleRedactor::new())
+            .build();
+
+        let json = serde_json::to_string(&engine.to_serializable(&Secret::new(42))).unwrap();
+        assert_eq!(json, r#"{"class":"strict_test/secret","value":"**"}"#);
+    }
+}
@@ -0,0 +1,20 @@
+/// The kind of environment an application runs in, used to select a redaction preset.
+///
+/// See [`RedactionEngineBuilder::preset`](crate::RedactionEngineBuilder::preset) for the configuration
+/// each environment implies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Environment {
+    /// A developer's machine, where seeing the data is more useful than hiding it.
+    Dev,
+
+    /// A pre-production environment, where data is masked but its classification remains visible.
+    Staging,
+
+    /// A production environment, where sensitive data must never be exposed.
+    ///
+    /// The preset for this environment erases sensitive data. To hash it with a keyed hash instead, so
+    /// that values can be correlated without being revealed, use `RedactionEngineBuilder::prod_preset`,
+    /// which requires the `xxh3` feature.
+    Prod,
+}
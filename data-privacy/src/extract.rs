@@ -0,0 +1,665 @@
+use crate::formatting::{to_hex, with_formatted};
+use crate::{DataClass, DynamicClassified, MultiClassified};
+use alloc::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// Receives the pieces of a value as it is being [extracted](Extract).
+///
+/// The pieces of a value are either plain text, which holds no sensitive data, or classified text,
+/// which is tagged with its data class. Classified data can also be sent as raw bytes. A [`RedactionEngine`](crate::RedactionEngine) acts as an
+/// extractor when using [`extract_redacted`](crate::RedactionEngine::extract_redacted), redacting
+/// each piece of classified text with the redactor registered for its class.
+///
+/// Classified text can also be sent as a named field, identified by its [path](FieldPath) within the
+/// value, such as `address.zip`. This lets a single value hold several fields of the same class which
+/// are nonetheless redacted differently, using the redactors registered for specific fields of specific
+/// types with [`add_field_redactor`](crate::RedactionEngineBuilder::add_field_redactor).
+pub trait Extractor {
+    /// Receives a piece of text which holds no sensitive data.
+    fn write_plain(&mut self, text: &str);
+
+    /// Receives a piece of text holding data of the given class.
+    fn write_classified(&mut self, data_class: &DataClass, text: &str);
+
+    /// Receives a named field holding data of the given class.
+    ///
+    /// The path identifies the field within the extracted value. Only the field's value is sent, so the
+    /// name should also be sent as plain text if it's to appear in the output.
+    ///
+    /// The default implementation ignores the path and behaves like [`write_classified`](Self::write_classified).
+    fn write_field(&mut self, path: &FieldPath<'_>, data_class: &DataClass, text: &str) {
+        _ = path;
+        self.write_classified(data_class, text);
+    }
+
+    /// Receives binary data of the given class.
+    ///
+    /// This lets payloads which have no textual form, such as keys or images, be redacted without first
+    /// being converted to text, so that hashing redactors see the original bytes.
+    ///
+    /// The default implementation encodes the bytes as lowercase hexadecimal and passes them to
+    /// [`write_classified`](Self::write_classified).
+    fn write_classified_bytes(&mut self, data_class: &DataClass, bytes: &[u8]) {
+        self.write_classified(data_class, &to_hex(bytes));
+    }
+}
+
+/// The path of a named field within an extracted value, such as `address.zip`.
+///
+/// A path is a chain of field names, outermost first, each one along with the type declaring the field.
+/// Paths are built on the stack as extraction descends into nested fields, so naming fields doesn't
+/// allocate. Their [`Display`] form joins the names with dots.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::FieldPath;
+///
+/// struct Address;
+/// struct Customer;
+///
+/// let zip = FieldPath::new::<Address>("zip");
+/// let path = FieldPath::new::<Customer>("address").join(&zip);
+/// assert_eq!(path.to_string(), "address.zip");
+/// ```
+#[derive(Clone, Copy)]
+pub struct FieldPath<'a> {
+    owner: &'static str,
+    name: &'a str,
+    field: Option<&'a Self>,
+}
+
+impl<'a> FieldPath<'a> {
+    /// Creates a path naming a field declared by the type `T`.
+    #[must_use]
+    pub fn new<T: ?Sized>(name: &'a str) -> Self {
+        Self {
+            owner: core::any::type_name::<T>(),
+            name,
+            field: None,
+        }
+    }
+
+    /// Creates a path naming a field which isn't declared by a type, such as a field of a record.
+    #[cfg(feature = "std")]
+    pub(crate) const fn unowned(name: &'a str) -> Self {
+        Self {
+            owner: "",
+            name,
+            field: None,
+        }
+    }
+
+    /// Returns a path naming a field nested within the field named by this path.
+    #[must_use]
+    pub const fn join(self, field: &'a Self) -> Self {
+        Self {
+            owner: self.owner,
+            name: self.name,
+            field: Some(field),
+        }
+    }
+
+    /// Returns the name of the outermost field of the path.
+    #[must_use]
+    pub const fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Returns the path of the nested field within the outermost field, if any.
+    #[must_use]
+    pub const fn field(&self) -> Option<&'a Self> {
+        self.field
+    }
+
+    /// Returns the name of the type declaring the outermost field, or an empty string if there is none.
+    #[cfg(feature = "std")]
+    pub(crate) const fn owner(&self) -> &'static str {
+        self.owner
+    }
+
+    /// Returns this path and the paths of each of the nested fields.
+    pub(crate) fn nested(&self) -> impl Iterator<Item = &FieldPath<'a>> {
+        core::iter::successors(Some(self), |path| path.field)
+    }
+
+    /// Indicates whether the names of the path, joined with dots, are the given text.
+    #[cfg(feature = "std")]
+    pub(crate) fn matches(&self, mut text: &str) -> bool {
+        for path in self.nested() {
+            let Some(rest) = text.strip_prefix(path.name) else {
+                return false;
+            };
+
+            text = match path.field {
+                Some(_) => match rest.strip_prefix('.') {
+                    Some(rest) => rest,
+                    None => return false,
+                },
+                None => rest,
+            };
+        }
+
+        text.is_empty()
+    }
+}
+
+impl Display for FieldPath<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (index, path) in self.nested().enumerate() {
+            if index > 0 {
+                f.write_str(".")?;
+            }
+            f.write_str(path.name)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl core::fmt::Debug for FieldPath<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "FieldPath(\"{self}\")")
+    }
+}
+
+/// An extractor which names the classified text of a field of a value.
+///
+/// Classified text sent to this extractor is forwarded to the underlying extractor as a field with the
+/// given name, declared by the type `T` given when creating the extractor, and the paths of nested
+/// fields are [joined](FieldPath::join) to it, as in `address.zip`. Plain text is forwarded as is.
+///
+/// This is used by the code generated by the [`Extract`](macro@crate::Extract) derive, and can be used
+/// by manual implementations of [`Extract`] to name the fields they extract.
+pub struct FieldExtractor<'a> {
+    extractor: &'a mut dyn Extractor,
+    path: FieldPath<'a>,
+}
+
+impl<'a> FieldExtractor<'a> {
+    /// Creates a new extractor naming the text it receives after the given field of the type `T`.
+    #[must_use]
+    pub fn new<T: ?Sized>(extractor: &'a mut dyn Extractor, name: &'a str) -> Self {
+        Self {
+            extractor,
+            path: FieldPath::new::<T>(name),
+        }
+    }
+
+    /// Creates a new extractor naming the text it receives after a field which isn't declared by a type.
+    #[cfg(feature = "std")]
+    pub(crate) const fn unowned(extractor: &'a mut dyn Extractor, name: &'a str) -> Self {
+        Self {
+            extractor,
+            path: FieldPath::unowned(name),
+        }
+    }
+}
+
+impl Extractor for FieldExtractor<'_> {
+    fn write_plain(&mut self, text: &str) {
+        self.extractor.write_plain(text);
+    }
+
+    fn write_classified(&mut self, data_class: &DataClass, text: &str) {
+        self.extractor.write_field(&self.path, data_class, text);
+    }
+
+    fn write_field(&mut self, path: &FieldPath<'_>, data_class: &DataClass, text: &str) {
+        self.extractor
+            .write_field(&self.path.join(path), data_class, text);
+    }
+}
+
+impl core::fmt::Debug for FieldExtractor<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FieldExtractor")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Produces a textual form of a value, keeping its classified parts separate from the plain parts.
+///
+/// Types implementing this trait can be formatted as a whole while each classified piece of data
+/// they contain is redacted according to its own data class. Classified containers generated by the
+/// [`taxonomy`](crate::taxonomy) attribute implement this trait when their payload implements
+/// [`Display`].
+///
+/// Options, sequences, and tuples are extracted like their [`Debug`](core::fmt::Debug) output, as in
+/// `Some(...)`, `[..., ...]`, and `(..., ...)`, with each element extracted with its own class.
+///
+/// Maps are extracted as `{key: value, ...}`, with keys and values extracted independently, so that
+/// a map such as `HashMap<Email<String>, Preferences>` has its keys redacted as well as its values.
+///
+/// # Example
+///
+#[cfg_attr(feature = "std", doc = "```rust")]
+#[cfg_attr(not(feature = "std"), doc = "```ignore")]
+/// use std::collections::BTreeMap;
+/// use data_privacy::core_taxonomy::{CoreTaxonomy, Insensitive, Sensitive};
+/// use data_privacy::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+///
+/// let engine = RedactionEngineBuilder::new()
+///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+///     .add_class_redactor(
+///         &CoreTaxonomy::Insensitive.data_class(),
+///         SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+///     )
+///     .build();
+///
+/// let mut visits = BTreeMap::new();
+/// visits.insert(Sensitive::new("jdoe"), Insensitive::new(3));
+///
+/// let mut output = String::new();
+/// engine.extract_redacted(&visits, |s| output.push_str(s));
+/// assert_eq!(output, "{****: 3}");
+/// ```
+pub trait Extract {
+    /// Sends the pieces of this value to the extractor.
+    fn extract(&self, extractor: &mut dyn Extractor);
+}
+
+impl<T: Extract + ?Sized> Extract for &T {
+    fn extract(&self, extractor: &mut dyn Extractor) {
+        (**self).extract(extractor);
+    }
+}
+
+impl Extract for str {
+    fn extract(&self, extractor: &mut dyn Extractor) {
+        extractor.write_plain(self);
+    }
+}
+
+impl Extract for String {
+    fn extract(&self, extractor: &mut dyn Extractor) {
+        extractor.write_plain(self);
+    }
+}
+
+macro_rules! impl_extract_plain {
+    ($($ty:ty),*) => {
+        $(
+            impl Extract for $ty {
+                fn extract(&self, extractor: &mut dyn Extractor) {
+                    with_formatted(format_args!("{self}"), |s| extractor.write_plain(s));
+                }
+            }
+        )*
+    };
+}
+
+impl_extract_plain!(
+    bool, char, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
+impl<T: Extract + ?Sized> Extract for Box<T> {
+    fn extract(&self, extractor: &mut dyn Extractor) {
+        (**self).extract(extractor);
+    }
+}
+
+impl<T: Extract> Extract for Option<T> {
+    fn extract(&self, extractor: &mut dyn Extractor) {
+        match self {
+            Some(value) => {
+                extractor.write_plain("Some(");
+                value.extract(extractor);
+                extractor.write_plain(")");
+            }
+            None => extractor.write_plain("None"),
+        }
+    }
+}
+
+impl<T: Extract> Extract for [T] {
+    fn extract(&self, extractor: &mut dyn Extractor) {
+        extract_sequence(self.iter(), extractor);
+    }
+}
+
+impl<T: Extract, const N: usize> Extract for [T; N] {
+    fn extract(&self, extractor: &mut dyn Extractor) {
+        extract_sequence(self.iter(), extractor);
+    }
+}
+
+impl<T: Extract> Extract for Vec<T> {
+    fn extract(&self, extractor: &mut dyn Extractor) {
+        extract_sequence(self.iter(), extractor);
+    }
+}
+
+macro_rules! impl_extract_tuple {
+    ($(($($name:ident $index:tt),+)),*) => {
+        $(
+            impl<$($name: Extract),+> Extract for ($($name,)+) {
+                fn extract(&self, extractor: &mut dyn Extractor) {
+                    extractor.write_plain("(");
+                    $(
+                        if $index > 0 {
+                            extractor.write_plain(", ");
+                        }
+                        self.$index.extract(extractor);
+                    )+
+                    extractor.write_plain(")");
+                }
+            }
+        )*
+    };
+}
+
+impl_extract_tuple!(
+    (A 0),
+    (A 0, B 1),
+    (A 0, B 1, C 2),
+    (A 0, B 1, C 2, D 3),
+    (A 0, B 1, C 2, D 3, E 4),
+    (A 0, B 1, C 2, D 3, E 4, F 5)
+);
+
+impl<T: Display> Extract for DynamicClassified<T> {
+    fn extract(&self, extractor: &mut dyn Extractor) {
+        crate::Classified::visit(self, |v| {
+            with_formatted(format_args!("{v}"), |s| {
+                extractor.write_classified(&crate::Classified::data_class(self), s);
+            });
+        });
+    }
+}
+
+impl<T: Display> Extract for MultiClassified<T> {
+    fn extract(&self, extractor: &mut dyn Extractor) {
+        crate::Classified::visit(self, |v| {
+            with_formatted(format_args!("{v}"), |s| {
+                extractor.write_classified(&crate::Classified::data_class(self), s);
+            });
+        });
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Extract, V: Extract, S> Extract for HashMap<K, V, S> {
+    fn extract(&self, extractor: &mut dyn Extractor) {
+        extract_entries(self.iter(), extractor);
+    }
+}
+
+impl<K: Extract, V: Extract> Extract for BTreeMap<K, V> {
+    fn extract(&self, extractor: &mut dyn Extractor) {
+        extract_entries(self.iter(), extractor);
+    }
+}
+
+/// Extracts a sequence as `[value, ...]`, extracting each value with its own class.
+fn extract_sequence<'a, T: Extract + 'a>(
+    values: impl Iterator<Item = &'a T>,
+    extractor: &mut dyn Extractor,
+) {
+    extractor.write_plain("[");
+    for (index, value) in values.enumerate() {
+        if index > 0 {
+            extractor.write_plain(", ");
+        }
+
+        value.extract(extractor);
+    }
+    extractor.write_plain("]");
+}
+
+/// Extracts map entries as `{key: value, ...}`, extracting each key and value with its own class.
+fn extract_entries<'a, K, V>(
+    entries: impl Iterator<Item = (&'a K, &'a V)>,
+    extractor: &mut dyn Extractor,
+) where
+    K: Extract + 'a,
+    V: Extract + 'a,
+{
+    extractor.write_plain("{");
+    for (index, (key, value)) in entries.enumerate() {
+        if index > 0 {
+            extractor.write_plain(", ");
+        }
+
+        key.extract(extractor);
+        extractor.write_plain(": ");
+        value.extract(extractor);
+    }
+    extractor.write_plain("}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::{Insensitive, Sensitive};
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, string::ToString, vec};
+    use core::fmt::Write;
+
+    /// Records extracted pieces, marking classified ones with their class.
+    #[derive(Default)]
+    struct Recorder(String);
+
+    impl Extractor for Recorder {
+        fn write_plain(&mut self, text: &str) {
+            self.0.push_str(text);
+        }
+
+        fn write_classified(&mut self, data_class: &DataClass, text: &str) {
+            _ = write!(self.0, "[{data_class}:{text}]");
+        }
+    }
+
+    fn extract(value: &impl Extract) -> String {
+        let mut recorder = Recorder::default();
+        value.extract(&mut recorder);
+        recorder.0
+    }
+
+    #[test]
+    fn plain_values_should_be_written_as_is() {
+        assert_eq!(extract(&"text"), "text");
+        assert_eq!(extract(&"text".to_string()), "text");
+        assert_eq!(extract(&42_u8), "42");
+        assert_eq!(extract(&true), "true");
+    }
+
+    #[test]
+    fn classified_values_should_carry_their_class() {
+        assert_eq!(extract(&Sensitive::new(42)), "[core/sensitive:42]");
+        assert_eq!(
+            extract(&DynamicClassified::new("x", DataClass::new("a", "b"))),
+            "[a/b:x]"
+        );
+        assert_eq!(
+            extract(
+                &MultiClassified::new("x", DataClass::new("a", "b"))
+                    .with_class(DataClass::new("a", "c").with_level(1))
+            ),
+            "[a/c:x]"
+        );
+    }
+
+    #[derive(crate::Extract, crate::RedactedDebug)]
+    struct Profile {
+        #[classified(crate::core_taxonomy::CoreTaxonomy::Sensitive)]
+        email: &'static str,
+        tags: BTreeMap<&'static str, Insensitive<u32>>,
+        visits: u32,
+    }
+
+    #[derive(crate::Extract, crate::RedactedDebug)]
+    struct Wrapper(
+        Profile,
+        #[classified(crate::core_taxonomy::CoreTaxonomy::Insensitive)] u8,
+    );
+
+    #[test]
+    fn derived_structs_should_extract_each_field() {
+        let profile = Profile {
+            email: "a@b.com",
+            tags: BTreeMap::from([("x", Insensitive::new(1))]),
+            visits: 3,
+        };
+
+        assert_eq!(
+            extract(&Wrapper(profile, 7)),
+            "Wrapper(Profile { email: [core/sensitive:a@b.com], tags: {x: [core/insensitive:1]}, visits: 3 }, [core/insensitive:7])"
+        );
+    }
+
+    #[test]
+    fn derived_debug_should_redact_classified_fields() {
+        let profile = Profile {
+            email: "a@b.com",
+            tags: BTreeMap::from([("x", Insensitive::new(1))]),
+            visits: 3,
+        };
+
+        assert_eq!(
+            format!("{:?}", Wrapper(profile, 7)),
+            "Wrapper(Profile { email: <core/sensitive:REDACTED>, tags: {\"x\": <core/insensitive:REDACTED>}, visits: 3 }, <core/insensitive:REDACTED>)"
+        );
+
+        assert_eq!(
+            format!(
+                "{:#?}",
+                Contact::Phone {
+                    country: 1,
+                    number: Sensitive::new("555-1234"),
+                }
+            ),
+            "Phone {\n    country: 1,\n    number: <core/sensitive:REDACTED>,\n}"
+        );
+        assert_eq!(
+            format!("{:?}", Contact::Email("a@b.com")),
+            "Email(<core/sensitive:REDACTED>)"
+        );
+        assert_eq!(format!("{:?}", Contact::Unknown), "Unknown");
+    }
+
+    #[test]
+    fn map_keys_and_values_should_be_extracted_independently() {
+        let mut map = BTreeMap::new();
+        _ = map.insert(Sensitive::new("a@b.com"), Insensitive::new("dark"));
+        _ = map.insert(Sensitive::new("c@d.com"), Insensitive::new("light"));
+        assert_eq!(
+            extract(&map),
+            "{[core/sensitive:a@b.com]: [core/insensitive:dark], [core/sensitive:c@d.com]: [core/insensitive:light]}"
+        );
+
+        assert_eq!(extract(&BTreeMap::<u8, u8>::new()), "{}");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn hash_maps_should_be_extracted() {
+        let mut map = HashMap::new();
+        _ = map.insert("theme", Sensitive::new(1));
+        assert_eq!(extract(&map), "{theme: [core/sensitive:1]}");
+    }
+
+    #[test]
+    fn options_sequences_and_tuples_should_extract_each_element() {
+        assert_eq!(
+            extract(&Some(Sensitive::new(1))),
+            "Some([core/sensitive:1])"
+        );
+        assert_eq!(extract(&None::<Sensitive<u8>>), "None");
+        assert_eq!(
+            extract(&vec![Sensitive::new("a"), Sensitive::new("b")]),
+            "[[core/sensitive:a], [core/sensitive:b]]"
+        );
+        assert_eq!(extract(&[1, 2]), "[1, 2]");
+        assert_eq!(extract(&Vec::<u8>::new()), "[]");
+        assert_eq!(
+            extract(&("id", Sensitive::new(7), Box::new(Insensitive::new(true)))),
+            "(id, [core/sensitive:7], [core/insensitive:true])"
+        );
+        assert_eq!(extract(&(1,)), "(1)");
+    }
+
+    struct Address {
+        street: &'static str,
+        zip: &'static str,
+    }
+
+    impl Extract for Address {
+        fn extract(&self, extractor: &mut dyn Extractor) {
+            let data_class = crate::core_taxonomy::CoreTaxonomy::Sensitive.data_class();
+            extractor.write_plain("street: ");
+            extractor.write_field(&FieldPath::new::<Self>("street"), &data_class, self.street);
+            extractor.write_plain(", zip: ");
+            extractor.write_field(&FieldPath::new::<Self>("zip"), &data_class, self.zip);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn field_paths_should_match_dotted_names() {
+        let zip = FieldPath::new::<Address>("zip");
+        let path = FieldPath::new::<u8>("address").join(&zip);
+
+        assert_eq!(path.to_string(), "address.zip");
+        assert_eq!(format!("{path:?}"), r#"FieldPath("address.zip")"#);
+        assert!(path.matches("address.zip"));
+        assert!(!path.matches("address"));
+        assert!(!path.matches("address.zipcode"));
+        assert!(!path.matches("addresszip"));
+        assert!(zip.matches("zip"));
+
+        let owners: Vec<_> = path.nested().map(FieldPath::owner).collect();
+        assert_eq!(
+            owners,
+            [
+                core::any::type_name::<u8>(),
+                core::any::type_name::<Address>()
+            ]
+        );
+    }
+
+    #[test]
+    fn fields_should_default_to_classified_text() {
+        let address = Address {
+            street: "1 Main St",
+            zip: "98052",
+        };
+
+        assert_eq!(
+            extract(&address),
+            "street: [core/sensitive:1 Main St], zip: [core/sensitive:98052]"
+        );
+    }
+
+    #[derive(crate::Extract, crate::RedactedDebug)]
+    enum Contact {
+        Email(#[classified(crate::core_taxonomy::CoreTaxonomy::Sensitive)] &'static str),
+        Phone {
+            country: u16,
+            number: Sensitive<&'static str>,
+        },
+        Unknown,
+        Other(#[extract(skip)] Vec<u8>),
+    }
+
+    #[test]
+    fn derived_enums_should_extract_each_field() {
+        assert_eq!(
+            extract(&vec![
+                Contact::Email("a@b.com"),
+                Contact::Phone {
+                    country: 1,
+                    number: Sensitive::new("555-1234"),
+                },
+                Contact::Unknown,
+                Contact::Other(vec![1]),
+            ]),
+            "[Email([core/sensitive:a@b.com]), Phone { country: 1, number: [core/sensitive:555-1234] }, Unknown, Other]"
+        );
+    }
+}
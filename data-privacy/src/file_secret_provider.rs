@@ -0,0 +1,60 @@
+use crate::SecretProvider;
+use std::io;
+use std::path::PathBuf;
+
+/// A [`SecretProvider`] reading the secret from a file.
+///
+/// The secret is the whole content of the file, read each time the secret is fetched, which suits
+/// secrets mounted into the file system by an orchestrator and rotated in place. The content is used
+/// as is, so a trailing newline is part of the secret.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::{FileSecretProvider, SecretProvider};
+///
+/// let provider = FileSecretProvider::new("/run/secrets/redaction-key");
+/// # _ = provider.secret();
+/// ```
+#[derive(Clone, Debug)]
+pub struct FileSecretProvider {
+    path: PathBuf,
+}
+
+impl FileSecretProvider {
+    /// Creates a new provider reading the file at the given path.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl SecretProvider for FileSecretProvider {
+    fn secret(&self) -> io::Result<Vec<u8>> {
+        std::fs::read(&self.path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("can't read secret file `{}`: {e}", self.path.display()),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_should_come_from_file() {
+        let path = std::env::temp_dir().join(format!("data-privacy-secret-{}", std::process::id()));
+        std::fs::write(&path, b"s3cr3t").unwrap();
+
+        let provider = FileSecretProvider::new(&path);
+        assert_eq!(provider.secret().unwrap(), b"s3cr3t");
+
+        std::fs::remove_file(&path).unwrap();
+        let error = provider.secret().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(error.to_string().contains("data-privacy-secret-"));
+    }
+}
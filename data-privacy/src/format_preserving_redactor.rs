@@ -0,0 +1,213 @@
+use crate::{DataClass, Redactor};
+use core::fmt::Debug;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// The number of character positions covered by each block of the keystream, at two bytes per position.
+const POSITIONS_PER_BLOCK: usize = 16;
+
+/// A redactor that replaces values with deterministic look-alikes of the same length and shape.
+///
+/// Each digit is replaced by a digit, and each letter by a letter of the same case, while every other
+/// character, such as the separators of phone numbers and order identifiers, is kept as is. Parsers and
+/// validators downstream of the redacted data keep working, as `+1 (425) 555-0123` becomes something
+/// like `+7 (018) 396-4471` and `ORD-2024-ab12` becomes something like `KXF-8310-qd95`. Digits and
+/// letters outside of the ASCII range are replaced by ASCII digits and lowercase ASCII letters.
+///
+/// Each character is drawn from a keystream derived with HMAC-SHA256 from a secret key, the data class
+/// of the value, the whole value, and the character's position. The same value therefore always
+/// produces the same output within a data class, so redacted values can still be correlated, while
+/// values sharing a prefix don't produce outputs sharing a prefix.
+///
+/// The output keeps the length and the layout of the original value, which can on its own be
+/// identifying for short or unusual values. Prefer a hashing redactor when the shape of values doesn't
+/// need to be preserved.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::{DataClass, FormatPreservingRedactor, Redactor};
+///
+/// let redactor = FormatPreservingRedactor::new(b"a secret key");
+///
+/// let mut output = String::new();
+/// redactor.redact(&DataClass::new("corp", "phone"), "+1 (425) 555-0123", &mut |s| output.push_str(s));
+///
+/// assert_eq!(output.len(), "+1 (425) 555-0123".len());
+/// assert!(output.starts_with("+"));
+/// assert_eq!(&output[2..4], " (");
+/// assert!(output[4..7].chars().all(|c| c.is_ascii_digit()));
+/// ```
+#[derive(Clone)]
+pub struct FormatPreservingRedactor {
+    mac: Hmac<Sha256>,
+}
+
+impl FormatPreservingRedactor {
+    /// Creates a new instance keyed with the given secret.
+    #[must_use]
+    #[expect(
+        clippy::missing_panics_doc,
+        reason = "HMAC accepts keys of any length, so this can't panic"
+    )]
+    pub fn new(key: impl AsRef<[u8]>) -> Self {
+        Self {
+            mac: Hmac::<Sha256>::new_from_slice(key.as_ref())
+                .expect("HMAC accepts keys of any length"),
+        }
+    }
+
+    /// Returns the block of the keystream with the given index, derived from the seed of a value.
+    fn keystream_block(&self, seed: &[u8], index: usize) -> [u8; POSITIONS_PER_BLOCK * 2] {
+        let mut mac = self.mac.clone();
+        mac.update(seed);
+        mac.update(&(index as u64).to_le_bytes());
+        mac.finalize().into_bytes().into()
+    }
+}
+
+impl Redactor for FormatPreservingRedactor {
+    fn redact(&self, data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
+        // each variable-length part is length-prefixed, so different parts can't produce the same input
+        let mut mac = self.mac.clone();
+        for part in [
+            data_class.taxonomy().as_bytes(),
+            data_class.name().as_bytes(),
+        ] {
+            mac.update(&(part.len() as u64).to_le_bytes());
+            mac.update(part);
+        }
+        mac.update(value.as_bytes());
+        let seed = mac.finalize().into_bytes();
+
+        let mut result = String::with_capacity(value.len());
+        let mut block = None;
+        for (position, c) in value.chars().enumerate() {
+            if !c.is_alphanumeric() {
+                result.push(c);
+                continue;
+            }
+
+            let (index, offset) = (
+                position / POSITIONS_PER_BLOCK,
+                position % POSITIONS_PER_BLOCK * 2,
+            );
+            let bytes = match block {
+                Some((current, bytes)) if current == index => bytes,
+                _ => {
+                    let bytes = self.keystream_block(&seed, index);
+                    block = Some((index, bytes));
+                    bytes
+                }
+            };
+
+            let random = u16::from_le_bytes([bytes[offset], bytes[offset + 1]]);
+            let (first, count) = if c.is_numeric() {
+                (b'0', 10)
+            } else if c.is_ascii_uppercase() {
+                (b'A', 26)
+            } else {
+                (b'a', 26)
+            };
+
+            #[expect(
+                clippy::cast_possible_truncation,
+                reason = "The remainder is always less than 26"
+            )]
+            result.push(char::from(first + (random % count) as u8));
+        }
+
+        output(&result);
+    }
+}
+
+impl Debug for FormatPreservingRedactor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FormatPreservingRedactor")
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::CoreTaxonomy;
+
+    fn redact_to_string(
+        redactor: &FormatPreservingRedactor,
+        data_class: &DataClass,
+        value: &str,
+    ) -> String {
+        let mut output = String::new();
+        redactor.redact(data_class, value, &mut |s| output.push_str(s));
+        output
+    }
+
+    fn shape(value: &str) -> String {
+        value
+            .chars()
+            .map(|c| match c {
+                '0'..='9' => '9',
+                'A'..='Z' => 'A',
+                'a'..='z' => 'a',
+                c => c,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn redact_should_preserve_shape() {
+        let redactor = FormatPreservingRedactor::new(b"key");
+        let data_class = CoreTaxonomy::Sensitive.data_class();
+
+        for value in [
+            "+1 (425) 555-0123",
+            "ORD-2024-ab12",
+            "",
+            "a long value spanning several blocks of the keystream, 0123456789",
+        ] {
+            let output = redact_to_string(&redactor, &data_class, value);
+            assert_eq!(shape(&output), shape(value));
+        }
+
+        let output = redact_to_string(&redactor, &data_class, "Zoë-٣");
+        assert_eq!(shape(&output), "Aaa-9");
+    }
+
+    #[test]
+    fn redact_should_be_deterministic_and_keyed() {
+        let redactor = FormatPreservingRedactor::new(b"key");
+        let other = FormatPreservingRedactor::new(b"other key");
+        let data_class = CoreTaxonomy::Sensitive.data_class();
+        let value = "4255550123";
+
+        let output = redact_to_string(&redactor, &data_class, value);
+        assert_eq!(output, redact_to_string(&redactor, &data_class, value));
+        assert_ne!(output, value);
+        assert_ne!(output, redact_to_string(&other, &data_class, value));
+        assert_ne!(
+            output,
+            redact_to_string(
+                &redactor,
+                &CoreTaxonomy::UnknownSensitivity.data_class(),
+                value
+            )
+        );
+    }
+
+    #[test]
+    fn redact_should_not_leak_shared_prefixes() {
+        let redactor = FormatPreservingRedactor::new(b"key");
+        let data_class = CoreTaxonomy::Sensitive.data_class();
+
+        let first = redact_to_string(&redactor, &data_class, "4255550123");
+        let second = redact_to_string(&redactor, &data_class, "4255550124");
+        assert_ne!(first.as_bytes()[..9], second.as_bytes()[..9]);
+    }
+
+    #[test]
+    fn debug_should_not_expose_key() {
+        let redactor = FormatPreservingRedactor::new(b"key");
+        assert_eq!(format!("{redactor:?}"), "FormatPreservingRedactor { .. }");
+    }
+}
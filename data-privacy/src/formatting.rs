@@ -0,0 +1,90 @@
+#[cfg(feature = "defmt")]
+use crate::DataClass;
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+use core::fmt::{Arguments, Write};
+
+/// Writes the masked form of a classified value of the given class, such as `<core/sensitive:REDACTED>`,
+/// to a `defmt` formatter.
+///
+/// The format string is interned, so only the names of the data class go over the wire.
+#[cfg(feature = "defmt")]
+pub fn format_masked(data_class: &DataClass, fmt: defmt::Formatter<'_>) {
+    defmt::write!(
+        fmt,
+        "<{=str}/{=str}:REDACTED>",
+        data_class.taxonomy(),
+        data_class.name()
+    );
+}
+
+/// Formats the given arguments and hands the resulting string to the callback.
+///
+/// Short outputs are formatted into a stack buffer, avoiding a heap allocation.
+pub fn with_formatted<R>(args: Arguments<'_>, f: impl FnOnce(&str) -> R) -> R {
+    let mut local_buf = StackBuffer {
+        buf: [0u8; 128],
+        len: 0,
+    };
+
+    if local_buf.write_fmt(args).is_ok() {
+        // SAFETY: We know the buffer contains valid UTF-8 because only whole strings are ever written to it.
+        let s = unsafe { core::str::from_utf8_unchecked(&local_buf.buf[..local_buf.len]) };
+        f(s)
+    } else {
+        // If the value is too large to fit in the buffer, we fall back to allocating.
+        f(&alloc::fmt::format(args))
+    }
+}
+
+/// Encodes bytes as lowercase hexadecimal, which is how binary data is represented where text is expected.
+pub fn to_hex(bytes: &[u8]) -> String {
+    const HEX_LOWER_CHARS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        hex.push(char::from(HEX_LOWER_CHARS[usize::from(byte >> 4)]));
+        hex.push(char::from(HEX_LOWER_CHARS[usize::from(byte & 0x0f)]));
+    }
+
+    hex
+}
+
+/// A fixed-size buffer which fails writes that don't fit.
+struct StackBuffer {
+    buf: [u8; 128],
+    len: usize,
+}
+
+impl Write for StackBuffer {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        let dest = self.buf.get_mut(self.len..end).ok_or(core::fmt::Error)?;
+        dest.copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    #[test]
+    fn with_formatted_should_handle_short_and_long_output() {
+        let short = with_formatted(format_args!("{}-{}", "a", 1), str::to_string);
+        assert_eq!(short, "a-1");
+
+        let long_value = "x".repeat(300);
+        let long = with_formatted(format_args!("{long_value}"), str::to_string);
+        assert_eq!(long, long_value);
+    }
+
+    #[test]
+    fn to_hex_should_encode_every_byte() {
+        assert_eq!(to_hex(&[]), "");
+        assert_eq!(to_hex(&[0x00, 0x0f, 0xa5, 0xff]), "000fa5ff");
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{RedactionEngine, RedactionEngineBuilder};
+use core::cell::RefCell;
+use std::sync::{Arc, OnceLock};
+
+static GLOBAL_ENGINE: OnceLock<RedactionEngine> = OnceLock::new();
+static DEFAULT_ENGINE: OnceLock<RedactionEngine> = OnceLock::new();
+
+thread_local! {
+    /// The engines installed on this thread by [`with_redaction_engine`], innermost last.
+    static SCOPED_ENGINES: RefCell<Vec<Arc<RedactionEngine>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Installs the process-wide redaction engine.
+///
+/// The global engine is used by facilities which don't take an explicit engine, such as the
+/// [`redact!`](crate::redact) macro. The global engine can only be set once, typically when the
+/// application starts.
+///
+/// # Errors
+///
+/// Returns the given engine back if a global engine has already been set.
+#[expect(
+    clippy::result_large_err,
+    reason = "Handing the engine back mirrors OnceLock::set, and this is only called once at startup"
+)]
+pub fn set_global_redaction_engine(engine: RedactionEngine) -> Result<(), RedactionEngine> {
+    GLOBAL_ENGINE.set(engine)
+}
+
+/// Returns the process-wide redaction engine.
+///
+/// If no engine has been installed with [`set_global_redaction_engine`], this returns
+/// an engine which erases all classified data, so sensitive data is never leaked by accident.
+///
+/// Engines installed on the current thread with [`with_redaction_engine`] aren't taken into account,
+/// use [`with_current_redaction_engine`] to honor them.
+#[must_use]
+pub fn global_redaction_engine() -> &'static RedactionEngine {
+    GLOBAL_ENGINE
+        .get()
+        .unwrap_or_else(|| DEFAULT_ENGINE.get_or_init(|| RedactionEngineBuilder::new().build()))
+}
+
+/// Runs a function with the given engine overriding the global redaction engine on the current thread.
+///
+/// While the function runs, facilities which don't take an explicit engine, such as the
+/// [`redact!`](crate::redact) macro, use the given engine instead of the one installed with
+/// [`set_global_redaction_engine`]. Overrides can be nested, in which case the innermost one wins, and
+/// the previous engine is restored when the function returns or panics.
+///
+/// Since the global engine can only be set once per process, this lets tests each use the engine they
+/// need, without interfering with tests running concurrently on other threads.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+/// use data_privacy::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode, redact, with_redaction_engine};
+///
+/// let engine = RedactionEngineBuilder::new()
+///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')))
+///     .build()
+///     .into_shared();
+///
+/// let user = Sensitive::new("jdoe");
+/// let output = with_redaction_engine(&engine, || redact!("user {}", user));
+/// assert_eq!(output, "user ####");
+/// ```
+pub fn with_redaction_engine<R>(engine: &Arc<RedactionEngine>, f: impl FnOnce() -> R) -> R {
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            _ = SCOPED_ENGINES.with_borrow_mut(Vec::pop);
+        }
+    }
+
+    SCOPED_ENGINES.with_borrow_mut(|engines| engines.push(Arc::clone(engine)));
+    let _guard = Guard;
+    f()
+}
+
+/// Runs a function with the redaction engine currently in effect on this thread.
+///
+/// This is the innermost engine installed with [`with_redaction_engine`], if any, and otherwise the
+/// [global redaction engine](global_redaction_engine).
+pub fn with_current_redaction_engine<R>(f: impl FnOnce(&RedactionEngine) -> R) -> R {
+    // the engine is cloned out so the function can install engines of its own
+    match SCOPED_ENGINES.with_borrow(|engines| engines.last().cloned()) {
+        Some(engine) => f(&engine),
+        None => f(global_redaction_engine()),
+    }
+}
+
+/// Formats a string, redacting classified arguments with the global redaction engine.
+///
+/// This works like [`format!`], except that every positional argument must implement
+/// [`Extract`](crate::Extract). Each argument is redacted using the engine
+/// installed with [`set_global_redaction_engine`](crate::set_global_redaction_engine), or the one
+/// overriding it on the current thread with [`with_redaction_engine`](crate::with_redaction_engine),
+/// before being inserted into the resulting string.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::Sensitive;
+/// use data_privacy::redact;
+///
+/// let user = Sensitive::new("John Doe".to_string());
+/// let ip = Sensitive::new("10.0.0.1".to_string());
+///
+/// // No global engine is installed, so classified data is erased.
+/// assert_eq!(redact!("user {} from {}", user, ip), "user  from ");
+/// ```
+#[macro_export]
+macro_rules! redact {
+    ($fmt:literal $(,)?) => {
+        ::std::format!($fmt)
+    };
+    ($fmt:literal $(, $arg:expr)+ $(,)?) => {
+        $crate::redact!(@bind $fmt [] $($arg),+)
+    };
+    // each argument is evaluated in the caller's context, so it may use `?`, `.await` or `return`
+    (@bind $fmt:literal [$($bound:ident)*] $arg:expr $(, $rest:expr)*) => {
+        match &$arg {
+            arg => $crate::redact!(@bind $fmt [$($bound)* arg] $($rest),*),
+        }
+    };
+    (@bind $fmt:literal [$($bound:ident)*]) => {
+        $crate::with_current_redaction_engine(|engine| {
+            ::std::format!(
+                $fmt
+                $(, engine.display($bound))*
+            )
+        })
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::{CoreTaxonomy, Sensitive};
+    use crate::{SimpleRedactor, SimpleRedactorMode};
+
+    #[test]
+    fn redact_should_use_global_engine() {
+        // This is the only test in the crate which installs a global engine.
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+            )
+            .build();
+
+        set_global_redaction_engine(engine).unwrap();
+        assert!(set_global_redaction_engine(RedactionEngineBuilder::new().build()).is_err());
+
+        let user = Sensitive::new("jdoe".to_string());
+        let ip = Sensitive::new("10.0.0.1");
+        assert_eq!(
+            redact!("user {} from {}", user, ip),
+            "user **** from ********"
+        );
+        assert_eq!(redact!("no arguments"), "no arguments");
+
+        let lookup = |user: Option<&Sensitive<String>>| -> Option<String> {
+            Some(redact!("user {} from {}", *user?, ip))
+        };
+        assert_eq!(
+            lookup(Some(&user)).as_deref(),
+            Some("user **** from ********")
+        );
+        assert_eq!(lookup(None), None);
+    }
+
+    #[test]
+    fn scoped_engines_should_override_global_engine() {
+        let create_engine = |c| {
+            RedactionEngineBuilder::new()
+                .add_class_redactor(
+                    &CoreTaxonomy::Sensitive.data_class(),
+                    SimpleRedactor::with_mode(SimpleRedactorMode::Replace(c)),
+                )
+                .build()
+                .into_shared()
+        };
+        let (outer, inner) = (create_engine('#'), create_engine('-'));
+        let user = Sensitive::new("jdoe");
+
+        with_redaction_engine(&outer, || {
+            assert_eq!(redact!("{}", user), "####");
+            assert_eq!(
+                with_redaction_engine(&inner, || redact!("{}", user)),
+                "----"
+            );
+
+            let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+                with_redaction_engine(&inner, || panic!("restores the previous engine"));
+            }));
+            assert!(result.is_err());
+            assert_eq!(redact!("{}", user), "####");
+        });
+
+        let other_thread = std::thread::spawn({
+            let outer = Arc::clone(&outer);
+            move || with_redaction_engine(&outer, || redact!("{}", Sensitive::new("ab")))
+        });
+        assert_eq!(other_thread.join().unwrap(), "##");
+        assert_eq!(SCOPED_ENGINES.with_borrow(Vec::len), 0);
+    }
+}
@@ -0,0 +1,112 @@
+/// The textual encoding of the hashes produced by hashing redactors.
+///
+/// Denser encodings produce shorter output for the same hash, which reduces the size of logs without
+/// increasing the risk of collisions.
+///
+/// | Encoding | 64-bit hash | 128-bit hash |
+/// |----------|-------------|--------------|
+/// | `Hex`    | 16 chars    | 32 chars     |
+/// | `Base32` | 13 chars    | 26 chars     |
+/// | `Base64` | 11 chars    | 22 chars     |
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum HashEncoding {
+    /// Lowercase hexadecimal, as in `8f0c6e3b1a9d2c47`.
+    #[default]
+    Hex,
+
+    /// Lowercase base32 using the RFC 4648 alphabet, without padding.
+    Base32,
+
+    /// Base64 using the URL-safe RFC 4648 alphabet, without padding, so the output never contains `+`, `/`, or `=`.
+    Base64,
+}
+
+/// The length of the longest encoded hash, a 128-bit hash in hexadecimal.
+pub const MAX_ENCODED_LEN: usize = 32;
+
+impl HashEncoding {
+    /// Returns the length of the encoding of the given number of bytes.
+    pub(crate) const fn encoded_len(self, len: usize) -> usize {
+        (len * 8).div_ceil(self.bits_per_char())
+    }
+
+    /// Encodes the bytes into the buffer, returning the length of the encoding.
+    pub(crate) fn encode(self, bytes: &[u8], buffer: &mut [u8; MAX_ENCODED_LEN]) -> usize {
+        let alphabet: &[u8] = match self {
+            Self::Hex => b"0123456789abcdef",
+            Self::Base32 => b"abcdefghijklmnopqrstuvwxyz234567",
+            Self::Base64 => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+        };
+
+        let bits = self.bits_per_char();
+        let mask = (1_usize << bits) - 1;
+        let (mut acc, mut acc_bits, mut len) = (0_usize, 0, 0);
+        for &byte in bytes {
+            acc = (acc << 8) | usize::from(byte);
+            acc_bits += 8;
+            while acc_bits >= bits {
+                acc_bits -= bits;
+                buffer[len] = alphabet[(acc >> acc_bits) & mask];
+                len += 1;
+            }
+            acc &= (1 << acc_bits) - 1;
+        }
+
+        // the last character holds the remaining bits, padded with zeroes
+        if acc_bits > 0 {
+            buffer[len] = alphabet[(acc << (bits - acc_bits)) & mask];
+            len += 1;
+        }
+
+        len
+    }
+
+    const fn bits_per_char(self) -> usize {
+        match self {
+            Self::Hex => 4,
+            Self::Base32 => 5,
+            Self::Base64 => 6,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(encoding: HashEncoding, bytes: &[u8]) -> String {
+        let mut buffer = [0; MAX_ENCODED_LEN];
+        let len = encoding.encode(bytes, &mut buffer);
+        assert_eq!(len, encoding.encoded_len(bytes.len()));
+        String::from_utf8(buffer[..len].to_vec()).unwrap()
+    }
+
+    #[test]
+    fn encodings_should_match_rfc_4648() {
+        let value = 0x1234_5678_9abc_def0_u64.to_be_bytes();
+        assert_eq!(encode(HashEncoding::Hex, &value), "123456789abcdef0");
+        assert_eq!(encode(HashEncoding::Hex, &[0; 8]), "0000000000000000");
+        assert_eq!(encode(HashEncoding::Hex, &[0xff; 8]), "ffffffffffffffff");
+
+        // the test vectors of RFC 4648, without padding and in lowercase for base32
+        for (input, base32, base64) in [
+            ("", "", ""),
+            ("f", "my", "Zg"),
+            ("fo", "mzxq", "Zm8"),
+            ("foo", "mzxw6", "Zm9v"),
+            ("foob", "mzxw6yq", "Zm9vYg"),
+            ("fooba", "mzxw6ytb", "Zm9vYmE"),
+            ("foobar", "mzxw6ytboi", "Zm9vYmFy"),
+        ] {
+            assert_eq!(encode(HashEncoding::Base32, input.as_bytes()), base32);
+            assert_eq!(encode(HashEncoding::Base64, input.as_bytes()), base64);
+        }
+
+        assert_eq!(encode(HashEncoding::Base64, &[0xfb, 0xff]), "-_8");
+        assert_eq!(
+            encode(HashEncoding::Hex, &[0xab; 16]).len(),
+            MAX_ENCODED_LEN
+        );
+    }
+}
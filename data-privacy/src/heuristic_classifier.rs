@@ -0,0 +1,141 @@
+use crate::DataClass;
+
+/// Classifies unannotated values based on the name of the field holding them.
+///
+/// Values that don't carry a type-level classification, such as data coming from
+/// third-party libraries, would otherwise flow into telemetry unredacted. This classifier
+/// provides a safety net: it maps field names that look sensitive, such as `password`
+/// or `ssn`, to a data class so the value can be redacted anyway.
+///
+/// Patterns are matched against field names without regard to case or punctuation, so
+/// the pattern `api_key` matches fields named `apiKey`, `API-KEY`, or `x_api_key`. Rules
+/// are evaluated in the order they were added and the first match wins.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::{DataClass, HeuristicClassifier};
+///
+/// let secret = DataClass::new("corp", "secret");
+/// let classifier = HeuristicClassifier::new()
+///     .add_pattern("password", &secret)
+///     .add_pattern("token", &secret);
+///
+/// assert_eq!(classifier.classify("userPassword"), Some(&secret));
+/// assert_eq!(classifier.classify("Refresh-Token"), Some(&secret));
+/// assert_eq!(classifier.classify("user_name"), None);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct HeuristicClassifier {
+    rules: Vec<(String, DataClass)>,
+}
+
+/// Field name patterns that commonly indicate sensitive data.
+const COMMON_PATTERNS: &[&str] = &[
+    "password",
+    "passwd",
+    "secret",
+    "token",
+    "api_key",
+    "private_key",
+    "credential",
+    "authorization",
+    "cookie",
+    "session",
+    "ssn",
+    "social_security",
+    "credit_card",
+    "card_number",
+    "cvv",
+];
+
+impl HeuristicClassifier {
+    /// Creates a new classifier with no patterns.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Creates a new classifier which maps a set of commonly sensitive field names to the given data class.
+    ///
+    /// The set includes patterns such as `password`, `secret`, `token`, `api_key`, `ssn`, and `credit_card`.
+    #[must_use]
+    pub fn with_common_patterns(data_class: &DataClass) -> Self {
+        COMMON_PATTERNS
+            .iter()
+            .fold(Self::new(), |classifier, pattern| {
+                classifier.add_pattern(*pattern, data_class)
+            })
+    }
+
+    /// Adds a pattern which classifies any field whose name contains the pattern.
+    #[must_use]
+    pub fn add_pattern(mut self, pattern: impl AsRef<str>, data_class: &DataClass) -> Self {
+        self.rules
+            .push((normalize(pattern.as_ref()), data_class.clone()));
+        self
+    }
+
+    /// Returns the data class for a field name, if it matches any of the patterns.
+    #[must_use]
+    pub fn classify(&self, field_name: &str) -> Option<&DataClass> {
+        let field_name = normalize(field_name);
+        self.rules
+            .iter()
+            .find(|(pattern, _)| field_name.contains(pattern.as_str()))
+            .map(|(_, data_class)| data_class)
+    }
+}
+
+/// Lowercases a name and strips anything that isn't alphanumeric.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: DataClass = DataClass::new("test_taxonomy", "secret");
+    const PERSONAL: DataClass = DataClass::new("test_taxonomy", "personal");
+
+    #[test]
+    fn classify_should_ignore_case_and_punctuation() {
+        let classifier = HeuristicClassifier::new().add_pattern("api_key", &SECRET);
+
+        assert_eq!(classifier.classify("api_key"), Some(&SECRET));
+        assert_eq!(classifier.classify("apiKey"), Some(&SECRET));
+        assert_eq!(classifier.classify("X-API-KEY"), Some(&SECRET));
+        assert_eq!(classifier.classify("api"), None);
+    }
+
+    #[test]
+    fn classify_should_use_first_matching_rule() {
+        let classifier = HeuristicClassifier::new()
+            .add_pattern("ssn", &PERSONAL)
+            .add_pattern("s", &SECRET);
+
+        assert_eq!(classifier.classify("user_ssn"), Some(&PERSONAL));
+        assert_eq!(classifier.classify("session"), Some(&SECRET));
+    }
+
+    #[test]
+    fn common_patterns_should_cover_typical_names() {
+        let classifier = HeuristicClassifier::with_common_patterns(&SECRET);
+
+        assert_eq!(classifier.classify("password"), Some(&SECRET));
+        assert_eq!(classifier.classify("AccessToken"), Some(&SECRET));
+        assert_eq!(classifier.classify("customer_ssn"), Some(&SECRET));
+        assert_eq!(classifier.classify("age"), None);
+        assert_eq!(classifier.classify("user_name"), None);
+    }
+
+    #[test]
+    fn empty_classifier_should_not_match() {
+        let classifier = HeuristicClassifier::default();
+        assert_eq!(classifier.classify("password"), None);
+    }
+}
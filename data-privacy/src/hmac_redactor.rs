@@ -0,0 +1,299 @@
+use crate::streaming_redactor::read_chunks;
+use crate::{DataClass, RedactionContext, Redactor, StreamingRedactor};
+use core::fmt::Debug;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::borrow::Cow;
+use std::io::{self, Read};
+
+/// The number of bytes of each digest included in the output.
+const DIGEST_LEN: usize = 16;
+
+/// A redactor that replaces the original string with keyed HMAC-SHA256 digests of the string.
+///
+/// Each key has an identifier which is included in the output, as in `k2:0f3a...`, so consumers of
+/// redacted data know which key produced a digest. Only the first 16 bytes of each digest are used,
+/// producing 32 hexadecimal characters.
+///
+/// Several keys can be active at once, in which case the output holds one digest per key, separated by
+/// commas, as in `k1:9b2c...,k2:0f3a...`. This keeps redacted values correlatable across a key rotation:
+/// add the new key, wait until everything logged with the old key alone has aged out or been linked, then
+/// remove the old key.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::{DataClass, HmacRedactor, Redactor};
+///
+/// let redactor = HmacRedactor::new("k1", b"an old secret key").with_key("k2", b"a new secret key");
+///
+/// let mut output = String::new();
+/// redactor.redact(&DataClass::new("corp", "email"), "jdoe@contoso.com", &mut |s| output.push_str(s));
+/// assert!(output.starts_with("k1:"));
+/// assert!(output.contains(",k2:"));
+/// assert_eq!(output.len(), redactor.exact_len().unwrap());
+/// ```
+#[derive(Clone)]
+pub struct HmacRedactor {
+    keys: Vec<(Cow<'static, str>, Hmac<Sha256>)>,
+}
+
+impl HmacRedactor {
+    /// Creates a new instance with a single active key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key identifier is empty or contains a `:` or `,` character.
+    #[must_use]
+    pub fn new(key_id: impl Into<Cow<'static, str>>, key: impl AsRef<[u8]>) -> Self {
+        Self { keys: Vec::new() }.with_key(key_id, key)
+    }
+
+    /// Adds another active key, whose digest is appended to the output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key identifier is empty, contains a `:` or `,` character, or is already in use.
+    #[must_use]
+    pub fn with_key(mut self, key_id: impl Into<Cow<'static, str>>, key: impl AsRef<[u8]>) -> Self {
+        let key_id = key_id.into();
+        assert!(
+            !key_id.is_empty() && !key_id.contains([':', ',']),
+            "Key identifiers must be non-empty and can't contain `:` or `,`"
+        );
+        assert!(
+            self.keys.iter().all(|(id, _)| *id != key_id),
+            "Key identifier `{key_id}` is already in use"
+        );
+
+        let mac =
+            Hmac::<Sha256>::new_from_slice(key.as_ref()).expect("HMAC accepts keys of any length");
+        self.keys.push((key_id, mac));
+        self
+    }
+
+    /// Returns the identifiers of the active keys, in output order.
+    pub fn key_ids(&self) -> impl Iterator<Item = &str> {
+        self.keys.iter().map(|(id, _)| id.as_ref())
+    }
+
+    /// Outputs the digests computed by the keyed hashers of each key, in the order of the keys.
+    fn output_digests(
+        &self,
+        macs: impl Iterator<Item = Hmac<Sha256>>,
+        output: &mut dyn FnMut(&str),
+    ) {
+        const HEX_LOWER_CHARS: &[u8; 16] = b"0123456789abcdef";
+
+        for (index, ((key_id, _), mac)) in self.keys.iter().zip(macs).enumerate() {
+            if index > 0 {
+                output(",");
+            }
+
+            output(key_id);
+            output(":");
+
+            let digest = mac.finalize().into_bytes();
+            let mut buffer = [0u8; DIGEST_LEN * 2];
+            for (chunk, byte) in buffer.chunks_exact_mut(2).zip(&digest[..DIGEST_LEN]) {
+                chunk.copy_from_slice(&[
+                    HEX_LOWER_CHARS[usize::from(byte >> 4)],
+                    HEX_LOWER_CHARS[usize::from(byte & 0x0f)],
+                ]);
+            }
+
+            // SAFETY: The buffer is guaranteed to be valid UTF-8 because it only contains hex digits.
+            output(unsafe { core::str::from_utf8_unchecked(&buffer) });
+        }
+    }
+}
+
+impl Redactor for HmacRedactor {
+    fn redact(&self, data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
+        self.redact_bytes(
+            data_class,
+            value.as_bytes(),
+            &RedactionContext::new(),
+            output,
+        );
+    }
+
+    fn redact_bytes(
+        &self,
+        _data_class: &DataClass,
+        value: &[u8],
+        _context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) {
+        let macs = self.keys.iter().map(|(_, mac)| {
+            let mut mac = mac.clone();
+            mac.update(value);
+            mac
+        });
+        self.output_digests(macs, output);
+    }
+
+    fn exact_len(&self) -> Option<usize> {
+        let ids: usize = self.keys.iter().map(|(id, _)| id.len()).sum();
+        Some(ids + self.keys.len() * (DIGEST_LEN * 2 + 2) - 1)
+    }
+
+    fn as_streaming(&self) -> Option<&dyn StreamingRedactor> {
+        Some(self)
+    }
+
+    fn reveals_input(&self) -> bool {
+        false
+    }
+}
+
+impl StreamingRedactor for HmacRedactor {
+    fn redact_reader(
+        &self,
+        _data_class: &DataClass,
+        reader: &mut dyn Read,
+        output: &mut dyn FnMut(&str),
+    ) -> io::Result<()> {
+        let mut macs: Vec<_> = self.keys.iter().map(|(_, mac)| mac.clone()).collect();
+        read_chunks(reader, |chunk| {
+            for mac in &mut macs {
+                mac.update(chunk);
+            }
+        })?;
+
+        self.output_digests(macs.into_iter(), output);
+        Ok(())
+    }
+}
+
+impl Debug for HmacRedactor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HmacRedactor")
+            .field("key_ids", &self.key_ids().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::CoreTaxonomy;
+
+    fn redact_to_string(redactor: &HmacRedactor, value: &str) -> String {
+        let mut output = String::new();
+        redactor.redact(&CoreTaxonomy::Sensitive.data_class(), value, &mut |s| {
+            output.push_str(s);
+        });
+        output
+    }
+
+    #[test]
+    fn redact_should_match_known_digest() {
+        // RFC 4231 test case 2, truncated to 16 bytes
+        let redactor = HmacRedactor::new("k1", b"Jefe");
+        assert_eq!(
+            redact_to_string(&redactor, "what do ya want for nothing?"),
+            "k1:5bdcc146bf60754e6a042426089575c7"
+        );
+    }
+
+    #[test]
+    fn redact_reader_should_match_redact() {
+        let redactor = HmacRedactor::new("k1", b"Jefe").with_key("k2", b"other");
+        let value = "what do ya want for nothing?".repeat(1000);
+
+        let mut streamed = String::new();
+        redactor
+            .redact_reader(
+                &CoreTaxonomy::Sensitive.data_class(),
+                &mut value.as_bytes(),
+                &mut |s| streamed.push_str(s),
+            )
+            .unwrap();
+        assert_eq!(streamed, redact_to_string(&redactor, &value));
+    }
+
+    #[test]
+    fn redact_bytes_should_match_redact_for_text() {
+        let redactor = HmacRedactor::new("k1", b"Jefe");
+        let mut output = String::new();
+        redactor.redact_bytes(
+            &CoreTaxonomy::Sensitive.data_class(),
+            b"what do ya want for nothing?",
+            &RedactionContext::new(),
+            &mut |s| output.push_str(s),
+        );
+
+        assert_eq!(
+            output,
+            redact_to_string(&redactor, "what do ya want for nothing?")
+        );
+    }
+
+    #[test]
+    fn redact_should_be_deterministic_and_keyed() {
+        let redactor = HmacRedactor::new("k1", b"secret");
+        let other = HmacRedactor::new("k1", b"other secret");
+
+        assert_eq!(
+            redact_to_string(&redactor, "a"),
+            redact_to_string(&redactor, "a")
+        );
+        assert_ne!(
+            redact_to_string(&redactor, "a"),
+            redact_to_string(&redactor, "b")
+        );
+        assert_ne!(
+            redact_to_string(&redactor, "a"),
+            redact_to_string(&other, "a")
+        );
+    }
+
+    #[test]
+    fn rotation_should_keep_values_correlatable() {
+        let old = HmacRedactor::new("k1", b"old");
+        let rotating = HmacRedactor::new("k1", b"old").with_key("k2", b"new");
+        let new = HmacRedactor::new("k2", b"new");
+
+        let before = redact_to_string(&old, "jdoe");
+        let during = redact_to_string(&rotating, "jdoe");
+        let after = redact_to_string(&new, "jdoe");
+
+        assert_eq!(during, format!("{before},{after}"));
+        assert_eq!(rotating.key_ids().collect::<Vec<_>>(), ["k1", "k2"]);
+    }
+
+    #[test]
+    fn exact_len_should_match_output() {
+        for redactor in [
+            HmacRedactor::new("k1", b"a"),
+            HmacRedactor::new("primary", b"a").with_key("k2", b"b"),
+        ] {
+            assert_eq!(
+                redactor.exact_len(),
+                Some(redact_to_string(&redactor, "value").len())
+            );
+        }
+    }
+
+    #[test]
+    fn debug_should_not_expose_keys() {
+        let redactor = HmacRedactor::new("k1", b"secret");
+        assert_eq!(
+            format!("{redactor:?}"),
+            "HmacRedactor { key_ids: [\"k1\"], .. }"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Key identifiers must be non-empty")]
+    fn invalid_key_ids_should_panic() {
+        _ = HmacRedactor::new("k:1", b"secret");
+    }
+
+    #[test]
+    #[should_panic(expected = "already in use")]
+    fn duplicate_key_ids_should_panic() {
+        _ = HmacRedactor::new("k1", b"a").with_key("k1", b"b");
+    }
+}
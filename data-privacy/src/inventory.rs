@@ -0,0 +1,320 @@
+use crate::DataClass;
+use std::collections::BTreeMap;
+
+/// Describes which fields of a type hold classified data, without involving any values.
+///
+/// This trait is normally implemented with `#[derive(DataInventory)]`, which inspects the type of each field.
+/// Fields whose type is a classified container known to the [`ClassifiedType`](crate::ClassifiedType) trait,
+/// such as those generated by the [`taxonomy`](crate::taxonomy) attribute, are reported with their data class.
+/// Other fields are reported as unclassified, except for fields whose type depends on a type parameter of
+/// the type, whose classification depends on how the type is instantiated. These are reported as
+/// [unknown](FieldInventory::is_known). Classified containers nested within other types, such as
+/// `Option<Sensitive<T>>`, aren't detected.
+///
+/// Inventories can be aggregated across types with [`Inventory`], to feed privacy impact assessment tooling.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+/// use data_privacy::DataInventory;
+///
+/// #[derive(DataInventory)]
+/// struct Employee {
+///     name: Sensitive<String>,
+///     age: u32,
+/// }
+///
+/// let inventory = Employee::data_inventory();
+/// assert_eq!(inventory.type_name(), "Employee");
+/// assert_eq!(inventory.fields()[0].name(), "name");
+/// assert_eq!(inventory.fields()[0].data_class(), Some(&CoreTaxonomy::Sensitive.data_class()));
+/// assert_eq!(inventory.fields()[1].data_class(), None);
+/// ```
+pub trait DataInventory {
+    /// Returns a description of the classified fields of this type.
+    #[must_use]
+    fn data_inventory() -> TypeInventory;
+}
+
+/// The classification of the fields of a single type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeInventory {
+    type_name: &'static str,
+    fields: Vec<FieldInventory>,
+}
+
+impl TypeInventory {
+    /// Creates a new inventory for a type.
+    #[must_use]
+    pub const fn new(type_name: &'static str, fields: Vec<FieldInventory>) -> Self {
+        Self { type_name, fields }
+    }
+
+    /// Returns the name of the type.
+    #[must_use]
+    pub const fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns the fields of the type, in declaration order.
+    #[must_use]
+    pub fn fields(&self) -> &[FieldInventory] {
+        &self.fields
+    }
+
+    /// Returns the fields of the type which hold classified data.
+    pub fn classified_fields(&self) -> impl Iterator<Item = &FieldInventory> {
+        self.fields
+            .iter()
+            .filter(|field| field.data_class.is_some())
+    }
+}
+
+/// The classification of a single field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldInventory {
+    name: &'static str,
+    data_class: Option<DataClass>,
+    known: bool,
+}
+
+impl FieldInventory {
+    /// Creates a new field description.
+    #[must_use]
+    pub const fn new(name: &'static str, data_class: Option<DataClass>) -> Self {
+        Self {
+            name,
+            data_class,
+            known: true,
+        }
+    }
+
+    /// Creates a new description of a field whose classification isn't known.
+    #[must_use]
+    pub const fn unknown(name: &'static str) -> Self {
+        Self {
+            name,
+            data_class: None,
+            known: false,
+        }
+    }
+
+    /// Returns the name of the field, or its index for tuple structs.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the data class of the field, if it holds classified data.
+    #[must_use]
+    pub const fn data_class(&self) -> Option<&DataClass> {
+        self.data_class.as_ref()
+    }
+
+    /// Returns whether the classification of the field is known.
+    ///
+    /// Fields whose classification isn't known have no data class, but may still hold classified data.
+    #[must_use]
+    pub const fn is_known(&self) -> bool {
+        self.known
+    }
+}
+
+/// An aggregation of the inventories of many types.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::{CoreTaxonomy, Insensitive, Sensitive};
+/// use data_privacy::{DataInventory, Inventory};
+///
+/// #[derive(DataInventory)]
+/// struct Employee {
+///     name: Sensitive<String>,
+///     team: Insensitive<String>,
+/// }
+///
+/// #[derive(DataInventory)]
+/// struct Customer {
+///     email: Sensitive<String>,
+/// }
+///
+/// let inventory = Inventory::new().add::<Employee>().add::<Customer>();
+/// let sensitive = &inventory.by_class()[&CoreTaxonomy::Sensitive.data_class()];
+/// assert_eq!(sensitive, &[("Employee", "name"), ("Customer", "email")]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Inventory {
+    types: Vec<TypeInventory>,
+}
+
+impl Inventory {
+    /// Creates a new empty inventory.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { types: Vec::new() }
+    }
+
+    /// Adds the inventory of a type.
+    #[must_use]
+    pub fn add<T: DataInventory>(self) -> Self {
+        self.add_type(T::data_inventory())
+    }
+
+    /// Adds the inventory of a type which was produced separately.
+    #[must_use]
+    pub fn add_type(mut self, inventory: TypeInventory) -> Self {
+        self.types.push(inventory);
+        self
+    }
+
+    /// Returns the inventories of all types, in the order they were added.
+    #[must_use]
+    pub fn types(&self) -> &[TypeInventory] {
+        &self.types
+    }
+
+    /// Groups the classified fields of all types by data class.
+    ///
+    /// Each data class maps to the type and field names holding data of that class.
+    #[must_use]
+    pub fn by_class(&self) -> BTreeMap<DataClass, Vec<(&'static str, &'static str)>> {
+        let mut result: BTreeMap<DataClass, Vec<_>> = BTreeMap::new();
+        for ty in &self.types {
+            for field in ty.classified_fields() {
+                if let Some(data_class) = field.data_class() {
+                    result
+                        .entry(data_class.clone())
+                        .or_default()
+                        .push((ty.type_name, field.name));
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// Determines the data class of a field's type, as used by `#[derive(DataInventory)]`.
+///
+/// This relies on method resolution preferring the impl for `&InventoryProbe<T>`, which only
+/// applies to classified types, over the fallback impl for `InventoryProbe<T>`.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct InventoryProbe<T: ?Sized>(core::marker::PhantomData<T>);
+
+impl<T: ?Sized> InventoryProbe<T> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(core::marker::PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait ProbeClassified {
+    fn probe_data_class(&self) -> Option<DataClass>;
+}
+
+impl<T: crate::ClassifiedType + ?Sized> ProbeClassified for &InventoryProbe<T> {
+    fn probe_data_class(&self) -> Option<DataClass> {
+        Some(T::DATA_CLASS)
+    }
+}
+
+#[doc(hidden)]
+pub trait ProbePlain {
+    fn probe_data_class(&self) -> Option<DataClass>;
+}
+
+impl<T: ?Sized> ProbePlain for InventoryProbe<T> {
+    fn probe_data_class(&self) -> Option<DataClass> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::{CoreTaxonomy, Insensitive, Sensitive};
+
+    #[derive(crate::DataInventory)]
+    #[expect(dead_code, reason = "Only the shape of the type matters")]
+    struct Employee {
+        name: Sensitive<String>,
+        team: Insensitive<String>,
+        age: u32,
+    }
+
+    #[derive(crate::DataInventory)]
+    #[expect(dead_code, reason = "Only the shape of the type matters")]
+    struct Pair(Sensitive<u32>, String);
+
+    #[derive(crate::DataInventory)]
+    #[expect(dead_code, reason = "Only the shape of the type matters")]
+    struct Wrapper<T> {
+        id: Sensitive<u32>,
+        value: T,
+        values: Vec<T>,
+    }
+
+    #[test]
+    fn derive_should_describe_fields() {
+        let inventory = Employee::data_inventory();
+        assert_eq!(inventory.type_name(), "Employee");
+        assert_eq!(
+            inventory.fields(),
+            &[
+                FieldInventory::new("name", Some(CoreTaxonomy::Sensitive.data_class())),
+                FieldInventory::new("team", Some(CoreTaxonomy::Insensitive.data_class())),
+                FieldInventory::new("age", None),
+            ]
+        );
+        assert_eq!(inventory.classified_fields().count(), 2);
+
+        let inventory = Pair::data_inventory();
+        assert_eq!(
+            inventory.fields(),
+            &[
+                FieldInventory::new("0", Some(CoreTaxonomy::Sensitive.data_class())),
+                FieldInventory::new("1", None),
+            ]
+        );
+        assert!(inventory.fields().iter().all(FieldInventory::is_known));
+    }
+
+    #[test]
+    fn derive_should_report_generic_fields_as_unknown() {
+        let inventory = Wrapper::<Sensitive<String>>::data_inventory();
+        assert_eq!(
+            inventory.fields(),
+            &[
+                FieldInventory::new("id", Some(CoreTaxonomy::Sensitive.data_class())),
+                FieldInventory::unknown("value"),
+                FieldInventory::unknown("values"),
+            ]
+        );
+        assert!(!inventory.fields()[1].is_known());
+        assert_ne!(
+            FieldInventory::unknown("value"),
+            FieldInventory::new("value", None)
+        );
+    }
+
+    #[test]
+    fn inventory_should_group_by_class() {
+        let inventory = Inventory::new().add::<Employee>().add::<Pair>();
+        assert_eq!(inventory.types().len(), 2);
+
+        let by_class = inventory.by_class();
+        assert_eq!(by_class.len(), 2);
+        assert_eq!(
+            by_class[&CoreTaxonomy::Sensitive.data_class()],
+            [("Employee", "name"), ("Pair", "0")]
+        );
+        assert_eq!(
+            by_class[&CoreTaxonomy::Insensitive.data_class()],
+            [("Employee", "team")]
+        );
+    }
+}
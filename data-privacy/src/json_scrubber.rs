@@ -0,0 +1,256 @@
+use crate::{DataClass, RedactionEngine};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Redacts the sensitive parts of JSON documents whose shape isn't known statically.
+///
+/// Payloads received from third parties, such as webhooks, often can't be deserialized into types
+/// using classified containers, yet still need to be sanitized before being logged. A scrubber is
+/// configured with the data classes of the parts of such documents, and redacts them in place with a
+/// [`RedactionEngine`].
+///
+/// Parts of a document are identified in one of two ways:
+///
+/// * [`with_pointer`](Self::with_pointer) classifies the value at a JSON pointer, as defined by RFC 6901,
+///   such as `/customer/email`. A segment consisting of `*` matches any object member or array element,
+///   so `/items/*/name` matches the `name` of each item.
+///
+/// * [`with_field`](Self::with_field) classifies the values of object members with a given name,
+///   wherever they appear in the document.
+///
+/// Pointers take precedence over field names. Strings are replaced with their redacted form, while
+/// numbers and booleans are redacted as their textual form and replaced with the resulting strings. When
+/// an object or array is classified, everything it holds is redacted as that class. Values of data classes
+/// the engine [omits](RedactionEngine::is_omitted) are removed from their parent object or array, and
+/// the whole document is replaced with `null` if it is omitted itself.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::CoreTaxonomy;
+/// use data_privacy::{JsonScrubber, RedactionEngineBuilder, SimpleRedactor};
+/// use serde_json::json;
+///
+/// let engine = RedactionEngineBuilder::new()
+///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+///     .build();
+///
+/// let scrubber = JsonScrubber::new()
+///     .with_pointer("/customer/name", CoreTaxonomy::Sensitive.data_class())
+///     .with_field("email", CoreTaxonomy::Sensitive.data_class());
+///
+/// let mut payload = json!({
+///     "event": "order.created",
+///     "customer": { "name": "Jane", "email": "jane@contoso.com" },
+///     "contacts": [{ "email": "joe@contoso.com" }],
+/// });
+///
+/// scrubber.scrub(&engine, &mut payload);
+/// assert_eq!(payload, json!({
+///     "event": "order.created",
+///     "customer": { "name": "****", "email": "****************" },
+///     "contacts": [{ "email": "***************" }],
+/// }));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct JsonScrubber {
+    pointers: Vec<(Vec<String>, DataClass)>,
+    fields: HashMap<String, DataClass>,
+}
+
+impl JsonScrubber {
+    /// Creates a new scrubber which doesn't redact anything.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Classifies the value at the given JSON pointer.
+    ///
+    /// The empty pointer designates the whole document. In other pointers, each segment is preceded by
+    /// a `/`, and `~1` and `~0` stand for `/` and `~` within segments. A segment consisting of `*`
+    /// matches any object member or array element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pointer is neither empty nor starts with a `/`.
+    #[must_use]
+    pub fn with_pointer(mut self, pointer: &str, data_class: DataClass) -> Self {
+        assert!(
+            pointer.is_empty() || pointer.starts_with('/'),
+            "JSON pointer `{pointer}` must be empty or start with `/`"
+        );
+
+        let segments = pointer.get(1..).map_or_else(Vec::new, |rest| {
+            rest.split('/')
+                .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+                .collect()
+        });
+
+        self.pointers.push((segments, data_class));
+        self
+    }
+
+    /// Classifies the values of all object members with the given name.
+    #[must_use]
+    pub fn with_field(mut self, name: impl Into<String>, data_class: DataClass) -> Self {
+        _ = self.fields.insert(name.into(), data_class);
+        self
+    }
+
+    /// Redacts the classified parts of a document in place.
+    pub fn scrub(&self, engine: &RedactionEngine, value: &mut Value) {
+        if !self.scrub_value(engine, self.pointer_class(&[]), value, &mut Vec::new()) {
+            *value = Value::Null;
+        }
+    }
+
+    /// Redacts a value if it's classified, or its classified members or elements otherwise, returning
+    /// `false` if the value should be removed.
+    fn scrub_value(
+        &self,
+        engine: &RedactionEngine,
+        data_class: Option<&DataClass>,
+        value: &mut Value,
+        path: &mut Vec<String>,
+    ) -> bool {
+        if let Some(data_class) = data_class {
+            return redact_value(engine, data_class, value);
+        }
+
+        match value {
+            Value::Object(members) => members.retain(|name, member| {
+                path.push(name.clone());
+                let data_class = self.pointer_class(path).or_else(|| self.fields.get(name));
+                let keep = self.scrub_value(engine, data_class, member, path);
+                _ = path.pop();
+                keep
+            }),
+            Value::Array(elements) => {
+                let mut index = 0_usize;
+                elements.retain_mut(|element| {
+                    path.push(index.to_string());
+                    index += 1;
+                    let keep = self.scrub_value(engine, self.pointer_class(path), element, path);
+                    _ = path.pop();
+                    keep
+                });
+            }
+            Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => {}
+        }
+
+        true
+    }
+
+    /// Returns the data class of the first pointer matching the path, if any.
+    fn pointer_class(&self, path: &[String]) -> Option<&DataClass> {
+        self.pointers
+            .iter()
+            .find(|(segments, _)| {
+                segments.len() == path.len()
+                    && segments
+                        .iter()
+                        .zip(path)
+                        .all(|(segment, name)| segment == "*" || segment == name)
+            })
+            .map(|(_, data_class)| data_class)
+    }
+}
+
+/// Redacts a value and everything it holds as the given class, returning `false` if the value should be removed.
+fn redact_value(engine: &RedactionEngine, data_class: &DataClass, value: &mut Value) -> bool {
+    if engine.is_omitted(data_class) {
+        return false;
+    }
+
+    match value {
+        Value::Null => {}
+        Value::Bool(b) => {
+            *value = Value::String(engine.redact_to_string_as_class(data_class, b.to_string()));
+        }
+        Value::Number(n) => {
+            *value = Value::String(engine.redact_to_string_as_class(data_class, n.to_string()));
+        }
+        Value::String(s) => *s = engine.redact_to_string_as_class(data_class, &*s),
+        Value::Array(elements) => {
+            for element in elements {
+                _ = redact_value(engine, data_class, element);
+            }
+        }
+        Value::Object(members) => {
+            for member in members.values_mut() {
+                _ = redact_value(engine, data_class, member);
+            }
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::CoreTaxonomy;
+    use crate::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+    use serde_json::json;
+
+    fn engine() -> RedactionEngine {
+        RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')),
+            )
+            .omit_class(&CoreTaxonomy::UnknownSensitivity.data_class())
+            .build()
+    }
+
+    #[test]
+    fn scrub_should_redact_classified_parts() {
+        let scrubber = JsonScrubber::new()
+            .with_pointer("/items/*/sku", CoreTaxonomy::Sensitive.data_class())
+            .with_pointer("/a~1b", CoreTaxonomy::Sensitive.data_class())
+            .with_pointer("/address", CoreTaxonomy::Sensitive.data_class())
+            .with_field("token", CoreTaxonomy::UnknownSensitivity.data_class())
+            .with_field("pin", CoreTaxonomy::Sensitive.data_class());
+
+        let mut value = json!({
+            "items": [{ "sku": "ab", "qty": 2 }, { "sku": 12345, "token": "t" }],
+            "a/b": true,
+            "address": { "street": "Main", "lines": ["x", null] },
+            "nested": { "pin": 42, "token": ["t"] },
+            "plain": "visible",
+        });
+        scrubber.scrub(&engine(), &mut value);
+
+        assert_eq!(
+            value,
+            json!({
+                "items": [{ "sku": "##", "qty": 2 }, { "sku": "#####" }],
+                "a/b": "####",
+                "address": { "street": "####", "lines": ["#", null] },
+                "nested": { "pin": "##" },
+                "plain": "visible",
+            })
+        );
+    }
+
+    #[test]
+    fn scrub_should_handle_whole_document() {
+        let mut value = json!(["secret"]);
+        JsonScrubber::new()
+            .with_pointer("", CoreTaxonomy::Sensitive.data_class())
+            .scrub(&engine(), &mut value);
+        assert_eq!(value, json!(["######"]));
+
+        JsonScrubber::new()
+            .with_pointer("", CoreTaxonomy::UnknownSensitivity.data_class())
+            .scrub(&engine(), &mut value);
+        assert_eq!(value, Value::Null);
+    }
+
+    #[test]
+    #[should_panic(expected = "JSON pointer `name` must be empty or start with `/`")]
+    fn with_pointer_should_reject_relative_pointers() {
+        _ = JsonScrubber::new().with_pointer("name", CoreTaxonomy::Sensitive.data_class());
+    }
+}
@@ -42,7 +42,7 @@
 //!
 //! * The [`Redactor`] trait defines the logic needed by an individual redactor. This crate provides a
 //!   few implementations of this trait, such as [`SimpleRedactor`], but others can
-//!   be implemented and used by applications as well.
+//!   be implemented and used by applications as well. Redactors can be composed with a [`ChainedRedactor`].
 //!
 //! # Data Classes
 //!
@@ -82,6 +82,20 @@
 //!
 //! * When it's time to log or otherwise process the sensitive data, the application uses the redaction engine to redact the data.
 //!
+//! # `no_std` Support
+//!
+//! The crate supports `no_std` environments which provide `alloc`, by disabling the default `std` feature.
+//! Data classes, classified containers (including those generated by the [`taxonomy`] attribute), and
+//! the [`Extract`] trait remain available, so components such as firmware can produce classified telemetry
+//! that is redacted by a backend. The [`Redactor`] trait and the basic redactors ([`SimpleRedactor`],
+//! [`PartialRedactor`], [`TruncatingRedactor`], and [`ChainedRedactor`]) are available too, so devices can
+//! redact values themselves before emitting them. Redaction engines and the audit sinks require `std`,
+//! and declassification isn't audited without it.
+//!
+//! With or without `std`, the crate requires targets with pointer-sized atomics (`target_has_atomic = "ptr"`),
+//! as the [declassification observer](audit::set_declassify_observer) is stored in an atomic pointer and
+//! redactors are shared through [`Arc`](alloc::sync::Arc).
+//!
 //! # Examples
 //!
 //! This example shows how to use the `Sensitive` type to classify sensitive data.
@@ -118,7 +132,8 @@
 //!
 //! This example shows how to initialize and use a redaction engine.
 //!
-//! ```rust
+#![cfg_attr(feature = "std", doc = "```rust")]
+#![cfg_attr(not(feature = "std"), doc = "```ignore")]
 //! use std::fmt::Write;
 //! use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
 //! use data_privacy::{SimpleRedactor, SimpleRedactorMode, Redactor, RedactionEngineBuilder};
@@ -153,40 +168,425 @@
 //! }
 //! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+// lets the code generated by this crate's macros name it the same way within and outside of it
+extern crate self as data_privacy;
+
+#[cfg(feature = "futures")]
+mod async_redacted_writer;
+pub mod audit;
+#[cfg(feature = "std")]
+mod builder_error;
+mod chained_redactor;
+mod class_code;
+mod class_marker;
 mod classified;
+mod classified_as;
+mod classified_key;
+#[cfg(feature = "futures")]
+mod classified_stream;
+mod classified_type;
+mod classify_fields;
+#[cfg(feature = "std")]
+mod conformance;
 pub mod core_taxonomy;
 mod data_class;
+#[cfg(feature = "registry")]
+mod data_class_registry;
+#[cfg(feature = "registry")]
+mod duplicate_class;
+mod dynamic_classified;
+#[cfg(feature = "std")]
+mod env_secret_provider;
+#[cfg(feature = "serde")]
+mod envelope;
+#[cfg(feature = "std")]
+mod environment;
+mod extract;
+#[cfg(feature = "std")]
+mod file_secret_provider;
+mod formatting;
+#[cfg(feature = "std")]
+mod global_engine;
+#[cfg(feature = "std")]
+mod heuristic_classifier;
+#[cfg(feature = "std")]
+mod inventory;
+#[cfg(feature = "json")]
+mod json_scrubber;
+mod multi_classified;
+mod partial_redactor;
+pub mod policy;
+#[cfg(feature = "std")]
+mod record_builder;
+#[cfg(feature = "std")]
+mod redact_fmt;
+#[cfg(feature = "std")]
+mod redacted;
+#[cfg(feature = "std")]
+mod redacting_fmt_writer;
+#[cfg(all(feature = "serde", feature = "std"))]
+mod redacting_serializer;
+#[cfg(feature = "std")]
+mod redacting_writer;
+mod redaction_context;
+#[cfg(feature = "std")]
 mod redaction_engine;
+#[cfg(feature = "std")]
 mod redaction_engine_builder;
+#[cfg(feature = "std")]
+mod redaction_engine_handle;
+#[cfg(feature = "std")]
+mod redaction_event;
+#[cfg(feature = "std")]
+mod redaction_policy;
+#[cfg(feature = "std")]
+mod redaction_stats;
 mod redactor;
+#[cfg(feature = "std")]
+mod redactor_registry;
+#[cfg(feature = "std")]
+mod reloadable_redactor;
+#[cfg(feature = "std")]
+mod secret_provider;
+#[cfg(all(feature = "serde", feature = "std"))]
+mod serialize_redacted;
 mod simple_redactor;
+#[cfg(feature = "std")]
+mod static_engine;
+#[cfg(feature = "std")]
+mod streaming_redactor;
+mod tag_format;
+#[cfg(feature = "std")]
+mod tag_scanner;
+#[cfg(feature = "std")]
+mod taxonomy_mapper;
+mod truncating_redactor;
+mod unknown_class_error;
 
+#[cfg(feature = "hmac")]
+mod format_preserving_redactor;
+#[cfg(feature = "xxh3")]
+mod hash_encoding;
+#[cfg(feature = "hmac")]
+mod hmac_redactor;
+#[cfg(feature = "opentelemetry")]
+pub mod otel;
+#[cfg(feature = "regex")]
+mod pattern_redactor;
+#[cfg(feature = "xxh3")]
+mod rotating_hash_redactor;
+#[cfg(feature = "hmac")]
+mod token_vault;
+#[cfg(feature = "hmac")]
+mod tokenizing_redactor;
+#[cfg(feature = "tracing")]
+pub mod tracing_layer;
+#[cfg(feature = "uuid")]
+mod uuid_redactor;
 #[cfg(feature = "xxh3")]
 mod xxh3_redactor;
 
+#[cfg(feature = "futures")]
+pub use async_redacted_writer::AsyncRedactedWriter;
+#[cfg(feature = "std")]
+pub use builder_error::BuilderError;
+pub use chained_redactor::ChainedRedactor;
+pub use class_code::ClassCode;
+pub use class_marker::ClassMarker;
 pub use classified::Classified;
+pub use classified_as::ClassifiedAs;
+pub use classified_key::ClassifiedKey;
+#[cfg(feature = "futures")]
+pub use classified_stream::{ClassifiedRead, ClassifiedStream};
+pub use classified_type::ClassifiedType;
 pub use data_class::DataClass;
+#[cfg(feature = "std")]
+pub use data_class::{BuildClassHasher, ClassHasher};
+#[cfg(feature = "registry")]
+pub use data_class_registry::DataClassRegistry;
+#[cfg(feature = "registry")]
+pub use duplicate_class::DuplicateClass;
+pub use dynamic_classified::DynamicClassified;
+#[cfg(feature = "std")]
+pub use env_secret_provider::EnvSecretProvider;
+#[cfg(feature = "std")]
+pub use environment::Environment;
+pub use extract::{Extract, Extractor, FieldExtractor, FieldPath};
+#[cfg(feature = "std")]
+pub use file_secret_provider::FileSecretProvider;
+#[cfg(feature = "std")]
+pub use global_engine::{
+    global_redaction_engine, set_global_redaction_engine, with_current_redaction_engine,
+    with_redaction_engine,
+};
+#[cfg(feature = "std")]
+pub use heuristic_classifier::HeuristicClassifier;
+#[cfg(feature = "std")]
+pub use inventory::{DataInventory, FieldInventory, Inventory, TypeInventory};
+#[cfg(feature = "json")]
+pub use json_scrubber::JsonScrubber;
+pub use multi_classified::MultiClassified;
+pub use partial_redactor::PartialRedactor;
+#[cfg(feature = "std")]
+pub use record_builder::{LogfmtSink, RecordBuilder, RecordSink};
+#[cfg(feature = "std")]
+pub use redacted::Redacted;
+#[cfg(feature = "std")]
+pub use redacting_fmt_writer::RedactingFmtWriter;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub use redacting_serializer::{RedactingCompound, RedactingSerializer};
+#[cfg(feature = "std")]
+pub use redacting_writer::RedactingWriter;
+pub use redaction_context::RedactionContext;
+#[cfg(feature = "std")]
 pub use redaction_engine::RedactionEngine;
+#[cfg(feature = "std")]
 pub use redaction_engine_builder::RedactionEngineBuilder;
+#[cfg(feature = "std")]
+pub use redaction_engine_handle::RedactionEngineHandle;
+#[cfg(feature = "std")]
+pub use redaction_event::RedactionEvent;
+#[cfg(feature = "std")]
+pub use redaction_policy::{PolicyValue, RedactionPolicy, RedactorSpec};
+#[cfg(feature = "std")]
+pub use redaction_stats::RedactionStats;
 pub use redactor::Redactor;
-pub use simple_redactor::{SimpleRedactor, SimpleRedactorMode};
+#[cfg(feature = "std")]
+pub use redactor_registry::RedactorRegistry;
+#[cfg(feature = "std")]
+pub use reloadable_redactor::ReloadableRedactor;
+#[cfg(feature = "std")]
+pub use secret_provider::SecretProvider;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub use serialize_redacted::SerializeRedacted;
+pub use simple_redactor::{MaskLength, SimpleRedactor, SimpleRedactorMode};
+#[cfg(feature = "std")]
+pub use streaming_redactor::StreamingRedactor;
+pub use tag_format::TagFormat;
+#[cfg(feature = "std")]
+pub use taxonomy_mapper::TaxonomyMapper;
+pub use truncating_redactor::TruncatingRedactor;
+pub use unknown_class_error::UnknownClassError;
 
+#[cfg(feature = "hmac")]
+pub use crate::format_preserving_redactor::FormatPreservingRedactor;
+#[cfg(feature = "xxh3")]
+pub use crate::hash_encoding::HashEncoding;
+#[cfg(feature = "hmac")]
+pub use crate::hmac_redactor::HmacRedactor;
+#[cfg(feature = "regex")]
+pub use crate::pattern_redactor::PatternRedactor;
+#[cfg(feature = "xxh3")]
+pub use crate::rotating_hash_redactor::RotatingHashRedactor;
+#[cfg(feature = "hmac")]
+pub use crate::token_vault::{MemoryTokenVault, TokenVault};
+#[cfg(feature = "hmac")]
+pub use crate::tokenizing_redactor::TokenizingRedactor;
+#[cfg(feature = "uuid")]
+pub use crate::uuid_redactor::UuidRedactor;
 #[cfg(feature = "xxh3")]
 pub use crate::xxh3_redactor::xxH3Redactor;
 
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde as __serde;
+
+/// Registers the data classes of a taxonomy, which does nothing without the `registry` feature.
+#[cfg(not(feature = "registry"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_taxonomy {
+    ($classes:expr, $source:ident) => {};
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::audit::record_declassification;
+    pub use crate::class_code::contains_code;
+    pub use crate::classified::PayloadToken;
+    #[cfg(feature = "std")]
+    pub use crate::conformance::{
+        check_arbitrary_unicode, check_determinism, check_empty_input, check_exact_len,
+        check_no_echo, check_utf8_safety,
+    };
+    #[cfg(feature = "registry")]
+    pub use crate::data_class_registry::TaxonomyRegistration;
+    #[cfg(feature = "serde")]
+    pub use crate::envelope::{deserialize_enveloped, serialize_enveloped};
+    #[cfg(feature = "defmt")]
+    pub use crate::formatting::format_masked;
+    pub use crate::formatting::with_formatted;
+    #[cfg(feature = "std")]
+    pub use crate::inventory::{InventoryProbe, ProbeClassified, ProbePlain};
+    pub use crate::policy::check_declassification;
+    #[cfg(all(feature = "serde", feature = "std"))]
+    pub use crate::serialize_redacted::serialize_classified;
+    #[cfg(feature = "defmt")]
+    pub use defmt;
+    #[cfg(feature = "registry")]
+    pub use inventory;
+    #[cfg(feature = "zeroize")]
+    pub use zeroize;
+
+    /// Formats as the redacted form of a field holding data of the given class.
+    ///
+    /// This is used by the code generated by the `RedactedDebug` derive.
+    pub struct RedactedField(pub crate::DataClass);
+
+    impl core::fmt::Debug for RedactedField {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "<{}:REDACTED>", self.0)
+        }
+    }
+
+    /// Serializes the payload of a classified container.
+    ///
+    /// Without `std` there is no redaction engine, so the payload is always serialized as is.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by the serializer.
+    #[cfg(all(feature = "serde", not(feature = "std")))]
+    pub fn serialize_classified<T, S>(
+        payload: &T,
+        _data_class: &crate::DataClass,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: serde::Serialize + ?Sized,
+        S: serde::Serializer,
+    {
+        payload.serialize(serializer)
+    }
+}
+
 /// Generates implementation logic and types to expose a data taxonomy.
 ///
 /// This macro is applied to an enum declaration. Each variant of the enum
 /// represents a data class within the taxonomy.
 ///
-/// You provide a taxonomy name as first argument, followed by an optional `serde = false` or `serde = true`
-/// argument to control whether serde support is included in the generated taxonomy code.
-/// The default value for `serde` is `true`, meaning that serde support is included by default.
+/// You provide a taxonomy name as first argument, followed by an optional `serde` argument to control whether
+/// serde support is included in the generated taxonomy code. The argument can be `serde = true` to implement
+/// both `Serialize` and `Deserialize`, `serde = false` to implement neither, or `serde = "serialize"` or
+/// `serde = "deserialize"` to only implement one of them. The default value for `serde` is `true`, meaning
+/// that serde support is included by default.
+///
+/// Implementing a single direction enforces the flow of sensitive data at compile time. For example, with
+/// `serde = "deserialize"`, classified data can be read from storage but can never be serialized back out
+/// in plain text.
+///
+/// With `serde = "strict"`, both traits are implemented, but payloads are wrapped in an envelope naming
+/// their data class, as in `{"class": "contoso/customer_content", "value": ...}`. Deserialization fails if
+/// the envelope is missing or names another class, so sensitive data can't silently be read from untagged
+/// input, or be read back as data of a different class.
+///
+/// Serializing a container normally produces its payload in plain text. Within
+/// [`RedactionEngine::to_serializable`], containers instead serialize as their redacted form.
+/// The generated serde code refers to serde through this crate, so crates defining taxonomies don't need
+/// to depend on serde themselves, but this crate's `serde` feature must be enabled.
 ///
 /// This attribute produces an implementation block for the enum which includes one method for
 /// each variant of the enum. These methods each return a [`DataClass`] instance representing that data class.
 /// In addition, classified data container types are generated for each data class.
 ///
+/// The generated containers forward the standard arithmetic operators (`+`, `-`, `*`, `/`, `%`, their
+/// compound assignment forms, unary `-`, and [`Sum`](core::iter::Sum)) to their payloads. Combining two values
+/// of the same data class yields a value of that same class, so classified amounts can be aggregated
+/// without being declassified.
+///
+/// Taxonomies whose classes hold secrets such as credentials can be given a `zeroize = true` argument, which
+/// requires this crate's `zeroize` feature. The generated containers then require payloads implementing
+/// `Zeroize`, and scrub them when dropped. These containers implement neither `Clone` nor `Copy`, and don't
+/// provide the arithmetic operators or the combinators that move the payload into a new container, so the
+/// only way for the payload to leave its container is to be declassified. Their `declassify_zeroizing`
+/// method hands the payload over in a `Zeroizing` wrapper, so it's still scrubbed once the caller drops it.
+///
+/// For embedded devices logging with [`defmt`](https://docs.rs/defmt), taxonomies can be given a `defmt = true`
+/// argument, which requires this crate's `defmt` feature. The generated containers then implement
+/// `defmt::Format`, emitting the same masked representation as their `Debug` implementation, such as
+/// `<core/sensitive:REDACTED>`, regardless of their payload. The containers of the [core taxonomy](core_taxonomy)
+/// implement `defmt::Format` whenever the `defmt` feature is enabled.
+///
+/// Both `serde` and `defmt` also accept a `cfg!(predicate)` value, such as `defmt = cfg!(feature = "defmt")`.
+/// The corresponding trait implementations are then generated, but only compiled when the predicate holds,
+/// which lets crates tie them to their own features.
+///
+/// Besides `declassify`, the generated containers have `declassify_for` and `declassify_with_justification`
+/// methods, which take the [purpose](policy::Purpose) the payload is needed for and only hand it over if the
+/// process-wide [declassification policy](policy::DeclassificationPolicy) allows it.
+///
+/// Each class normally gets its own container type, along with a dozen or so trait implementations, which
+/// adds to compile times for taxonomies with many classes. Taxonomies can instead be given a `generic = true`
+/// argument, in which case all their classes share the [`ClassifiedAs`] container of this crate. The attribute
+/// then only generates a [marker type](ClassMarker) per class, named after its variant with a `Class` suffix,
+/// and a type alias naming the container, as in `type Sensitive<T> = ClassifiedAs<T, SensitiveClass>`. The
+/// aliases are used like the containers generated by default, but the serde and `defmt` support of generic
+/// containers is controlled by the features of this crate, so `generic = true` can't be combined with the
+/// `serde`, `zeroize`, or `defmt` arguments, and the classes of generic taxonomies can't declare upcasts.
+///
+/// Nesting a container in another one of the same data class, as in `Sensitive<Sensitive<T>>`, adds no
+/// protection and hides the payload from redaction, since redactors would only see the inner container.
+/// Such containers can be flattened with their `flatten` method, or converted with `From`/`Into`.
+///
+/// Data borrowed from elsewhere, such as from a parser or a database row, can be classified without being
+/// copied by wrapping a reference to it. Each container gets an alias for this, named after it with a `Ref`
+/// suffix, as in `SensitiveRef<'a, T>` for `Sensitive<&'a T>`, which also accepts unsized payloads such as
+/// `SensitiveRef<'_, str>`. Borrowed containers are redacted like owned ones, and the `as_ref` method of
+/// an owned container produces one. Taxonomies created with `zeroize = true` don't get these aliases, as
+/// borrowed payloads can't be scrubbed.
+///
+/// Individual variants can be annotated with `#[class(level = N)]` to set the restriction
+/// [level](DataClass::level) of their data class, which determines the [most restrictive](DataClass::most_restrictive)
+/// class when values of different classes are combined. Variants have a level of 0 by default.
+///
+/// By default, the name of each data class is the `snake_case` form of its variant's name. Variants can
+/// be given a stable name with `#[class(name = "...")]`, and a stable numeric [identifier](DataClass::id)
+/// with `#[class(id = N)]`, so that the identity of a class seen by external tooling survives renaming
+/// the variant. Names may contain ASCII letters, digits, `_`, `.`, and `-`. Names and identifiers must
+/// be unique within a taxonomy.
+///
+/// Handling policies can be attached to a data class with `#[class(retention_days = N)]`, which records how
+/// long its data may be kept, and `#[class(never_log)]`, which makes redaction engines refuse to emit its
+/// data in the clear, even with a passthrough redactor. See [`DataClass::retention_days`] and
+/// [`DataClass::never_log`].
+///
+/// A variant can be annotated with `#[class(upcast = "path::to::Container")]` to generate a `From`
+/// conversion from its container into the container of another taxonomy, such as
+/// `#[class(upcast = "data_privacy::core_taxonomy::Sensitive")]` to hand application data to libraries
+/// expecting `Sensitive<T>`. The payload moves between the containers without being declassified, so it
+/// stays classified end to end and no declassification is recorded. The attribute can be repeated to
+/// convert into several containers. The target class must have at least the [level](DataClass::level) of
+/// the annotated class, and must be [never logged](DataClass::never_log) if the annotated class is, or
+/// compilation fails.
+///
+/// The containers compare, order, and hash like their payloads, so they can be used as the keys of maps and
+/// sets. Such collections can be queried with plain keys wrapped in a [`ClassifiedKey`], as in
+/// `map.get(ClassifiedKey::new("jdoe"))` for a `HashMap<Sensitive<String>, V>`, without building a container
+/// or exposing the keys of the collection.
+///
+/// Each variant's data class has a compact [`ClassCode`], available as the `code` method of the enum and
+/// the `CODE` constant of the container, which can be matched on to dispatch on data classes cheaply.
+/// Compilation fails if two classes of the taxonomy, including those of the taxonomies it extends, share
+/// a code, and the enum gets a `has_code` function telling whether a code is one of these classes.
+///
+/// The enum gets a `classes` function returning the data classes of all its variants, a `variants` function
+/// returning the variants themselves, and an `all_data_classes` function which also yields the classes of
+/// extended taxonomies. The enum implements [`FromStr`](core::str::FromStr), parsing names of the form
+/// `taxonomy/class` back into variants and failing with [`UnknownClassError`] for other names. With the `registry`
+/// feature, the data classes are also registered with the [`DataClassRegistry`], which reports data classes
+/// defined by more than one taxonomy enum.
+///
+/// A taxonomy can extend other taxonomies with an `extends(...)` argument listing the paths of their enums,
+/// such as `extends(data_privacy::core_taxonomy::CoreTaxonomy)`. Each extended taxonomy is embedded in a
+/// variant named after its enum, which the enum's `data_class` method resolves to the class of the embedded
+/// taxonomy, and the enum implements `From` for each of them. This lets an application's taxonomy cover the
+/// classes used by libraries without redefining their containers. The classes of extended taxonomies keep
+/// their own taxonomy name, and aren't included in the enum's `classes`.
+///
 /// ## Example
 ///
 /// ```ignore
@@ -195,8 +595,125 @@ pub use crate::xxh3_redactor::xxH3Redactor;
 /// #[taxonomy(contoso, serde = false)]
 /// enum ContosoTaxonomy {
 ///     CustomerContent,
+///     #[class(level = 1)]
 ///     CustomerIdentifier,
 ///     OrganizationIdentifier,
 /// }
+///
+/// #[taxonomy(contoso_ads, serde = false, extends(ContosoTaxonomy))]
+/// enum ContosoAdsTaxonomy {
+///     CampaignBudget,
+/// }
+///
+/// let data_class = ContosoAdsTaxonomy::from(ContosoTaxonomy::CustomerContent).data_class();
+/// assert_eq!(data_class, ContosoTaxonomy::CustomerContent.data_class());
 /// ```
+#[cfg_attr(
+    not(feature = "registry"),
+    allow(
+        rustdoc::broken_intra_doc_links,
+        reason = "DataClassRegistry only exists with the registry feature"
+    )
+)]
 pub use data_privacy_macros::taxonomy;
+
+/// Derives the [`DataInventory`] trait, describing which fields of a struct hold classified data.
+///
+/// See the [`DataInventory`] trait for details.
+#[cfg(feature = "std")]
+pub use data_privacy_macros::DataInventory;
+
+/// Derives the [`Extract`] trait for a struct or enum, walking each of its fields.
+///
+/// Fields annotated with `#[classified(Taxonomy::Class)]` are formatted with their [`Display`](core::fmt::Display)
+/// trait and extracted as data of the given class, which lets plain fields such as a `String` be classified
+/// without wrapping them in a container. The argument is a variant of a taxonomy enum generated by the
+/// [`taxonomy`] attribute. Other fields are extracted with their own implementation of [`Extract`], so
+/// they can be classified containers, plain values, options, collections, or other types deriving `Extract`.
+///
+/// The extracted form follows the layout of `#[derive(Debug)]`, as in `Employee { name: ..., age: 33 }`, with
+/// enums extracted as the variant holding the value.
+///
+/// Classified text is sent to the extractor as a [named field](Extractor::write_field), whose [path](FieldPath)
+/// is the name of the field holding it, or its index for tuple fields, prefixed by the names of the fields
+/// enclosing it, as in `address.zip`. This lets [`RedactionEngine::redact_fields`] produce `name=value` pairs,
+/// and lets redactors be registered for individual fields of the deriving type.
+///
+/// Fields annotated with `#[extract(skip)]` are left out of the extracted form entirely, which suits fields
+/// that are irrelevant to telemetry or whose type doesn't implement [`Extract`].
+///
+/// ## Example
+///
+#[cfg_attr(feature = "std", doc = "```rust")]
+#[cfg_attr(not(feature = "std"), doc = "```ignore")]
+/// use data_privacy::core_taxonomy::{CoreTaxonomy, Insensitive};
+/// use data_privacy::{Extract, RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+///
+/// #[derive(Extract)]
+/// struct Employee {
+///     #[classified(CoreTaxonomy::Sensitive)]
+///     email: String,
+///     team: Insensitive<String>,
+///     age: u32,
+///     #[extract(skip)]
+///     badge: Vec<u8>,
+/// }
+///
+/// let engine = RedactionEngineBuilder::new()
+///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+///     .add_class_redactor(
+///         &CoreTaxonomy::Insensitive.data_class(),
+///         SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+///     )
+///     .build();
+///
+/// let employee = Employee {
+///     email: "jdoe@contoso.com".to_string(),
+///     team: Insensitive::new("ops".to_string()),
+///     age: 33,
+///     badge: vec![0x2a],
+/// };
+///
+/// let mut output = String::new();
+/// engine.extract_redacted(&employee, |s| output.push_str(s));
+/// assert_eq!(output, "Employee { email: ****************, team: ops, age: 33 }");
+/// ```
+pub use data_privacy_macros::Extract;
+
+/// Derives [`Debug`](core::fmt::Debug) for a struct or enum without exposing its classified fields.
+///
+/// The output follows the layout of `#[derive(Debug)]`, including its alternate form. Fields annotated with
+/// `#[classified(Taxonomy::Class)]` are printed as `<taxonomy/class:REDACTED>`, like the classified containers
+/// generated by the [`taxonomy`] attribute, which lets plain fields such as a `String` be classified without
+/// wrapping them in a container. The argument is a variant of a taxonomy enum generated by the [`taxonomy`]
+/// attribute. Other fields are printed with their own implementation of `Debug`, so classified containers
+/// stay redacted.
+///
+/// The annotations are the same as those of the [`Extract`](macro@Extract) derive, so a type can derive both.
+///
+/// ## Example
+///
+/// ```rust
+/// use data_privacy::RedactedDebug;
+/// use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+///
+/// #[derive(RedactedDebug)]
+/// struct Employee {
+///     #[classified(CoreTaxonomy::Sensitive)]
+///     email: String,
+///     name: Sensitive<String>,
+///     age: u32,
+/// }
+///
+/// let employee = Employee {
+///     email: "jdoe@contoso.com".to_string(),
+///     name: Sensitive::new("John Doe".to_string()),
+///     age: 33,
+/// };
+///
+/// assert_eq!(
+///     format!("{employee:?}"),
+///     "Employee { email: <core/sensitive:REDACTED>, name: <core/sensitive:REDACTED>, age: 33 }"
+/// );
+/// ```
+pub use data_privacy_macros::RedactedDebug;
@@ -0,0 +1,145 @@
+use crate::{Classified, DataClass};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt::Debug;
+
+/// A classified container whose payload falls under several data classes at once.
+///
+/// Real data frequently falls under more than one policy regime, such as a value which is both personal
+/// data and export controlled. This container records every class of its payload, rather than forcing a
+/// choice of a single class up front.
+///
+/// When a single class is needed, as through the [`Classified`] trait, the container reports the
+/// [most restrictive](DataClass::most_restrictive_of) of its classes, with the earliest class winning if
+/// the levels are equal. The [`RedactionEngine`](crate::RedactionEngine) redacts the payload with the
+/// redactor of that class however the value reaches it, see
+/// [`RedactionEngine::effective_class`](crate::RedactionEngine::effective_class).
+///
+/// Containers generated by the [`taxonomy`](crate::taxonomy) attribute can be turned into this container
+/// with their `with_class` method.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::{Classified, DataClass, MultiClassified};
+///
+/// let pii = DataClass::new("corp", "pii").with_level(1);
+/// let export = DataClass::new("corp", "export_controlled").with_level(2);
+///
+/// let value = MultiClassified::new("John", pii.clone()).with_class(export.clone());
+/// assert_eq!(value.data_classes(), [pii, export.clone()]);
+/// assert_eq!(value.data_class(), export);
+/// assert_eq!(format!("{value:?}"), "<corp/pii+corp/export_controlled:REDACTED>");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct MultiClassified<T> {
+    payload: T,
+    data_classes: Vec<DataClass>,
+}
+
+impl<T> MultiClassified<T> {
+    /// Creates a new instance of the classified data container with a single data class.
+    #[must_use]
+    pub fn new(payload: T, data_class: DataClass) -> Self {
+        Self {
+            payload,
+            data_classes: vec![data_class],
+        }
+    }
+
+    /// Adds a data class to the payload.
+    ///
+    /// Adding a class the payload already has has no effect.
+    #[must_use]
+    pub fn with_class(mut self, data_class: DataClass) -> Self {
+        if !self.data_classes.contains(&data_class) {
+            self.data_classes.push(data_class);
+        }
+
+        self
+    }
+
+    /// Returns every data class of the payload, in the order they were added.
+    #[must_use]
+    pub fn data_classes(&self) -> &[DataClass] {
+        &self.data_classes
+    }
+}
+
+impl<T> Classified<T> for MultiClassified<T> {
+    #[track_caller]
+    fn declassify(self) -> T {
+        for data_class in self.data_classes {
+            crate::audit::record_declassification(|| data_class);
+        }
+
+        self.payload
+    }
+
+    fn take_payload(self, _token: crate::__private::PayloadToken) -> T {
+        self.payload
+    }
+
+    fn visit(&self, operation: impl FnOnce(&T)) {
+        operation(&self.payload);
+    }
+
+    fn visit_mut(&mut self, operation: impl FnOnce(&mut T)) {
+        operation(&mut self.payload);
+    }
+
+    fn data_class(&self) -> DataClass {
+        DataClass::most_restrictive_of(&self.data_classes)
+            .cloned()
+            .expect("a multi-classified value always has at least one data class")
+    }
+}
+
+impl<T> Debug for MultiClassified<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("<")?;
+        for (index, data_class) in self.data_classes.iter().enumerate() {
+            if index > 0 {
+                f.write_str("+")?;
+            }
+
+            write!(f, "{data_class}")?;
+        }
+
+        f.write_str(":REDACTED>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::format;
+
+    #[test]
+    fn container_should_hold_payload_and_classes() {
+        let low = DataClass::new("taxonomy", "low").with_level(1);
+        let high = DataClass::new("taxonomy", "high").with_level(2);
+        let mut value = MultiClassified::new(1, low.clone())
+            .with_class(high.clone())
+            .with_class(low.clone());
+
+        value.visit_mut(|payload| *payload += 1);
+        value.visit(|payload| assert_eq!(*payload, 2));
+        assert_eq!(value.data_classes(), [low, high.clone()]);
+        assert_eq!(value.data_class(), high);
+        assert_eq!(
+            format!("{value:?}"),
+            "<taxonomy/low+taxonomy/high:REDACTED>"
+        );
+        assert_eq!(value.declassify(), 2);
+    }
+
+    #[test]
+    fn data_class_should_prefer_earliest_on_equal_levels() {
+        let first = DataClass::new("taxonomy", "first");
+        let second = DataClass::new("taxonomy", "second");
+        let value = MultiClassified::new((), first.clone()).with_class(second);
+        assert_eq!(value.data_class(), first);
+    }
+}
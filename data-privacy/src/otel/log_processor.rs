@@ -0,0 +1,137 @@
+use super::redact_any_value;
+use crate::RedactionEngine;
+use core::time::Duration;
+use opentelemetry::InstrumentationScope;
+use opentelemetry::logs::{AnyValue, LogRecord};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::logs::{LogProcessor, SdkLogRecord};
+use std::sync::Arc;
+
+/// A log processor which redacts the body of log records before passing them to another processor.
+///
+/// The body of each log record is redacted as described in the [module documentation](super), including
+/// the values nested within lists and maps. Map entries whose key is classified as an
+/// [omitted](RedactionEngine::is_omitted) class are dropped.
+///
+/// The OpenTelemetry SDK doesn't let processors rewrite or remove the attributes of a log record, so
+/// records with an attribute which would be changed by redaction, such as one holding a tag or whose key
+/// is classified by the engine's [heuristic classifier](crate::HeuristicClassifier), are dropped rather
+/// than passed on with the classified data in the clear. Classified data should be recorded in the body of
+/// log records, or be redacted before it's recorded as an attribute.
+#[derive(Debug)]
+pub struct RedactingLogProcessor<P> {
+    engine: Arc<RedactionEngine>,
+    inner: P,
+}
+
+impl<P> RedactingLogProcessor<P> {
+    /// Creates a new processor which redacts log records with the given engine before passing them to `inner`.
+    #[must_use]
+    pub const fn new(engine: Arc<RedactionEngine>, inner: P) -> Self {
+        Self { engine, inner }
+    }
+}
+
+impl<P: LogProcessor> LogProcessor for RedactingLogProcessor<P> {
+    fn emit(&self, data: &mut SdkLogRecord, instrumentation: &InstrumentationScope) {
+        if data
+            .attributes_iter()
+            .any(|(key, value)| needs_redaction(&self.engine, key.as_str(), value))
+        {
+            return;
+        }
+
+        if let Some(body) = data.body() {
+            let body = redact_any_value(&self.engine, "", body);
+            data.set_body(body);
+        }
+
+        self.inner.emit(data, instrumentation);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
+
+/// Returns whether redacting an attribute of a log record would change it.
+fn needs_redaction(engine: &RedactionEngine, key: &str, value: &AnyValue) -> bool {
+    engine.classify_field(key).is_some() || redact_any_value(engine, key, value) != *value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::otel::tests::create_engine;
+    use opentelemetry::logs::{Logger, LoggerProvider};
+    use opentelemetry_sdk::logs::SdkLoggerProvider;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default, Clone)]
+    struct Recorder(Arc<Mutex<Vec<AnyValue>>>);
+
+    impl LogProcessor for Recorder {
+        fn emit(&self, data: &mut SdkLogRecord, _instrumentation: &InstrumentationScope) {
+            self.0.lock().unwrap().extend(data.body().cloned());
+        }
+
+        fn force_flush(&self) -> OTelSdkResult {
+            Ok(())
+        }
+    }
+
+    fn create_logger() -> (SdkLoggerProvider, Recorder) {
+        let recorder = Recorder::default();
+        let provider = SdkLoggerProvider::builder()
+            .with_log_processor(RedactingLogProcessor::new(
+                Arc::new(create_engine()),
+                recorder.clone(),
+            ))
+            .build();
+
+        (provider, recorder)
+    }
+
+    #[test]
+    fn log_body_should_be_redacted() {
+        let (provider, recorder) = create_logger();
+        let logger = provider.logger("test");
+        let mut record = logger.create_log_record();
+        record.set_body("user <core/sensitive:jdoe> logged in".into());
+        record.add_attribute("count", 42);
+        record.add_attribute("outcome", "success");
+        logger.emit(record);
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [AnyValue::String("user **** logged in".into())]
+        );
+    }
+
+    #[test]
+    fn log_records_with_classified_attributes_should_be_dropped() {
+        let (provider, recorder) = create_logger();
+        let logger = provider.logger("test");
+
+        let mut record = logger.create_log_record();
+        record.set_body("tagged".into());
+        record.add_attribute("user", "<core/sensitive:jdoe>");
+        logger.emit(record);
+
+        let mut record = logger.create_log_record();
+        record.set_body("classified key".into());
+        record.add_attribute("email", "a@b.c");
+        logger.emit(record);
+
+        assert!(recorder.0.lock().unwrap().is_empty());
+    }
+}
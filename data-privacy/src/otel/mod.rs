@@ -0,0 +1,334 @@
+//! Redaction of OpenTelemetry telemetry before it's exported.
+//!
+//! Attribute values are plain strings by the time they reach the OpenTelemetry SDK, so classified data
+//! must be marked in-process to be found again. The processors of this module use the two-phase pipeline
+//! of [`RedactionEngine::redact_tags`]: classified values are recorded as tags in the engine's
+//! [tag format](RedactionEngine::tag_format), such as `<taxonomy/class:value>` by default, with a
+//! [`SimpleRedactor`](crate::SimpleRedactor) in one of its tagging modes, and the processors replace each tag with the output of the redactor registered for
+//! its data class before the telemetry reaches the next processor or exporter.
+//!
+//! Values implementing [`Extract`], such as classified containers and types deriving
+//! [`Extract`](macro@crate::Extract), can be recorded in their tagged form with [`tagged_value`], so their
+//! classified text is found by the processors without any other preparation.
+//!
+//! Values whose key is mapped to a data class by the engine's
+//! [heuristic classifier](crate::HeuristicClassifier) are redacted as a whole as that class, whether
+//! they're tagged or not.
+//!
+//! This module is available with the `opentelemetry` feature and provides the following processors:
+//!
+//! * [`RedactingSpanProcessor`] redacts the names, status descriptions, and attributes of spans, as well
+//!   as the names and attributes of their events and the attributes of their links.
+//! * [`RedactingLogProcessor`] redacts the body of log records, and drops the records whose attributes
+//!   hold classified data.
+//!
+//! # Example
+//!
+//! ```rust
+//! use data_privacy::core_taxonomy::CoreTaxonomy;
+//! use data_privacy::otel::RedactingSpanProcessor;
+//! use data_privacy::{RedactionEngineBuilder, SimpleRedactor};
+//! use opentelemetry_sdk::trace::{SdkTracerProvider, SimpleSpanProcessor, SpanExporter};
+//!
+//! fn create_provider(exporter: impl SpanExporter + 'static) -> SdkTracerProvider {
+//!     let engine = RedactionEngineBuilder::new()
+//!         .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+//!         .build()
+//!         .into_shared();
+//!
+//!     SdkTracerProvider::builder()
+//!         .with_span_processor(RedactingSpanProcessor::new(engine, SimpleSpanProcessor::new(exporter)))
+//!         .build()
+//! }
+//! ```
+
+mod log_processor;
+mod span_processor;
+
+pub use log_processor::RedactingLogProcessor;
+pub use span_processor::RedactingSpanProcessor;
+
+use crate::{DataClass, Extract, Extractor, RedactionEngine, TagFormat};
+use ::opentelemetry::logs::AnyValue;
+use ::opentelemetry::{Array, Key, KeyValue, StringValue, Value};
+use std::borrow::Cow;
+
+/// Returns the tagged form of a value, ready to be recorded as an attribute or log body.
+///
+/// The plain text of the value is kept as is, while each piece of classified text is wrapped in a tag
+/// in the engine's [tag format](RedactionEngine::tag_format), which the processors of this module redact
+/// with the redactor registered for its data class. The tag delimiters within classified text are
+/// escaped, so the text can't end its tag early and let part of itself through unredacted.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::RedactionEngineBuilder;
+/// use data_privacy::core_taxonomy::Sensitive;
+/// use data_privacy::otel::tagged_value;
+/// use opentelemetry::KeyValue;
+///
+/// let engine = RedactionEngineBuilder::new().build();
+/// let name = Sensitive::new("jdoe".to_string());
+/// let attribute = KeyValue::new("user", tagged_value(&engine, &name));
+/// assert_eq!(attribute.value.as_str(), "<core/sensitive:jdoe>");
+/// ```
+#[must_use]
+pub fn tagged_value(engine: &RedactionEngine, value: &(impl Extract + ?Sized)) -> Value {
+    let mut extractor = TaggingExtractor {
+        tag_format: engine.tag_format(),
+        output: String::new(),
+    };
+    value.extract(&mut extractor);
+    Value::String(extractor.output.into())
+}
+
+/// An extractor which wraps classified text in tags.
+struct TaggingExtractor<'a> {
+    tag_format: &'a TagFormat,
+    output: String,
+}
+
+impl Extractor for TaggingExtractor<'_> {
+    fn write_plain(&mut self, text: &str) {
+        self.output.push_str(text);
+    }
+
+    fn write_classified(&mut self, data_class: &DataClass, text: &str) {
+        let output = &mut self.output;
+        self.tag_format
+            .tag(data_class, "", text, &mut |s| output.push_str(s));
+    }
+}
+
+/// Redacts a string found under the given key.
+fn redact_str(engine: &RedactionEngine, key: &str, text: &str) -> String {
+    let mut output = String::new();
+    match engine.classify_field(key) {
+        Some(data_class) => engine.redact(data_class, text, |s| output.push_str(s)),
+        None => engine.redact_tags(text, |s| output.push_str(s)),
+    }
+
+    output
+}
+
+/// Redacts a piece of text which isn't associated with a key, such as the name of a span.
+fn redact_text(engine: &RedactionEngine, text: &mut Cow<'static, str>) {
+    let mut output = String::new();
+    engine.redact_tags(text.as_ref(), |s| output.push_str(s));
+    *text = Cow::Owned(output);
+}
+
+/// Redacts a list of attributes, dropping those whose key is classified as an omitted class.
+fn redact_attributes(engine: &RedactionEngine, attributes: &mut Vec<KeyValue>) {
+    attributes.retain(|attribute| {
+        engine
+            .classify_field(attribute.key.as_str())
+            .is_none_or(|data_class| !engine.is_omitted(data_class))
+    });
+
+    for attribute in attributes {
+        attribute.value = redact_value(engine, attribute.key.as_str(), &attribute.value);
+    }
+}
+
+/// Redacts the value of an attribute.
+fn redact_value(engine: &RedactionEngine, key: &str, value: &Value) -> Value {
+    if engine.classify_field(key).is_some() {
+        return Value::String(redact_str(engine, key, &value.as_str()).into());
+    }
+
+    match value {
+        Value::String(s) => Value::String(redact_str(engine, key, s.as_str()).into()),
+        Value::Array(Array::String(values)) => Value::Array(Array::String(
+            values
+                .iter()
+                .map(|s| StringValue::from(redact_str(engine, key, s.as_str())))
+                .collect(),
+        )),
+        _ => value.clone(),
+    }
+}
+
+/// Redacts a value of a log record, recursing into lists and maps.
+fn redact_any_value(engine: &RedactionEngine, key: &str, value: &AnyValue) -> AnyValue {
+    match value {
+        AnyValue::String(s) => AnyValue::String(redact_str(engine, key, s.as_str()).into()),
+        AnyValue::ListAny(values) => AnyValue::ListAny(Box::new(
+            values
+                .iter()
+                .map(|value| redact_any_value(engine, key, value))
+                .collect(),
+        )),
+        AnyValue::Map(entries) => AnyValue::Map(Box::new(
+            entries
+                .iter()
+                .filter(|(key, _)| {
+                    engine
+                        .classify_field(key.as_str())
+                        .is_none_or(|data_class| !engine.is_omitted(data_class))
+                })
+                .map(|(key, value)| {
+                    let value = if engine.classify_field(key.as_str()).is_some() {
+                        let text = any_value_text(value);
+                        AnyValue::String(redact_str(engine, key.as_str(), &text).into())
+                    } else {
+                        redact_any_value(engine, key.as_str(), value)
+                    };
+
+                    (Key::clone(key), value)
+                })
+                .collect(),
+        )),
+        _ => value.clone(),
+    }
+}
+
+/// Returns the text of a scalar log record value, or an empty string for other values.
+fn any_value_text(value: &AnyValue) -> String {
+    match value {
+        AnyValue::String(s) => s.as_str().to_owned(),
+        AnyValue::Int(i) => i.to_string(),
+        AnyValue::Double(d) => d.to_string(),
+        AnyValue::Boolean(b) => b.to_string(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::{CoreTaxonomy, Sensitive};
+    use crate::{HeuristicClassifier, RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+
+    pub(super) fn create_engine() -> RedactionEngine {
+        RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+            )
+            .omit_class(&CoreTaxonomy::UnknownSensitivity.data_class())
+            .set_heuristic_classifier(
+                HeuristicClassifier::new()
+                    .add_pattern("email", &CoreTaxonomy::Sensitive.data_class())
+                    .add_pattern("secret", &CoreTaxonomy::UnknownSensitivity.data_class()),
+            )
+            .build()
+    }
+
+    #[test]
+    fn attributes_should_be_redacted() {
+        let engine = create_engine();
+        let mut attributes = vec![
+            KeyValue::new("user", "name <core/sensitive:jdoe>"),
+            KeyValue::new("email", "jdoe@contoso.com"),
+            KeyValue::new("secret", "hunter2"),
+            KeyValue::new("count", 42),
+            KeyValue::new(
+                "tags",
+                Value::Array(Array::String(vec![
+                    "<core/sensitive:ab>".into(),
+                    "cd".into(),
+                ])),
+            ),
+        ];
+
+        redact_attributes(&engine, &mut attributes);
+        assert_eq!(
+            attributes,
+            [
+                KeyValue::new("user", "name ****"),
+                KeyValue::new("email", "****************"),
+                KeyValue::new("count", 42),
+                KeyValue::new(
+                    "tags",
+                    Value::Array(Array::String(vec!["**".into(), "cd".into()]))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn tagged_values_should_be_redacted() {
+        let engine = create_engine();
+        let mut attributes = vec![
+            KeyValue::new(
+                "user",
+                tagged_value(&engine, &Sensitive::new("jdoe".to_string())),
+            ),
+            KeyValue::new(
+                "odd",
+                tagged_value(&engine, &Sensitive::new("a>b<c".to_string())),
+            ),
+            KeyValue::new(
+                "nested",
+                tagged_value(&engine, &Sensitive::new("<x>".to_string())),
+            ),
+        ];
+
+        assert_eq!(attributes[1].value.as_str(), r"<core/sensitive:a\>b\<c>");
+        assert_eq!(attributes[2].value.as_str(), r"<core/sensitive:\<x\>>");
+
+        redact_attributes(&engine, &mut attributes);
+        assert_eq!(
+            attributes,
+            [
+                KeyValue::new("user", "****"),
+                KeyValue::new("odd", "*****"),
+                KeyValue::new("nested", "***"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tagged_values_should_use_the_engine_tag_format() {
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+            )
+            .set_tag_format(TagFormat::new().with_delimiters("[[", "]]"))
+            .build();
+
+        let value = tagged_value(&engine, &Sensitive::new("a]b".to_string()));
+        assert_eq!(value.as_str(), r"[[core/sensitive:a\]b]]");
+        assert_eq!(redact_value(&engine, "user", &value).as_str(), "***");
+    }
+
+    #[test]
+    fn log_values_should_be_redacted_recursively() {
+        let engine = create_engine();
+        let value = AnyValue::Map(Box::new(
+            [
+                (Key::new("email"), AnyValue::String("a@b.c".into())),
+                (Key::new("secret"), AnyValue::Int(7)),
+                (
+                    Key::new("list"),
+                    AnyValue::ListAny(Box::new(vec![
+                        AnyValue::String("<core/sensitive:abc>".into()),
+                        AnyValue::Int(1),
+                    ])),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        ));
+
+        let AnyValue::Map(entries) = redact_any_value(&engine, "", &value) else {
+            panic!("expected a map");
+        };
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[&Key::new("email")],
+            AnyValue::String("*****".into())
+        );
+        assert_eq!(
+            entries[&Key::new("list")],
+            AnyValue::ListAny(Box::new(vec![
+                AnyValue::String("***".into()),
+                AnyValue::Int(1)
+            ]))
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use super::{redact_attributes, redact_text};
+use crate::RedactionEngine;
+use core::time::Duration;
+use opentelemetry::Context;
+use opentelemetry::trace::Status;
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::trace::{Span, SpanData, SpanProcessor};
+use std::sync::Arc;
+
+/// A span processor which redacts spans before passing them to another processor.
+///
+/// The attributes of each ended span, and of each of its events and links, are redacted as described in
+/// the [module documentation](super). Attributes whose key is classified as an [omitted](RedactionEngine::is_omitted)
+/// class are dropped. The tags found in the names of spans and events, and in the description of an
+/// error status, are redacted as well.
+///
+/// Unlike log records, each span processor receives its own copy of a span, so this processor wraps the
+/// processor which should receive the redacted spans, typically the one feeding the exporter.
+#[derive(Debug)]
+pub struct RedactingSpanProcessor<P> {
+    engine: Arc<RedactionEngine>,
+    inner: P,
+}
+
+impl<P> RedactingSpanProcessor<P> {
+    /// Creates a new processor which redacts spans with the given engine before passing them to `inner`.
+    #[must_use]
+    pub const fn new(engine: Arc<RedactionEngine>, inner: P) -> Self {
+        Self { engine, inner }
+    }
+}
+
+impl<P: SpanProcessor> SpanProcessor for RedactingSpanProcessor<P> {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        self.inner.on_start(span, cx);
+    }
+
+    fn on_end(&self, mut span: SpanData) {
+        redact_text(&self.engine, &mut span.name);
+        if let Status::Error { description } = &mut span.status {
+            redact_text(&self.engine, description);
+        }
+
+        redact_attributes(&self.engine, &mut span.attributes);
+        for event in &mut span.events.events {
+            redact_text(&self.engine, &mut event.name);
+            redact_attributes(&self.engine, &mut event.attributes);
+        }
+
+        for link in &mut span.links.links {
+            redact_attributes(&self.engine, &mut link.attributes);
+        }
+
+        self.inner.on_end(span);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::otel::tests::create_engine;
+    use opentelemetry::KeyValue;
+    use opentelemetry::trace::{
+        Link, Span as _, SpanContext, SpanId, TraceFlags, TraceId, TraceState, Tracer,
+        TracerProvider,
+    };
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default, Clone)]
+    struct Recorder(Arc<Mutex<Vec<SpanData>>>);
+
+    impl SpanProcessor for Recorder {
+        fn on_start(&self, _span: &mut Span, _cx: &Context) {}
+
+        fn on_end(&self, span: SpanData) {
+            self.0.lock().unwrap().push(span);
+        }
+
+        fn force_flush(&self) -> OTelSdkResult {
+            Ok(())
+        }
+
+        fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+            Ok(())
+        }
+    }
+
+    fn record(build: impl FnOnce(&SdkTracerProvider)) -> SpanData {
+        let recorder = Recorder::default();
+        let provider = SdkTracerProvider::builder()
+            .with_span_processor(RedactingSpanProcessor::new(
+                Arc::new(create_engine()),
+                recorder.clone(),
+            ))
+            .build();
+
+        build(&provider);
+
+        let mut spans = recorder.0.lock().unwrap().clone();
+        assert_eq!(spans.len(), 1);
+        spans.remove(0)
+    }
+
+    #[test]
+    fn span_attributes_should_be_redacted() {
+        let span = record(|provider| {
+            let mut span = provider.tracer("test").start("login");
+            span.set_attribute(KeyValue::new("user", "<core/sensitive:jdoe>"));
+            span.set_attribute(KeyValue::new("secret", "hunter2"));
+            span.add_event("lookup", vec![KeyValue::new("email", "a@b.c")]);
+            span.end();
+        });
+
+        assert_eq!(span.attributes, [KeyValue::new("user", "****")]);
+        assert_eq!(
+            span.events.events[0].attributes,
+            [KeyValue::new("email", "*****")]
+        );
+    }
+
+    #[test]
+    fn span_names_should_be_redacted() {
+        let span = record(|provider| {
+            let mut span = provider.tracer("test").start("login <core/sensitive:jdoe>");
+            span.add_event("lookup <core/sensitive:abc>", vec![]);
+            span.end();
+        });
+
+        assert_eq!(span.name, "login ****");
+        assert_eq!(span.events.events[0].name, "lookup ***");
+    }
+
+    #[test]
+    fn span_status_should_be_redacted() {
+        let span = record(|provider| {
+            let mut span = provider.tracer("test").start("login");
+            span.set_status(Status::error("no user <core/sensitive:jdoe>"));
+            span.end();
+        });
+
+        assert_eq!(span.status, Status::error("no user ****"));
+    }
+
+    #[test]
+    fn span_link_attributes_should_be_redacted() {
+        let span = record(|provider| {
+            let context = SpanContext::new(
+                TraceId::from(1_u128),
+                SpanId::from(1_u64),
+                TraceFlags::SAMPLED,
+                false,
+                TraceState::default(),
+            );
+            let link = Link::new(
+                context,
+                vec![KeyValue::new("user", "<core/sensitive:jdoe>")],
+                0,
+            );
+            let tracer = provider.tracer("test");
+            let mut span = tracer
+                .span_builder("login")
+                .with_links(vec![link])
+                .start(&tracer);
+            span.end();
+        });
+
+        assert_eq!(
+            span.links.links[0].attributes,
+            [KeyValue::new("user", "****")]
+        );
+    }
+}
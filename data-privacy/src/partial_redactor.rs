@@ -0,0 +1,197 @@
+use crate::{DataClass, Redactor};
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+/// A redactor which keeps the first and last few characters of a string and masks the rest.
+///
+/// Completely masking values can make logs useless for support tooling, where seeing part of a value
+/// is often enough to tell values apart. This redactor keeps a configurable number of leading and
+/// trailing characters, replacing each character in between with a mask character, so that the length
+/// of the value is preserved.
+///
+/// Values which are too short to have anything masked between the kept characters are masked entirely,
+/// so that no value is ever passed through unchanged.
+///
+/// Delimiters, such as the `@` and `.` of email addresses or the `-` of card numbers, can be preserved
+/// with [`keep_delimiters`](Self::keep_delimiters), which keeps the shape of structured identifiers
+/// recognizable while masking their content. Different data classes can use different settings
+/// by registering separate instances with the [`RedactionEngineBuilder`](crate::RedactionEngineBuilder).
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::{DataClass, PartialRedactor, Redactor};
+///
+/// let redactor = PartialRedactor::new().keep_first(2).keep_last(12);
+///
+/// let mut output = String::new();
+/// redactor.redact(&DataClass::new("corp", "email"), "john@example.com", &mut |s| output.push_str(s));
+/// assert_eq!(output, "jo**@example.com");
+///
+/// let redactor = PartialRedactor::new().keep_first(1).keep_delimiters("@.");
+///
+/// let mut output = String::new();
+/// redactor.redact(&DataClass::new("corp", "email"), "john@example.com", &mut |s| output.push_str(s));
+/// assert_eq!(output, "j***@*******.***");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartialRedactor {
+    keep_first: usize,
+    keep_last: usize,
+    mask: char,
+    delimiters: String,
+}
+
+impl PartialRedactor {
+    /// Creates a new instance which masks every character with `*`.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            keep_first: 0,
+            keep_last: 0,
+            mask: '*',
+            delimiters: String::new(),
+        }
+    }
+
+    /// Sets the number of leading characters to keep.
+    #[must_use]
+    pub const fn keep_first(mut self, count: usize) -> Self {
+        self.keep_first = count;
+        self
+    }
+
+    /// Sets the number of trailing characters to keep.
+    #[must_use]
+    pub const fn keep_last(mut self, count: usize) -> Self {
+        self.keep_last = count;
+        self
+    }
+
+    /// Sets the character used to mask characters.
+    #[must_use]
+    pub const fn with_mask(mut self, mask: char) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    /// Sets the characters which are kept rather than masked wherever they appear in a value.
+    ///
+    /// Delimiters still count toward the characters kept with [`keep_first`](Self::keep_first) and
+    /// [`keep_last`](Self::keep_last).
+    #[must_use]
+    pub fn keep_delimiters(mut self, delimiters: impl Into<String>) -> Self {
+        self.delimiters = delimiters.into();
+        self
+    }
+}
+
+impl Default for PartialRedactor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Redactor for PartialRedactor {
+    fn redact(&self, _data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
+        let len = value.chars().count();
+        let (keep_first, keep_last) = if self.keep_first.saturating_add(self.keep_last) < len {
+            (self.keep_first, self.keep_last)
+        } else {
+            (0, 0)
+        };
+
+        let redacted: String = value
+            .chars()
+            .enumerate()
+            .map(|(index, c)| {
+                if index < keep_first || index >= len - keep_last || self.delimiters.contains(c) {
+                    c
+                } else {
+                    self.mask
+                }
+            })
+            .collect();
+
+        output(&redacted);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::CoreTaxonomy;
+
+    fn redact_to_string(redactor: &PartialRedactor, value: &str) -> String {
+        let mut output = String::new();
+        redactor.redact(&CoreTaxonomy::Sensitive.data_class(), value, &mut |s| {
+            output.push_str(s);
+        });
+        output
+    }
+
+    #[test]
+    fn redact_should_keep_prefix_and_suffix() {
+        let redactor = PartialRedactor::new().keep_last(4);
+        assert_eq!(
+            redact_to_string(&redactor, "4111-1111-1111-1234"),
+            "***************1234"
+        );
+
+        let redactor = PartialRedactor::new().keep_first(1).keep_last(1);
+        assert_eq!(redact_to_string(&redactor, "secret"), "s****t");
+    }
+
+    #[test]
+    fn redact_should_mask_short_values_entirely() {
+        let redactor = PartialRedactor::new().keep_first(2).keep_last(2);
+        assert_eq!(redact_to_string(&redactor, "abcd"), "****");
+        assert_eq!(redact_to_string(&redactor, "abcde"), "ab*de");
+        assert_eq!(redact_to_string(&redactor, ""), "");
+    }
+
+    #[test]
+    fn redact_should_not_overflow_with_huge_counts() {
+        let redactor = PartialRedactor::new().keep_first(usize::MAX).keep_last(1);
+        assert_eq!(redact_to_string(&redactor, "secret"), "******");
+
+        let redactor = PartialRedactor::new()
+            .keep_first(usize::MAX)
+            .keep_last(usize::MAX);
+        assert_eq!(redact_to_string(&redactor, "secret"), "******");
+    }
+
+    #[test]
+    fn redact_should_handle_multibyte_characters() {
+        let redactor = PartialRedactor::new()
+            .keep_first(1)
+            .keep_last(1)
+            .with_mask('#');
+        assert_eq!(redact_to_string(&redactor, "こんにちは"), "こ###は");
+    }
+
+    #[test]
+    fn redact_should_keep_delimiters() {
+        let redactor = PartialRedactor::new().keep_last(4).keep_delimiters("-");
+        assert_eq!(
+            redact_to_string(&redactor, "4111-1111-1111-1234"),
+            "****-****-****-1234"
+        );
+
+        let redactor = PartialRedactor::new()
+            .keep_first(1)
+            .keep_last(1)
+            .keep_delimiters("@.");
+        assert_eq!(redact_to_string(&redactor, "jo@x.io"), "j*@*.*o");
+        assert_eq!(redact_to_string(&redactor, "a."), "*.");
+    }
+
+    #[test]
+    fn default_should_mask_everything() {
+        assert_eq!(PartialRedactor::default(), PartialRedactor::new());
+        assert_eq!(redact_to_string(&PartialRedactor::default(), "abc"), "***");
+    }
+
+    #[cfg(feature = "std")]
+    crate::redactor_conformance_tests!(PartialRedactor::new().keep_first(1).keep_last(1));
+}
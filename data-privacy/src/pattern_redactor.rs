@@ -0,0 +1,261 @@
+use crate::{DataClass, Redactor};
+use regex::{Captures, Regex};
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+static EMAIL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}")
+        .expect("the email pattern is valid")
+});
+
+static CREDIT_CARD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?:\d[ -]?){12,18}\d\b").expect("the credit card pattern is valid")
+});
+
+static SSN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b\d{3}-\d{2}-\d{4}\b").expect("the SSN pattern is valid"));
+
+/// A redactor which replaces the parts of a string matching regular expressions, passing the rest through.
+///
+/// Classified containers protect values which are known to be sensitive, but free-form text, such as a
+/// comment or an error message, can still embed sensitive data. This redactor scans text for patterns,
+/// such as email addresses or credit card numbers, and replaces each match with a fixed text. It can be
+/// registered for the data classes of free-form text, or as the fallback redactor of an engine, as a
+/// safety net.
+///
+/// Patterns are applied in the order they were added, each one to the output of the previous one.
+/// Replacements can refer to capture groups, as described in [`Regex::replace_all`]. Patterns can be
+/// given a validator, which must accept a match for it to be replaced; the credit card pattern uses
+/// this to only replace numbers with a valid Luhn checksum.
+///
+/// Besides redacting classified data, the redactor can [scrub](Self::scrub) free-form messages which
+/// carry no classification at all.
+///
+/// Since text which doesn't match any pattern is passed through unchanged, this redactor only removes
+/// the kinds of data it's been told about.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::{DataClass, PatternRedactor, Redactor};
+///
+/// let redactor = PatternRedactor::new().with_common_patterns();
+///
+/// let mut output = String::new();
+/// redactor.redact(
+///     &DataClass::new("corp", "comment"),
+///     "contact jdoe@contoso.com, SSN 123-45-6789",
+///     &mut |s| output.push_str(s),
+/// );
+/// assert_eq!(output, "contact [email], SSN [ssn]");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PatternRedactor {
+    patterns: Vec<(Regex, String, Option<Validator>)>,
+}
+
+/// Decides whether a match of a pattern should be replaced.
+type Validator = fn(&str) -> bool;
+
+impl PatternRedactor {
+    /// Creates a new instance without any patterns, which passes text through unchanged.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            patterns: Vec::new(),
+        }
+    }
+
+    /// Adds a pattern, replacing each of its matches with the given replacement.
+    #[must_use]
+    pub fn with_pattern(mut self, pattern: Regex, replacement: impl Into<String>) -> Self {
+        self.patterns.push((pattern, replacement.into(), None));
+        self
+    }
+
+    /// Adds a pattern, replacing each of its matches accepted by the validator with the given replacement.
+    ///
+    /// The validator receives the text of the whole match. Matches it rejects are left unchanged.
+    #[must_use]
+    pub fn with_validated_pattern(
+        mut self,
+        pattern: Regex,
+        replacement: impl Into<String>,
+        validator: fn(&str) -> bool,
+    ) -> Self {
+        self.patterns
+            .push((pattern, replacement.into(), Some(validator)));
+        self
+    }
+
+    /// Adds a pattern replacing email addresses with `[email]`.
+    #[must_use]
+    pub fn with_email(self) -> Self {
+        self.with_pattern(EMAIL.clone(), "[email]")
+    }
+
+    /// Adds a pattern replacing credit card numbers, with 13 to 19 digits optionally grouped by spaces or dashes,
+    /// with `[credit_card]`.
+    ///
+    /// Only numbers with a valid Luhn checksum are replaced, so that other long numbers, such as order
+    /// numbers, are left alone.
+    #[must_use]
+    pub fn with_credit_card(self) -> Self {
+        self.with_validated_pattern(CREDIT_CARD.clone(), "[credit_card]", is_luhn_valid)
+    }
+
+    /// Adds a pattern replacing US social security numbers, written as `123-45-6789`, with `[ssn]`.
+    #[must_use]
+    pub fn with_ssn(self) -> Self {
+        self.with_pattern(SSN.clone(), "[ssn]")
+    }
+
+    /// Adds the patterns for email addresses, credit card numbers, and social security numbers.
+    #[must_use]
+    pub fn with_common_patterns(self) -> Self {
+        self.with_email().with_credit_card().with_ssn()
+    }
+
+    /// Replaces the matches of all the patterns in a piece of text.
+    ///
+    /// This lets the redactor serve as a standalone scrubber for free-form messages, such as log messages
+    /// assembled from unclassified strings. The text is borrowed back when no pattern matches.
+    #[must_use]
+    pub fn scrub<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut text = Cow::Borrowed(text);
+        for (pattern, replacement, validator) in &self.patterns {
+            let replaced = validator.map_or_else(
+                || pattern.replace_all(&text, replacement.as_str()),
+                |validator| {
+                    pattern.replace_all(&text, |captures: &Captures<'_>| {
+                        let mut expanded = String::new();
+                        if validator(&captures[0]) {
+                            captures.expand(replacement, &mut expanded);
+                        } else {
+                            expanded.push_str(&captures[0]);
+                        }
+                        expanded
+                    })
+                },
+            );
+
+            if let Cow::Owned(replaced) = replaced {
+                text = Cow::Owned(replaced);
+            }
+        }
+
+        text
+    }
+}
+
+impl Redactor for PatternRedactor {
+    fn redact(&self, _data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
+        output(&self.scrub(value));
+    }
+}
+
+/// Checks the Luhn checksum of the digits in a piece of text, ignoring anything else.
+fn is_luhn_valid(text: &str) -> bool {
+    let sum: u32 = text
+        .bytes()
+        .rev()
+        .filter(u8::is_ascii_digit)
+        .map(|digit| u32::from(digit - b'0'))
+        .enumerate()
+        .map(|(i, digit)| match (i % 2, digit * 2) {
+            (0, _) => digit,
+            (_, doubled) if doubled > 9 => doubled - 9,
+            (_, doubled) => doubled,
+        })
+        .sum();
+
+    sum.is_multiple_of(10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::CoreTaxonomy;
+
+    fn redact_to_string(redactor: &PatternRedactor, value: &str) -> String {
+        let mut output = String::new();
+        redactor.redact(&CoreTaxonomy::Sensitive.data_class(), value, &mut |s| {
+            output.push_str(s);
+        });
+        output
+    }
+
+    #[test]
+    fn common_patterns_should_be_replaced() {
+        let redactor = PatternRedactor::new().with_common_patterns();
+        assert_eq!(
+            redact_to_string(
+                &redactor,
+                "mail a.b+c@mail.contoso.co.uk, card 4111 1111 1111 1111, ssn 123-45-6789."
+            ),
+            "mail [email], card [credit_card], ssn [ssn]."
+        );
+        assert_eq!(
+            redact_to_string(&redactor, "order 12345 shipped"),
+            "order 12345 shipped"
+        );
+    }
+
+    #[test]
+    fn credit_cards_should_require_a_valid_checksum() {
+        let redactor = PatternRedactor::new().with_credit_card();
+        assert_eq!(
+            redact_to_string(
+                &redactor,
+                "card 4111-1111-1111-1111, order 1234 5678 9012 3456"
+            ),
+            "card [credit_card], order 1234 5678 9012 3456"
+        );
+
+        assert!(is_luhn_valid("4111111111111111"));
+        assert!(is_luhn_valid("5500 0000 0000 0004"));
+        assert!(!is_luhn_valid("4111111111111112"));
+    }
+
+    #[test]
+    fn scrub_should_borrow_unmatched_text() {
+        let redactor = PatternRedactor::new().with_common_patterns();
+        assert!(matches!(
+            redactor.scrub("nothing to see here"),
+            Cow::Borrowed("nothing to see here")
+        ));
+        assert_eq!(
+            redactor.scrub("failed login for jdoe@contoso.com"),
+            "failed login for [email]"
+        );
+    }
+
+    #[test]
+    fn validated_patterns_should_expand_captures() {
+        let redactor = PatternRedactor::new().with_validated_pattern(
+            Regex::new(r"id=(\d+)").unwrap(),
+            "id=<$1>",
+            |text| text.len() > 4,
+        );
+        assert_eq!(redact_to_string(&redactor, "id=1 id=22"), "id=1 id=<22>");
+    }
+
+    #[test]
+    fn custom_patterns_should_apply_in_order() {
+        let redactor = PatternRedactor::new()
+            .with_pattern(Regex::new(r"token=(\w+)").unwrap(), "token=<$1>")
+            .with_pattern(Regex::new(r"<\w+>").unwrap(), "***");
+        assert_eq!(
+            redact_to_string(&redactor, "token=abc&x=1"),
+            "token=***&x=1"
+        );
+    }
+
+    #[test]
+    fn empty_redactor_should_pass_through() {
+        assert_eq!(
+            redact_to_string(&PatternRedactor::new(), "a@b.com"),
+            "a@b.com"
+        );
+    }
+}
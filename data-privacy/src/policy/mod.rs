@@ -0,0 +1,293 @@
+//! Enforcement of the purposes for which classified data may be declassified.
+//!
+//! Besides the unconditional `declassify` method, the containers generated by the [`taxonomy`](crate::taxonomy)
+//! attribute have a `declassify_for` method, which takes the [`Purpose`] the caller needs the data for, and a
+//! `declassify_with_justification` method, which also takes a free-form justification such as a ticket number.
+//! Before handing the payload over, these methods consult the process-wide [`DeclassificationPolicy`], which
+//! decides per data class and purpose whether the data may be declassified. Policies can also log the
+//! requests they evaluate, giving security teams a single point to enforce and review how sensitive data
+//! is used.
+//!
+//! No policy is installed by default, in which case all requests are allowed. Install a policy once at
+//! startup with [`set_declassification_policy`]. Declassifications allowed by the policy are reported to
+//! the [audit sink](crate::audit) like any other.
+//!
+//! # Example
+//!
+//! ```rust
+//! use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+//! use data_privacy::policy::{
+//!     set_declassification_policy, DeclassificationRequest, PolicyDecision, PolicyError, Purpose,
+//! };
+//!
+//! let policy = |request: &DeclassificationRequest<'_>| {
+//!     match (request.data_class() == &CoreTaxonomy::Sensitive.data_class(), request.purpose()) {
+//!         (false, _) => PolicyDecision::Allow,
+//!         (true, Purpose::Support) => PolicyDecision::RequireJustification,
+//!         (true, _) => PolicyDecision::Deny,
+//!     }
+//! };
+//! assert!(set_declassification_policy(Box::new(policy)).is_ok());
+//!
+//! let email = Sensitive::new("john@contoso.com");
+//! assert!(matches!(
+//!     email.declassify_for(Purpose::Analytics),
+//!     Err(PolicyError::Denied { .. })
+//! ));
+//!
+//! let email = Sensitive::new("john@contoso.com");
+//! assert!(matches!(
+//!     email.declassify_for(Purpose::Support),
+//!     Err(PolicyError::JustificationRequired { .. })
+//! ));
+//!
+//! let email = Sensitive::new("john@contoso.com");
+//! assert_eq!(
+//!     email.declassify_with_justification(Purpose::Support, "ticket 4711"),
+//!     Ok("john@contoso.com")
+//! );
+//! ```
+
+mod policy_error;
+mod purpose;
+
+pub use policy_error::PolicyError;
+pub use purpose::Purpose;
+
+use crate::DataClass;
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+use core::panic::Location;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+/// The installed policy, stored as a raw pointer to a leaked box so it can be set without `std`.
+static POLICY: AtomicPtr<Box<dyn DeclassificationPolicy>> = AtomicPtr::new(core::ptr::null_mut());
+
+/// The decision of a [`DeclassificationPolicy`] about a request to declassify data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PolicyDecision {
+    /// The data may be declassified.
+    Allow,
+
+    /// The data may not be declassified.
+    Deny,
+
+    /// The data may only be declassified if the caller gives a justification which isn't blank.
+    RequireJustification,
+}
+
+/// A request to declassify data for a given purpose, evaluated by a [`DeclassificationPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeclassificationRequest<'a> {
+    data_class: &'a DataClass,
+    purpose: Purpose,
+    justification: Option<&'a str>,
+    location: &'static Location<'static>,
+}
+
+impl<'a> DeclassificationRequest<'a> {
+    /// Creates a new request to declassify data of the given class for the given purpose.
+    #[must_use]
+    #[track_caller]
+    pub const fn new(data_class: &'a DataClass, purpose: Purpose) -> Self {
+        Self {
+            data_class,
+            purpose,
+            justification: None,
+            location: Location::caller(),
+        }
+    }
+
+    /// Sets the justification given by the caller.
+    #[must_use]
+    pub const fn with_justification(mut self, justification: &'a str) -> Self {
+        self.justification = Some(justification);
+        self
+    }
+
+    /// Returns the data class of the data to declassify.
+    #[must_use]
+    pub const fn data_class(&self) -> &'a DataClass {
+        self.data_class
+    }
+
+    /// Returns the purpose the data is declassified for.
+    #[must_use]
+    pub const fn purpose(&self) -> Purpose {
+        self.purpose
+    }
+
+    /// Returns the justification given by the caller, if any.
+    #[must_use]
+    pub const fn justification(&self) -> Option<&'a str> {
+        self.justification
+    }
+
+    /// Returns the source location where the data is declassified.
+    #[must_use]
+    pub const fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+}
+
+/// Decides whether classified data may be declassified for a given purpose.
+///
+/// Policies are invoked synchronously on the thread doing the declassification, so they
+/// should be quick and must not panic.
+///
+/// This trait is implemented for functions taking a request and returning a decision.
+pub trait DeclassificationPolicy: Send + Sync {
+    /// Evaluates a request to declassify data.
+    fn evaluate(&self, request: &DeclassificationRequest<'_>) -> PolicyDecision;
+}
+
+impl<F> DeclassificationPolicy for F
+where
+    F: Fn(&DeclassificationRequest<'_>) -> PolicyDecision + Send + Sync,
+{
+    fn evaluate(&self, request: &DeclassificationRequest<'_>) -> PolicyDecision {
+        self(request)
+    }
+}
+
+/// Installs the process-wide declassification policy.
+///
+/// The policy can only be set once, typically when the application starts.
+///
+/// # Errors
+///
+/// Returns the given policy back if a policy has already been set.
+pub fn set_declassification_policy(
+    policy: Box<dyn DeclassificationPolicy>,
+) -> Result<(), Box<dyn DeclassificationPolicy>> {
+    let policy = Box::into_raw(Box::new(policy));
+    POLICY
+        .compare_exchange(
+            core::ptr::null_mut(),
+            policy,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        )
+        .map(|_| ())
+        .map_err(|_current| {
+            // SAFETY: The pointer was just created by `Box::into_raw` and wasn't stored.
+            *unsafe { Box::from_raw(policy) }
+        })
+}
+
+/// Returns the installed declassification policy, if any.
+fn declassification_policy() -> Option<&'static dyn DeclassificationPolicy> {
+    let policy = POLICY.load(Ordering::Acquire);
+
+    // SAFETY: The pointer is only ever set from a leaked box in `set_declassification_policy`, and is never freed.
+    unsafe { policy.as_ref() }.map(|policy| &**policy)
+}
+
+/// Checks with the installed policy, if any, that data of the given class may be declassified for the given purpose.
+///
+/// A justification which is empty or only whitespace is treated as missing.
+#[doc(hidden)]
+#[track_caller]
+pub fn check_declassification(
+    data_class: impl FnOnce() -> DataClass,
+    purpose: Purpose,
+    justification: Option<&str>,
+) -> Result<(), PolicyError> {
+    let Some(policy) = declassification_policy() else {
+        return Ok(());
+    };
+
+    let justification = justification.filter(|justification| !justification.trim().is_empty());
+    let data_class = data_class();
+    let mut request = DeclassificationRequest::new(&data_class, purpose);
+    request.justification = justification;
+
+    match policy.evaluate(&request) {
+        PolicyDecision::Allow => Ok(()),
+        PolicyDecision::RequireJustification if justification.is_some() => Ok(()),
+        PolicyDecision::RequireJustification => Err(PolicyError::JustificationRequired {
+            data_class,
+            purpose,
+        }),
+        PolicyDecision::Deny => Err(PolicyError::Denied {
+            data_class,
+            purpose,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::taxonomy;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    #[taxonomy(policy_test, serde = false)]
+    enum PolicyTestTaxonomy {
+        Public,
+        Restricted,
+        Secret,
+    }
+
+    fn evaluate(request: &DeclassificationRequest<'_>) -> PolicyDecision {
+        if request.data_class() == &PolicyTestTaxonomy::Secret.data_class() {
+            PolicyDecision::Deny
+        } else if request.data_class() == &PolicyTestTaxonomy::Restricted.data_class()
+            && request.purpose() != Purpose::Compliance
+        {
+            assert_eq!(request.location().file(), file!());
+            PolicyDecision::RequireJustification
+        } else {
+            PolicyDecision::Allow
+        }
+    }
+
+    // This is the only test that installs the process-wide policy.
+    #[test]
+    fn declassify_for_should_enforce_installed_policy() {
+        assert!(set_declassification_policy(Box::new(evaluate)).is_ok());
+        assert!(
+            set_declassification_policy(Box::new(|_: &DeclassificationRequest<'_>| {
+                PolicyDecision::Allow
+            }))
+            .is_err()
+        );
+
+        assert_eq!(Public::new(1).declassify_for(Purpose::Analytics), Ok(1));
+
+        assert_eq!(
+            Restricted::new(2).declassify_for(Purpose::Support),
+            Err(PolicyError::JustificationRequired {
+                data_class: PolicyTestTaxonomy::Restricted.data_class(),
+                purpose: Purpose::Support,
+            })
+        );
+        assert_eq!(
+            Restricted::new(2).declassify_with_justification(Purpose::Support, "ticket 4711"),
+            Ok(2)
+        );
+        for justification in ["", " \t\n"] {
+            assert_eq!(
+                Restricted::new(2).declassify_with_justification(Purpose::Support, justification),
+                Err(PolicyError::JustificationRequired {
+                    data_class: PolicyTestTaxonomy::Restricted.data_class(),
+                    purpose: Purpose::Support,
+                })
+            );
+        }
+        assert_eq!(
+            Restricted::new(2).declassify_for(Purpose::Compliance),
+            Ok(2)
+        );
+
+        let err = Secret::new(3)
+            .declassify_with_justification(Purpose::Other("curiosity"), "because")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "declassifying policy_test/secret data for curiosity is denied by policy"
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use super::Purpose;
+use crate::DataClass;
+use core::fmt::{Display, Formatter};
+
+/// Errors reported when the [declassification policy](super::DeclassificationPolicy) refuses to declassify data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PolicyError {
+    /// The policy doesn't allow data of the given class to be declassified for the given purpose.
+    Denied {
+        /// The data class of the data.
+        data_class: DataClass,
+
+        /// The purpose the data was to be declassified for.
+        purpose: Purpose,
+    },
+
+    /// The policy only allows data of the given class to be declassified for the given purpose with a justification.
+    JustificationRequired {
+        /// The data class of the data.
+        data_class: DataClass,
+
+        /// The purpose the data was to be declassified for.
+        purpose: Purpose,
+    },
+}
+
+impl Display for PolicyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Denied {
+                data_class,
+                purpose,
+            } => write!(
+                f,
+                "declassifying {data_class} data for {purpose} is denied by policy"
+            ),
+            Self::JustificationRequired {
+                data_class,
+                purpose,
+            } => write!(
+                f,
+                "declassifying {data_class} data for {purpose} requires a justification"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for PolicyError {}
@@ -0,0 +1,52 @@
+use core::fmt::{Display, Formatter};
+
+/// The reason given by a caller for declassifying data.
+///
+/// Purposes are passed to `declassify_for` on the containers generated by the [`taxonomy`](crate::taxonomy)
+/// attribute, and are checked against the process-wide [`DeclassificationPolicy`](super::DeclassificationPolicy).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Purpose {
+    /// Providing the service the data was collected for.
+    Service,
+
+    /// Helping the person the data is about, such as when handling a support request.
+    Support,
+
+    /// Investigating a defect or an incident.
+    Debugging,
+
+    /// Investigating or responding to a security threat.
+    Security,
+
+    /// Meeting a legal or regulatory obligation, such as a data subject access request.
+    Compliance,
+
+    /// Producing aggregated statistics.
+    Analytics,
+
+    /// An application-defined purpose, identified by its name.
+    Other(&'static str),
+}
+
+impl Purpose {
+    /// Returns the name of the purpose, such as `support`.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Service => "service",
+            Self::Support => "support",
+            Self::Debugging => "debugging",
+            Self::Security => "security",
+            Self::Compliance => "compliance",
+            Self::Analytics => "analytics",
+            Self::Other(name) => name,
+        }
+    }
+}
+
+impl Display for Purpose {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
@@ -0,0 +1,375 @@
+use crate::{Classified, RedactionEngine};
+use core::fmt::{Debug, Display};
+use std::borrow::Cow;
+use std::io::Write;
+
+/// Receives the fields of structured records produced by a [`RecordBuilder`].
+///
+/// Sinks bridge redacted records to whatever logging or telemetry system an application uses.
+/// By the time a sink sees a value, any classified data within it has already been redacted.
+pub trait RecordSink {
+    /// Receives a single named field of a record.
+    fn write_field(&mut self, name: &str, value: &str);
+
+    /// Called once all the fields of a record have been written.
+    fn end_record(&mut self) {}
+}
+
+impl<S: RecordSink + ?Sized> RecordSink for &mut S {
+    fn write_field(&mut self, name: &str, value: &str) {
+        (**self).write_field(name, value);
+    }
+
+    fn end_record(&mut self) {
+        (**self).end_record();
+    }
+}
+
+/// Collects the fields of records as owned name/value pairs, which is mostly useful for testing.
+impl RecordSink for Vec<(String, String)> {
+    fn write_field(&mut self, name: &str, value: &str) {
+        self.push((name.to_string(), value.to_string()));
+    }
+}
+
+/// A sink which writes each record as a line of `logfmt`-style `name=value` pairs.
+///
+/// Values containing spaces, quotes, equal signs, backslashes, or control characters are quoted, with
+/// quotes and backslashes escaped by a backslash and control characters written as escape sequences
+/// such as `\n`, so that a value can't break a record across lines or forge other fields. Characters of
+/// field names which would be ambiguous in the output are replaced with underscores. Write errors are
+/// ignored since there is nowhere to report them from within a logging path.
+#[derive(Debug)]
+pub struct LogfmtSink<W> {
+    writer: W,
+    first_field: bool,
+}
+
+impl<W: Write> LogfmtSink<W> {
+    /// Creates a new sink writing to the given writer.
+    #[must_use]
+    pub const fn new(writer: W) -> Self {
+        Self {
+            writer,
+            first_field: true,
+        }
+    }
+
+    /// Consumes the sink, returning the underlying writer.
+    #[must_use]
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> RecordSink for LogfmtSink<W> {
+    fn write_field(&mut self, name: &str, value: &str) {
+        if !self.first_field {
+            _ = self.writer.write_all(b" ");
+        }
+        self.first_field = false;
+
+        if name.is_empty() {
+            _ = self.writer.write_all(b"_");
+        }
+        for c in name.chars() {
+            let c = if needs_quoting(c) { '_' } else { c };
+            _ = self.writer.write_all(c.encode_utf8(&mut [0; 4]).as_bytes());
+        }
+        _ = self.writer.write_all(b"=");
+
+        if !value.is_empty() && !value.chars().any(needs_quoting) {
+            _ = self.writer.write_all(value.as_bytes());
+            return;
+        }
+
+        _ = self.writer.write_all(b"\"");
+        for c in value.chars() {
+            _ = match c {
+                '"' => self.writer.write_all(b"\\\""),
+                '\\' => self.writer.write_all(b"\\\\"),
+                '\n' => self.writer.write_all(b"\\n"),
+                '\r' => self.writer.write_all(b"\\r"),
+                '\t' => self.writer.write_all(b"\\t"),
+                c if c.is_control() => write!(self.writer, "\\u{{{:x}}}", u32::from(c)),
+                c => self.writer.write_all(c.encode_utf8(&mut [0; 4]).as_bytes()),
+            };
+        }
+        _ = self.writer.write_all(b"\"");
+    }
+
+    fn end_record(&mut self) {
+        self.first_field = true;
+        _ = writeln!(self.writer);
+        _ = self.writer.flush();
+    }
+}
+
+/// Indicates whether a character can't appear as is in an unquoted `logfmt` name or value.
+fn needs_quoting(c: char) -> bool {
+    c.is_whitespace() || c.is_control() || matches!(c, '"' | '=' | '\\')
+}
+
+/// Builds structured records whose classified values are redacted by a [`RedactionEngine`].
+///
+/// Each field of a record has a name and a value. Values can be formatted with their [`Display`] or
+/// [`Debug`] traits, or can be classified containers, in which case they are redacted according to the
+/// engine's configuration. Fields whose data class the engine [omits](RedactionEngine::is_omitted)
+/// are dropped from the record altogether. Plain values are checked against the engine's
+/// heuristic classifier, if any, and are redacted when their field name looks sensitive.
+///
+/// Once all fields have been added, the record is emitted to a [`RecordSink`].
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+/// use data_privacy::{RecordBuilder, RedactionEngineBuilder, SimpleRedactor};
+///
+/// let engine = RedactionEngineBuilder::new()
+///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+///     .build();
+///
+/// let name = Sensitive::new("John Doe".to_string());
+///
+/// let mut sink = Vec::new();
+/// RecordBuilder::new(&engine)
+///     .debug("event", &"Employee created")
+///     .classified("name", &name)
+///     .display("age", &33)
+///     .emit(&mut sink);
+///
+/// assert_eq!(sink[0], ("event".to_string(), "\"Employee created\"".to_string()));
+/// assert_eq!(sink[1], ("name".to_string(), "********".to_string()));
+/// assert_eq!(sink[2], ("age".to_string(), "33".to_string()));
+/// ```
+#[derive(Debug)]
+pub struct RecordBuilder<'a> {
+    engine: &'a RedactionEngine,
+    fields: Vec<(Cow<'static, str>, String)>,
+}
+
+impl<'a> RecordBuilder<'a> {
+    /// Creates a new record builder which redacts values using the given engine.
+    #[must_use]
+    pub const fn new(engine: &'a RedactionEngine) -> Self {
+        Self {
+            engine,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Adds a field whose value is formatted with its [`Display`] trait.
+    #[must_use]
+    pub fn display(self, name: impl Into<Cow<'static, str>>, value: &impl Display) -> Self {
+        self.unclassified(name.into(), &value.to_string())
+    }
+
+    /// Adds a field whose value is formatted with its [`Debug`] trait.
+    #[must_use]
+    pub fn debug(self, name: impl Into<Cow<'static, str>>, value: &impl Debug) -> Self {
+        self.unclassified(name.into(), &format!("{value:?}"))
+    }
+
+    /// Adds a field holding classified data, formatted with the payload's [`Display`] trait and then redacted.
+    #[must_use]
+    pub fn classified<C, T>(mut self, name: impl Into<Cow<'static, str>>, value: &C) -> Self
+    where
+        C: Classified<T>,
+        T: Display,
+    {
+        if !self.engine.is_omitted(&value.data_class()) {
+            let mut redacted = String::new();
+            self.engine
+                .display_redacted(value, |s| redacted.push_str(s));
+            self.fields.push((name.into(), redacted));
+        }
+
+        self
+    }
+
+    /// Adds a field holding classified data, formatted with the payload's [`Debug`] trait and then redacted.
+    #[must_use]
+    pub fn classified_debug<C, T>(mut self, name: impl Into<Cow<'static, str>>, value: &C) -> Self
+    where
+        C: Classified<T>,
+        T: Debug,
+    {
+        if !self.engine.is_omitted(&value.data_class()) {
+            let mut redacted = String::new();
+            self.engine.debug_redacted(value, |s| redacted.push_str(s));
+            self.fields.push((name.into(), redacted));
+        }
+
+        self
+    }
+
+    /// Sends all the fields of the record to the given sink.
+    pub fn emit(self, sink: &mut (impl RecordSink + ?Sized)) {
+        for (name, value) in &self.fields {
+            sink.write_field(name, value);
+        }
+
+        sink.end_record();
+    }
+
+    fn unclassified(mut self, name: Cow<'static, str>, value: &str) -> Self {
+        if self
+            .engine
+            .classify_field(&name)
+            .is_some_and(|data_class| self.engine.is_omitted(data_class))
+        {
+            return self;
+        }
+
+        let mut redacted = String::new();
+        self.engine
+            .redact_unclassified(&name, value, |s| redacted.push_str(s));
+        self.fields.push((name, redacted));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::{CoreTaxonomy, Insensitive, Sensitive};
+    use crate::{HeuristicClassifier, RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+
+    fn create_engine() -> RedactionEngine {
+        RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+            )
+            .add_class_redactor(
+                &CoreTaxonomy::Insensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+            )
+            .omit_class(&CoreTaxonomy::UnknownSensitivity.data_class())
+            .set_heuristic_classifier(
+                HeuristicClassifier::new()
+                    .add_pattern("password", &CoreTaxonomy::Sensitive.data_class()),
+            )
+            .build()
+    }
+
+    fn field(name: &str, value: &str) -> (String, String) {
+        (name.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn emit_should_write_all_fields_in_order() {
+        let engine = create_engine();
+        let mut sink = Vec::new();
+
+        RecordBuilder::new(&engine)
+            .display("count", &3)
+            .debug("label", &"x")
+            .classified("user", &Sensitive::new("jdoe"))
+            .classified_debug("team", &Insensitive::new("ops"))
+            .emit(&mut sink);
+
+        assert_eq!(
+            sink,
+            vec![
+                field("count", "3"),
+                field("label", "\"x\""),
+                field("user", "****"),
+                field("team", "\"ops\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn omitted_classes_should_drop_fields() {
+        let engine = create_engine();
+        let mut sink = Vec::new();
+
+        RecordBuilder::new(&engine)
+            .classified(
+                "notes",
+                &crate::core_taxonomy::UnknownSensitivity::new("hidden"),
+            )
+            .display("count", &3)
+            .emit(&mut sink);
+
+        assert_eq!(sink, vec![field("count", "3")]);
+    }
+
+    #[test]
+    fn heuristics_should_redact_plain_values() {
+        let engine = create_engine();
+        let mut sink = Vec::new();
+
+        RecordBuilder::new(&engine)
+            .display("password", &"hunter2")
+            .display("user", &"jdoe")
+            .emit(&mut sink);
+
+        assert_eq!(
+            sink,
+            vec![field("password", "*******"), field("user", "jdoe")]
+        );
+    }
+
+    #[test]
+    fn heuristics_should_drop_fields_of_omitted_classes() {
+        let engine = RedactionEngineBuilder::new()
+            .omit_class(&CoreTaxonomy::Sensitive.data_class())
+            .set_heuristic_classifier(HeuristicClassifier::with_common_patterns(
+                &CoreTaxonomy::Sensitive.data_class(),
+            ))
+            .build();
+
+        let mut sink = Vec::new();
+        RecordBuilder::new(&engine)
+            .display("api_key", &"abc")
+            .display("user", &"jdoe")
+            .emit(&mut sink);
+
+        assert_eq!(sink, vec![field("user", "jdoe")]);
+    }
+
+    #[test]
+    fn logfmt_sink_should_format_records() {
+        let engine = create_engine();
+        let mut sink = LogfmtSink::new(Vec::new());
+
+        RecordBuilder::new(&engine)
+            .display("event", &"user created")
+            .display("empty", &"")
+            .display("quote", &"a\"b")
+            .classified("user", &Sensitive::new("jdoe"))
+            .emit(&mut sink);
+
+        RecordBuilder::new(&engine)
+            .display("count", &1)
+            .emit(&mut sink);
+
+        let output = String::from_utf8(sink.into_inner()).unwrap();
+        assert_eq!(
+            output,
+            "event=\"user created\" empty=\"\" quote=\"a\\\"b\" user=****\ncount=1\n"
+        );
+    }
+
+    #[test]
+    fn logfmt_sink_should_escape_injected_fields() {
+        let mut sink = LogfmtSink::new(Vec::new());
+
+        sink.write_field("msg", "ok\nlevel=error user=admin");
+        sink.write_field("path", "C:\\tmp\t\u{1b}[31m");
+        sink.write_field("user id=\"x\"\n", "1");
+        sink.write_field("", "unnamed");
+        sink.end_record();
+
+        let output = String::from_utf8(sink.into_inner()).unwrap();
+        assert_eq!(
+            output,
+            "msg=\"ok\\nlevel=error user=admin\" path=\"C:\\\\tmp\\t\\u{1b}[31m\" \
+             user_id__x__=1 _=unnamed\n"
+        );
+        assert_eq!(output.lines().count(), 1);
+    }
+}
@@ -0,0 +1,71 @@
+/// Formats a string, redacting classified arguments with the given redaction engine.
+///
+/// This works like [`format!`], except that the first argument is the engine to redact with, and every
+/// positional argument must implement [`Extract`](crate::Extract). Classified containers are redacted by
+/// the engine, while plain values such as strings and numbers are inserted unchanged. Values which mix
+/// both, such as types deriving [`Extract`](macro@crate::Extract), have only their classified fields redacted.
+///
+/// The engine can be given as anything which dereferences to a
+/// [`RedactionEngine`](crate::RedactionEngine), such as a reference or an [`Arc`](std::sync::Arc).
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+/// use data_privacy::{RedactionEngineBuilder, SimpleRedactor, redact_fmt};
+///
+/// let engine = RedactionEngineBuilder::new()
+///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+///     .build();
+///
+/// let user = Sensitive::new("John");
+/// let attempts = 3;
+/// assert_eq!(redact_fmt!(engine, "user {} failed {} times", user, attempts), "user **** failed 3 times");
+/// ```
+#[macro_export]
+macro_rules! redact_fmt {
+    ($engine:expr, $fmt:literal $(,)?) => {{
+        let _: &$crate::RedactionEngine = &$engine;
+        ::std::format!($fmt)
+    }};
+    ($engine:expr, $fmt:literal $(, $arg:expr)* $(,)?) => {{
+        let engine: &$crate::RedactionEngine = &$engine;
+        ::std::format!(
+            $fmt
+            $(, engine.display(&$arg))*
+        )
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core_taxonomy::{CoreTaxonomy, Insensitive, Sensitive};
+    use crate::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+
+    #[test]
+    fn redact_fmt_should_redact_only_classified_arguments() {
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')),
+            )
+            .add_class_redactor(
+                &CoreTaxonomy::Insensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+            )
+            .build()
+            .into_shared();
+
+        let user = Sensitive::new("John".to_string());
+        let ip = Insensitive::new("10.0.0.1");
+        assert_eq!(
+            redact_fmt!(engine, "user {} from {} on port {}", user, ip, 443_u16),
+            "user #### from 10.0.0.1 on port 443"
+        );
+        assert_eq!(
+            redact_fmt!(&*engine, "{} {}", "plain", Some(Sensitive::new(7)),),
+            "plain Some(#)"
+        );
+        assert_eq!(redact_fmt!(engine, "no arguments"), "no arguments");
+    }
+}
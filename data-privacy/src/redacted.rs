@@ -0,0 +1,105 @@
+use crate::{Extract, RedactionEngine};
+use core::fmt::{Debug, Display, Formatter};
+
+/// Formats a value by redacting it with a [`RedactionEngine`].
+///
+/// This adapter implements [`Display`] and [`Debug`], both of which produce the redacted form of the value,
+/// and [`Serialize`](serde::Serialize) with the `serde` feature, which serializes the redacted form as a
+/// string. This lets values be used directly with `format!`, format-based logging, or serde structs without
+/// first redacting into a buffer. Redaction is performed lazily, each time the adapter is used.
+///
+/// The value can be anything implementing [`Extract`]: classified containers are redacted by the engine,
+/// plain values such as strings and numbers are kept unchanged, and types deriving
+/// [`Extract`](macro@crate::Extract) have only their classified fields redacted.
+///
+/// You create instances of this type with [`RedactionEngine::display`].
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+/// use data_privacy::{RedactionEngineBuilder, SimpleRedactor};
+///
+/// let engine = RedactionEngineBuilder::new()
+///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+///     .build();
+///
+/// let name = Sensitive::new("John");
+/// assert_eq!(format!("name={}", engine.display(&name)), "name=****");
+/// assert_eq!(format!("{:?}", engine.display(&name)), "****");
+/// ```
+pub struct Redacted<'a, T: ?Sized> {
+    engine: &'a RedactionEngine,
+    value: &'a T,
+}
+
+impl<'a, T: Extract + ?Sized> Redacted<'a, T> {
+    /// Creates a new adapter which redacts the given value with the given engine.
+    #[must_use]
+    pub(crate) const fn new(engine: &'a RedactionEngine, value: &'a T) -> Self {
+        Self { engine, value }
+    }
+}
+
+impl<T: ?Sized> Clone for Redacted<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for Redacted<'_, T> {}
+
+impl<T: Extract + ?Sized> Display for Redacted<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut result = Ok(());
+        self.engine.extract_redacted(self.value, |s| {
+            if result.is_ok() {
+                result = f.write_str(s);
+            }
+        });
+
+        result
+    }
+}
+
+impl<T: Extract + ?Sized> Debug for Redacted<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Extract + ?Sized> serde::Serialize for Redacted<'_, T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core_taxonomy::{CoreTaxonomy, Sensitive};
+    use crate::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+
+    #[test]
+    fn display_should_redact_value() {
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::ReplaceAndTag('#')),
+            )
+            .build();
+
+        let value = Sensitive::new(1234);
+        let redacted = engine.display(&value);
+        assert_eq!(format!("[{redacted}]"), "[<core/sensitive:####>]");
+        assert_eq!(redacted.to_string(), "<core/sensitive:####>");
+        assert_eq!(format!("{redacted:?}"), "<core/sensitive:####>");
+        assert_eq!(engine.display("plain").to_string(), "plain");
+
+        #[cfg(feature = "serde")]
+        assert_eq!(
+            serde_json::to_string(&[redacted, redacted]).unwrap(),
+            r#"["<core/sensitive:####>","<core/sensitive:####>"]"#
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{Extract, RedactionEngine};
+use core::fmt::{self, Debug, Formatter, Write};
+use std::sync::Arc;
+
+/// Wraps a [`fmt::Write`] sink, redacting the classified values written to it.
+///
+/// This is the counterpart of [`RedactingWriter`](crate::RedactingWriter) for sinks which receive text,
+/// such as a [`String`] or a [`Formatter`]. Plain text is written with [`write_plain`](Self::write_plain)
+/// and classified values with [`write_classified`](Self::write_classified), which writes their redacted
+/// form.
+///
+/// The adapter also implements [`fmt::Write`], passing text through unchanged, so it can be used with
+/// [`write!`]. Text written this way is not redacted.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+/// use data_privacy::{RedactingFmtWriter, RedactionEngineBuilder, SimpleRedactor};
+///
+/// let engine = RedactionEngineBuilder::new()
+///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+///     .build()
+///     .into_shared();
+///
+/// let mut writer = RedactingFmtWriter::new(engine, String::new());
+/// writer.write_plain("user=").unwrap();
+/// writer.write_classified(&Sensitive::new("jdoe")).unwrap();
+///
+/// assert_eq!(writer.into_inner(), "user=****");
+/// ```
+pub struct RedactingFmtWriter<W> {
+    engine: Arc<RedactionEngine>,
+    writer: W,
+}
+
+impl<W> RedactingFmtWriter<W> {
+    /// Creates a new writer which redacts classified values before passing them on to the given writer.
+    #[must_use]
+    pub const fn new(engine: Arc<RedactionEngine>, writer: W) -> Self {
+        Self { engine, writer }
+    }
+
+    /// Returns a reference to the underlying writer.
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Anything written directly to the underlying writer bypasses redaction.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consumes the adapter, returning the underlying writer.
+    #[must_use]
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> RedactingFmtWriter<W> {
+    /// Writes text which holds no sensitive data, unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by the underlying writer.
+    pub fn write_plain(&mut self, text: &str) -> fmt::Result {
+        self.writer.write_str(text)
+    }
+
+    /// Writes the redacted form of a value.
+    ///
+    /// Plain values are written unchanged, while classified data is redacted according to the engine's
+    /// configuration. See [`RedactionEngine::extract_redacted`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by the underlying writer, in which case part of the redacted value
+    /// may already have been written.
+    pub fn write_classified(&mut self, value: &(impl Extract + ?Sized)) -> fmt::Result {
+        write!(self.writer, "{}", self.engine.display(value))
+    }
+}
+
+impl<W: Write> Write for RedactingFmtWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.writer.write_str(s)
+    }
+}
+
+impl<W: Debug> Debug for RedactingFmtWriter<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedactingFmtWriter")
+            .field("writer", &self.writer)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::{CoreTaxonomy, Sensitive};
+    use crate::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+
+    #[test]
+    fn classified_values_should_be_redacted() {
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')),
+            )
+            .build()
+            .into_shared();
+
+        let mut writer = RedactingFmtWriter::new(engine, String::new());
+        writer.write_plain("names=").unwrap();
+        writer
+            .write_classified(&[Sensitive::new("ab"), Sensitive::new("cde")])
+            .unwrap();
+        write!(writer, " count={}", 2).unwrap();
+        writer.write_classified("plain").unwrap();
+
+        assert_eq!(writer.get_ref(), "names=[##, ###] count=2plain");
+        assert_eq!(
+            format!("{writer:?}"),
+            "RedactingFmtWriter { writer: \"names=[##, ###] count=2plain\", .. }"
+        );
+    }
+}
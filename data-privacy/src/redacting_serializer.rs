@@ -0,0 +1,473 @@
+use crate::{RedactionEngine, SerializeRedacted};
+use core::fmt::Display;
+use serde::ser::{
+    Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+    SerializeTupleStruct, SerializeTupleVariant, Serializer,
+};
+
+/// A serializer adapter which redacts every classified container serialized through it.
+///
+/// This wraps any [`Serializer`], such as that of `serde_json`, and forwards everything to it, except that
+/// classified containers serialize as a string produced by the [`RedactionEngine`] for their data class,
+/// like they do within a [`SerializeRedacted`](crate::SerializeRedacted). Other values pass through unchanged.
+///
+/// This is useful when the code driving serialization only accepts a serializer, such as a logging
+/// framework that serializes records itself. When the value is at hand,
+/// [`RedactionEngine::to_serializable`] is simpler.
+///
+/// Each value handed to the serializer, such as the fields of a struct or the elements of a sequence, is
+/// serialized within a [`SerializeRedacted`] carrying the engine, so the engine is only in effect while
+/// that value is being serialized. The value the serializer itself is given is serialized by its own
+/// [`Serialize`] implementation before the serializer sees any of it, so a classified container handed
+/// directly to the serializer isn't redacted. Wrap such values with [`RedactionEngine::to_serializable`]
+/// instead.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+/// use data_privacy::{RedactingSerializer, RedactionEngineBuilder, SimpleRedactor};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Employee {
+///     name: Sensitive<String>,
+///     age: u32,
+/// }
+///
+/// let engine = RedactionEngineBuilder::new()
+///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+///     .build();
+///
+/// let employee = Employee { name: Sensitive::new("John".to_string()), age: 33 };
+///
+/// let mut json = Vec::new();
+/// let mut serializer = serde_json::Serializer::new(&mut json);
+/// employee.serialize(RedactingSerializer::new(&engine, &mut serializer)).unwrap();
+/// assert_eq!(json, br#"{"name":"****","age":33}"#);
+/// ```
+#[derive(Debug)]
+pub struct RedactingSerializer<'a, S> {
+    inner: S,
+    engine: &'a RedactionEngine,
+}
+
+impl<'a, S> RedactingSerializer<'a, S> {
+    /// Creates a new serializer which redacts classified containers with the given engine before passing them
+    /// to `serializer`.
+    #[must_use]
+    pub const fn new(engine: &'a RedactionEngine, serializer: S) -> Self {
+        Self {
+            inner: serializer,
+            engine,
+        }
+    }
+}
+
+/// A compound serializer which redacts the classified containers within each of its elements.
+#[derive(Debug)]
+pub struct RedactingCompound<'a, C> {
+    inner: C,
+    engine: &'a RedactionEngine,
+}
+
+/// Forwards scalar serializer methods to the inner serializer.
+macro_rules! forward_scalars {
+    ($($method:ident($ty:ty)),*) => {
+        $(
+            fn $method(self, v: $ty) -> Result<S::Ok, S::Error> {
+                self.inner.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'a, S: Serializer> Serializer for RedactingSerializer<'a, S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = RedactingCompound<'a, S::SerializeSeq>;
+    type SerializeTuple = RedactingCompound<'a, S::SerializeTuple>;
+    type SerializeTupleStruct = RedactingCompound<'a, S::SerializeTupleStruct>;
+    type SerializeTupleVariant = RedactingCompound<'a, S::SerializeTupleVariant>;
+    type SerializeMap = RedactingCompound<'a, S::SerializeMap>;
+    type SerializeStruct = RedactingCompound<'a, S::SerializeStruct>;
+    type SerializeStructVariant = RedactingCompound<'a, S::SerializeStructVariant>;
+
+    forward_scalars!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8])
+    );
+
+    fn serialize_none(self) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_none()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        self.inner
+            .serialize_some(&SerializeRedacted::new(self.engine, value))
+    }
+
+    fn serialize_unit(self) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_unit()
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_unit_struct(name)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner
+            .serialize_unit_variant(name, variant_index, variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner
+            .serialize_newtype_struct(name, &SerializeRedacted::new(self.engine, value))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_newtype_variant(
+            name,
+            variant_index,
+            variant,
+            &SerializeRedacted::new(self.engine, value),
+        )
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+        let inner = self.inner.serialize_seq(len)?;
+        Ok(RedactingCompound {
+            inner,
+            engine: self.engine,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, S::Error> {
+        let inner = self.inner.serialize_tuple(len)?;
+        Ok(RedactingCompound {
+            inner,
+            engine: self.engine,
+        })
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        let inner = self.inner.serialize_tuple_struct(name, len)?;
+        Ok(RedactingCompound {
+            inner,
+            engine: self.engine,
+        })
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        let inner = self
+            .inner
+            .serialize_tuple_variant(name, variant_index, variant, len)?;
+        Ok(RedactingCompound {
+            inner,
+            engine: self.engine,
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        let inner = self.inner.serialize_map(len)?;
+        Ok(RedactingCompound {
+            inner,
+            engine: self.engine,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        let inner = self.inner.serialize_struct(name, len)?;
+        Ok(RedactingCompound {
+            inner,
+            engine: self.engine,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        let inner = self
+            .inner
+            .serialize_struct_variant(name, variant_index, variant, len)?;
+        Ok(RedactingCompound {
+            inner,
+            engine: self.engine,
+        })
+    }
+
+    fn collect_str<T: Display + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        self.inner.collect_str(value)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+impl<C: SerializeSeq> SerializeSeq for RedactingCompound<'_, C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+        self.inner
+            .serialize_element(&SerializeRedacted::new(self.engine, value))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: SerializeTuple> SerializeTuple for RedactingCompound<'_, C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+        self.inner
+            .serialize_element(&SerializeRedacted::new(self.engine, value))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: SerializeTupleStruct> SerializeTupleStruct for RedactingCompound<'_, C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+        self.inner
+            .serialize_field(&SerializeRedacted::new(self.engine, value))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: SerializeTupleVariant> SerializeTupleVariant for RedactingCompound<'_, C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+        self.inner
+            .serialize_field(&SerializeRedacted::new(self.engine, value))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: SerializeMap> SerializeMap for RedactingCompound<'_, C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), C::Error> {
+        self.inner
+            .serialize_key(&SerializeRedacted::new(self.engine, key))
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+        self.inner
+            .serialize_value(&SerializeRedacted::new(self.engine, value))
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: SerializeStruct> SerializeStruct for RedactingCompound<'_, C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), C::Error> {
+        self.inner
+            .serialize_field(key, &SerializeRedacted::new(self.engine, value))
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), C::Error> {
+        self.inner.skip_field(key)
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+impl<C: SerializeStructVariant> SerializeStructVariant for RedactingCompound<'_, C> {
+    type Ok = C::Ok;
+    type Error = C::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), C::Error> {
+        self.inner
+            .serialize_field(key, &SerializeRedacted::new(self.engine, value))
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), C::Error> {
+        self.inner.skip_field(key)
+    }
+
+    fn end(self) -> Result<C::Ok, C::Error> {
+        self.inner.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::{CoreTaxonomy, Insensitive, Sensitive};
+    use crate::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+    use std::collections::BTreeMap;
+
+    #[derive(serde::Serialize)]
+    struct Record {
+        name: Sensitive<String>,
+        team: Insensitive<&'static str>,
+        visits: BTreeMap<&'static str, Sensitive<u32>>,
+        tags: Vec<(u8, Sensitive<&'static str>)>,
+        contact: Contact,
+    }
+
+    #[derive(serde::Serialize)]
+    enum Contact {
+        Email { address: Sensitive<&'static str> },
+    }
+
+    fn create_engine() -> RedactionEngine {
+        RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+            )
+            .add_class_redactor(
+                &CoreTaxonomy::Insensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+            )
+            .build()
+    }
+
+    fn to_json(value: &impl Serialize) -> String {
+        let engine = create_engine();
+        let mut json = Vec::new();
+        let mut serializer = serde_json::Serializer::new(&mut json);
+        value
+            .serialize(RedactingSerializer::new(&engine, &mut serializer))
+            .unwrap();
+        String::from_utf8(json).unwrap()
+    }
+
+    #[test]
+    fn classified_values_should_be_redacted() {
+        let record = Record {
+            name: Sensitive::new("John".to_string()),
+            team: Insensitive::new("ops"),
+            visits: BTreeMap::from([("home", Sensitive::new(12))]),
+            tags: vec![(1, Sensitive::new("abc"))],
+            contact: Contact::Email {
+                address: Sensitive::new("a@b.c"),
+            },
+        };
+
+        assert_eq!(
+            to_json(&record),
+            r#"{"name":"****","team":"ops","visits":{"home":"**"},"tags":[[1,"***"]],"contact":{"Email":{"address":"*****"}}}"#
+        );
+
+        // the engine is no longer in effect once serialization completes
+        assert_eq!(serde_json::to_string(&record.name).unwrap(), r#""John""#);
+    }
+
+    #[test]
+    fn nested_top_level_classified_values_should_be_redacted() {
+        assert_eq!(to_json(&Some(Sensitive::new(42))), r#""**""#);
+        assert_eq!(to_json(&[Sensitive::new("John")]), r#"["****"]"#);
+        assert_eq!(to_json(&7), "7");
+
+        let engine = create_engine();
+        let mut json = Vec::new();
+        let mut serializer = serde_json::Serializer::new(&mut json);
+        engine
+            .to_serializable(&Sensitive::new("John"))
+            .serialize(RedactingSerializer::new(&engine, &mut serializer))
+            .unwrap();
+        assert_eq!(json, br#""****""#);
+    }
+
+    #[test]
+    fn abandoned_serializers_should_not_affect_later_serialization() {
+        let engine = create_engine();
+        let mut json = Vec::new();
+        let mut serializer = serde_json::Serializer::new(&mut json);
+        let abandon = |serializer| {
+            let mut seq = RedactingSerializer::new(&engine, serializer)
+                .serialize_seq(None)
+                .unwrap();
+            SerializeSeq::serialize_element(&mut seq, &Sensitive::new("John")).unwrap();
+        };
+        abandon(&mut serializer);
+
+        assert_eq!(
+            serde_json::to_string(&Sensitive::new("John")).unwrap(),
+            r#""John""#
+        );
+    }
+}
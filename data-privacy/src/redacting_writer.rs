@@ -0,0 +1,166 @@
+use crate::{Extract, RedactionEngine};
+use core::fmt::{Debug, Formatter};
+use std::io::{self, Write};
+use std::sync::Arc;
+
+/// Wraps an [`io::Write`] sink, redacting the classified values written to it.
+///
+/// The writer accepts plain text with [`write_plain`](Self::write_plain) and classified values with
+/// [`write_classified`](Self::write_classified). Classified values can be anything implementing
+/// [`Extract`], and are written in their redacted form, with each classified piece of data redacted by
+/// the redactor registered for its class. This lets redaction be plugged directly into existing log
+/// writers, without first redacting each value into a buffer.
+///
+/// The adapter also implements [`io::Write`], passing bytes through unchanged, so it can be used with
+/// [`write!`] and anything else expecting a writer. Bytes written this way are not redacted.
+///
+/// See [`RedactingFmtWriter`](crate::RedactingFmtWriter) for the [`fmt::Write`](core::fmt::Write)
+/// counterpart of this adapter.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+/// use data_privacy::{RedactingWriter, RedactionEngineBuilder, SimpleRedactor};
+///
+/// let engine = RedactionEngineBuilder::new()
+///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+///     .build()
+///     .into_shared();
+///
+/// let mut writer = RedactingWriter::new(engine, Vec::new());
+/// writer.write_plain("user=").unwrap();
+/// writer.write_classified(&Sensitive::new("jdoe")).unwrap();
+/// writer.write_plain("\n").unwrap();
+///
+/// assert_eq!(writer.into_inner(), b"user=****\n");
+/// ```
+pub struct RedactingWriter<W> {
+    engine: Arc<RedactionEngine>,
+    writer: W,
+}
+
+impl<W> RedactingWriter<W> {
+    /// Creates a new writer which redacts classified values before passing them on to the given writer.
+    #[must_use]
+    pub const fn new(engine: Arc<RedactionEngine>, writer: W) -> Self {
+        Self { engine, writer }
+    }
+
+    /// Returns a reference to the underlying writer.
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Anything written directly to the underlying writer bypasses redaction.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consumes the adapter, returning the underlying writer.
+    #[must_use]
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> RedactingWriter<W> {
+    /// Writes text which holds no sensitive data, unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by the underlying writer.
+    pub fn write_plain(&mut self, text: &str) -> io::Result<()> {
+        self.writer.write_all(text.as_bytes())
+    }
+
+    /// Writes the redacted form of a value.
+    ///
+    /// Plain values are written unchanged, while classified data is redacted according to the engine's
+    /// configuration. See [`RedactionEngine::extract_redacted`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by the underlying writer, in which case part of the redacted value
+    /// may already have been written.
+    pub fn write_classified(&mut self, value: &(impl Extract + ?Sized)) -> io::Result<()> {
+        write!(self.writer, "{}", self.engine.display(value))
+    }
+}
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl<W: Debug> Debug for RedactingWriter<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RedactingWriter")
+            .field("writer", &self.writer)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::{CoreTaxonomy, Insensitive, Sensitive};
+    use crate::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+
+    #[test]
+    fn classified_values_should_be_redacted() {
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')),
+            )
+            .add_class_redactor(
+                &CoreTaxonomy::Insensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+            )
+            .build()
+            .into_shared();
+
+        let mut writer = RedactingWriter::new(engine, Vec::new());
+        writer.write_plain("a=").unwrap();
+        writer
+            .write_classified(&(Sensitive::new("xyz"), Insensitive::new(7)))
+            .unwrap();
+        write!(writer, " n={}", 3).unwrap();
+        writer.flush().unwrap();
+
+        assert_eq!(writer.get_ref(), b"a=(###, 7) n=3");
+    }
+
+    #[test]
+    fn errors_should_be_reported() {
+        struct FailingWriter;
+
+        impl Write for FailingWriter {
+            fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+                Err(io::Error::other("disk full"))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let engine = RedactionEngineBuilder::new().build().into_shared();
+        let mut writer = RedactingWriter::new(engine, FailingWriter);
+        assert_eq!(
+            writer.write_classified("plain").unwrap_err().to_string(),
+            "disk full"
+        );
+        assert!(writer.write_plain("x").is_err());
+    }
+}
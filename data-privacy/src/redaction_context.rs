@@ -0,0 +1,127 @@
+use crate::TagFormat;
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+/// Per-call information made available to redactors.
+///
+/// A context holds key/value pairs describing the circumstances of a redaction, such as the tenant
+/// the data belongs to or the identifier of the request being served. It is passed to redactors through
+/// [`Redactor::redact_ctx`](crate::Redactor::redact_ctx) by
+/// [`RedactionEngine::redact_with_context`](crate::RedactionEngine::redact_with_context), which lets a
+/// single engine serve many tenants while redactors salt their output per tenant, include correlation
+/// identifiers in their output, or otherwise adapt their behavior.
+///
+/// A context also carries the [`TagFormat`] which redactors tagging their output should use. A
+/// [`RedactionEngine`](crate::RedactionEngine) always passes its own
+/// [tag format](crate::RedactionEngine::tag_format), whatever the format of the context it's given.
+///
+/// Redactors which don't use a context ignore it, and redactions performed without a context see an
+/// empty one.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::RedactionContext;
+///
+/// let context = RedactionContext::new()
+///     .with_value(RedactionContext::TENANT, "contoso")
+///     .with_value(RedactionContext::CORRELATION_ID, "req-42");
+///
+/// assert_eq!(context.get(RedactionContext::TENANT), Some("contoso"));
+/// assert_eq!(context.get("region"), None);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RedactionContext {
+    values: BTreeMap<Cow<'static, str>, String>,
+    tag_format: TagFormat,
+}
+
+impl RedactionContext {
+    /// The conventional key of the tenant the data belongs to.
+    pub const TENANT: &'static str = "tenant";
+
+    /// The conventional key of an identifier correlating the redaction with a request or operation.
+    pub const CORRELATION_ID: &'static str = "correlation_id";
+
+    /// Creates a new empty context, with the default tag format.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            values: BTreeMap::new(),
+            tag_format: TagFormat::new(),
+        }
+    }
+
+    /// Sets the value of a key, replacing any previous value.
+    #[must_use]
+    pub fn with_value(
+        mut self,
+        key: impl Into<Cow<'static, str>>,
+        value: impl Into<String>,
+    ) -> Self {
+        _ = self.values.insert(key.into(), value.into());
+        self
+    }
+
+    /// Returns the value of a key, if it's set.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    /// Returns the key/value pairs of the context, ordered by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values.iter().map(|(k, v)| (k.as_ref(), v.as_str()))
+    }
+
+    /// Sets the format of the tags produced by redactors which tag their output.
+    #[must_use]
+    pub fn with_tag_format(mut self, tag_format: TagFormat) -> Self {
+        self.tag_format = tag_format;
+        self
+    }
+
+    /// Returns the format of the tags produced by redactors which tag their output.
+    #[must_use]
+    pub const fn tag_format(&self) -> &TagFormat {
+        &self.tag_format
+    }
+
+    /// Returns whether the context holds no values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    #[test]
+    fn values_should_be_replaced_and_ordered() {
+        let context = RedactionContext::new()
+            .with_value("b", "1")
+            .with_value("a", "2")
+            .with_value("b", "3");
+
+        assert_eq!(context.get("b"), Some("3"));
+        assert_eq!(context.iter().collect::<Vec<_>>(), [("a", "2"), ("b", "3")]);
+        assert!(!context.is_empty());
+        assert!(RedactionContext::default().is_empty());
+    }
+
+    #[test]
+    fn tag_format_should_default_and_be_replaced() {
+        let format = TagFormat::new().with_delimiters("[", "]");
+        let context = RedactionContext::new().with_tag_format(format.clone());
+
+        assert_eq!(RedactionContext::default().tag_format(), &TagFormat::new());
+        assert_eq!(context.tag_format(), &format);
+        assert!(context.is_empty());
+    }
+}
@@ -1,10 +1,20 @@
 use crate::Redactor;
-use crate::{Classified, DataClass};
+use crate::data_class::{BuildClassHasher, ClassKey};
+use crate::formatting::with_formatted;
+use crate::redaction_event::RedactionObserver;
+use crate::streaming_redactor::read_str_chunks;
+use crate::tag_scanner::{Segment, scan_tags};
+use crate::{
+    Classified, DataClass, Extract, Extractor, FieldExtractor, FieldPath, HeuristicClassifier,
+    MultiClassified, RecordSink, RedactionContext, RedactionEvent, RedactionStats, TagFormat,
+    TaxonomyMapper,
+};
 use core::fmt::Debug;
-use core::fmt::Display;
-use std::collections::HashMap;
-use std::io::Cursor;
-use std::io::Write;
+use core::fmt::{Display, Write as _};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Read};
+use std::sync::{Arc, Mutex, PoisonError};
 
 /// Lets you apply redaction to classified data.
 ///
@@ -52,132 +62,2140 @@ use std::io::Write;
 /// # }
 /// ```
 pub struct RedactionEngine {
-    redactors: HashMap<DataClass, Box<dyn Redactor + Send + Sync>>,
-    fallback: Box<dyn Redactor + Send + Sync>,
+    redactors: HashMap<DataClass, Box<dyn Redactor>, BuildClassHasher>,
+    fallback: Box<dyn Redactor>,
+    taxonomy_redactors: HashMap<String, Box<dyn Redactor>>,
+    field_redactors: FieldRedactors,
+    omitted: HashSet<DataClass, BuildClassHasher>,
+    never_log: HashSet<DataClass, BuildClassHasher>,
+    heuristics: Option<HeuristicClassifier>,
+    mapper: TaxonomyMapper,
+    context: RedactionContext,
+    observers: Vec<RedactionObserver>,
+    stats: Option<Mutex<RedactionStats>>,
 }
 
+/// The redactors registered for fields, by the name of the type declaring them and then by path.
+pub type FieldRedactors = HashMap<&'static str, Vec<(String, Box<dyn Redactor>)>>;
+
 impl RedactionEngine {
     #[must_use]
     pub(crate) fn new(
-        mut redactors: HashMap<DataClass, Box<dyn Redactor + Send + Sync>>,
-        fallback: Box<dyn Redactor + Send + Sync>,
+        redactors: HashMap<DataClass, Box<dyn Redactor>>,
+        fallback: Box<dyn Redactor>,
+    ) -> Self {
+        let never_log = redactors
+            .keys()
+            .filter(|data_class| data_class.never_log())
+            .cloned()
+            .collect();
+        #[cfg(feature = "registry")]
+        let never_log = {
+            let mut never_log: HashSet<DataClass, BuildClassHasher> = never_log;
+            never_log.extend(
+                crate::DataClassRegistry::global()
+                    .classes()
+                    .filter(|data_class| data_class.never_log())
+                    .cloned(),
+            );
+            never_log
+        };
+
+        Self {
+            redactors: redactors.into_iter().collect(),
+            fallback,
+            taxonomy_redactors: HashMap::new(),
+            field_redactors: HashMap::new(),
+            omitted: HashSet::default(),
+            never_log,
+            heuristics: None,
+            mapper: TaxonomyMapper::new(),
+            context: RedactionContext::new(),
+            observers: Vec::new(),
+            stats: None,
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn with_tag_format(mut self, tag_format: TagFormat) -> Self {
+        self.context = RedactionContext::new().with_tag_format(tag_format);
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn with_heuristic_classifier(
+        mut self,
+        heuristics: Option<HeuristicClassifier>,
+    ) -> Self {
+        self.heuristics = heuristics;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn with_observers(
+        mut self,
+        mut observers: Vec<RedactionObserver>,
+        collect_stats: bool,
+    ) -> Self {
+        observers.shrink_to_fit();
+        self.observers = observers;
+        self.stats = collect_stats.then(Mutex::default);
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn with_taxonomy_mapper(mut self, mapper: TaxonomyMapper) -> Self {
+        self.mapper = mapper;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn with_taxonomy_redactors(
+        mut self,
+        mut taxonomy_redactors: HashMap<String, Box<dyn Redactor>>,
     ) -> Self {
-        redactors.shrink_to_fit();
+        taxonomy_redactors.shrink_to_fit();
+        self.taxonomy_redactors = taxonomy_redactors;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn with_field_redactors(mut self, mut field_redactors: FieldRedactors) -> Self {
+        field_redactors.shrink_to_fit();
+        self.field_redactors = field_redactors;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn with_omitted_classes(mut self, omitted: HashSet<DataClass>) -> Self {
+        self.omitted = omitted.into_iter().collect();
+        self
+    }
+
+    /// Adds data classes whose metadata says they must never be logged to the ones the engine knows about.
+    #[must_use]
+    pub(crate) fn with_never_log_classes(mut self, data_classes: &[DataClass]) -> Self {
+        self.never_log.extend(
+            data_classes
+                .iter()
+                .filter(|data_class| data_class.never_log())
+                .cloned(),
+        );
+        self
+    }
+
+    /// Indicates whether data of the given class should be omitted entirely.
+    ///
+    /// Structured logging integrations should consult this method and drop the whole
+    /// name/value pair for omitted classes, rather than emitting a field with a blank value,
+    /// since the mere presence of some fields can itself be sensitive.
+    #[must_use]
+    pub fn is_omitted(&self, data_class: &DataClass) -> bool {
+        self.omitted.contains(self.mapped_class(data_class))
+    }
+
+    /// Indicates whether data of the given class must never be logged in the clear.
+    ///
+    /// This holds if the data class, or the class the engine [maps](Self::mapped_class) it to, must
+    /// [never be logged](DataClass::never_log) according to either its own metadata or the metadata of
+    /// the data classes the engine knows about. These are the classes redactors are registered for, and
+    /// with the `registry` feature, all the classes of the global `DataClassRegistry`.
+    /// Since they're looked up by name, data classes which lost their metadata, such as those created
+    /// from the names in a tag, are still recognized.
+    ///
+    /// Data of such classes is only ever redacted with redactors which don't
+    /// [reveal their input](Redactor::reveals_input), and produces no output otherwise.
+    #[must_use]
+    pub fn is_never_log(&self, data_class: &DataClass) -> bool {
+        let mapped = self.mapped_class(data_class);
+        data_class.never_log()
+            || mapped.never_log()
+            || self.never_log.contains(data_class)
+            || self.never_log.contains(mapped)
+    }
+
+    /// Returns the data class the engine handles a data class as.
+    ///
+    /// This is the class the engine's [`TaxonomyMapper`] translates the data class into, or the data
+    /// class itself if it has no mapping. Redactors and omissions registered for the returned class are
+    /// the ones which apply.
+    #[must_use]
+    pub fn mapped_class<'a>(&'a self, data_class: &'a DataClass) -> &'a DataClass {
+        self.mapper.translate(data_class).unwrap_or(data_class)
+    }
+
+    /// Returns the format of the tags the engine's tagging redactors produce.
+    ///
+    /// This is the format set with [`RedactionEngineBuilder::set_tag_format`](crate::RedactionEngineBuilder::set_tag_format).
+    #[must_use]
+    pub const fn tag_format(&self) -> &TagFormat {
+        self.context.tag_format()
+    }
+
+    /// Redacts the output of a classified value's [`Debug`] trait.
+    ///
+    /// Given a classified value whose payload implements the [`Debug`] trait, this method will
+    /// redact the output of that trait using the redactor registered for the data class of the value.
+    pub fn debug_redacted<C, T>(&self, value: &C, output: impl FnMut(&str))
+    where
+        C: Classified<T>,
+        T: Debug,
+    {
+        value.visit(|v| {
+            with_formatted(format_args!("{v:?}"), |s| {
+                self.redact(&value.data_class(), s, output);
+            });
+        });
+    }
+
+    /// Redacts the output of a classified value's [`Display`] trait.
+    ///
+    /// Given a classified value whose payload implements the [`Display`] trait, this method will
+    /// redact the output of that trait using the redactor registered for the data class of the value.
+    pub fn display_redacted<C, T>(&self, value: &C, output: impl FnMut(&str))
+    where
+        C: Classified<T>,
+        T: Display,
+    {
+        value.visit(|v| {
+            with_formatted(format_args!("{v}"), |s| {
+                self.redact(&value.data_class(), s, output);
+            });
+        });
+    }
+
+    /// Redacts the bytes of a classified value whose payload has no textual form.
+    ///
+    /// Given a classified value whose payload can be viewed as bytes, such as a `Vec<u8>`, this method
+    /// redacts the bytes with [`redact_bytes`](Self::redact_bytes), so the payload doesn't need to
+    /// implement [`Display`].
+    pub fn bytes_redacted<C, T>(&self, value: &C, output: impl FnMut(&str))
+    where
+        C: Classified<T>,
+        T: AsRef<[u8]>,
+    {
+        value.visit(|v| self.redact_bytes(&value.data_class(), v, output));
+    }
+
+    /// Wraps a value so that formatting or serializing it redacts it.
+    ///
+    /// The value is redacted like with [`extract_redacted`](Self::extract_redacted), each time the adapter
+    /// is used. See [`Redacted`](crate::Redacted) for details.
+    #[must_use]
+    pub const fn display<'a, T: Extract + ?Sized>(
+        &'a self,
+        value: &'a T,
+    ) -> crate::Redacted<'a, T> {
+        crate::Redacted::new(self, value)
+    }
+
+    /// Redacts the output of the [`Debug`] trait of the innermost payload of a nested classified value.
+    ///
+    /// The payload is redacted using the redactor registered for the effective class of the nested
+    /// containers, as described in [Nested Containers](Classified#nested-containers). The redactor of
+    /// the less restrictive class is never applied.
+    pub fn debug_redacted_nested<C, I, T>(&self, value: &C, output: impl FnMut(&str))
+    where
+        C: Classified<I>,
+        I: Classified<T>,
+        T: Debug,
+    {
+        let data_class = value.nested_data_class();
+        value.visit(|inner| {
+            inner.visit(|v| {
+                with_formatted(format_args!("{v:?}"), |s| {
+                    self.redact(&data_class, s, output);
+                });
+            });
+        });
+    }
+
+    /// Redacts the output of the [`Display`] trait of the innermost payload of a nested classified value.
+    ///
+    /// The payload is redacted using the redactor registered for the effective class of the nested
+    /// containers, as described in [Nested Containers](Classified#nested-containers). The redactor of
+    /// the less restrictive class is never applied.
+    pub fn display_redacted_nested<C, I, T>(&self, value: &C, output: impl FnMut(&str))
+    where
+        C: Classified<I>,
+        I: Classified<T>,
+        T: Display,
+    {
+        let data_class = value.nested_data_class();
+        value.visit(|inner| {
+            inner.visit(|v| {
+                with_formatted(format_args!("{v}"), |s| {
+                    self.redact(&data_class, s, output);
+                });
+            });
+        });
+    }
+
+    /// Picks the data class whose redactor applies to a value falling under several classes.
+    ///
+    /// This is the [most restrictive](DataClass::most_restrictive_of) of the classes, the same class a
+    /// [`MultiClassified`] value reports and is [extracted](Extract) with, so the value is redacted the
+    /// same way however it reaches the engine. A less restrictive class never takes precedence, even if it
+    /// has a redactor registered or is omitted while the most restrictive one doesn't. Returns `None` if no
+    /// classes are given.
+    #[must_use]
+    pub fn effective_class<'a>(&self, data_classes: &'a [DataClass]) -> Option<&'a DataClass> {
+        DataClass::most_restrictive_of(data_classes)
+    }
+
+    /// Redacts the output of the [`Debug`] trait of a value falling under several data classes.
+    ///
+    /// The payload is redacted using the redactor of the [effective class](Self::effective_class) of the
+    /// value's data classes.
+    pub fn debug_redacted_multi<T: Debug>(
+        &self,
+        value: &MultiClassified<T>,
+        output: impl FnMut(&str),
+    ) {
+        let Some(data_class) = self.effective_class(value.data_classes()) else {
+            return;
+        };
+
+        value.visit(|v| {
+            with_formatted(format_args!("{v:?}"), |s| {
+                self.redact(data_class, s, output);
+            });
+        });
+    }
+
+    /// Redacts the output of the [`Display`] trait of a value falling under several data classes.
+    ///
+    /// The payload is redacted using the redactor of the [effective class](Self::effective_class) of the
+    /// value's data classes.
+    pub fn display_redacted_multi<T: Display>(
+        &self,
+        value: &MultiClassified<T>,
+        output: impl FnMut(&str),
+    ) {
+        let Some(data_class) = self.effective_class(value.data_classes()) else {
+            return;
+        };
+
+        value.visit(|v| {
+            with_formatted(format_args!("{v}"), |s| {
+                self.redact(data_class, s, output);
+            });
+        });
+    }
+
+    /// Redacts a string with an explicit data classification, sending the results to the output callback.
+    ///
+    /// Nothing is sent to the output callback for data classes that are [omitted](Self::is_omitted).
+    /// For data classes which must [never be logged](Self::is_never_log), nothing is sent either when
+    /// the redactor [may reveal its input](Redactor::reveals_input), as a passthrough redactor would.
+    pub fn redact(&self, data_class: &DataClass, value: impl AsRef<str>, output: impl FnMut(&str)) {
+        self.redact_with_context(data_class, value, &self.context, output);
+    }
+
+    /// Redacts a string with an explicit data classification and per-call context, sending the results to the output callback.
+    ///
+    /// This behaves like [`redact`](Self::redact), passing the context to the redactor through
+    /// [`Redactor::redact_ctx`]. Redactors can use it to salt their output per tenant, include a
+    /// correlation identifier, or otherwise adapt to the call, without needing one engine per tenant.
+    /// The context the redactor sees always carries the engine's [tag format](Self::tag_format),
+    /// whatever the tag format of the given context.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use data_privacy::core_taxonomy::CoreTaxonomy;
+    /// use data_privacy::{DataClass, RedactionContext, RedactionEngineBuilder, Redactor};
+    ///
+    /// struct TenantRedactor;
+    ///
+    /// impl Redactor for TenantRedactor {
+    ///     fn redact(&self, data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
+    ///         self.redact_ctx(data_class, value, &RedactionContext::new(), output);
+    ///     }
+    ///
+    ///     fn redact_ctx(
+    ///         &self,
+    ///         _data_class: &DataClass,
+    ///         value: &str,
+    ///         context: &RedactionContext,
+    ///         output: &mut dyn FnMut(&str),
+    ///     ) {
+    ///         output(context.get(RedactionContext::TENANT).unwrap_or("unknown"));
+    ///         output(":");
+    ///         output(&"*".repeat(value.len()));
+    ///     }
+    /// }
+    ///
+    /// let engine = RedactionEngineBuilder::new()
+    ///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), TenantRedactor)
+    ///     .build();
+    ///
+    /// let context = RedactionContext::new().with_value(RedactionContext::TENANT, "contoso");
+    /// let mut output = String::new();
+    /// engine.redact_with_context(&CoreTaxonomy::Sensitive.data_class(), "jdoe", &context, |s| {
+    ///     output.push_str(s);
+    /// });
+    /// assert_eq!(output, "contoso:****");
+    /// ```
+    pub fn redact_with_context(
+        &self,
+        data_class: &DataClass,
+        value: impl AsRef<str>,
+        context: &RedactionContext,
+        mut output: impl FnMut(&str),
+    ) {
+        let never_log = self.is_never_log(data_class);
+        let data_class = self.mapped_class(data_class);
+        if self.omitted.contains(data_class) {
+            return;
+        }
+
+        let (redactor, fallback) = self.class_redactor(data_class);
+        self.redact_with(
+            redactor,
+            fallback,
+            data_class,
+            never_log,
+            value.as_ref(),
+            context,
+            &mut output,
+        );
+    }
+
+    /// Redacts binary data with an explicit data classification, sending the results to the output callback.
+    ///
+    /// The bytes are handed to the redactor as is, so hashing redactors such as
+    /// `HmacRedactor` hash the original data. Redactors which only work on text see
+    /// the bytes as lowercase hexadecimal, as described in [`Redactor::redact_bytes`].
+    ///
+    /// Nothing is sent to the output callback for data classes that are [omitted](Self::is_omitted),
+    /// and data classes which must [never be logged](Self::is_never_log) are guarded like
+    /// [`redact`](Self::redact) does.
+    pub fn redact_bytes(
+        &self,
+        data_class: &DataClass,
+        value: impl AsRef<[u8]>,
+        output: impl FnMut(&str),
+    ) {
+        self.redact_bytes_with_context(data_class, value, &self.context, output);
+    }
+
+    /// Redacts binary data with an explicit data classification and per-call context, sending the results
+    /// to the output callback.
+    ///
+    /// This behaves like [`redact_bytes`](Self::redact_bytes), passing the context to the redactor like
+    /// [`redact_with_context`](Self::redact_with_context) does.
+    pub fn redact_bytes_with_context(
+        &self,
+        data_class: &DataClass,
+        value: impl AsRef<[u8]>,
+        context: &RedactionContext,
+        mut output: impl FnMut(&str),
+    ) {
+        let never_log = self.is_never_log(data_class);
+        let data_class = self.mapped_class(data_class);
+        if self.omitted.contains(data_class) {
+            return;
+        }
+
+        let value = value.as_ref();
+        let (redactor, fallback) = self.class_redactor(data_class);
+        self.observe(data_class, value, fallback, &mut output, |output| {
+            if !(never_log && redactor.reveals_input()) {
+                redactor.redact_bytes(data_class, value, &self.redactor_context(context), output);
+            }
+        });
+    }
+
+    /// Redacts a value read from a reader with an explicit data classification, sending the results to the
+    /// output callback.
+    ///
+    /// This lets very large values, such as documents being archived, be redacted without loading them
+    /// into memory. Redactors which [support streaming](Redactor::as_streaming) read the value in chunks,
+    /// and may call the output callback once per chunk. Other redactors are given the whole value read
+    /// into a string. Data classes which must [never be logged](Self::is_never_log) are guarded like
+    /// [`redact`](Self::redact) does.
+    ///
+    /// Nothing is read from the reader for data classes that are [omitted](Self::is_omitted).
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by the reader, or an error of kind [`InvalidData`](io::ErrorKind::InvalidData)
+    /// if the value isn't valid UTF-8. Part of the redacted value may have been sent to the output callback
+    /// by then.
+    pub fn redact_reader(
+        &self,
+        data_class: &DataClass,
+        reader: &mut dyn Read,
+        output: impl FnMut(&str),
+    ) -> io::Result<()> {
+        self.redact_reader_with_context(data_class, reader, &self.context, output)
+    }
+
+    /// Redacts a value read from a reader with an explicit data classification and per-call context,
+    /// sending the results to the output callback.
+    ///
+    /// This behaves like [`redact_reader`](Self::redact_reader), passing the context to the redactor like
+    /// [`redact_with_context`](Self::redact_with_context) does.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`redact_reader`](Self::redact_reader).
+    pub fn redact_reader_with_context(
+        &self,
+        data_class: &DataClass,
+        reader: &mut dyn Read,
+        context: &RedactionContext,
+        mut output: impl FnMut(&str),
+    ) -> io::Result<()> {
+        let never_log = self.is_never_log(data_class);
+        let data_class = self.mapped_class(data_class);
+        if self.omitted.contains(data_class) {
+            return Ok(());
+        }
+
+        let (redactor, fallback) = self.class_redactor(data_class);
+        match redactor.as_streaming() {
+            _ if never_log && redactor.reveals_input() => {
+                // nothing is output, so the value is drained rather than buffered
+                let mut input_len = 0;
+                read_str_chunks(reader, |s| input_len += s.len())?;
+                self.notify(&RedactionEvent::new(
+                    data_class, input_len, 0, fallback, false,
+                ));
+            }
+            Some(streaming) => {
+                let mut reader = CountingReader { reader, len: 0 };
+                let mut output_len = 0;
+                streaming.redact_reader_ctx(
+                    data_class,
+                    &mut reader,
+                    &self.redactor_context(context),
+                    &mut |s| {
+                        output_len += s.len();
+                        output(s);
+                    },
+                )?;
+
+                let passthrough = streaming.is_passthrough() && reader.len > 0;
+                self.notify(&RedactionEvent::new(
+                    data_class,
+                    reader.len,
+                    output_len,
+                    fallback,
+                    passthrough,
+                ));
+            }
+            None => {
+                let mut value = String::new();
+                _ = reader.read_to_string(&mut value)?;
+                self.redact_with(
+                    redactor,
+                    fallback,
+                    data_class,
+                    never_log,
+                    &value,
+                    context,
+                    &mut output,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Redacts a named field of a value, sending the results to the output callback.
+    ///
+    /// The field is redacted with the redactor [registered](crate::RedactionEngineBuilder::add_field_redactor)
+    /// for its path within the type declaring it, if any, unless the redactor of the field's data class is
+    /// more restrictive, and otherwise like [`redact`](Self::redact) with the redactor registered for its
+    /// data class. A redactor which [may reveal its input](Redactor::reveals_input) is less restrictive than
+    /// one which doesn't, so a field redactor can't weaken the redaction of its data class. Nothing is sent
+    /// to the output callback for data classes that are [omitted](Self::is_omitted), even if a redactor is
+    /// registered for the field, and data classes which must [never be logged](Self::is_never_log) are
+    /// guarded the same way.
+    pub fn redact_field(
+        &self,
+        path: &FieldPath<'_>,
+        data_class: &DataClass,
+        value: impl AsRef<str>,
+        output: impl FnMut(&str),
+    ) {
+        self.redact_field_with_context(path, data_class, value, &self.context, output);
+    }
+
+    /// Redacts a named field of a value with a per-call context, sending the results to the output callback.
+    ///
+    /// This behaves like [`redact_field`](Self::redact_field), passing the context to the redactor like
+    /// [`redact_with_context`](Self::redact_with_context) does.
+    pub fn redact_field_with_context(
+        &self,
+        path: &FieldPath<'_>,
+        data_class: &DataClass,
+        value: impl AsRef<str>,
+        context: &RedactionContext,
+        mut output: impl FnMut(&str),
+    ) {
+        let never_log = self.is_never_log(data_class);
+        let data_class = self.mapped_class(data_class);
+        if self.omitted.contains(data_class) {
+            return;
+        }
+
+        let (class_redactor, fallback) = self.class_redactor(data_class);
+        let (redactor, fallback) = match self.field_redactor(path) {
+            Some(redactor) if class_redactor.reveals_input() || !redactor.reveals_input() => {
+                (redactor, false)
+            }
+            _ => (class_redactor, fallback),
+        };
+        self.redact_with(
+            redactor,
+            fallback,
+            data_class,
+            never_log,
+            value.as_ref(),
+            context,
+            &mut output,
+        );
+    }
+
+    /// Redacts the extracted form of a value, sending the results to the output callback.
+    ///
+    /// The value's plain text is passed through unchanged, while each piece of classified text is
+    /// redacted using the redactor registered for its own data class. See [`Extract`] for details.
+    pub fn extract_redacted(&self, value: &(impl Extract + ?Sized), output: impl FnMut(&str)) {
+        self.extract_redacted_with_context(value, &self.context, output);
+    }
+
+    /// Redacts the extracted form of a value with a per-call context, sending the results to the output callback.
+    ///
+    /// This behaves like [`extract_redacted`](Self::extract_redacted), passing the context to the redactor
+    /// of each piece of classified text like [`redact_with_context`](Self::redact_with_context) does.
+    pub fn extract_redacted_with_context(
+        &self,
+        value: &(impl Extract + ?Sized),
+        context: &RedactionContext,
+        mut output: impl FnMut(&str),
+    ) {
+        value.extract(&mut RedactingExtractor {
+            engine: self,
+            context,
+            output: &mut output,
+        });
+    }
+
+    /// Redacts the named fields of a value, sending the path and redacted value of each field to the output callback.
+    ///
+    /// This lets telemetry backends emit `name=value` pairs rather than a single string. Fields are the
+    /// classified text sent through [`Extractor::write_field`], such as each classified field of a type
+    /// deriving [`Extract`](macro@crate::Extract), and are redacted like [`redact_field`](Self::redact_field)
+    /// does. Fields of [omitted](Self::is_omitted) classes are dropped entirely. Plain text and classified
+    /// text sent without a name don't produce any output.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+    /// use data_privacy::{Extract, RedactionEngineBuilder, SimpleRedactor};
+    ///
+    /// #[derive(Extract)]
+    /// struct Employee {
+    ///     #[classified(CoreTaxonomy::Sensitive)]
+    ///     email: String,
+    ///     name: Sensitive<String>,
+    ///     age: u32,
+    /// }
+    ///
+    /// let engine = RedactionEngineBuilder::new()
+    ///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+    ///     .build();
+    ///
+    /// let employee = Employee {
+    ///     email: "jdoe@contoso.com".to_string(),
+    ///     name: Sensitive::new("John".to_string()),
+    ///     age: 33,
+    /// };
+    ///
+    /// let mut pairs = Vec::new();
+    /// engine.redact_fields(&employee, |name, value| pairs.push(format!("{name}={value}")));
+    /// assert_eq!(pairs, ["email=****************", "name=****"]);
+    /// ```
+    pub fn redact_fields(
+        &self,
+        value: &(impl Extract + ?Sized),
+        mut output: impl FnMut(&str, &str),
+    ) {
+        value.extract(&mut FieldsExtractor {
+            engine: self,
+            name: String::new(),
+            buffer: String::new(),
+            output: &mut output,
+        });
+    }
+
+    /// Redacts a record made of named fields in a single call, sending each redacted field to the sink.
+    ///
+    /// Each value is redacted like [`redact_into`](Self::redact_into), with its classified text sent as a
+    /// field named after the record's field, so redactors registered for the fields of the types the
+    /// values hold with [`add_field_redactor`](crate::RedactionEngineBuilder::add_field_redactor) apply. Fields holding
+    /// data of an [omitted](Self::is_omitted) class are dropped from the record altogether. Values holding
+    /// no classified data are checked against the engine's heuristic classifier, like
+    /// [`redact_unclassified`](Self::redact_unclassified) does.
+    ///
+    /// A single buffer is reused across the fields of the record, and the sink's
+    /// [`end_record`](RecordSink::end_record) method is called once all fields have been written.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+    /// use data_privacy::{Extract, RedactionEngineBuilder, SimpleRedactor};
+    ///
+    /// let engine = RedactionEngineBuilder::new()
+    ///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+    ///     .build();
+    ///
+    /// let user = Sensitive::new("jdoe");
+    /// let mut sink = Vec::new();
+    /// engine.redact_record(
+    ///     [("event", &"login" as &dyn Extract), ("user", &user), ("attempts", &3)],
+    ///     &mut sink,
+    /// );
+    ///
+    /// assert_eq!(sink[1], ("user".to_string(), "****".to_string()));
+    /// assert_eq!(sink[2], ("attempts".to_string(), "3".to_string()));
+    /// ```
+    pub fn redact_record<'v>(
+        &self,
+        fields: impl IntoIterator<Item = (&'v str, &'v dyn Extract)>,
+        mut sink: impl RecordSink,
+    ) {
+        let (mut buffer, mut heuristic) = (String::new(), String::new());
+        let mut extractor = RecordExtractor {
+            inner: StringExtractor {
+                engine: self,
+                output: &mut buffer,
+            },
+            classified: false,
+            omitted: false,
+        };
+
+        for (name, value) in fields {
+            extractor.inner.output.clear();
+            extractor.classified = false;
+            extractor.omitted = false;
+            value.extract(&mut FieldExtractor::unowned(&mut extractor, name));
+
+            if extractor.omitted {
+                continue;
+            }
+
+            match self.classify_field(name) {
+                Some(data_class) if !extractor.classified => {
+                    if !self.is_omitted(data_class) {
+                        heuristic.clear();
+                        self.redact(data_class, &*extractor.inner.output, |s| {
+                            heuristic.push_str(s);
+                        });
+                        sink.write_field(name, &heuristic);
+                    }
+                }
+                _ => sink.write_field(name, extractor.inner.output),
+            }
+        }
+
+        sink.end_record();
+    }
+
+    /// Redacts the extracted form of a value into a new string.
+    ///
+    /// This behaves like [`extract_redacted`](Self::extract_redacted), reserving space up front for the
+    /// output of redactors which report a [length hint](Self::len_hint).
+    #[must_use]
+    pub fn redact_to_string(&self, value: &(impl Extract + ?Sized)) -> String {
+        let mut output = String::new();
+        self.redact_into(value, &mut output);
+        output
+    }
+
+    /// Redacts a string with an explicit data classification into a new string.
+    ///
+    /// This behaves like [`redact`](Self::redact), reserving space up front when the redactor registered
+    /// for the class reports a [length hint](Self::len_hint).
+    #[must_use]
+    pub fn redact_to_string_as_class(
+        &self,
+        data_class: &DataClass,
+        value: impl AsRef<str>,
+    ) -> String {
+        let value = value.as_ref();
+        let mut output = String::with_capacity(self.len_hint(data_class, value.len()).unwrap_or(0));
+        self.redact(data_class, value, |s| output.push_str(s));
+        output
+    }
+
+    /// Redacts the extracted form of a value, appending the results to a string.
+    ///
+    /// This behaves like [`extract_redacted`](Self::extract_redacted), reserving space up front for the
+    /// output of redactors which report a [length hint](Self::len_hint).
+    pub fn redact_into(&self, value: &(impl Extract + ?Sized), output: &mut String) {
+        value.extract(&mut StringExtractor {
+            engine: self,
+            output,
+        });
+    }
+
+    /// Wraps a value so that serializing it redacts every classified container it holds.
+    ///
+    /// See [`SerializeRedacted`](crate::SerializeRedacted) for details.
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub const fn to_serializable<'a, T: ?Sized>(
+        &'a self,
+        value: &'a T,
+    ) -> crate::SerializeRedacted<'a, T> {
+        crate::SerializeRedacted::new(self, value)
+    }
+
+    /// Redacts classification tags embedded in already-formatted text, sending the results to the output callback.
+    ///
+    /// This scans the text for tags in the engine's [tag format](Self::tag_format), such as
+    /// `<taxonomy/class:value>` by default, as produced by the tagging modes of
+    /// [`SimpleRedactor`](crate::SimpleRedactor), and replaces each one with the output of the redactor
+    /// registered for its data class. Text outside of tags is passed through unchanged.
+    ///
+    /// Values are unescaped before being redacted, and a tag which is never terminated, such as one cut
+    /// short by truncation, extends to the end of the text and is redacted as a whole rather than being
+    /// passed through.
+    ///
+    /// This enables a two-phase pipeline, where data is tagged in-process and final redaction is
+    /// performed later, for example by a log shipper that owns the redaction policy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use data_privacy::core_taxonomy::CoreTaxonomy;
+    /// use data_privacy::{RedactionEngineBuilder, SimpleRedactor};
+    ///
+    /// let engine = RedactionEngineBuilder::new()
+    ///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+    ///     .build();
+    ///
+    /// let mut output = String::new();
+    /// engine.redact_tags("user <core/sensitive:jdoe> logged in", |s| output.push_str(s));
+    /// assert_eq!(output, "user **** logged in");
+    /// ```
+    pub fn redact_tags(&self, text: &str, output: impl FnMut(&str)) {
+        self.redact_tags_with_context(text, &self.context, output);
+    }
+
+    /// Redacts classification tags embedded in already-formatted text with a per-call context, sending the
+    /// results to the output callback.
+    ///
+    /// This behaves like [`redact_tags`](Self::redact_tags), passing the context to the redactor of each
+    /// tag like [`redact_with_context`](Self::redact_with_context) does.
+    pub fn redact_tags_with_context(
+        &self,
+        text: &str,
+        context: &RedactionContext,
+        mut output: impl FnMut(&str),
+    ) {
+        scan_tags(text, self.tag_format(), |segment| match segment {
+            Segment::Text(s) => output(s),
+            Segment::Tag(taxonomy, name, value) => {
+                self.redact_as_parts_with_context(taxonomy, name, &*value, context, &mut output);
+            }
+        });
+    }
+
+    /// Redacts a string classified by taxonomy and class names, sending the results to the output callback.
+    ///
+    /// This behaves like [`redact`](Self::redact), but lets integrations which only have the names of
+    /// a data class at hand use the engine without constructing a [`DataClass`]. Looking up the redactor
+    /// doesn't allocate, except when no redactor is registered for the class, and a taxonomy-wide or the
+    /// fallback redactor is used.
+    pub fn redact_as_parts(
+        &self,
+        taxonomy: &str,
+        name: &str,
+        value: impl AsRef<str>,
+        output: impl FnMut(&str),
+    ) {
+        self.redact_as_parts_with_context(taxonomy, name, value, &self.context, output);
+    }
+
+    /// Redacts a string classified by taxonomy and class names with a per-call context, sending the results
+    /// to the output callback.
+    ///
+    /// This behaves like [`redact_as_parts`](Self::redact_as_parts), passing the context to the redactor
+    /// like [`redact_with_context`](Self::redact_with_context) does.
+    pub fn redact_as_parts_with_context(
+        &self,
+        taxonomy: &str,
+        name: &str,
+        value: impl AsRef<str>,
+        context: &RedactionContext,
+        mut output: impl FnMut(&str),
+    ) {
+        let key: &dyn ClassKey = &(taxonomy, name);
+        let never_log = self.never_log.contains(key);
+
+        if let Some(data_class) = self.mapper.translate_parts(taxonomy, name) {
+            if !self.omitted.contains(data_class) {
+                let never_log = never_log || self.is_never_log(data_class);
+                let (redactor, fallback) = self.class_redactor(data_class);
+                self.redact_with(
+                    redactor,
+                    fallback,
+                    data_class,
+                    never_log,
+                    value.as_ref(),
+                    context,
+                    &mut output,
+                );
+            }
+            return;
+        }
+
+        if self.omitted.contains(key) {
+            return;
+        }
+
+        if let Some((data_class, redactor)) = self.redactors.get_key_value(key) {
+            self.redact_with(
+                redactor.as_ref(),
+                false,
+                data_class,
+                never_log,
+                value.as_ref(),
+                context,
+                &mut output,
+            );
+        } else {
+            let data_class = DataClass::from_parts(taxonomy.to_owned(), name.to_owned())
+                .with_never_log(never_log);
+            let (redactor, fallback) = self.taxonomy_redactors.get(taxonomy).map_or_else(
+                || (self.fallback.as_ref(), true),
+                |redactor| (redactor.as_ref(), false),
+            );
+            self.redact_with(
+                redactor,
+                fallback,
+                &data_class,
+                never_log,
+                value.as_ref(),
+                context,
+                &mut output,
+            );
+        }
+    }
+
+    /// Returns the data class inferred from a field's name by the engine's heuristic classifier.
+    ///
+    /// This returns `None` if no heuristic classifier is configured, or if the field name doesn't
+    /// match any of its patterns.
+    #[must_use]
+    pub fn classify_field(&self, field_name: &str) -> Option<&DataClass> {
+        self.heuristics.as_ref()?.classify(field_name)
+    }
+
+    /// Redacts a value which carries no type-level classification, based on the name of its field.
+    ///
+    /// Integrations use this for values that don't come from classified containers. If the
+    /// engine's heuristic classifier maps the field name to a data class, the value is redacted as
+    /// that class. Otherwise, the value is sent to the output callback unchanged.
+    pub fn redact_unclassified(
+        &self,
+        field_name: &str,
+        value: impl AsRef<str>,
+        mut output: impl FnMut(&str),
+    ) {
+        match self.classify_field(field_name) {
+            Some(data_class) => self.redact(data_class, value, output),
+            None => output(value.as_ref()),
+        }
+    }
+
+    /// The exact length of the redacted output if it is a constant.
+    ///
+    /// This can be used as a hint to optimize buffer allocations.
+    #[must_use]
+    pub fn exact_len(&self, data_class: &DataClass) -> Option<usize> {
+        match self.emitting_redactor(data_class) {
+            Some((redactor, _)) => redactor.exact_len(),
+            None => Some(0),
+        }
+    }
+
+    /// The length of the redacted output of a value of the given length in bytes, if it can be known without the value.
+    ///
+    /// This accounts for the length of the value and for the engine's [`TagFormat`], so unlike
+    /// [`exact_len`](Self::exact_len), it's available for masking and tagging redactors. See
+    /// [`Redactor::len_hint`] for details.
+    ///
+    /// This can be used as a hint to optimize buffer allocations.
+    #[must_use]
+    pub fn len_hint(&self, data_class: &DataClass, input_len: usize) -> Option<usize> {
+        match self.emitting_redactor(data_class) {
+            Some((redactor, data_class)) => {
+                redactor.len_hint(data_class, input_len, self.tag_format())
+            }
+            None => Some(0),
+        }
+    }
+
+    /// Returns a snapshot of the redactions performed by the engine so far.
+    ///
+    /// This returns `None` unless the engine was built with
+    /// [`collect_stats`](crate::RedactionEngineBuilder::collect_stats).
+    #[must_use]
+    pub fn stats(&self) -> Option<RedactionStats> {
+        self.stats
+            .as_ref()
+            .map(|stats| stats.lock().unwrap_or_else(PoisonError::into_inner).clone())
+    }
+
+    /// Returns whether data of a class would be redacted by the fallback redactor.
+    pub(crate) fn uses_fallback(&self, data_class: &DataClass) -> bool {
+        let data_class = self.mapped_class(data_class);
+        !self.omitted.contains(data_class) && self.class_redactor(data_class).1
+    }
+
+    /// Returns the redactor registered for a field, trying its path from each of the types along it.
+    fn field_redactor(&self, path: &FieldPath<'_>) -> Option<&dyn Redactor> {
+        if self.field_redactors.is_empty() {
+            return None;
+        }
+
+        path.nested().find_map(|path| {
+            self.field_redactors
+                .get(path.owner())?
+                .iter()
+                .find(|(field, _)| path.matches(field))
+                .map(|(_, redactor)| redactor.as_ref())
+        })
+    }
+
+    /// Returns the redactor which produces the output for data of a class, along with the class it
+    /// redacts the data as, or `None` if nothing is output for the class.
+    ///
+    /// Nothing is output for omitted classes, nor for classes which must never be logged when their
+    /// redactor may reveal its input.
+    fn emitting_redactor<'a>(
+        &'a self,
+        data_class: &'a DataClass,
+    ) -> Option<(&'a dyn Redactor, &'a DataClass)> {
+        let never_log = self.is_never_log(data_class);
+        let data_class = self.mapped_class(data_class);
+        if self.omitted.contains(data_class) {
+            return None;
+        }
+
+        let (redactor, _) = self.class_redactor(data_class);
+        if never_log && redactor.reveals_input() {
+            return None;
+        }
+
+        Some((redactor, data_class))
+    }
+
+    /// Returns the redactor for a data class, falling back to the redactor of its taxonomy and then to
+    /// the engine's fallback redactor, along with whether the fallback redactor is the one returned.
+    fn class_redactor(&self, data_class: &DataClass) -> (&dyn Redactor, bool) {
+        self.redactors
+            .get(data_class)
+            .or_else(|| self.taxonomy_redactors.get(data_class.taxonomy()))
+            .map_or_else(
+                || (self.fallback.as_ref(), true),
+                |redactor| (redactor.as_ref(), false),
+            )
+    }
+
+    /// Redacts a value with the given redactor and context, refusing to use a redactor which may reveal
+    /// its input for a class which must never be logged.
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "This is the common path of all the engine's redactions"
+    )]
+    fn redact_with(
+        &self,
+        redactor: &dyn Redactor,
+        fallback: bool,
+        data_class: &DataClass,
+        never_log: bool,
+        value: &str,
+        context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) {
+        self.observe(data_class, value.as_bytes(), fallback, output, |output| {
+            if !(never_log && redactor.reveals_input()) {
+                redactor.redact_ctx(data_class, value, &self.redactor_context(context), output);
+            }
+        });
+    }
+
+    /// Returns the given context, carrying the engine's tag format for redactors to use.
+    fn redactor_context<'a>(&self, context: &'a RedactionContext) -> Cow<'a, RedactionContext> {
+        if context.tag_format() == self.tag_format() {
+            Cow::Borrowed(context)
+        } else {
+            Cow::Owned(context.clone().with_tag_format(self.tag_format().clone()))
+        }
+    }
+
+    /// Returns a data class which must never be logged but would be redacted by a redactor which may
+    /// reveal its input, among the ones redactors are registered for and the given ones.
+    pub(crate) fn revealing_class<'a>(
+        &'a self,
+        known_classes: &'a [DataClass],
+    ) -> Option<&'a DataClass> {
+        self.redactors
+            .keys()
+            .chain(known_classes)
+            .find(|data_class| {
+                let mapped = self.mapped_class(data_class);
+                self.is_never_log(data_class)
+                    && !self.omitted.contains(mapped)
+                    && self.class_redactor(mapped).0.reveals_input()
+            })
+    }
+
+    /// Runs a redaction, reporting it to the engine's observers and statistics, if any.
+    fn observe(
+        &self,
+        data_class: &DataClass,
+        value: &[u8],
+        fallback: bool,
+        output: &mut dyn FnMut(&str),
+        redact: impl FnOnce(&mut dyn FnMut(&str)),
+    ) {
+        if self.observers.is_empty() && self.stats.is_none() {
+            redact(output);
+            return;
+        }
+
+        // the output is a passthrough as long as it keeps matching the value
+        let mut output_len = 0;
+        let mut matching = true;
+        redact(&mut |s| {
+            matching = matching
+                && value
+                    .get(output_len..)
+                    .is_some_and(|rest| rest.starts_with(s.as_bytes()));
+            output_len += s.len();
+            output(s);
+        });
+
+        let passthrough = matching && !value.is_empty() && output_len == value.len();
+        self.notify(&RedactionEvent::new(
+            data_class,
+            value.len(),
+            output_len,
+            fallback,
+            passthrough,
+        ));
+    }
+
+    /// Reports a redaction to the engine's observers and statistics.
+    fn notify(&self, event: &RedactionEvent<'_>) {
+        for observer in &self.observers {
+            observer(event);
+        }
+
+        if let Some(stats) = &self.stats {
+            stats
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .record(event);
+        }
+    }
+
+    /// Moves the engine into an [`Arc`] so it can be shared between threads.
+    ///
+    /// Since all redactors are required to be [`Send`] and [`Sync`], the engine is too, which makes it
+    /// suitable for installation in multi-threaded logging infrastructure.
+    #[must_use]
+    pub fn into_shared(self) -> Arc<Self> {
+        Arc::new(self)
+    }
+}
+
+/// Counts the bytes read from a reader.
+struct CountingReader<'a> {
+    reader: &'a mut dyn Read,
+    len: usize,
+}
+
+impl Read for CountingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.reader.read(buf)?;
+        self.len += len;
+        Ok(len)
+    }
+}
+
+/// Sends extracted plain text straight to the output, and classified text through the engine.
+struct RedactingExtractor<'a> {
+    engine: &'a RedactionEngine,
+    context: &'a RedactionContext,
+    output: &'a mut dyn FnMut(&str),
+}
+
+impl Extractor for RedactingExtractor<'_> {
+    fn write_plain(&mut self, text: &str) {
+        (self.output)(text);
+    }
+
+    fn write_classified(&mut self, data_class: &DataClass, text: &str) {
+        self.engine
+            .redact_with_context(data_class, text, self.context, &mut *self.output);
+    }
+
+    fn write_classified_bytes(&mut self, data_class: &DataClass, bytes: &[u8]) {
+        self.engine
+            .redact_bytes_with_context(data_class, bytes, self.context, &mut *self.output);
+    }
+
+    fn write_field(&mut self, path: &FieldPath<'_>, data_class: &DataClass, text: &str) {
+        self.engine.redact_field_with_context(
+            path,
+            data_class,
+            text,
+            self.context,
+            &mut *self.output,
+        );
+    }
+}
+
+/// Appends extracted plain text straight to a string, and classified text through the engine.
+struct StringExtractor<'a> {
+    engine: &'a RedactionEngine,
+    output: &'a mut String,
+}
+
+impl Extractor for StringExtractor<'_> {
+    fn write_plain(&mut self, text: &str) {
+        self.output.push_str(text);
+    }
+
+    fn write_classified(&mut self, data_class: &DataClass, text: &str) {
+        if let Some(len) = self.engine.len_hint(data_class, text.len()) {
+            self.output.reserve(len);
+        }
+
+        self.engine
+            .redact(data_class, text, |s| self.output.push_str(s));
+    }
+
+    fn write_classified_bytes(&mut self, data_class: &DataClass, bytes: &[u8]) {
+        self.engine
+            .redact_bytes(data_class, bytes, |s| self.output.push_str(s));
+    }
+
+    fn write_field(&mut self, path: &FieldPath<'_>, data_class: &DataClass, text: &str) {
+        self.engine
+            .redact_field(path, data_class, text, |s| self.output.push_str(s));
+    }
+}
+
+/// Redacts each named field into a buffer, sending it to the output along with the field's path.
+struct FieldsExtractor<'a> {
+    engine: &'a RedactionEngine,
+    name: String,
+    buffer: String,
+    output: &'a mut dyn FnMut(&str, &str),
+}
+
+impl Extractor for FieldsExtractor<'_> {
+    fn write_plain(&mut self, _text: &str) {}
+
+    fn write_classified(&mut self, _data_class: &DataClass, _text: &str) {}
+
+    fn write_field(&mut self, path: &FieldPath<'_>, data_class: &DataClass, text: &str) {
+        if self.engine.is_omitted(data_class) {
+            return;
+        }
+
+        self.buffer.clear();
+        self.engine
+            .redact_field(path, data_class, text, |s| self.buffer.push_str(s));
+        self.name.clear();
+        _ = write!(self.name, "{path}");
+        (self.output)(&self.name, &self.buffer);
+    }
+}
+
+/// Redacts the fields of a record into a buffer, noting whether they hold classified data and whether it's omitted.
+struct RecordExtractor<'a> {
+    inner: StringExtractor<'a>,
+    classified: bool,
+    omitted: bool,
+}
+
+impl RecordExtractor<'_> {
+    fn note_class(&mut self, data_class: &DataClass) {
+        self.classified = true;
+        self.omitted |= self.inner.engine.is_omitted(data_class);
+    }
+}
+
+impl Extractor for RecordExtractor<'_> {
+    fn write_plain(&mut self, text: &str) {
+        self.inner.write_plain(text);
+    }
+
+    fn write_classified(&mut self, data_class: &DataClass, text: &str) {
+        self.note_class(data_class);
+        self.inner.write_classified(data_class, text);
+    }
+
+    fn write_classified_bytes(&mut self, data_class: &DataClass, bytes: &[u8]) {
+        self.note_class(data_class);
+        self.inner.write_classified_bytes(data_class, bytes);
+    }
+
+    fn write_field(&mut self, path: &FieldPath<'_>, data_class: &DataClass, text: &str) {
+        self.note_class(data_class);
+        self.inner.write_field(path, data_class, text);
+    }
+}
+
+impl Debug for RedactionEngine {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.redactors.keys()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::{CoreTaxonomy, Insensitive, Sensitive, UnknownSensitivity};
+    use crate::taxonomy;
+    use crate::{
+        BuilderError, DynamicClassified, RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode,
+    };
+    use core::fmt::Write;
+
+    #[taxonomy(test, serde = false)]
+    enum TestTaxonomy {
+        Personal,
+    }
+
+    #[taxonomy(contoso, serde = false)]
+    enum ContosoTaxonomy {
+        #[class(name = "pii.email", id = 17)]
+        Email,
+    }
+
+    #[taxonomy(governed, serde = false)]
+    enum GovernedTaxonomy {
+        #[class(never_log, retention_days = 7)]
+        Secret,
+    }
+
+    const fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn redact_reader_should_stream_large_values() {
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::ReplaceAndTag('#')),
+            )
+            .add_taxonomy_redactor(
+                "governed",
+                SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+            )
+            .omit_class(&TestTaxonomy::Personal.data_class())
+            .set_tag_format(TagFormat::new().with_delimiters("[", "]"))
+            .collect_stats()
+            .build();
+
+        let value = "a".repeat(100_000);
+        let mut chunks = 0;
+        let mut output = String::new();
+        engine
+            .redact_reader(
+                &CoreTaxonomy::Sensitive.data_class(),
+                &mut value.as_bytes(),
+                |s| {
+                    chunks += 1;
+                    output.push_str(s);
+                },
+            )
+            .unwrap();
+        assert!(chunks > 2);
+        assert_eq!(output, format!("[core/sensitive:{}]", "#".repeat(100_000)));
+
+        // redactors without streaming support get the whole value
+        output.clear();
+        engine
+            .redact_reader(
+                &CoreTaxonomy::Insensitive.data_class(),
+                &mut b"abc".as_slice(),
+                |s| output.push_str(s),
+            )
+            .unwrap();
+        assert_eq!(output, "");
+
+        // never-log classes are still guarded
+        engine
+            .redact_reader(
+                &GovernedTaxonomy::Secret.data_class(),
+                &mut b"hunter2".as_slice(),
+                |s| output.push_str(s),
+            )
+            .unwrap();
+        assert_eq!(output, "");
+
+        // large never-log values are drained without being output
+        let mut reader = io::repeat(b'a').take(100_000);
+        engine
+            .redact_reader(&GovernedTaxonomy::Secret.data_class(), &mut reader, |s| {
+                output.push_str(s);
+            })
+            .unwrap();
+        assert_eq!(output, "");
+        assert_eq!(reader.limit(), 0);
+
+        let mut reader: &[u8] = b"unread";
+        engine
+            .redact_reader(&TestTaxonomy::Personal.data_class(), &mut reader, |_| {})
+            .unwrap();
+        assert_eq!(reader, b"unread");
+
+        let stats = engine.stats().unwrap();
+        assert_eq!(stats.redactions(), 4);
+        assert_eq!(stats.bytes_in(), 200_010);
+
+        assert_eq!(
+            engine
+                .redact_reader(
+                    &CoreTaxonomy::Insensitive.data_class(),
+                    &mut &[0xff_u8][..],
+                    |_| {}
+                )
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn observers_and_stats_should_see_every_redaction() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&events);
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+            )
+            .add_class_redactor(
+                &CoreTaxonomy::Insensitive.data_class(),
+                SimpleRedactor::new(),
+            )
+            .omit_class(&TestTaxonomy::Personal.data_class())
+            .on_redaction(move |event| {
+                recorded.lock().unwrap().push((
+                    event.data_class().clone(),
+                    event.input_len(),
+                    event.output_len(),
+                    event.is_fallback(),
+                    event.is_passthrough(),
+                ));
+            })
+            .collect_stats()
+            .build();
+
+        _ = engine.redact_to_string(&Sensitive::new("John".to_string()));
+        _ = engine.redact_to_string(&Insensitive::new("Doe".to_string()));
+        _ = engine.redact_to_string(&UnknownSensitivity::new("x".to_string()));
+        _ = engine.redact_to_string(&Personal::new("omitted".to_string()));
+        engine.redact_bytes(&CoreTaxonomy::Sensitive.data_class(), [0xab_u8], |_| {});
+        engine
+            .redact_reader(
+                &CoreTaxonomy::Sensitive.data_class(),
+                &mut &b"abc"[..],
+                |_| {},
+            )
+            .unwrap();
+        engine
+            .redact_reader(
+                &CoreTaxonomy::Insensitive.data_class(),
+                &mut &b"de"[..],
+                |_| {},
+            )
+            .unwrap();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                (CoreTaxonomy::Sensitive.data_class(), 4, 4, false, true),
+                (CoreTaxonomy::Insensitive.data_class(), 3, 3, false, false),
+                (
+                    CoreTaxonomy::UnknownSensitivity.data_class(),
+                    1,
+                    0,
+                    true,
+                    false
+                ),
+                (CoreTaxonomy::Sensitive.data_class(), 1, 2, false, false),
+                (CoreTaxonomy::Sensitive.data_class(), 3, 3, false, true),
+                (CoreTaxonomy::Insensitive.data_class(), 2, 2, false, false),
+            ]
+        );
+
+        let stats = engine.stats().unwrap();
+        assert_eq!(stats.redactions(), 6);
+        assert_eq!(
+            stats.redactions_of(&CoreTaxonomy::Sensitive.data_class()),
+            3
+        );
+        assert_eq!(stats.redactions_of(&TestTaxonomy::Personal.data_class()), 0);
+        assert_eq!(stats.fallbacks(), 1);
+        assert_eq!(stats.passthroughs(), 2);
+        assert_eq!(stats.bytes_in(), 14);
+        assert_eq!(stats.bytes_out(), 14);
+
+        assert!(RedactionEngineBuilder::new().build().stats().is_none());
+    }
+
+    #[test]
+    fn taxonomy_mapper_should_translate_classes_before_lookup() {
+        let customer_content = DataClass::new("contoso", "customer_content");
+        let mapper = TaxonomyMapper::new()
+            .add_mapping(&CoreTaxonomy::Sensitive.data_class(), &customer_content)
+            .add_mapping(
+                &CoreTaxonomy::UnknownSensitivity.data_class(),
+                &TestTaxonomy::Personal.data_class(),
+            );
+
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+            )
+            .add_class_redactor(
+                &customer_content,
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')),
+            )
+            .omit_class(&TestTaxonomy::Personal.data_class())
+            .set_taxonomy_mapper(mapper)
+            .build();
+
+        assert_eq!(
+            engine.mapped_class(&CoreTaxonomy::Sensitive.data_class()),
+            &customer_content
+        );
+        assert_eq!(
+            engine.redact_to_string(&Sensitive::new("secret".to_string())),
+            "######"
+        );
+        assert_eq!(
+            engine.exact_len(&CoreTaxonomy::Sensitive.data_class()),
+            None
+        );
+
+        let mut output = String::new();
+        engine.redact_as_parts("core", "sensitive", "abc", |s| output.push_str(s));
+        engine.redact_tags(" <core/sensitive:de>", |s| output.push_str(s));
+        assert_eq!(output, "### ##");
+
+        assert!(engine.is_omitted(&CoreTaxonomy::UnknownSensitivity.data_class()));
+        assert_eq!(
+            engine.redact_to_string(&UnknownSensitivity::new("secret".to_string())),
+            ""
+        );
+        assert_eq!(
+            engine.redact_to_string(&Insensitive::new("public".to_string())),
+            ""
+        );
+    }
+
+    #[test]
+    fn never_log_classes_should_not_pass_through() {
+        let data_class = GovernedTaxonomy::Secret.data_class();
+        assert!(data_class.never_log());
+        assert_eq!(data_class.retention_days(), Some(7));
+
+        let engine = RedactionEngineBuilder::new()
+            .set_fallback_redactor(SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough))
+            .build();
+        assert_eq!(engine.redact_to_string_as_class(&data_class, "hunter2"), "");
+        assert_eq!(
+            engine.redact_to_string(&Secret::new("hunter2".to_string())),
+            ""
+        );
+
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(&data_class, SimpleRedactor::new())
+            .build();
+        assert_eq!(
+            engine.redact_to_string_as_class(&data_class, "hunter2"),
+            "*******"
+        );
+    }
+
+    #[test]
+    fn never_log_classes_should_not_pass_through_any_path() {
+        let data_class = GovernedTaxonomy::Secret.data_class();
+        let alias = DataClass::new("corp", "password");
+        let engine = RedactionEngineBuilder::new()
+            .set_fallback_redactor(SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough))
+            .set_taxonomy_mapper(TaxonomyMapper::new().add_mapping(&alias, &data_class))
+            .build()
+            .with_never_log_classes(core::slice::from_ref(&data_class));
+
+        let mut output = String::new();
+        engine.redact_bytes(&data_class, b"hunter2", |s| output.push_str(s));
+        assert_eq!(output, "");
+
+        // Data classes parsed from tags have no metadata, and are recognized by name.
+        let mut output = String::new();
+        engine.redact_tags("pw <governed/secret:hunter2>", |s| output.push_str(s));
+        assert_eq!(output, "pw ");
+
+        assert!(engine.is_never_log(&alias));
+        assert_eq!(engine.redact_to_string_as_class(&alias, "hunter2"), "");
+    }
+
+    #[test]
+    fn try_build_should_reject_revealing_redactors_for_never_log_classes() {
+        let data_class = GovernedTaxonomy::Secret.data_class();
+        let builder = || {
+            RedactionEngineBuilder::new().add_class_redactor(
+                &data_class,
+                SimpleRedactor::with_mode(SimpleRedactorMode::PassthroughAndTag),
+            )
+        };
+
+        assert_eq!(
+            builder().try_build().unwrap_err(),
+            BuilderError::RevealingRedactor(data_class.clone())
+        );
+
+        let engine = builder().build();
+        assert_eq!(engine.redact_to_string_as_class(&data_class, "hunter2"), "");
+
+        let mut builder = RedactionEngineBuilder::new();
+        assert_eq!(
+            builder
+                .try_add_class_redactor(
+                    &data_class,
+                    SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+                )
+                .unwrap_err(),
+            BuilderError::RevealingRedactor(data_class.clone())
+        );
+        assert!(builder.try_build().is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "registry")]
+    fn from_policy_should_reject_revealing_redactors_for_never_log_classes() {
+        let policy = crate::RedactionPolicy::new()
+            .with_class("governed/secret", crate::RedactorSpec::new("passthrough"));
+        assert_eq!(
+            RedactionEngineBuilder::from_policy(&policy, &crate::RedactorRegistry::new())
+                .unwrap_err(),
+            BuilderError::RevealingRedactor(DataClass::new("governed", "secret"))
+        );
+    }
+
+    #[test]
+    fn build_strict_should_reject_revealing_redactors_for_never_log_classes() {
+        let data_class = GovernedTaxonomy::Secret.data_class();
+        let result = RedactionEngineBuilder::new()
+            .set_fallback_redactor(SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough))
+            .build_strict(core::slice::from_ref(&data_class));
+        assert_eq!(
+            result.unwrap_err(),
+            BuilderError::RevealingRedactor(data_class)
+        );
+    }
+
+    #[test]
+    fn custom_class_names_should_be_used_throughout() {
+        let data_class = ContosoTaxonomy::Email.data_class();
+        assert_eq!(data_class, DataClass::new("contoso", "pii.email"));
+        assert_eq!(data_class.id(), Some(17));
+        assert_eq!(Email::<u8>::data_class().id(), Some(17));
+        assert_eq!(
+            format!("{:?}", Email::new("a@b.com")),
+            "<contoso/pii.email:REDACTED>"
+        );
+
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(&data_class, SimpleRedactor::new())
+            .build();
+
+        let mut output = String::new();
+        engine.redact_tags("to <contoso/pii.email:a@b.com>", |s| output.push_str(s));
+        assert_eq!(output, "to *******");
+    }
+
+    #[test]
+    fn engine_should_be_shareable_between_threads() {
+        const { assert_send_sync::<RedactionEngine>() };
+
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+            )
+            .build()
+            .into_shared();
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let engine = Arc::clone(&engine);
+                std::thread::spawn(move || collect_output(&engine, &Sensitive::new("abc")))
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), "***");
+        }
+    }
+
+    #[test]
+    fn redact_tags_should_redact_embedded_tags() {
+        let tagging_engine = RedactionEngineBuilder::new()
+            .set_fallback_redactor(SimpleRedactor::with_mode(
+                SimpleRedactorMode::PassthroughAndTag,
+            ))
+            .build();
+
+        let mut tagged = String::new();
+        tagging_engine.display_redacted(&Sensitive::new("jdoe"), |s| tagged.push_str(s));
+        tagged.push_str(" saw ");
+        tagging_engine.display_redacted(&Insensitive::new(42), |s| tagged.push_str(s));
+        tagged.push_str(" and ");
+        tagging_engine.display_redacted(&UnknownSensitivity::new("x"), |s| tagged.push_str(s));
+        assert_eq!(
+            tagged,
+            "<core/sensitive:jdoe> saw <core/insensitive:42> and <core/unknown_sensitivity:x>"
+        );
+
+        let final_engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+            )
+            .add_class_redactor(
+                &CoreTaxonomy::Insensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+            )
+            .omit_class(&CoreTaxonomy::UnknownSensitivity.data_class())
+            .build();
+
+        let mut output = String::new();
+        final_engine.redact_tags(&tagged, |s| output.push_str(s));
+        assert_eq!(output, "**** saw 42 and ");
+    }
+
+    #[test]
+    fn redact_tags_should_use_the_engine_tag_format() {
+        let format = TagFormat::new()
+            .with_delimiters("{{", "}}")
+            .with_separator(" = ")
+            .include_redactor(true);
+
+        let tagging_engine = RedactionEngineBuilder::new()
+            .set_fallback_redactor(SimpleRedactor::with_mode(
+                SimpleRedactorMode::PassthroughAndTag,
+            ))
+            .set_tag_format(format.clone())
+            .build();
+
+        let mut tagged = String::new();
+        tagging_engine.display_redacted(&Sensitive::new("a}} b"), |s| tagged.push_str(s));
+        tagged.push_str(" <core/sensitive:c>");
+        assert_eq!(
+            tagged,
+            r"{{core/sensitive/simple = a\}\} b}} <core/sensitive:c>"
+        );
+
+        let final_engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+            )
+            .set_tag_format(format)
+            .build();
+
+        let mut output = String::new();
+        final_engine.redact_tags(&tagged, |s| output.push_str(s));
+        assert_eq!(output, "***** <core/sensitive:c>");
+    }
+
+    #[test]
+    fn redact_tags_should_fail_closed_on_hostile_values() {
+        let tagging_engine = RedactionEngineBuilder::new()
+            .set_fallback_redactor(SimpleRedactor::with_mode(
+                SimpleRedactorMode::PassthroughAndTag,
+            ))
+            .build();
+
+        let mut tagged = String::new();
+        tagging_engine.display_redacted(&Sensitive::new("a> secret \\"), |s| tagged.push_str(s));
+        tagged.push_str(" ok ");
+
+        let final_engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+            )
+            .build();
+
+        let mut output = String::new();
+        final_engine.redact_tags(&tagged, |s| output.push_str(s));
+        assert_eq!(output, "*********** ok ");
+
+        // truncated and unbalanced tags are redacted up to the end of the text
+        for text in [
+            "ok <core/sensitive:secret",
+            "ok <core/sensitive:a<b> secret",
+        ] {
+            let mut output = String::new();
+            final_engine.redact_tags(text, |s| output.push_str(s));
+            assert!(output.starts_with("ok *"), "{output}");
+            assert!(!output.contains("secret"), "{output}");
+        }
+    }
+
+    #[test]
+    fn nested_values_should_use_most_restrictive_redactor() {
+        let low = DataClass::new("test", "low").with_level(1);
+        let high = DataClass::new("test", "high").with_level(2);
+
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &low,
+                SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+            )
+            .add_class_redactor(
+                &high,
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')),
+            )
+            .build();
+
+        let value = DynamicClassified::new(DynamicClassified::new("abc", high), low);
+
+        let mut output = String::new();
+        engine.display_redacted_nested(&value, |s| output.push_str(s));
+        assert_eq!(output, "###");
+
+        output.clear();
+        engine.debug_redacted_nested(&value, |s| output.push_str(s));
+        assert_eq!(output, "#####");
+
+        // without resolution, the outer class only ever sees the inner placeholder
+        output.clear();
+        engine.debug_redacted(&value, |s| output.push_str(s));
+        assert_eq!(output, "<test/high:REDACTED>");
+    }
+
+    #[test]
+    fn redact_to_string_should_match_extract_redacted() {
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Insert("[x]".to_string())),
+            )
+            .add_class_redactor(
+                &CoreTaxonomy::Insensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+            )
+            .build();
+
+        let value = (Sensitive::new("jdoe"), Insensitive::new("ops"), "plain");
+        let mut expected = String::new();
+        engine.extract_redacted(&value, |s| expected.push_str(s));
+
+        assert_eq!(engine.redact_to_string(&value), expected);
+        assert_eq!(engine.redact_to_string(&value), "([x], ops, plain)");
+
+        let mut output = String::from("value=");
+        engine.redact_into(&value, &mut output);
+        assert_eq!(output, "value=([x], ops, plain)");
+    }
+
+    #[test]
+    fn bytes_should_reach_redactor_unconverted() {
+        struct Key(Vec<u8>);
+
+        impl Extract for Key {
+            fn extract(&self, extractor: &mut dyn Extractor) {
+                extractor.write_plain("key=");
+                extractor.write_classified_bytes(&CoreTaxonomy::Sensitive.data_class(), &self.0);
+            }
+        }
+
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+            )
+            .omit_class(&CoreTaxonomy::UnknownSensitivity.data_class())
+            .build();
+
+        let mut output = String::new();
+        engine.bytes_redacted(&Sensitive::new(vec![0xca_u8, 0xfe]), |s| output.push_str(s));
+        assert_eq!(output, "cafe");
+
+        let mut output = String::new();
+        engine.bytes_redacted(&UnknownSensitivity::new([1_u8]), |s| output.push_str(s));
+        assert_eq!(output, "");
+
+        assert_eq!(engine.redact_to_string(&Key(vec![0x01, 0x23])), "key=0123");
+        let mut output = String::new();
+        engine.extract_redacted(&Key(vec![0xab]), |s| output.push_str(s));
+        assert_eq!(output, "key=ab");
+    }
+
+    #[test]
+    fn redact_to_string_as_class_should_match_redact() {
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Insert("[x]".to_string())),
+            )
+            .omit_class(&CoreTaxonomy::UnknownSensitivity.data_class())
+            .build();
+
+        assert_eq!(
+            engine.redact_to_string_as_class(&CoreTaxonomy::Sensitive.data_class(), "jdoe"),
+            "[x]"
+        );
+        assert_eq!(
+            engine.redact_to_string_as_class(&CoreTaxonomy::Insensitive.data_class(), "ops"),
+            ""
+        );
+        assert_eq!(
+            engine.redact_to_string_as_class(
+                &CoreTaxonomy::UnknownSensitivity.data_class(),
+                String::from("x")
+            ),
+            ""
+        );
+    }
+
+    #[test]
+    fn multi_classified_values_should_use_most_restrictive_class() {
+        let low = DataClass::new("test", "low").with_level(1);
+        let high = DataClass::new("test", "high").with_level(2);
+        let unmatched = DataClass::new("test", "unmatched").with_level(3);
+        let omitted = DataClass::new("test", "omitted");
+
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &low,
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('-')),
+            )
+            .add_class_redactor(
+                &high,
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')),
+            )
+            .omit_class(&omitted)
+            .build();
+
+        let value = MultiClassified::new("abc", low.clone()).with_class(high.clone());
+        assert_eq!(engine.effective_class(value.data_classes()), Some(&high));
+
+        let mut output = String::new();
+        engine.display_redacted_multi(&value, |s| output.push_str(s));
+        assert_eq!(output, "###");
+
+        output.clear();
+        engine.debug_redacted_multi(&value, |s| output.push_str(s));
+        assert_eq!(output, "#####");
+
+        assert_eq!(
+            engine.effective_class(&[low, unmatched.clone()]),
+            Some(&unmatched)
+        );
+        assert_eq!(
+            engine.effective_class(&[high.clone(), omitted.clone()]),
+            Some(&high)
+        );
+        assert_eq!(
+            engine.effective_class(&[omitted.clone(), DataClass::new("test", "other")]),
+            Some(&omitted)
+        );
+        assert_eq!(engine.effective_class(&[]), None);
+    }
 
-        Self {
-            redactors,
-            fallback,
-        }
+    #[test]
+    fn multi_classified_values_should_redact_alike_when_extracted() {
+        let low = DataClass::new("test", "low").with_level(1);
+        let unmatched = DataClass::new("test", "unmatched").with_level(3);
+
+        // The less restrictive class has a passthrough redactor, while the most restrictive one falls
+        // back to erasing.
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &low,
+                SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+            )
+            .build();
+
+        let value = MultiClassified::new("abc", low).with_class(unmatched);
+
+        let mut direct = String::new();
+        engine.display_redacted_multi(&value, |s| direct.push_str(s));
+        let mut extracted = String::new();
+        engine.extract_redacted(&value, |s| extracted.push_str(s));
+
+        assert_eq!(direct, "");
+        assert_eq!(extracted, direct);
     }
 
-    /// Redacts the output of a classified value's [`Debug`] trait.
-    ///
-    /// Given a classified value whose payload implements the [`Debug`] trait, this method will
-    /// redact the output of that trait using the redactor registered for the data class of the value.
-    #[expect(
-        clippy::cast_possible_truncation,
-        reason = "Converting from u64 to usize, value is known to be <= 128"
-    )]
-    pub fn debug_redacted<C, T>(&self, value: &C, output: impl FnMut(&str))
-    where
-        C: Classified<T>,
-        T: Debug,
-    {
-        value.visit(|v| {
-            let mut local_buf = [0u8; 128];
-            let (written, amount) = {
-                let mut cursor = Cursor::new(&mut local_buf[..]);
-                (
-                    write!(&mut cursor, "{v:?}").is_ok(),
-                    cursor.position() as usize,
-                )
-            };
+    #[test]
+    fn extract_redacted_should_redact_each_class() {
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+            )
+            .add_class_redactor(
+                &CoreTaxonomy::Insensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+            )
+            .omit_class(&CoreTaxonomy::UnknownSensitivity.data_class())
+            .build();
 
-            if written {
-                // SAFETY: We know the buffer contains valid UTF-8 because the Debug impl can only write valid UTF-8.
-                let s = unsafe { core::str::from_utf8_unchecked(&local_buf[..amount]) };
+        let mut map = std::collections::BTreeMap::new();
+        _ = map.insert(Sensitive::new("jdoe"), UnknownSensitivity::new("x"));
+        _ = map.insert(Sensitive::new("root"), UnknownSensitivity::new("y"));
 
-                self.redact(&value.data_class(), s, output);
-            } else {
-                // If the value is too large to fit in the buffer, we fall back to using the debug format directly.
-                self.redact(&value.data_class(), format!("{v:?}"), output);
-            }
-        });
+        let mut output = String::new();
+        engine.extract_redacted(&map, |s| output.push_str(s));
+        assert_eq!(output, "{****: , ****: }");
+
+        output.clear();
+        engine.extract_redacted(&Insensitive::new(7), |s| output.push_str(s));
+        assert_eq!(output, "7");
     }
 
-    /// Redacts the output of a classified value's [`Display`] trait.
-    ///
-    /// Given a classified value whose payload implements the [`Display`] trait, this method will
-    /// redact the output of that trait using the redactor registered for the data class of the value.
-    #[expect(
-        clippy::cast_possible_truncation,
-        reason = "Converting from u64 to usize, value is known to be <= 128"
-    )]
-    pub fn display_redacted<C, T>(&self, value: &C, output: impl FnMut(&str))
-    where
-        C: Classified<T>,
-        T: Display,
-    {
-        value.visit(|v| {
-            let mut local_buf = [0u8; 128];
-            let (written, amount) = {
-                let mut cursor = Cursor::new(&mut local_buf[..]);
-                (
-                    write!(&mut cursor, "{v}").is_ok(),
-                    cursor.position() as usize,
-                )
-            };
+    #[test]
+    fn context_should_reach_redactors_on_every_path() {
+        struct TenantRedactor;
 
-            if written {
-                // SAFETY: We know the buffer contains valid UTF-8 because the Debug impl can only write valid UTF-8.
-                let s = unsafe { core::str::from_utf8_unchecked(&local_buf[..amount]) };
+        impl Redactor for TenantRedactor {
+            fn redact(&self, data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
+                self.redact_ctx(data_class, value, &RedactionContext::new(), output);
+            }
 
-                self.redact(&value.data_class(), s, output);
-            } else {
-                // If the value is too large to fit in the buffer, we fall back to using the debug format directly.
-                self.redact(&value.data_class(), format!("{v}"), output);
+            fn redact_ctx(
+                &self,
+                _data_class: &DataClass,
+                value: &str,
+                context: &RedactionContext,
+                output: &mut dyn FnMut(&str),
+            ) {
+                output(context.get(RedactionContext::TENANT).unwrap_or("none"));
+                output(":");
+                output(&"*".repeat(value.len()));
             }
+        }
+
+        let data_class = CoreTaxonomy::Sensitive.data_class();
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(&data_class, TenantRedactor)
+            .build();
+        let context = RedactionContext::new().with_value(RedactionContext::TENANT, "contoso");
+
+        let mut output = String::new();
+        engine.extract_redacted_with_context(&Sensitive::new("ab"), &context, |s| {
+            output.push_str(s);
         });
-    }
+        output.push(' ');
+        engine.redact_field_with_context(
+            &FieldPath::new::<u8>("field"),
+            &data_class,
+            "ab",
+            &context,
+            |s| output.push_str(s),
+        );
+        output.push(' ');
+        engine.redact_bytes_with_context(&data_class, [0xab], &context, |s| output.push_str(s));
+        output.push(' ');
+        engine
+            .redact_reader_with_context(&data_class, &mut &b"ab"[..], &context, |s| {
+                output.push_str(s);
+            })
+            .unwrap();
+        output.push(' ');
+        engine.redact_tags_with_context("<core/sensitive:ab>", &context, |s| output.push_str(s));
+        output.push(' ');
+        engine.redact_as_parts_with_context("core", "sensitive", "ab", &context, |s| {
+            output.push_str(s);
+        });
+        assert_eq!(
+            output,
+            "contoso:** contoso:** contoso:** contoso:** contoso:** contoso:**"
+        );
 
-    /// Redacts a string with an explicit data classification, sending the results to the output callback.
-    pub fn redact(
-        &self,
-        data_class: &DataClass,
-        value: impl AsRef<str>,
-        mut output: impl FnMut(&str),
-    ) {
-        let redactor = self.redactors.get(data_class).unwrap_or(&self.fallback);
-        redactor.redact(data_class, value.as_ref(), &mut output);
+        assert_eq!(engine.redact_to_string(&Sensitive::new("ab")), "none:**");
     }
 
-    /// The exact length of the redacted output if it is a constant.
-    ///
-    /// This can be used as a hint to optimize buffer allocations.
-    #[must_use]
-    pub fn exact_len(&self, data_class: &DataClass) -> Option<usize> {
-        let redactor = self.redactors.get(data_class).unwrap_or(&self.fallback);
-        redactor.exact_len()
-    }
-}
+    #[test]
+    fn redactors_should_see_the_engine_tag_format_in_the_context() {
+        let data_class = CoreTaxonomy::Sensitive.data_class();
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &data_class,
+                SimpleRedactor::with_mode(SimpleRedactorMode::PassthroughAndTag),
+            )
+            .set_tag_format(TagFormat::new().with_delimiters("[", "]"))
+            .build();
+        let context = RedactionContext::new()
+            .with_value(RedactionContext::TENANT, "contoso")
+            .with_tag_format(TagFormat::new().with_delimiters("{", "}"));
 
-impl Debug for RedactionEngine {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_list().entries(self.redactors.keys()).finish()
+        let mut output = String::new();
+        engine.redact(&data_class, "ab", |s| output.push_str(s));
+        engine.redact_with_context(&data_class, "cd", &context, |s| output.push_str(s));
+        engine.redact_bytes_with_context(&data_class, [0xef], &context, |s| output.push_str(s));
+        assert_eq!(
+            output,
+            "[core/sensitive:ab][core/sensitive:cd][core/sensitive:ef]"
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::core_taxonomy::{CoreTaxonomy, Insensitive, Sensitive, UnknownSensitivity};
-    use crate::taxonomy;
-    use crate::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
-    use core::fmt::Write;
+    #[test]
+    fn redact_as_parts_should_match_redact() {
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+            )
+            .set_fallback_redactor(SimpleRedactor::with_mode(SimpleRedactorMode::EraseAndTag))
+            .omit_class(&CoreTaxonomy::UnknownSensitivity.data_class())
+            .build();
 
-    #[taxonomy(test, serde = false)]
-    enum TestTaxonomy {
-        Personal,
+        let taxonomy = "core".to_string();
+        for name in ["sensitive", "insensitive", "unknown_sensitivity"] {
+            let mut by_parts = String::new();
+            engine.redact_as_parts(&taxonomy, name, "abc", |s| by_parts.push_str(s));
+
+            let mut by_class = String::new();
+            engine.redact(
+                &DataClass::from_parts("core", name.to_string()),
+                "abc",
+                |s| {
+                    by_class.push_str(s);
+                },
+            );
+
+            assert_eq!(by_parts, by_class);
+        }
+
+        let mut output = String::new();
+        engine.redact_as_parts("core", "insensitive", "abc", |s| output.push_str(s));
+        assert_eq!(output, "<core/insensitive:>");
     }
 
     fn create_test_redactor(mode: SimpleRedactorMode) -> SimpleRedactor {
@@ -209,7 +2227,7 @@ mod tests {
         let asterisk_redactor = create_test_redactor(SimpleRedactorMode::Replace('*'));
         let fallback_redactor = create_test_redactor(SimpleRedactorMode::Erase);
 
-        let mut redactors = HashMap::<DataClass, Box<dyn Redactor + Send + Sync>>::new();
+        let mut redactors = HashMap::<DataClass, Box<dyn Redactor>>::new();
         _ = redactors.insert(Sensitive::<()>::data_class(), Box::new(asterisk_redactor));
 
         let engine = RedactionEngine::new(redactors, Box::new(fallback_redactor));
@@ -223,7 +2241,7 @@ mod tests {
         let asterisk_redactor = create_test_redactor(SimpleRedactorMode::Replace('*'));
         let fallback_redactor = create_test_redactor(SimpleRedactorMode::Erase);
 
-        let mut redactors = HashMap::<DataClass, Box<dyn Redactor + Send + Sync>>::new();
+        let mut redactors = HashMap::<DataClass, Box<dyn Redactor>>::new();
         _ = redactors.insert(Sensitive::<()>::data_class(), Box::new(asterisk_redactor));
 
         let engine = RedactionEngine::new(redactors, Box::new(fallback_redactor));
@@ -239,7 +2257,7 @@ mod tests {
         let asterisk_redactor = create_test_redactor(SimpleRedactorMode::Replace('*'));
         let fallback_redactor = create_test_redactor(SimpleRedactorMode::Replace('X'));
 
-        let mut redactors = HashMap::<DataClass, Box<dyn Redactor + Send + Sync>>::new();
+        let mut redactors = HashMap::<DataClass, Box<dyn Redactor>>::new();
         _ = redactors.insert(Sensitive::<()>::data_class(), Box::new(asterisk_redactor));
 
         let engine = RedactionEngine::new(redactors, Box::new(fallback_redactor));
@@ -255,7 +2273,7 @@ mod tests {
         let asterisk_redactor = create_test_redactor(SimpleRedactorMode::Replace('*'));
         let fallback_redactor = create_test_redactor(SimpleRedactorMode::Erase);
 
-        let mut redactors = HashMap::<DataClass, Box<dyn Redactor + Send + Sync>>::new();
+        let mut redactors = HashMap::<DataClass, Box<dyn Redactor>>::new();
         _ = redactors.insert(Sensitive::<()>::data_class(), Box::new(asterisk_redactor));
 
         let engine = RedactionEngine::new(redactors, Box::new(fallback_redactor));
@@ -271,7 +2289,7 @@ mod tests {
         let asterisk_redactor = create_test_redactor(SimpleRedactorMode::Replace('*'));
         let fallback_redactor = create_test_redactor(SimpleRedactorMode::Replace('?'));
 
-        let mut redactors = HashMap::<DataClass, Box<dyn Redactor + Send + Sync>>::new();
+        let mut redactors = HashMap::<DataClass, Box<dyn Redactor>>::new();
         _ = redactors.insert(Sensitive::<()>::data_class(), Box::new(asterisk_redactor));
 
         let engine = RedactionEngine::new(redactors, Box::new(fallback_redactor));
@@ -288,7 +2306,7 @@ mod tests {
         let hash_redactor = create_test_redactor(SimpleRedactorMode::Replace('#'));
         let fallback_redactor = create_test_redactor(SimpleRedactorMode::Erase);
 
-        let mut redactors = HashMap::<DataClass, Box<dyn Redactor + Send + Sync>>::new();
+        let mut redactors = HashMap::<DataClass, Box<dyn Redactor>>::new();
         _ = redactors.insert(Sensitive::<()>::data_class(), Box::new(asterisk_redactor));
         _ = redactors.insert(TestTaxonomy::Personal.data_class(), Box::new(hash_redactor));
 
@@ -311,7 +2329,7 @@ mod tests {
         let passthrough_redactor = create_test_redactor(SimpleRedactorMode::Passthrough);
         let fallback_redactor = create_test_redactor(SimpleRedactorMode::Erase);
 
-        let mut redactors = HashMap::<DataClass, Box<dyn Redactor + Send + Sync>>::new();
+        let mut redactors = HashMap::<DataClass, Box<dyn Redactor>>::new();
         _ = redactors.insert(Sensitive::<()>::data_class(), Box::new(insert_redactor));
         _ = redactors.insert(
             UnknownSensitivity::<()>::data_class(),
@@ -338,7 +2356,7 @@ mod tests {
         let asterisk_redactor = create_test_redactor(SimpleRedactorMode::Replace('*'));
         let fallback_redactor = create_test_redactor(SimpleRedactorMode::Erase);
 
-        let mut redactors = HashMap::<DataClass, Box<dyn Redactor + Send + Sync>>::new();
+        let mut redactors = HashMap::<DataClass, Box<dyn Redactor>>::new();
         _ = redactors.insert(Sensitive::<()>::data_class(), Box::new(asterisk_redactor));
 
         let engine = RedactionEngine::new(redactors, Box::new(fallback_redactor));
@@ -354,7 +2372,7 @@ mod tests {
         let asterisk_redactor = create_test_redactor(SimpleRedactorMode::Replace('*'));
         let fallback_redactor = create_test_redactor(SimpleRedactorMode::Erase);
 
-        let mut redactors = HashMap::<DataClass, Box<dyn Redactor + Send + Sync>>::new();
+        let mut redactors = HashMap::<DataClass, Box<dyn Redactor>>::new();
         _ = redactors.insert(Sensitive::<()>::data_class(), Box::new(asterisk_redactor));
 
         let engine = RedactionEngine::new(redactors, Box::new(fallback_redactor));
@@ -369,7 +2387,7 @@ mod tests {
         let passthrough_redactor = create_test_redactor(SimpleRedactorMode::Passthrough);
         let fallback_redactor = create_test_redactor(SimpleRedactorMode::Erase);
 
-        let mut redactors = HashMap::<DataClass, Box<dyn Redactor + Send + Sync>>::new();
+        let mut redactors = HashMap::<DataClass, Box<dyn Redactor>>::new();
         _ = redactors.insert(
             Sensitive::<()>::data_class(),
             Box::new(passthrough_redactor),
@@ -458,7 +2476,7 @@ mod tests {
         let hash_redactor = create_test_redactor(SimpleRedactorMode::Replace('#'));
         let fallback_redactor = create_test_redactor(SimpleRedactorMode::Erase);
 
-        let mut redactors = HashMap::<DataClass, Box<dyn Redactor + Send + Sync>>::new();
+        let mut redactors = HashMap::<DataClass, Box<dyn Redactor>>::new();
         _ = redactors.insert(Sensitive::<()>::data_class(), Box::new(asterisk_redactor));
         _ = redactors.insert(TestTaxonomy::Personal.data_class(), Box::new(hash_redactor));
 
@@ -480,7 +2498,7 @@ mod tests {
     #[test]
     fn test_debug_trait_with_empty_redactors() {
         let fallback_redactor = create_test_redactor(SimpleRedactorMode::Erase);
-        let redactors = HashMap::<DataClass, Box<dyn Redactor + Send + Sync>>::new();
+        let redactors = HashMap::<DataClass, Box<dyn Redactor>>::new();
 
         let engine = RedactionEngine::new(redactors, Box::new(fallback_redactor));
 
@@ -500,7 +2518,7 @@ mod tests {
         let fallback_redactor =
             create_test_redactor(SimpleRedactorMode::Insert("REDACTED".to_string()));
 
-        let mut redactors = HashMap::<DataClass, Box<dyn Redactor + Send + Sync>>::new();
+        let mut redactors = HashMap::<DataClass, Box<dyn Redactor>>::new();
         _ = redactors.insert(Sensitive::<()>::data_class(), Box::new(erase_redactor));
         _ = redactors.insert(Insensitive::<()>::data_class(), Box::new(replace_redactor));
         _ = redactors.insert(
@@ -525,10 +2543,14 @@ mod tests {
             "Passthrough redactor should return None"
         );
 
-        // Test exact_len for fallback redactor (Insert mode) - should return None
+        // Test exact_len for fallback redactor (Insert mode) - should return the length of the inserted text
         let unknown_class = UnknownSensitivity::<()>::data_class();
         let fallback_len = engine.exact_len(&unknown_class);
-        assert_eq!(fallback_len, None, "Insert redactor should return None");
+        assert_eq!(
+            fallback_len,
+            Some(8),
+            "Insert redactor should return Some(8)"
+        );
 
         // Verify the actual behavior matches the exact_len hint
         let sensitive_data = Sensitive::new("test".to_string());
@@ -545,6 +2567,48 @@ mod tests {
         assert_eq!(fallback_result, "REDACTED");
     }
 
+    #[test]
+    fn len_hint_should_account_for_input_and_tag_format() {
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::ReplaceAndTag('*')),
+            )
+            .omit_class(&CoreTaxonomy::UnknownSensitivity.data_class())
+            .set_tag_format(TagFormat::new().with_delimiters("[", "]"))
+            .build();
+
+        let data_class = CoreTaxonomy::Sensitive.data_class();
+        let output = engine.redact_to_string_as_class(&data_class, "secret");
+        assert_eq!(output, "[core/sensitive:******]");
+        assert_eq!(engine.exact_len(&data_class), None);
+        assert_eq!(engine.len_hint(&data_class, 6), Some(output.len()));
+
+        assert_eq!(
+            engine.len_hint(&CoreTaxonomy::UnknownSensitivity.data_class(), 6),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn len_hint_should_account_for_never_log_classes() {
+        let data_class = GovernedTaxonomy::Secret.data_class();
+        let engine = RedactionEngineBuilder::new()
+            .set_fallback_redactor(SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough))
+            .build();
+
+        assert_eq!(engine.redact_to_string_as_class(&data_class, "hunter2"), "");
+        assert_eq!(engine.exact_len(&data_class), Some(0));
+        assert_eq!(engine.len_hint(&data_class, 7), Some(0));
+
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(&data_class, SimpleRedactor::new())
+            .build();
+
+        assert_eq!(engine.exact_len(&data_class), None);
+        assert_eq!(engine.len_hint(&data_class, 7), Some(7));
+    }
+
     #[test]
     fn test_long_strings() {
         let engine = RedactionEngineBuilder::new()
@@ -573,4 +2637,260 @@ mod tests {
         let expected_display_output = format!("<core/sensitive:{long_string}>");
         assert_eq!(output_buffer, expected_display_output);
     }
+
+    #[test]
+    fn test_omitted_classes_produce_no_output() {
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+            )
+            .omit_class(&CoreTaxonomy::Sensitive.data_class())
+            .set_fallback_redactor(SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough))
+            .build();
+
+        assert!(engine.is_omitted(&CoreTaxonomy::Sensitive.data_class()));
+        assert!(!engine.is_omitted(&CoreTaxonomy::Insensitive.data_class()));
+        assert_eq!(
+            engine.exact_len(&CoreTaxonomy::Sensitive.data_class()),
+            Some(0)
+        );
+
+        let sensitive_data = Sensitive::new("secret".to_string());
+        assert_eq!(collect_output(&engine, &sensitive_data), "");
+
+        let insensitive_data = Insensitive::new("public".to_string());
+        assert_eq!(collect_output(&engine, &insensitive_data), "public");
+    }
+
+    #[test]
+    fn test_redact_unclassified_uses_heuristic_classifier() {
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+            )
+            .set_heuristic_classifier(
+                HeuristicClassifier::new()
+                    .add_pattern("password", &CoreTaxonomy::Sensitive.data_class()),
+            )
+            .build();
+
+        assert_eq!(
+            engine.classify_field("user_password"),
+            Some(&CoreTaxonomy::Sensitive.data_class())
+        );
+        assert_eq!(engine.classify_field("user_name"), None);
+
+        let mut output = String::new();
+        engine.redact_unclassified("user_password", "hunter2", |s| output.push_str(s));
+        assert_eq!(output, "*******");
+
+        output.clear();
+        engine.redact_unclassified("user_name", "jdoe", |s| output.push_str(s));
+        assert_eq!(output, "jdoe");
+    }
+
+    #[test]
+    fn redact_record_should_redact_each_field() {
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+            )
+            .omit_class(&CoreTaxonomy::UnknownSensitivity.data_class())
+            .set_heuristic_classifier(
+                HeuristicClassifier::new()
+                    .add_pattern("password", &CoreTaxonomy::Sensitive.data_class())
+                    .add_pattern("notes", &CoreTaxonomy::UnknownSensitivity.data_class()),
+            )
+            .build();
+
+        let (user, email) = (Sensitive::new("jdoe"), Sensitive::new("jdoe@contoso.com"));
+        let hidden = UnknownSensitivity::new("hidden");
+        let mut sink = Vec::new();
+        for _ in 0..2 {
+            engine.redact_record(
+                [
+                    ("user", &user as &dyn Extract),
+                    ("email", &email),
+                    ("hidden", &hidden),
+                    ("password", &"hunter2"),
+                    ("notes", &"vip"),
+                    ("count", &Some(3)),
+                ],
+                &mut sink,
+            );
+        }
+
+        let expected = [
+            ("user", "****"),
+            ("email", "****************"),
+            ("password", "*******"),
+            ("count", "Some(3)"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+        assert_eq!(sink, [expected.clone(), expected].concat());
+    }
+
+    #[test]
+    fn test_redact_unclassified_without_heuristic_classifier() {
+        let engine = RedactionEngineBuilder::new().build();
+
+        assert_eq!(engine.classify_field("password"), None);
+
+        let mut output = String::new();
+        engine.redact_unclassified("password", "hunter2", |s| output.push_str(s));
+        assert_eq!(output, "hunter2");
+    }
+
+    #[test]
+    fn redact_fields_should_name_nested_fields() {
+        #[derive(crate::Extract)]
+        struct Address {
+            #[classified(CoreTaxonomy::Sensitive)]
+            street: &'static str,
+            zip: Sensitive<&'static str>,
+        }
+
+        #[derive(crate::Extract)]
+        struct Customer {
+            id: u32,
+            address: Address,
+            #[classified(CoreTaxonomy::UnknownSensitivity)]
+            notes: &'static str,
+            tags: Vec<Sensitive<&'static str>>,
+        }
+
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+            )
+            .add_field_redactor::<Customer>(
+                "address.zip",
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')),
+            )
+            .omit_class(&CoreTaxonomy::UnknownSensitivity.data_class())
+            .build();
+
+        let customer = Customer {
+            id: 7,
+            address: Address {
+                street: "1 Main",
+                zip: Sensitive::new("98052"),
+            },
+            notes: "vip",
+            tags: vec![Sensitive::new("a"), Sensitive::new("bc")],
+        };
+
+        let mut pairs = Vec::new();
+        engine.redact_fields(&customer, |name, value| {
+            pairs.push(format!("{name}={value}"));
+        });
+        assert_eq!(
+            pairs,
+            [
+                "address.street=******",
+                "address.zip=#####",
+                "tags=*",
+                "tags=**"
+            ]
+        );
+
+        assert_eq!(
+            engine.redact_to_string(&customer),
+            "Customer { id: 7, address: Address { street: ******, zip: ##### }, notes: , tags: [*, **] }"
+        );
+    }
+
+    #[test]
+    fn fields_should_use_their_own_redactors() {
+        struct Address {
+            street: &'static str,
+            zip: &'static str,
+        }
+
+        impl Extract for Address {
+            fn extract(&self, extractor: &mut dyn Extractor) {
+                let data_class = CoreTaxonomy::Sensitive.data_class();
+                extractor.write_field(&FieldPath::new::<Self>("street"), &data_class, self.street);
+                extractor.write_plain(" ");
+                extractor.write_field(&FieldPath::new::<Self>("zip"), &data_class, self.zip);
+            }
+        }
+
+        struct Parcel {
+            zip: &'static str,
+        }
+
+        impl Extract for Parcel {
+            fn extract(&self, extractor: &mut dyn Extractor) {
+                let data_class = CoreTaxonomy::Sensitive.data_class();
+                extractor.write_field(&FieldPath::new::<Self>("zip"), &data_class, self.zip);
+            }
+        }
+
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+            )
+            .add_field_redactor::<Address>(
+                "zip",
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')),
+            )
+            .build();
+
+        let address = Address {
+            street: "1 Main",
+            zip: "98052",
+        };
+
+        assert_eq!(engine.redact_to_string(&address), "****** #####");
+
+        let mut output = String::new();
+        engine.extract_redacted(&address, |s| output.push_str(s));
+        assert_eq!(output, "****** #####");
+
+        // fields are keyed by the type declaring them
+        assert_eq!(engine.redact_to_string(&Parcel { zip: "98052" }), "*****");
+
+        let engine = RedactionEngineBuilder::new()
+            .add_field_redactor::<Address>("zip", crate::TruncatingRedactor::new(3))
+            .omit_class(&CoreTaxonomy::Sensitive.data_class())
+            .build();
+        assert_eq!(engine.redact_to_string(&address), " ");
+    }
+
+    #[test]
+    fn field_redactors_should_not_weaken_class_redactors() {
+        #[derive(crate::Extract)]
+        struct Address {
+            #[classified(CoreTaxonomy::Sensitive)]
+            zip: &'static str,
+        }
+
+        let address = Address { zip: "98052" };
+
+        // the class redactor doesn't reveal its input, so it applies
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+            )
+            .add_field_redactor::<Address>("zip", crate::TruncatingRedactor::new(3))
+            .build();
+        assert_eq!(engine.redact_to_string(&address), "Address { zip: ***** }");
+
+        // both may reveal their input, so the field redactor applies
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+            )
+            .add_field_redactor::<Address>("zip", crate::TruncatingRedactor::new(3))
+            .build();
+        assert_eq!(engine.redact_to_string(&address), "Address { zip: 980 }");
+    }
 }
@@ -1,13 +1,27 @@
-use crate::DataClass;
-use crate::redaction_engine::RedactionEngine;
-use crate::{Redactor, SimpleRedactor, SimpleRedactorMode};
+use crate::core_taxonomy::CoreTaxonomy;
+use crate::redaction_engine::{FieldRedactors, RedactionEngine};
+use crate::redaction_event::RedactionObserver;
+use crate::redaction_policy::parse_data_class;
+use crate::{
+    BuilderError, DataClass, Environment, HeuristicClassifier, RedactionEvent, TagFormat,
+    TaxonomyMapper,
+};
+use crate::{RedactionPolicy, Redactor, RedactorRegistry, SimpleRedactor, SimpleRedactorMode};
 use core::fmt::Debug;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// A builder for creating a [`RedactionEngine`].
 pub struct RedactionEngineBuilder {
-    redactors: HashMap<DataClass, Box<dyn Redactor + Send + Sync>>,
-    fallback: Box<dyn Redactor + Send + Sync>,
+    redactors: HashMap<DataClass, Box<dyn Redactor>>,
+    fallback: Box<dyn Redactor>,
+    taxonomy_redactors: HashMap<String, Box<dyn Redactor>>,
+    field_redactors: FieldRedactors,
+    omitted: HashSet<DataClass>,
+    heuristics: Option<HeuristicClassifier>,
+    mapper: TaxonomyMapper,
+    tag_format: TagFormat,
+    observers: Vec<RedactionObserver>,
+    collect_stats: bool,
 }
 
 impl RedactionEngineBuilder {
@@ -19,17 +33,160 @@ impl RedactionEngineBuilder {
         Self {
             redactors: HashMap::new(),
             fallback: Box::new(SimpleRedactor::with_mode(SimpleRedactorMode::Erase)),
+            taxonomy_redactors: HashMap::new(),
+            field_redactors: HashMap::new(),
+            omitted: HashSet::new(),
+            heuristics: None,
+            mapper: TaxonomyMapper::new(),
+            observers: Vec::new(),
+            collect_stats: false,
+            tag_format: TagFormat::new(),
         }
     }
 
+    /// Creates a new builder configured with sensible defaults for the given environment.
+    ///
+    /// The presets configure the classes of the [core taxonomy](crate::core_taxonomy) and the fallback
+    /// redactor. Any later call on the builder overrides the preset, so services only need to specify
+    /// what differs from the defaults.
+    ///
+    /// | Environment | `sensitive` | `unknown_sensitivity` | `insensitive` | Fallback |
+    /// |-------------|-------------|-----------------------|---------------|----------|
+    /// | [`Dev`](Environment::Dev) | passthrough and tag | passthrough and tag | passthrough | erase |
+    /// | [`Staging`](Environment::Staging) | replace and tag | replace and tag | passthrough | erase |
+    /// | [`Prod`](Environment::Prod) | erase | erase | passthrough | erase |
+    ///
+    /// The presets produce the same output whatever features are enabled. The `Prod` preset erases sensitive
+    /// data, rather than masking it, so that not even its length is revealed. To hash sensitive data in
+    /// production instead, so that values can be correlated without being revealed, use `prod_preset` with
+    /// a secret shared by all instances of the service, which requires the `xxh3` feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+    /// use data_privacy::{Environment, RedactionEngineBuilder, SimpleRedactor};
+    ///
+    /// let engine = RedactionEngineBuilder::preset(Environment::Dev)
+    ///     .add_class_redactor(&CoreTaxonomy::Insensitive.data_class(), SimpleRedactor::new())
+    ///     .build();
+    ///
+    /// let mut output = String::new();
+    /// engine.display_redacted(&Sensitive::new("John"), |s| output.push_str(s));
+    /// assert_eq!(output, "<core/sensitive:John>");
+    /// ```
+    #[must_use]
+    pub fn preset(environment: Environment) -> Self {
+        let builder = Self::new().add_class_redactor(
+            &CoreTaxonomy::Insensitive.data_class(),
+            SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+        );
+
+        match environment {
+            Environment::Dev => builder
+                .add_class_redactor(
+                    &CoreTaxonomy::Sensitive.data_class(),
+                    SimpleRedactor::with_mode(SimpleRedactorMode::PassthroughAndTag),
+                )
+                .add_class_redactor(
+                    &CoreTaxonomy::UnknownSensitivity.data_class(),
+                    SimpleRedactor::with_mode(SimpleRedactorMode::PassthroughAndTag),
+                ),
+            Environment::Staging => builder
+                .add_class_redactor(
+                    &CoreTaxonomy::Sensitive.data_class(),
+                    SimpleRedactor::with_mode(SimpleRedactorMode::ReplaceAndTag('*')),
+                )
+                .add_class_redactor(
+                    &CoreTaxonomy::UnknownSensitivity.data_class(),
+                    SimpleRedactor::with_mode(SimpleRedactorMode::ReplaceAndTag('*')),
+                ),
+            // everything but insensitive data is erased by the fallback redactor
+            Environment::Prod => builder,
+        }
+    }
+
+    /// Creates a new builder configured like the [`Prod`](Environment::Prod) [preset](Self::preset), but
+    /// hashing sensitive data with the given secret rather than erasing it.
+    ///
+    /// Sensitive data is redacted by an [`xxH3Redactor`](crate::xxH3Redactor) keyed with the secret, so
+    /// values can be correlated across all the processes sharing the secret, without being revealed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the secret isn't valid for an [`xxH3Redactor`](crate::xxH3Redactor).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use data_privacy::core_taxonomy::Sensitive;
+    /// use data_privacy::RedactionEngineBuilder;
+    ///
+    /// let engine = RedactionEngineBuilder::prod_preset([7u8; 192]).build();
+    ///
+    /// let mut output = String::new();
+    /// engine.display_redacted(&Sensitive::new("John"), |s| output.push_str(s));
+    /// assert_eq!(output.len(), 16);
+    /// ```
+    #[cfg(feature = "xxh3")]
+    #[must_use]
+    pub fn prod_preset(secret: impl AsRef<[u8]>) -> Self {
+        Self::preset(Environment::Prod).add_class_redactor(
+            &CoreTaxonomy::Sensitive.data_class(),
+            crate::xxH3Redactor::with_secret(secret),
+        )
+    }
+
+    /// Creates a new builder configured according to a [`RedactionPolicy`].
+    ///
+    /// Each redactor named in the policy is created by the registry. Any later call on the builder
+    /// overrides the policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a data class in the policy isn't of the form `taxonomy/class`, or if the
+    /// registry fails to create one of the redactors, for example because its kind is unknown or one
+    /// of its options is invalid. Returns [`BuilderError::RevealingRedactor`] if the policy gives a data
+    /// class which must [never be logged](DataClass::never_log) a redactor which
+    /// [may reveal its input](crate::Redactor::reveals_input).
+    pub fn from_policy(
+        policy: &RedactionPolicy,
+        registry: &RedactorRegistry,
+    ) -> Result<Self, BuilderError> {
+        let mut builder = Self::new();
+
+        for (data_class, spec) in policy.classes() {
+            let data_class = parse_data_class(data_class)?;
+            let redactor = registry.create(spec)?;
+            check_revealing(&data_class, redactor.as_ref())?;
+            builder = builder.add_class_redactor_boxed(&data_class, redactor);
+        }
+
+        for (taxonomy, spec) in policy.taxonomies() {
+            builder = builder.add_taxonomy_redactor_boxed(taxonomy, registry.create(spec)?);
+        }
+
+        if let Some(spec) = policy.fallback() {
+            builder = builder.set_fallback_redactor_boxed(registry.create(spec)?);
+        }
+
+        for data_class in policy.omitted() {
+            builder = builder.omit_class(&parse_data_class(data_class)?);
+        }
+
+        Ok(builder)
+    }
+
     /// Adds a redactor for a specific data class.
     ///
     /// Whenever the redaction engine encounters data of this class, it will use the provided redactor.
+    /// If a redactor was already registered for the class, it is silently replaced. Use
+    /// [`try_add_class_redactor`](Self::try_add_class_redactor) to detect such conflicts instead.
     #[must_use]
     pub fn add_class_redactor(
         mut self,
         data_class: &DataClass,
-        redactor: impl Redactor + Send + Sync + 'static,
+        redactor: impl Redactor + 'static,
     ) -> Self {
         _ = self
             .redactors
@@ -37,23 +194,387 @@ impl RedactionEngineBuilder {
         self
     }
 
+    /// Adds an already boxed redactor for a specific data class.
+    ///
+    /// This behaves like [`add_class_redactor`](Self::add_class_redactor), but takes ownership of a redactor
+    /// whose concrete type has been erased, such as one selected from configuration at runtime, without
+    /// boxing it a second time. The engine owns all its redactors, so it is `'static` and can be stored in a
+    /// `static` and shared across threads.
+    #[must_use]
+    pub fn add_class_redactor_boxed(
+        mut self,
+        data_class: &DataClass,
+        redactor: Box<dyn Redactor>,
+    ) -> Self {
+        _ = self.redactors.insert(data_class.clone(), redactor);
+        self
+    }
+
+    /// Adds a redactor for a specific data class, failing if one was already registered for that class.
+    ///
+    /// This is useful when a configuration is assembled from several modules, where a silent overwrite
+    /// would let the wrong redactor win. The builder is borrowed rather than consumed, so it remains usable
+    /// after a conflict is reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuilderError::DuplicateClassRedactor`] if a redactor is already registered for the data class,
+    /// and [`BuilderError::RevealingRedactor`] if the data class must [never be logged](DataClass::never_log)
+    /// but the redactor [may reveal its input](crate::Redactor::reveals_input), leaving the builder unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use data_privacy::core_taxonomy::CoreTaxonomy;
+    /// use data_privacy::{BuilderError, RedactionEngineBuilder, SimpleRedactor};
+    ///
+    /// let sensitive = CoreTaxonomy::Sensitive.data_class();
+    /// let mut builder = RedactionEngineBuilder::new();
+    /// builder.try_add_class_redactor(&sensitive, SimpleRedactor::new())?;
+    ///
+    /// let error = builder
+    ///     .try_add_class_redactor(&sensitive, SimpleRedactor::new())
+    ///     .unwrap_err();
+    /// assert_eq!(error, BuilderError::DuplicateClassRedactor(sensitive));
+    ///
+    /// let engine = builder.build();
+    /// # Ok::<(), BuilderError>(())
+    /// ```
+    pub fn try_add_class_redactor(
+        &mut self,
+        data_class: &DataClass,
+        redactor: impl Redactor + 'static,
+    ) -> Result<&mut Self, BuilderError> {
+        if self.redactors.contains_key(data_class) {
+            return Err(BuilderError::DuplicateClassRedactor(data_class.clone()));
+        }
+
+        check_revealing(data_class, &redactor)?;
+        _ = self
+            .redactors
+            .insert(data_class.clone(), Box::new(redactor));
+        Ok(self)
+    }
+
+    /// Adds a redactor for all the data classes of a taxonomy.
+    ///
+    /// Whenever the redaction engine encounters data of a class of this taxonomy which has no redactor of its
+    /// own, it will use the provided redactor rather than the fallback redactor. If a redactor was already
+    /// registered for the taxonomy, it is silently replaced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use data_privacy::{DataClass, RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+    ///
+    /// let engine = RedactionEngineBuilder::new()
+    ///     .add_taxonomy_redactor("contoso", SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')))
+    ///     .add_class_redactor(&DataClass::new("contoso", "public"), SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough))
+    ///     .build();
+    ///
+    /// let mut output = String::new();
+    /// engine.redact(&DataClass::new("contoso", "customer_content"), "hello", |s| output.push_str(s));
+    /// engine.redact(&DataClass::new("contoso", "public"), " world", |s| output.push_str(s));
+    /// engine.redact(&DataClass::new("fabrikam", "secret"), "!", |s| output.push_str(s));
+    /// assert_eq!(output, "##### world");
+    /// ```
+    #[must_use]
+    pub fn add_taxonomy_redactor(
+        mut self,
+        taxonomy: impl Into<String>,
+        redactor: impl Redactor + 'static,
+    ) -> Self {
+        _ = self
+            .taxonomy_redactors
+            .insert(taxonomy.into(), Box::new(redactor));
+        self
+    }
+
+    /// Adds an already boxed redactor for all the data classes of a taxonomy.
+    #[must_use]
+    pub fn add_taxonomy_redactor_boxed(
+        mut self,
+        taxonomy: impl Into<String>,
+        redactor: Box<dyn Redactor>,
+    ) -> Self {
+        _ = self.taxonomy_redactors.insert(taxonomy.into(), redactor);
+        self
+    }
+
     /// Adds a redactor that's a fallback for when there is no redactor registered for a particular
     /// data class.
     ///
     /// The default fallback is to use an `ErasingRedactor`, which simply erases the original string.
     #[must_use]
-    pub fn set_fallback_redactor(
+    pub fn set_fallback_redactor(mut self, redactor: impl Redactor + 'static) -> Self {
+        self.fallback = Box::new(redactor);
+        self
+    }
+
+    /// Sets an already boxed redactor as the fallback for when there is no redactor registered for a
+    /// particular data class.
+    #[must_use]
+    pub fn set_fallback_redactor_boxed(mut self, redactor: Box<dyn Redactor>) -> Self {
+        self.fallback = redactor;
+        self
+    }
+
+    /// Adds a redactor for a specific named field of the type `T`.
+    ///
+    /// Values which send their classified data as named fields, through
+    /// [`Extractor::write_field`](crate::Extractor::write_field), such as types deriving
+    /// [`Extract`](macro@crate::Extract), have each field redacted with the redactor registered for its
+    /// [path](crate::FieldPath) within the type declaring it, such as `zip` for the `zip` field of an
+    /// `Address`, or `address.zip` for the same field within the `address` field of a `Customer`. The
+    /// field redactor applies instead of the redactor of the field's data class, unless that one is more
+    /// restrictive, as described in [`RedactionEngine::redact_field`]. If a redactor was already
+    /// registered for the field, it is silently replaced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use data_privacy::core_taxonomy::CoreTaxonomy;
+    /// use data_privacy::{Extract, RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+    ///
+    /// #[derive(Extract)]
+    /// struct Address {
+    ///     #[classified(CoreTaxonomy::Sensitive)]
+    ///     street: String,
+    ///     #[classified(CoreTaxonomy::Sensitive)]
+    ///     zip: String,
+    /// }
+    ///
+    /// let engine = RedactionEngineBuilder::new()
+    ///     .add_class_redactor(
+    ///         &CoreTaxonomy::Sensitive.data_class(),
+    ///         SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+    ///     )
+    ///     .add_field_redactor::<Address>("zip", SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')))
+    ///     .build();
+    ///
+    /// let address = Address { street: "1 Main".to_string(), zip: "98052".to_string() };
+    /// assert_eq!(engine.redact_to_string(&address), "Address { street: ******, zip: ##### }");
+    /// ```
+    #[must_use]
+    pub fn add_field_redactor<T: ?Sized>(
         mut self,
-        redactor: impl Redactor + Send + Sync + 'static,
+        path: impl Into<String>,
+        redactor: impl Redactor + 'static,
     ) -> Self {
-        self.fallback = Box::new(redactor);
+        let path = path.into();
+        let fields = self
+            .field_redactors
+            .entry(core::any::type_name::<T>())
+            .or_default();
+        fields.retain(|(field, _)| *field != path);
+        fields.push((path, Box::new(redactor)));
+        self
+    }
+
+    /// Marks a data class as one whose data should be omitted entirely.
+    ///
+    /// Data of this class produces no redacted output, regardless of any redactor registered for it.
+    /// In addition, structured logging integrations drop the whole name/value pair holding such data,
+    /// which matters when the mere presence of a field is itself sensitive.
+    #[must_use]
+    pub fn omit_class(mut self, data_class: &DataClass) -> Self {
+        _ = self.omitted.insert(data_class.clone());
+        self
+    }
+
+    /// Sets a classifier used to infer the data class of unannotated values from their field names.
+    ///
+    /// Integrations consult this classifier for values that don't carry a type-level classification.
+    /// By default, no heuristic classification is performed.
+    #[must_use]
+    pub fn set_heuristic_classifier(mut self, classifier: HeuristicClassifier) -> Self {
+        self.heuristics = Some(classifier);
+        self
+    }
+
+    /// Sets a mapper which translates data classes before their redactor is looked up.
+    ///
+    /// This lets data classified with one taxonomy, such as the classes used by libraries, follow the
+    /// redaction rules defined for another taxonomy. See [`TaxonomyMapper`] for details.
+    #[must_use]
+    pub fn set_taxonomy_mapper(mut self, mapper: TaxonomyMapper) -> Self {
+        self.mapper = mapper;
+        self
+    }
+
+    /// Adds a function which observes each redaction performed by the engine.
+    ///
+    /// Observers receive a [`RedactionEvent`] for every value the engine redacts, describing its data
+    /// class, the lengths of the value and of the redacted output, whether the fallback redactor was used,
+    /// and whether the value was emitted unchanged. This lets applications prove redaction happened for
+    /// compliance purposes, or alert on unexpected passthroughs.
+    ///
+    /// Observers are invoked synchronously on the thread doing the redaction, so they should be quick
+    /// and must not panic.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+    /// use data_privacy::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let passthroughs = Arc::new(AtomicUsize::new(0));
+    /// let counter = Arc::clone(&passthroughs);
+    /// let engine = RedactionEngineBuilder::new()
+    ///     .add_class_redactor(
+    ///         &CoreTaxonomy::Sensitive.data_class(),
+    ///         SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+    ///     )
+    ///     .on_redaction(move |event| {
+    ///         if event.is_passthrough() {
+    ///             _ = counter.fetch_add(1, Ordering::Relaxed);
+    ///         }
+    ///     })
+    ///     .build();
+    ///
+    /// let _ = engine.redact_to_string(&Sensitive::new("John"));
+    /// assert_eq!(passthroughs.load(Ordering::Relaxed), 1);
+    /// ```
+    #[must_use]
+    pub fn on_redaction(
+        mut self,
+        observer: impl Fn(&RedactionEvent<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    /// Makes the engine count the redactions it performs.
+    ///
+    /// The counts are available through [`RedactionEngine::stats`]. Counting requires taking a lock for
+    /// each redaction, so it's disabled by default.
+    #[must_use]
+    pub const fn collect_stats(mut self) -> Self {
+        self.collect_stats = true;
+        self
+    }
+
+    /// Sets the format of the tags produced by the tagging redactors of the engine.
+    ///
+    /// By default, tags have the form `<taxonomy/class:value>`. The engine's
+    /// [`redact_tags`](RedactionEngine::redact_tags) recognizes tags in the same format, so engines which
+    /// tag data and engines which redact the tags later on should be given the same format. See
+    /// [`TagFormat`] for details.
+    #[must_use]
+    pub fn set_tag_format(mut self, tag_format: TagFormat) -> Self {
+        self.tag_format = tag_format;
         self
     }
 
     /// Builds the `RedactionEngine`.
+    ///
+    /// Data classes which must [never be logged](DataClass::never_log) produce no output when their
+    /// redactor [may reveal its input](crate::Redactor::reveals_input), such as a passthrough redactor.
+    /// Use [`try_build`](Self::try_build) to reject such configurations instead.
     #[must_use]
     pub fn build(self) -> RedactionEngine {
         RedactionEngine::new(self.redactors, self.fallback)
+            .with_taxonomy_redactors(self.taxonomy_redactors)
+            .with_field_redactors(self.field_redactors)
+            .with_omitted_classes(self.omitted)
+            .with_heuristic_classifier(self.heuristics)
+            .with_taxonomy_mapper(self.mapper)
+            .with_observers(self.observers, self.collect_stats)
+            .with_tag_format(self.tag_format)
+    }
+
+    /// Builds the `RedactionEngine`, checking that data classes which must never be logged can't be revealed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuilderError::RevealingRedactor`] if the redactor registered for a data class which must
+    /// [never be logged](DataClass::never_log) [may reveal its input](crate::Redactor::reveals_input).
+    pub fn try_build(self) -> Result<RedactionEngine, BuilderError> {
+        let engine = self.build();
+        if let Some(data_class) = engine.revealing_class(&[]) {
+            return Err(BuilderError::RevealingRedactor(data_class.clone()));
+        }
+
+        Ok(engine)
+    }
+
+    /// Builds the `RedactionEngine`, checking that each of the given data classes is handled explicitly.
+    ///
+    /// Data classes without a redactor are silently redacted by the fallback redactor, which usually
+    /// erases them, so a forgotten registration shows up as missing telemetry rather than an error. This
+    /// catches such misconfigurations at startup instead. A data class is handled explicitly if a redactor
+    /// is registered for it or for its taxonomy, or if it's omitted, after being translated by the
+    /// [taxonomy mapper](Self::set_taxonomy_mapper).
+    ///
+    /// The data classes which must [never be logged](DataClass::never_log) among the given ones are also
+    /// checked not to be redacted by a redactor which [may reveal its input](crate::Redactor::reveals_input),
+    /// like those registered for data classes are by [`try_build`](Self::try_build).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuilderError::RevealingRedactor`] if a data class which must never be logged would be
+    /// redacted by a redactor which may reveal its input, and otherwise [`BuilderError::MissingClassRedactors`],
+    /// listing the data classes which would be redacted by the fallback redactor, if there are any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use data_privacy::core_taxonomy::CoreTaxonomy;
+    /// use data_privacy::{BuilderError, RedactionEngineBuilder, SimpleRedactor};
+    ///
+    /// let result = RedactionEngineBuilder::new()
+    ///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+    ///     .omit_class(&CoreTaxonomy::UnknownSensitivity.data_class())
+    ///     .build_strict(CoreTaxonomy::classes());
+    ///
+    /// assert_eq!(
+    ///     result.unwrap_err(),
+    ///     BuilderError::MissingClassRedactors(vec![CoreTaxonomy::Insensitive.data_class()])
+    /// );
+    /// ```
+    pub fn build_strict(
+        self,
+        known_classes: &[DataClass],
+    ) -> Result<RedactionEngine, BuilderError> {
+        let engine = self.build().with_never_log_classes(known_classes);
+        if let Some(data_class) = engine.revealing_class(known_classes) {
+            return Err(BuilderError::RevealingRedactor(data_class.clone()));
+        }
+
+        let missing: Vec<_> = known_classes
+            .iter()
+            .filter(|data_class| engine.uses_fallback(data_class))
+            .cloned()
+            .collect();
+
+        if missing.is_empty() {
+            Ok(engine)
+        } else {
+            Err(BuilderError::MissingClassRedactors(missing))
+        }
+    }
+}
+
+/// Checks that a redactor which may reveal its input isn't registered for a data class which must never be logged.
+///
+/// With the `registry` feature, data classes without metadata of their own, such as those parsed from
+/// policies, take the metadata of the registered data class of the same name.
+fn check_revealing(data_class: &DataClass, redactor: &dyn Redactor) -> Result<(), BuilderError> {
+    #[cfg(feature = "registry")]
+    let never_log = data_class.never_log()
+        || crate::DataClassRegistry::global()
+            .get(data_class.taxonomy(), data_class.name())
+            .is_some_and(DataClass::never_log);
+
+    #[cfg(not(feature = "registry"))]
+    let never_log = data_class.never_log();
+
+    if never_log && redactor.reveals_input() {
+        Err(BuilderError::RevealingRedactor(data_class.clone()))
+    } else {
+        Ok(())
     }
 }
 
@@ -72,6 +593,7 @@ impl Debug for RedactionEngineBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::RedactorSpec;
 
     fn test_redaction(
         engine: &RedactionEngine,
@@ -169,4 +691,314 @@ mod tests {
         let empty_debug_output = format!("{empty_builder:?}");
         assert_eq!(empty_debug_output, "[]");
     }
+
+    #[test]
+    fn build_strict_should_accept_explicitly_handled_classes() {
+        let public = DataClass::new("corp", "public");
+        let mapped = DataClass::new("corp", "mapped");
+        let known = [
+            DataClass::new("taxonomy", "class"),
+            DataClass::new("other", "class"),
+            public.clone(),
+            mapped.clone(),
+        ];
+
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(&known[0], SimpleRedactor::new())
+            .add_taxonomy_redactor("other", SimpleRedactor::new())
+            .omit_class(&public)
+            .set_taxonomy_mapper(TaxonomyMapper::new().add_mapping(&mapped, &known[0]))
+            .build_strict(&known);
+        assert!(engine.is_ok());
+
+        let error = RedactionEngineBuilder::new()
+            .add_taxonomy_redactor("other", SimpleRedactor::new())
+            .build_strict(&known)
+            .unwrap_err();
+        assert_eq!(
+            error,
+            BuilderError::MissingClassRedactors(vec![known[0].clone(), public, mapped])
+        );
+    }
+
+    #[test]
+    fn omit_class_marks_class_as_omitted() {
+        let data_class1 = DataClass::new("taxonomy", "class1");
+        let data_class2 = DataClass::new("taxonomy", "class2");
+
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &data_class1,
+                SimpleRedactor::with_mode(SimpleRedactorMode::Insert("XX".to_string())),
+            )
+            .omit_class(&data_class1)
+            .build();
+
+        assert!(engine.is_omitted(&data_class1));
+        assert!(!engine.is_omitted(&data_class2));
+        test_redaction(&engine, &data_class1, "sensitive data", "");
+    }
+
+    #[test]
+    fn try_add_class_redactor_rejects_duplicates() {
+        let data_class1 = DataClass::new("taxonomy", "class1");
+        let data_class2 = DataClass::new("taxonomy", "class2");
+
+        let mut builder = RedactionEngineBuilder::new();
+        _ = builder
+            .try_add_class_redactor(
+                &data_class1,
+                SimpleRedactor::with_mode(SimpleRedactorMode::Insert("XX".to_string())),
+            )
+            .unwrap()
+            .try_add_class_redactor(
+                &data_class2,
+                SimpleRedactor::with_mode(SimpleRedactorMode::Insert("YY".to_string())),
+            )
+            .unwrap();
+
+        let error = builder
+            .try_add_class_redactor(
+                &data_class1,
+                SimpleRedactor::with_mode(SimpleRedactorMode::Insert("ZZ".to_string())),
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            BuilderError::DuplicateClassRedactor(data_class1.clone())
+        );
+
+        // the builder keeps its redactors after the conflict
+        let engine = builder.build();
+        test_redaction(&engine, &data_class1, "abc", "XX");
+        test_redaction(&engine, &data_class2, "abc", "YY");
+    }
+
+    #[test]
+    fn presets_configure_core_classes() {
+        let sensitive = CoreTaxonomy::Sensitive.data_class();
+        let insensitive = CoreTaxonomy::Insensitive.data_class();
+        let unknown = CoreTaxonomy::UnknownSensitivity.data_class();
+        let other = DataClass::new("taxonomy", "class");
+
+        let engine = RedactionEngineBuilder::preset(Environment::Dev).build();
+        test_redaction(&engine, &sensitive, "abc", "<core/sensitive:abc>");
+        test_redaction(&engine, &unknown, "abc", "<core/unknown_sensitivity:abc>");
+        test_redaction(&engine, &insensitive, "abc", "abc");
+        test_redaction(&engine, &other, "abc", "");
+
+        let engine = RedactionEngineBuilder::preset(Environment::Staging).build();
+        test_redaction(&engine, &sensitive, "abc", "<core/sensitive:***>");
+        test_redaction(&engine, &unknown, "abc", "<core/unknown_sensitivity:***>");
+        test_redaction(&engine, &insensitive, "abc", "abc");
+        test_redaction(&engine, &other, "abc", "");
+
+        let engine = RedactionEngineBuilder::preset(Environment::Prod).build();
+        test_redaction(&engine, &sensitive, "abc", "");
+        test_redaction(&engine, &unknown, "abc", "");
+        test_redaction(&engine, &insensitive, "abc", "abc");
+        test_redaction(&engine, &other, "abc", "");
+    }
+
+    #[test]
+    #[cfg(feature = "xxh3")]
+    fn prod_preset_should_hash_with_the_secret() {
+        let sensitive = CoreTaxonomy::Sensitive.data_class();
+        let secret = |seed: u8| {
+            (0..192_u8)
+                .map(|i| i.wrapping_mul(seed).wrapping_add(seed))
+                .collect::<Vec<_>>()
+        };
+        let redact = |engine: &RedactionEngine| {
+            let mut output = String::new();
+            engine.redact(&sensitive, "abc", |s| output.push_str(s));
+            output
+        };
+
+        let engine = RedactionEngineBuilder::prod_preset(secret(1)).build();
+        let output = redact(&engine);
+        assert_eq!(output.len(), 16);
+        assert!(output.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(
+            output,
+            redact(&RedactionEngineBuilder::prod_preset(secret(1)).build())
+        );
+        assert_ne!(
+            output,
+            redact(&RedactionEngineBuilder::prod_preset(secret(2)).build())
+        );
+
+        test_redaction(
+            &engine,
+            &CoreTaxonomy::UnknownSensitivity.data_class(),
+            "abc",
+            "",
+        );
+        test_redaction(
+            &engine,
+            &CoreTaxonomy::Insensitive.data_class(),
+            "abc",
+            "abc",
+        );
+    }
+
+    #[test]
+    fn presets_can_be_overridden() {
+        let engine = RedactionEngineBuilder::preset(Environment::Prod)
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Insert("XX".to_string())),
+            )
+            .set_fallback_redactor(SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')))
+            .build();
+
+        test_redaction(&engine, &CoreTaxonomy::Sensitive.data_class(), "abc", "XX");
+        test_redaction(&engine, &DataClass::new("taxonomy", "class"), "abc", "###");
+    }
+
+    #[test]
+    fn tag_format_applies_to_all_tagging_redactors() {
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::ReplaceAndTag('*')),
+            )
+            .set_fallback_redactor(SimpleRedactor::with_mode(SimpleRedactorMode::EraseAndTag))
+            .set_tag_format(
+                TagFormat::new()
+                    .with_delimiters("[", "]")
+                    .include_redactor(true),
+            )
+            .build();
+
+        test_redaction(
+            &engine,
+            &CoreTaxonomy::Sensitive.data_class(),
+            "abc",
+            "[core/sensitive/simple:***]",
+        );
+        test_redaction(
+            &engine,
+            &DataClass::new("taxonomy", "class"),
+            "abc",
+            "[taxonomy/class/simple:]",
+        );
+
+        let mut output = String::new();
+        engine.redact_as_parts("other", "class", "abc", |s| output.push_str(s));
+        assert_eq!(output, "[other/class/simple:]");
+    }
+
+    #[test]
+    fn boxed_redactors_produce_static_engine() {
+        static ENGINE: std::sync::OnceLock<RedactionEngine> = std::sync::OnceLock::new();
+
+        let redactors: Vec<(DataClass, Box<dyn Redactor>)> = vec![(
+            CoreTaxonomy::Sensitive.data_class(),
+            Box::new(SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#'))),
+        )];
+
+        let engine = ENGINE.get_or_init(|| {
+            redactors
+                .into_iter()
+                .fold(
+                    RedactionEngineBuilder::new(),
+                    |builder, (data_class, redactor)| {
+                        builder.add_class_redactor_boxed(&data_class, redactor)
+                    },
+                )
+                .set_fallback_redactor_boxed(Box::new(SimpleRedactor::new()))
+                .build()
+        });
+
+        let handle = std::thread::spawn(|| {
+            let mut output = String::new();
+            ENGINE
+                .get()
+                .unwrap()
+                .redact(&CoreTaxonomy::Sensitive.data_class(), "abc", |s| {
+                    output.push_str(s);
+                });
+            output
+        });
+
+        assert_eq!(handle.join().unwrap(), "###");
+        test_redaction(engine, &DataClass::new("taxonomy", "class"), "abc", "***");
+    }
+
+    #[test]
+    fn taxonomy_redactor_applies_to_classes_without_redactors() {
+        let engine = RedactionEngineBuilder::new()
+            .add_taxonomy_redactor(
+                "core",
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')),
+            )
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+            )
+            .set_fallback_redactor(SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')))
+            .build();
+
+        test_redaction(&engine, &CoreTaxonomy::Sensitive.data_class(), "abc", "abc");
+        test_redaction(
+            &engine,
+            &CoreTaxonomy::Insensitive.data_class(),
+            "abc",
+            "###",
+        );
+        test_redaction(&engine, &DataClass::new("taxonomy", "class"), "abc", "***");
+
+        let mut output = String::new();
+        engine.redact_as_parts("core", "unregistered", "abc", |s| output.push_str(s));
+        assert_eq!(output, "###");
+    }
+
+    #[test]
+    fn from_policy_configures_engine() {
+        let policy = RedactionPolicy::new()
+            .with_class(
+                "core/sensitive",
+                RedactorSpec::new("replace").with_option("char", "#"),
+            )
+            .with_taxonomy(
+                "contoso",
+                RedactorSpec::new("replace").with_option("char", "*"),
+            )
+            .with_fallback(RedactorSpec::new("passthrough"))
+            .with_omitted("core/unknown_sensitivity");
+
+        let engine = RedactionEngineBuilder::from_policy(&policy, &RedactorRegistry::new())
+            .unwrap()
+            .build();
+
+        test_redaction(&engine, &CoreTaxonomy::Sensitive.data_class(), "abc", "###");
+        test_redaction(&engine, &DataClass::new("contoso", "class"), "abc", "***");
+        test_redaction(&engine, &DataClass::new("taxonomy", "class"), "abc", "abc");
+        assert!(engine.is_omitted(&CoreTaxonomy::UnknownSensitivity.data_class()));
+    }
+
+    #[test]
+    fn from_policy_reports_errors() {
+        let registry = RedactorRegistry::new();
+
+        let policy = RedactionPolicy::new().with_class("sensitive", RedactorSpec::new("erase"));
+        assert_eq!(
+            RedactionEngineBuilder::from_policy(&policy, &registry).unwrap_err(),
+            BuilderError::InvalidDataClass("sensitive".to_string())
+        );
+
+        let policy = RedactionPolicy::new().with_fallback(RedactorSpec::new("shred"));
+        assert_eq!(
+            RedactionEngineBuilder::from_policy(&policy, &registry).unwrap_err(),
+            BuilderError::UnknownRedactor("shred".to_string())
+        );
+
+        let policy = RedactionPolicy::new().with_omitted("core");
+        assert_eq!(
+            RedactionEngineBuilder::from_policy(&policy, &registry).unwrap_err(),
+            BuilderError::InvalidDataClass("core".to_string())
+        );
+    }
 }
@@ -0,0 +1,193 @@
+use crate::RedactionEngine;
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A shared handle to a redaction engine which can be swapped at runtime.
+///
+/// An engine is immutable once built. When redaction behavior must change while the application runs,
+/// such as to see tagged values while debugging an incident in a staging environment, components hold
+/// a handle instead of an engine and fetch the [current](Self::current) engine whenever they redact.
+///
+/// The engine can be replaced outright with [`replace`](Self::replace), or by switching between named
+/// profiles registered when the handle is created with [`select_profile`](Self::select_profile).
+/// Redaction already in progress completes with the engine it started with.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::Sensitive;
+/// use data_privacy::{Environment, RedactionEngineBuilder, RedactionEngineHandle};
+///
+/// let handle = RedactionEngineHandle::new(RedactionEngineBuilder::preset(Environment::Prod).build())
+///     .add_profile("prod", RedactionEngineBuilder::preset(Environment::Prod).build())
+///     .add_profile("dev", RedactionEngineBuilder::preset(Environment::Dev).build());
+///
+/// let user = Sensitive::new("John");
+/// assert_ne!(handle.current().display(&user).to_string(), "<core/sensitive:John>");
+///
+/// assert!(handle.select_profile("dev"));
+/// assert_eq!(handle.current().display(&user).to_string(), "<core/sensitive:John>");
+/// assert_eq!(handle.profile().as_deref(), Some("dev"));
+/// ```
+#[derive(Debug)]
+pub struct RedactionEngineHandle {
+    state: RwLock<State>,
+    profiles: HashMap<String, Arc<RedactionEngine>>,
+}
+
+#[derive(Debug)]
+struct State {
+    engine: Arc<RedactionEngine>,
+    profile: Option<String>,
+}
+
+impl RedactionEngineHandle {
+    /// Creates a new handle to the given engine.
+    ///
+    /// The engine doesn't belong to any profile, so [`profile`](Self::profile) returns `None` until a
+    /// profile is selected.
+    #[must_use]
+    pub fn new(engine: RedactionEngine) -> Self {
+        Self {
+            state: RwLock::new(State {
+                engine: Arc::new(engine),
+                profile: None,
+            }),
+            profiles: HashMap::new(),
+        }
+    }
+
+    /// Registers an engine as a named profile which can later be selected.
+    ///
+    /// If a profile was already registered with the same name, it is replaced.
+    #[must_use]
+    pub fn add_profile(mut self, name: impl Into<String>, engine: RedactionEngine) -> Self {
+        _ = self.profiles.insert(name.into(), Arc::new(engine));
+        self
+    }
+
+    /// Returns the current engine.
+    ///
+    /// The returned engine isn't affected by later changes to the handle, so callers should fetch the
+    /// engine each time they redact rather than holding onto it.
+    #[must_use]
+    pub fn current(&self) -> Arc<RedactionEngine> {
+        Arc::clone(&self.read().engine)
+    }
+
+    /// Returns the name of the selected profile, or `None` if the current engine isn't a profile.
+    #[must_use]
+    pub fn profile(&self) -> Option<String> {
+        self.read().profile.clone()
+    }
+
+    /// Returns the names of the registered profiles, in no particular order.
+    pub fn profiles(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+
+    /// Makes the engine of the named profile the current engine.
+    ///
+    /// Returns `false`, leaving the current engine unchanged, if no profile has the given name.
+    #[must_use]
+    pub fn select_profile(&self, name: &str) -> bool {
+        let Some((name, engine)) = self.profiles.get_key_value(name) else {
+            return false;
+        };
+
+        *self.write() = State {
+            engine: Arc::clone(engine),
+            profile: Some(name.clone()),
+        };
+
+        true
+    }
+
+    /// Makes the given engine the current engine, returning the engine it replaces.
+    ///
+    /// The new engine doesn't belong to any profile, even if another engine built the same way is
+    /// registered as one.
+    pub fn replace(&self, engine: RedactionEngine) -> Arc<RedactionEngine> {
+        let previous = core::mem::replace(
+            &mut *self.write(),
+            State {
+                engine: Arc::new(engine),
+                profile: None,
+            },
+        );
+
+        previous.engine
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, State> {
+        self.state.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, State> {
+        self.state.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::CoreTaxonomy;
+    use crate::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+
+    fn create_engine(mode: SimpleRedactorMode) -> RedactionEngine {
+        RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(mode),
+            )
+            .build()
+    }
+
+    fn redact(handle: &RedactionEngineHandle) -> String {
+        let mut output = String::new();
+        handle
+            .current()
+            .redact(&CoreTaxonomy::Sensitive.data_class(), "abc", |s| {
+                output.push_str(s);
+            });
+        output
+    }
+
+    #[test]
+    fn select_profile_should_switch_engines() {
+        let handle = RedactionEngineHandle::new(create_engine(SimpleRedactorMode::Erase))
+            .add_profile("dev", create_engine(SimpleRedactorMode::Passthrough))
+            .add_profile("prod", create_engine(SimpleRedactorMode::Replace('*')));
+
+        assert_eq!(redact(&handle), "");
+        assert_eq!(handle.profile(), None);
+
+        assert!(handle.select_profile("dev"));
+        assert_eq!(redact(&handle), "abc");
+        assert_eq!(handle.profile().as_deref(), Some("dev"));
+
+        assert!(!handle.select_profile("test"));
+        assert_eq!(handle.profile().as_deref(), Some("dev"));
+
+        assert!(handle.select_profile("prod"));
+        assert_eq!(redact(&handle), "***");
+
+        let mut profiles: Vec<_> = handle.profiles().collect();
+        profiles.sort_unstable();
+        assert_eq!(profiles, ["dev", "prod"]);
+    }
+
+    #[test]
+    fn replace_should_return_previous_engine() {
+        let handle = RedactionEngineHandle::new(create_engine(SimpleRedactorMode::Erase))
+            .add_profile("dev", create_engine(SimpleRedactorMode::Passthrough));
+        assert!(handle.select_profile("dev"));
+
+        let held = handle.current();
+        let previous = handle.replace(create_engine(SimpleRedactorMode::Replace('#')));
+
+        assert!(Arc::ptr_eq(&held, &previous));
+        assert_eq!(redact(&handle), "###");
+        assert_eq!(handle.profile(), None);
+    }
+}
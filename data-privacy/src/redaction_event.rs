@@ -0,0 +1,72 @@
+use crate::DataClass;
+
+/// A function observing the redactions performed by an engine.
+pub type RedactionObserver = Box<dyn Fn(&RedactionEvent<'_>) + Send + Sync>;
+
+/// A record of a redactor being applied to a piece of classified data.
+///
+/// Observers registered with [`RedactionEngineBuilder::on_redaction`](crate::RedactionEngineBuilder::on_redaction)
+/// receive one of these for each value the engine redacts, which lets applications prove that redaction
+/// happened, notice when data falls back to the engine's fallback redactor, or detect values which were
+/// emitted unchanged. Values of [omitted](crate::RedactionEngine::is_omitted) classes aren't reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedactionEvent<'a> {
+    data_class: &'a DataClass,
+    input_len: usize,
+    output_len: usize,
+    fallback: bool,
+    passthrough: bool,
+}
+
+impl<'a> RedactionEvent<'a> {
+    pub(crate) const fn new(
+        data_class: &'a DataClass,
+        input_len: usize,
+        output_len: usize,
+        fallback: bool,
+        passthrough: bool,
+    ) -> Self {
+        Self {
+            data_class,
+            input_len,
+            output_len,
+            fallback,
+            passthrough,
+        }
+    }
+
+    /// Returns the data class the value was redacted as.
+    #[must_use]
+    pub const fn data_class(&self) -> &'a DataClass {
+        self.data_class
+    }
+
+    /// Returns the length of the value before redaction, in bytes.
+    #[must_use]
+    pub const fn input_len(&self) -> usize {
+        self.input_len
+    }
+
+    /// Returns the length of the redacted output, in bytes.
+    #[must_use]
+    pub const fn output_len(&self) -> usize {
+        self.output_len
+    }
+
+    /// Returns whether the value was redacted by the engine's fallback redactor, because no redactor is
+    /// registered for its data class or taxonomy.
+    #[must_use]
+    pub const fn is_fallback(&self) -> bool {
+        self.fallback
+    }
+
+    /// Returns whether the redacted output is identical to a non-empty value, meaning the value was emitted unchanged.
+    ///
+    /// Values redacted in chunks by [`RedactionEngine::redact_reader`](crate::RedactionEngine::redact_reader)
+    /// aren't kept around for comparison, so they're reported as passthroughs when the streaming redactor
+    /// [says it passes values through](crate::StreamingRedactor::is_passthrough).
+    #[must_use]
+    pub const fn is_passthrough(&self) -> bool {
+        self.passthrough
+    }
+}
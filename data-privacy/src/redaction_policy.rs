@@ -0,0 +1,427 @@
+use crate::{BuilderError, DataClass};
+use std::collections::BTreeMap;
+
+/// A description of how to redact each data class, suitable for loading from external configuration.
+///
+/// Which redactor to use for which data class is normally a deployment decision rather than a code
+/// decision. A policy maps data classes, named as `taxonomy/class`, to [redactor specifications](RedactorSpec),
+/// and can also specify taxonomy-wide redactors, a fallback redactor and a list of classes to omit. With the `serde` feature, a
+/// policy can be deserialized from any self-describing format, such as JSON, YAML, or TOML. Unknown fields
+/// are rejected, so that a misspelled section doesn't silently leave data classes without their redactor.
+///
+/// A policy is turned into an engine with [`RedactionEngineBuilder::from_policy`](crate::RedactionEngineBuilder::from_policy),
+/// which uses a [`RedactorRegistry`](crate::RedactorRegistry) to create the redactors named in the policy.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::Sensitive;
+/// use data_privacy::{RedactionEngineBuilder, RedactionPolicy, RedactorRegistry};
+///
+/// let policy: RedactionPolicy = serde_json::from_str(r##"{
+///     "classes": {
+///         "core/sensitive": { "type": "partial", "keep_last": 2 },
+///         "core/insensitive": { "type": "passthrough" }
+///     },
+///     "taxonomies": { "contoso": { "type": "erase" } },
+///     "fallback": { "type": "replace", "char": "#" },
+///     "omit": ["core/unknown_sensitivity"]
+/// }"##).unwrap();
+///
+/// let engine = RedactionEngineBuilder::from_policy(&policy, &RedactorRegistry::new())
+///     .unwrap()
+///     .build();
+///
+/// let mut output = String::new();
+/// engine.display_redacted(&Sensitive::new("123456"), |s| output.push_str(s));
+/// assert_eq!(output, "****56");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct RedactionPolicy {
+    #[cfg_attr(feature = "serde", serde(default))]
+    classes: BTreeMap<String, RedactorSpec>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    taxonomies: BTreeMap<String, RedactorSpec>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    fallback: Option<RedactorSpec>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    omit: Vec<String>,
+}
+
+impl RedactionPolicy {
+    /// Creates an empty policy.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            classes: BTreeMap::new(),
+            taxonomies: BTreeMap::new(),
+            fallback: None,
+            omit: Vec::new(),
+        }
+    }
+
+    /// Sets the redactor for a data class, given as `taxonomy/class`.
+    #[must_use]
+    pub fn with_class(mut self, data_class: impl Into<String>, redactor: RedactorSpec) -> Self {
+        _ = self.classes.insert(data_class.into(), redactor);
+        self
+    }
+
+    /// Sets the redactor for all the data classes of a taxonomy which have no redactor of their own.
+    #[must_use]
+    pub fn with_taxonomy(mut self, taxonomy: impl Into<String>, redactor: RedactorSpec) -> Self {
+        _ = self.taxonomies.insert(taxonomy.into(), redactor);
+        self
+    }
+
+    /// Sets the fallback redactor, used for data classes without a redactor of their own or of their taxonomy.
+    #[must_use]
+    pub fn with_fallback(mut self, redactor: RedactorSpec) -> Self {
+        self.fallback = Some(redactor);
+        self
+    }
+
+    /// Marks a data class, given as `taxonomy/class`, as one whose data should be omitted entirely.
+    #[must_use]
+    pub fn with_omitted(mut self, data_class: impl Into<String>) -> Self {
+        self.omit.push(data_class.into());
+        self
+    }
+
+    /// Returns the data classes with a redactor of their own, along with their redactor.
+    pub fn classes(&self) -> impl Iterator<Item = (&str, &RedactorSpec)> {
+        self.classes
+            .iter()
+            .map(|(data_class, redactor)| (data_class.as_str(), redactor))
+    }
+
+    /// Returns the taxonomies with a taxonomy-wide redactor, along with their redactor.
+    pub fn taxonomies(&self) -> impl Iterator<Item = (&str, &RedactorSpec)> {
+        self.taxonomies
+            .iter()
+            .map(|(taxonomy, redactor)| (taxonomy.as_str(), redactor))
+    }
+
+    /// Returns the fallback redactor, if any.
+    #[must_use]
+    pub const fn fallback(&self) -> Option<&RedactorSpec> {
+        self.fallback.as_ref()
+    }
+
+    /// Returns the data classes whose data should be omitted.
+    pub fn omitted(&self) -> impl Iterator<Item = &str> {
+        self.omit.iter().map(String::as_str)
+    }
+}
+
+/// Parses a data class given as `taxonomy/class`.
+pub fn parse_data_class(text: &str) -> Result<DataClass, BuilderError> {
+    match text.split_once('/') {
+        Some((taxonomy, name))
+            if !taxonomy.is_empty() && !name.is_empty() && !name.contains('/') =>
+        {
+            Ok(DataClass::from_parts(taxonomy.to_owned(), name.to_owned()))
+        }
+        _ => Err(BuilderError::InvalidDataClass(text.to_owned())),
+    }
+}
+
+/// Describes a redactor by its kind and options.
+///
+/// The kind names a redactor known to a [`RedactorRegistry`](crate::RedactorRegistry), while the options
+/// are interpreted by that kind of redactor. When deserialized, the kind is given by the `type` field and
+/// every other field is an option, as in `{ "type": "replace", "char": "#" }`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RedactorSpec {
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    kind: String,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    options: BTreeMap<String, PolicyValue>,
+}
+
+impl RedactorSpec {
+    /// Creates a specification for the given kind of redactor, without any options.
+    #[must_use]
+    pub fn new(kind: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            options: BTreeMap::new(),
+        }
+    }
+
+    /// Sets an option.
+    #[must_use]
+    pub fn with_option(mut self, name: impl Into<String>, value: impl Into<PolicyValue>) -> Self {
+        _ = self.options.insert(name.into(), value.into());
+        self
+    }
+
+    /// Returns the kind of redactor.
+    #[must_use]
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Returns the value of an option, if it is set.
+    #[must_use]
+    pub fn option(&self, name: &str) -> Option<&PolicyValue> {
+        self.options.get(name)
+    }
+
+    /// Returns the value of a string option, if it is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuilderError::InvalidRedactorOption`] if the option isn't a string.
+    pub fn str_option(&self, name: &str) -> Result<Option<&str>, BuilderError> {
+        self.typed_option(name, |value| match value {
+            PolicyValue::String(s) => Some(s.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Returns the value of a Boolean option, if it is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuilderError::InvalidRedactorOption`] if the option isn't a Boolean.
+    pub fn bool_option(&self, name: &str) -> Result<Option<bool>, BuilderError> {
+        self.typed_option(name, |value| match value {
+            PolicyValue::Bool(b) => Some(*b),
+            _ => None,
+        })
+    }
+
+    /// Returns the value of a non-negative integer option, if it is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuilderError::InvalidRedactorOption`] if the option isn't a non-negative integer.
+    pub fn usize_option(&self, name: &str) -> Result<Option<usize>, BuilderError> {
+        self.typed_option(name, |value| match value {
+            PolicyValue::Integer(i) => usize::try_from(*i).ok(),
+            _ => None,
+        })
+    }
+
+    /// Returns the value of an option holding a single character, if it is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuilderError::InvalidRedactorOption`] if the option isn't a string of exactly one character.
+    pub fn char_option(&self, name: &str) -> Result<Option<char>, BuilderError> {
+        self.typed_option(name, |value| match value {
+            PolicyValue::String(s) => {
+                let mut chars = s.chars();
+                chars.next().filter(|_| chars.next().is_none())
+            }
+            _ => None,
+        })
+    }
+
+    /// Checks that every option set is one of the given ones.
+    ///
+    /// Factories of a [`RedactorRegistry`](crate::RedactorRegistry) call this with the options they
+    /// support, so that a misspelled option is reported rather than silently ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuilderError::UnknownRedactorOption`] naming the first option which isn't among the given ones.
+    pub fn check_options(&self, known: &[&str]) -> Result<(), BuilderError> {
+        self.options
+            .keys()
+            .find(|name| !known.contains(&name.as_str()))
+            .map_or(Ok(()), |name| {
+                Err(BuilderError::UnknownRedactorOption {
+                    redactor: self.kind.clone(),
+                    option: name.clone(),
+                })
+            })
+    }
+
+    /// Returns an error indicating that the given option has an invalid value.
+    #[must_use]
+    pub fn invalid_option(&self, name: &str) -> BuilderError {
+        BuilderError::InvalidRedactorOption {
+            redactor: self.kind.clone(),
+            option: name.to_owned(),
+        }
+    }
+
+    fn typed_option<'a, T>(
+        &'a self,
+        name: &str,
+        convert: impl FnOnce(&'a PolicyValue) -> Option<T>,
+    ) -> Result<Option<T>, BuilderError> {
+        self.options
+            .get(name)
+            .map(|value| convert(value).ok_or_else(|| self.invalid_option(name)))
+            .transpose()
+    }
+}
+
+/// The value of a redactor option.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum PolicyValue {
+    /// A Boolean value.
+    Bool(bool),
+
+    /// An integer value.
+    Integer(i64),
+
+    /// A floating-point value.
+    Float(f64),
+
+    /// A string value.
+    String(String),
+}
+
+impl From<bool> for PolicyValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for PolicyValue {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<f64> for PolicyValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<&str> for PolicyValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<String> for PolicyValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_data_class_should_require_both_parts() {
+        assert_eq!(
+            parse_data_class("core/sensitive"),
+            Ok(DataClass::new("core", "sensitive"))
+        );
+
+        for invalid in ["core", "/sensitive", "core/", "a/b/c"] {
+            assert_eq!(
+                parse_data_class(invalid),
+                Err(BuilderError::InvalidDataClass(invalid.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn typed_options_should_check_types() {
+        let spec = RedactorSpec::new("test")
+            .with_option("text", "abc")
+            .with_option("char", "#")
+            .with_option("flag", true)
+            .with_option("count", 3_i64)
+            .with_option("negative", -1_i64)
+            .with_option("ratio", 0.5);
+
+        assert_eq!(spec.kind(), "test");
+        assert_eq!(spec.str_option("text"), Ok(Some("abc")));
+        assert_eq!(spec.char_option("char"), Ok(Some('#')));
+        assert_eq!(spec.bool_option("flag"), Ok(Some(true)));
+        assert_eq!(spec.usize_option("count"), Ok(Some(3)));
+        assert_eq!(spec.option("ratio"), Some(&PolicyValue::Float(0.5)));
+        assert_eq!(spec.str_option("missing"), Ok(None));
+
+        let invalid = |option: &str| BuilderError::InvalidRedactorOption {
+            redactor: "test".to_string(),
+            option: option.to_string(),
+        };
+        assert_eq!(spec.char_option("text"), Err(invalid("text")));
+        assert_eq!(spec.bool_option("count"), Err(invalid("count")));
+        assert_eq!(spec.usize_option("negative"), Err(invalid("negative")));
+        assert_eq!(spec.str_option("flag"), Err(invalid("flag")));
+    }
+
+    #[test]
+    fn check_options_should_name_unknown_options() {
+        let spec = RedactorSpec::new("test")
+            .with_option("char", "#")
+            .with_option("chars", "#");
+        assert_eq!(spec.check_options(&["char", "chars", "tag"]), Ok(()));
+        assert_eq!(
+            spec.check_options(&["char", "tag"]),
+            Err(BuilderError::UnknownRedactorOption {
+                redactor: "test".to_string(),
+                option: "chars".to_string()
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn policy_should_deserialize() {
+        let policy: RedactionPolicy = serde_json::from_str(
+            r##"{
+                "classes": { "core/sensitive": { "type": "replace", "char": "#", "tag": true } },
+                "taxonomies": { "contoso": { "type": "passthrough" } },
+                "fallback": { "type": "erase" },
+                "omit": ["core/unknown_sensitivity"]
+            }"##,
+        )
+        .unwrap();
+
+        assert_eq!(
+            policy,
+            RedactionPolicy::new()
+                .with_class(
+                    "core/sensitive",
+                    RedactorSpec::new("replace")
+                        .with_option("char", "#")
+                        .with_option("tag", true)
+                )
+                .with_taxonomy("contoso", RedactorSpec::new("passthrough"))
+                .with_fallback(RedactorSpec::new("erase"))
+                .with_omitted("core/unknown_sensitivity")
+        );
+
+        let empty: RedactionPolicy = serde_json::from_str("{}").unwrap();
+        assert_eq!(empty, RedactionPolicy::default());
+        assert_eq!(serde_json::to_string(&empty).unwrap(), r#"{"classes":{}}"#);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn policy_should_reject_unknown_fields() {
+        let error = serde_json::from_str::<RedactionPolicy>(r#"{ "omitted": ["core/sensitive"] }"#)
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("unknown field `omitted`"),
+            "{error}"
+        );
+    }
+}
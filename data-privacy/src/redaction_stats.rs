@@ -0,0 +1,125 @@
+use crate::{DataClass, RedactionEvent};
+use std::collections::BTreeMap;
+
+/// A snapshot of the redactions performed by a [`RedactionEngine`](crate::RedactionEngine).
+///
+/// Engines only count redactions when built with
+/// [`RedactionEngineBuilder::collect_stats`](crate::RedactionEngineBuilder::collect_stats), and a
+/// snapshot is taken with [`RedactionEngine::stats`](crate::RedactionEngine::stats).
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+/// use data_privacy::{RedactionEngineBuilder, SimpleRedactor};
+///
+/// let engine = RedactionEngineBuilder::new()
+///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+///     .collect_stats()
+///     .build();
+///
+/// let _ = engine.redact_to_string(&Sensitive::new("John"));
+///
+/// let stats = engine.stats().unwrap();
+/// assert_eq!(stats.redactions(), 1);
+/// assert_eq!(stats.redactions_of(&CoreTaxonomy::Sensitive.data_class()), 1);
+/// assert_eq!(stats.fallbacks(), 0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RedactionStats {
+    classes: BTreeMap<DataClass, u64>,
+    redactions: u64,
+    fallbacks: u64,
+    passthroughs: u64,
+    bytes_in: u64,
+    bytes_out: u64,
+}
+
+impl RedactionStats {
+    /// Counts a redaction.
+    pub(crate) fn record(&mut self, event: &RedactionEvent<'_>) {
+        if let Some(count) = self.classes.get_mut(event.data_class()) {
+            *count += 1;
+        } else {
+            _ = self.classes.insert(event.data_class().clone(), 1);
+        }
+
+        self.redactions += 1;
+        self.fallbacks += u64::from(event.is_fallback());
+        self.passthroughs += u64::from(event.is_passthrough());
+        self.bytes_in += event.input_len() as u64;
+        self.bytes_out += event.output_len() as u64;
+    }
+
+    /// Returns the total number of values redacted.
+    #[must_use]
+    pub const fn redactions(&self) -> u64 {
+        self.redactions
+    }
+
+    /// Returns the number of values of the given data class redacted.
+    #[must_use]
+    pub fn redactions_of(&self, data_class: &DataClass) -> u64 {
+        self.classes.get(data_class).copied().unwrap_or(0)
+    }
+
+    /// Returns the number of values redacted for each data class.
+    pub fn classes(&self) -> impl Iterator<Item = (&DataClass, u64)> {
+        self.classes
+            .iter()
+            .map(|(data_class, count)| (data_class, *count))
+    }
+
+    /// Returns the number of values redacted by the engine's fallback redactor.
+    #[must_use]
+    pub const fn fallbacks(&self) -> u64 {
+        self.fallbacks
+    }
+
+    /// Returns the number of values emitted unchanged.
+    #[must_use]
+    pub const fn passthroughs(&self) -> u64 {
+        self.passthroughs
+    }
+
+    /// Returns the total length of the values before redaction, in bytes.
+    #[must_use]
+    pub const fn bytes_in(&self) -> u64 {
+        self.bytes_in
+    }
+
+    /// Returns the total length of the redacted output, in bytes.
+    #[must_use]
+    pub const fn bytes_out(&self) -> u64 {
+        self.bytes_out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: DataClass = DataClass::new("test", "secret");
+    const PUBLIC: DataClass = DataClass::new("test", "public");
+
+    #[test]
+    fn record_should_count_per_class() {
+        let mut stats = RedactionStats::default();
+        stats.record(&RedactionEvent::new(&SECRET, 6, 6, false, false));
+        stats.record(&RedactionEvent::new(&SECRET, 2, 0, true, false));
+        stats.record(&RedactionEvent::new(&PUBLIC, 3, 3, false, true));
+
+        assert_eq!(stats.redactions(), 3);
+        assert_eq!(stats.redactions_of(&SECRET), 2);
+        assert_eq!(stats.redactions_of(&PUBLIC), 1);
+        assert_eq!(stats.redactions_of(&DataClass::new("test", "other")), 0);
+        assert_eq!(
+            stats.classes().collect::<Vec<_>>(),
+            [(&PUBLIC, 1), (&SECRET, 2)]
+        );
+        assert_eq!(stats.fallbacks(), 1);
+        assert_eq!(stats.passthroughs(), 1);
+        assert_eq!(stats.bytes_in(), 11);
+        assert_eq!(stats.bytes_out(), 9);
+    }
+}
@@ -1,10 +1,58 @@
-use crate::DataClass;
+use crate::formatting::to_hex;
+use crate::{DataClass, RedactionContext, TagFormat};
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+use alloc::sync::Arc;
 
 /// Represents types that can redact data.
-pub trait Redactor {
+///
+/// Redactors must be [`Send`] and [`Sync`] so that the engines holding them can be shared across threads.
+///
+/// The trait is implemented for references, [`Box`], and [`Arc`] of redactors, so a single redactor, such
+/// as one holding a secret or a token vault, can be shared between several data classes or engines by
+/// wrapping it in an [`Arc`] and registering clones of it.
+pub trait Redactor: Send + Sync {
     /// Redacts the given value and calls the output function with the redacted value.
     fn redact(&self, data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str));
 
+    /// Redacts the given value like [`redact`](Self::redact), with access to the context of the call.
+    ///
+    /// A [`RedactionEngine`](crate::RedactionEngine) calls this method for every text value it redacts,
+    /// passing the context given to [`redact_with_context`](crate::RedactionEngine::redact_with_context)
+    /// or to one of the engine's other `_with_context` methods, or an empty context otherwise. The
+    /// context always carries the engine's [`TagFormat`].
+    ///
+    /// Redactors which tag their output should override this method to use the context's
+    /// [tag format](RedactionContext::tag_format), as should redactors which adapt their output to the
+    /// context, such as by salting it per tenant. The default implementation ignores the context and
+    /// calls [`redact`](Self::redact).
+    fn redact_ctx(
+        &self,
+        data_class: &DataClass,
+        value: &str,
+        context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) {
+        _ = context;
+        self.redact(data_class, value, output);
+    }
+
+    /// Redacts binary data, with access to the context of the call.
+    ///
+    /// A [`RedactionEngine`](crate::RedactionEngine) calls this method for classified data which has no
+    /// textual form. Redactors which can work on bytes directly, such as hashing redactors, should override
+    /// it to avoid a lossy conversion, while the default implementation encodes the bytes as lowercase
+    /// hexadecimal and calls [`redact_ctx`](Self::redact_ctx).
+    fn redact_bytes(
+        &self,
+        data_class: &DataClass,
+        value: &[u8],
+        context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) {
+        self.redact_ctx(data_class, &to_hex(value), context, output);
+    }
+
     /// The exact length of the redacted output if it is a constant.
     ///
     /// This can be used as a hint to optimize buffer allocations.
@@ -12,12 +60,199 @@ pub trait Redactor {
     fn exact_len(&self) -> Option<usize> {
         None
     }
+
+    /// The length of the redacted output of a value of the given length in bytes, if it can be known without the value.
+    ///
+    /// Unlike [`exact_len`](Self::exact_len), this accounts for the length of the value, the data class, and the
+    /// format of any tags, so it's available for more redactors, such as those masking the value or tagging
+    /// their output. Redactors whose output length depends on the number of characters of the value, rather
+    /// than its number of bytes, return the length for ASCII values, which is an upper bound for other values.
+    ///
+    /// This can be used as a hint to optimize buffer allocations. The default implementation returns the
+    /// [`exact_len`](Self::exact_len) of the redactor.
+    #[must_use]
+    fn len_hint(
+        &self,
+        data_class: &DataClass,
+        input_len: usize,
+        tag_format: &TagFormat,
+    ) -> Option<usize> {
+        _ = (data_class, input_len, tag_format);
+        self.exact_len()
+    }
+
+    /// Returns whether the output of the redactor may contain the original value, or parts of it.
+    ///
+    /// A [`RedactionEngine`](crate::RedactionEngine) never uses such a redactor for data classes which
+    /// must [never be logged](DataClass::never_log). Passthrough, truncating, and partially masking
+    /// redactors return `true`, while hashing and fully masking redactors return `false`. The default
+    /// implementation conservatively returns `true`.
+    #[must_use]
+    fn reveals_input(&self) -> bool {
+        true
+    }
+
+    /// Returns this redactor as a [`StreamingRedactor`](crate::StreamingRedactor), if it can redact values incrementally.
+    ///
+    /// [`RedactionEngine::redact_reader`](crate::RedactionEngine::redact_reader) uses this to redact large
+    /// values with bounded memory. Redactors implementing `StreamingRedactor` should return `Some(self)`.
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn as_streaming(&self) -> Option<&dyn crate::StreamingRedactor> {
+        None
+    }
+}
+
+impl<R: Redactor + ?Sized> Redactor for &R {
+    fn redact(&self, data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
+        (**self).redact(data_class, value, output);
+    }
+
+    fn redact_ctx(
+        &self,
+        data_class: &DataClass,
+        value: &str,
+        context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) {
+        (**self).redact_ctx(data_class, value, context, output);
+    }
+
+    fn redact_bytes(
+        &self,
+        data_class: &DataClass,
+        value: &[u8],
+        context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) {
+        (**self).redact_bytes(data_class, value, context, output);
+    }
+
+    fn exact_len(&self) -> Option<usize> {
+        (**self).exact_len()
+    }
+
+    fn reveals_input(&self) -> bool {
+        (**self).reveals_input()
+    }
+
+    fn len_hint(
+        &self,
+        data_class: &DataClass,
+        input_len: usize,
+        tag_format: &TagFormat,
+    ) -> Option<usize> {
+        (**self).len_hint(data_class, input_len, tag_format)
+    }
+
+    #[cfg(feature = "std")]
+    fn as_streaming(&self) -> Option<&dyn crate::StreamingRedactor> {
+        (**self).as_streaming()
+    }
+}
+
+impl<R: Redactor + ?Sized> Redactor for Box<R> {
+    fn redact(&self, data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
+        (**self).redact(data_class, value, output);
+    }
+
+    fn redact_ctx(
+        &self,
+        data_class: &DataClass,
+        value: &str,
+        context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) {
+        (**self).redact_ctx(data_class, value, context, output);
+    }
+
+    fn redact_bytes(
+        &self,
+        data_class: &DataClass,
+        value: &[u8],
+        context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) {
+        (**self).redact_bytes(data_class, value, context, output);
+    }
+
+    fn exact_len(&self) -> Option<usize> {
+        (**self).exact_len()
+    }
+
+    fn reveals_input(&self) -> bool {
+        (**self).reveals_input()
+    }
+
+    fn len_hint(
+        &self,
+        data_class: &DataClass,
+        input_len: usize,
+        tag_format: &TagFormat,
+    ) -> Option<usize> {
+        (**self).len_hint(data_class, input_len, tag_format)
+    }
+
+    #[cfg(feature = "std")]
+    fn as_streaming(&self) -> Option<&dyn crate::StreamingRedactor> {
+        (**self).as_streaming()
+    }
+}
+
+impl<R: Redactor + ?Sized> Redactor for Arc<R> {
+    fn redact(&self, data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
+        (**self).redact(data_class, value, output);
+    }
+
+    fn redact_ctx(
+        &self,
+        data_class: &DataClass,
+        value: &str,
+        context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) {
+        (**self).redact_ctx(data_class, value, context, output);
+    }
+
+    fn redact_bytes(
+        &self,
+        data_class: &DataClass,
+        value: &[u8],
+        context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) {
+        (**self).redact_bytes(data_class, value, context, output);
+    }
+
+    fn exact_len(&self) -> Option<usize> {
+        (**self).exact_len()
+    }
+
+    fn reveals_input(&self) -> bool {
+        (**self).reveals_input()
+    }
+
+    fn len_hint(
+        &self,
+        data_class: &DataClass,
+        input_len: usize,
+        tag_format: &TagFormat,
+    ) -> Option<usize> {
+        (**self).len_hint(data_class, input_len, tag_format)
+    }
+
+    #[cfg(feature = "std")]
+    fn as_streaming(&self) -> Option<&dyn crate::StreamingRedactor> {
+        (**self).as_streaming()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::core_taxonomy::CoreTaxonomy::Sensitive;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::{String, ToString};
 
     struct TestRedactor;
 
@@ -38,4 +273,44 @@ mod tests {
         assert_eq!(redactor.exact_len(), None);
         assert_eq!(output_buffer, "test_valuetomato");
     }
+
+    #[test]
+    fn test_redact_bytes_default_behavior() {
+        let mut output = String::new();
+        TestRedactor.redact_bytes(
+            &Sensitive.data_class(),
+            &[0xde, 0xad],
+            &RedactionContext::new(),
+            &mut |s| output.push_str(s),
+        );
+
+        assert_eq!(output, "deadtomato");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn shared_redactor_should_serve_several_classes() {
+        use crate::core_taxonomy::CoreTaxonomy::Insensitive;
+        use crate::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+
+        let redactor = Arc::new(SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')));
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(&Sensitive.data_class(), Arc::clone(&redactor))
+            .add_class_redactor(&Insensitive.data_class(), Arc::clone(&redactor))
+            .build();
+
+        let mut output = String::new();
+        engine.redact(&Sensitive.data_class(), "abc", |s| output.push_str(s));
+        engine.redact(&Insensitive.data_class(), "de", |s| output.push_str(s));
+        assert_eq!(output, "#####");
+        assert_eq!(Arc::strong_count(&redactor), 3);
+
+        let boxed: Box<dyn Redactor> = Box::new(TestRedactor);
+        let engine = RedactionEngineBuilder::new()
+            .add_class_redactor(&Sensitive.data_class(), boxed)
+            .build();
+        let mut output = String::new();
+        engine.redact(&Sensitive.data_class(), "x", |s| output.push_str(s));
+        assert_eq!(output, "xtomato");
+    }
 }
@@ -0,0 +1,412 @@
+use crate::{
+    BuilderError, PartialRedactor, Redactor, RedactorSpec, SimpleRedactor, SimpleRedactorMode,
+    TruncatingRedactor,
+};
+use core::fmt::Debug;
+use std::collections::HashMap;
+
+type Factory = dyn Fn(&RedactorSpec) -> Result<Box<dyn Redactor>, BuilderError> + Send + Sync;
+
+/// Creates redactors from their [specification](RedactorSpec), by kind.
+///
+/// A registry maps each kind of redactor to a factory which creates a redactor from the options of a
+/// specification. It's used to build an engine from a [`RedactionPolicy`](crate::RedactionPolicy).
+///
+/// New registries know about the following kinds of redactors:
+///
+/// | Kind | Options | Redactor |
+/// |------|---------|----------|
+/// | `erase` | `tag` | [`SimpleRedactor`] erasing values |
+/// | `passthrough` | `tag` | [`SimpleRedactor`] passing values through |
+/// | `replace` | `char`, `bucketed`, `tag` | [`SimpleRedactor`] masking values, with `*` by default |
+/// | `insert` | `text` (required), `tag` | [`SimpleRedactor`] replacing values with a fixed text |
+/// | `partial` | `keep_first`, `keep_last`, `mask`, `delimiters` | [`PartialRedactor`] |
+/// | `truncate` | `max_chars` (required) | [`TruncatingRedactor`] |
+/// | `xxh3` | `secret` (required), `per_class_keys` | `xxH3Redactor`, with the `xxh3` feature |
+/// | `hmac` | `key_id` (required), `key` (required) | `HmacRedactor`, with the `hmac` feature |
+/// | `tokenize` | `pepper` (required), `salt`, `prefix` | `TokenizingRedactor`, with the `hmac` feature |
+/// | `pattern` | `common`, `regex`, `replacement` | `PatternRedactor`, with the `regex` feature |
+///
+/// Setting any other option is an error. Applications can add their own kinds with [`register`](Self::register),
+/// whose factories can reject unknown options with [`RedactorSpec::check_options`].
+pub struct RedactorRegistry {
+    factories: HashMap<String, Box<Factory>>,
+}
+
+impl RedactorRegistry {
+    /// Creates a registry which knows about the built-in kinds of redactors.
+    #[must_use]
+    pub fn new() -> Self {
+        let registry = Self {
+            factories: HashMap::new(),
+        }
+        .with_simple_kinds()
+        .register("partial", |spec| {
+            spec.check_options(&["keep_first", "keep_last", "mask", "delimiters"])?;
+            Ok(Box::new(
+                PartialRedactor::new()
+                    .keep_first(spec.usize_option("keep_first")?.unwrap_or(0))
+                    .keep_last(spec.usize_option("keep_last")?.unwrap_or(0))
+                    .with_mask(spec.char_option("mask")?.unwrap_or('*'))
+                    .keep_delimiters(spec.str_option("delimiters")?.unwrap_or_default()),
+            ))
+        })
+        .register("truncate", |spec| {
+            spec.check_options(&["max_chars"])?;
+            let max_chars = required(spec, "max_chars", spec.usize_option("max_chars")?)?;
+            Ok(Box::new(TruncatingRedactor::new(max_chars)))
+        });
+
+        #[cfg(feature = "xxh3")]
+        let registry = registry.register("xxh3", |spec| {
+            spec.check_options(&["secret", "per_class_keys"])?;
+            let secret = required(spec, "secret", spec.str_option("secret")?)?;
+            if !(136..=256).contains(&secret.len()) {
+                return Err(spec.invalid_option("secret"));
+            }
+
+            let redactor = crate::xxH3Redactor::with_secret(secret);
+            if spec.bool_option("per_class_keys")?.unwrap_or(false) {
+                Ok(Box::new(redactor.with_per_class_keys()))
+            } else {
+                Ok(Box::new(redactor))
+            }
+        });
+
+        #[cfg(feature = "hmac")]
+        let registry = registry.register("hmac", |spec| {
+            spec.check_options(&["key_id", "key"])?;
+            let key_id = required(spec, "key_id", spec.str_option("key_id")?)?;
+            let key = required(spec, "key", spec.str_option("key")?)?;
+            if key_id.is_empty() || key_id.contains([':', ',']) {
+                return Err(spec.invalid_option("key_id"));
+            }
+
+            Ok(Box::new(crate::HmacRedactor::new(key_id.to_owned(), key)))
+        });
+
+        #[cfg(feature = "hmac")]
+        let registry = registry.register("tokenize", |spec| {
+            spec.check_options(&["pepper", "salt", "prefix"])?;
+            let pepper = required(spec, "pepper", spec.str_option("pepper")?)?;
+            let mut redactor = crate::TokenizingRedactor::new(pepper);
+            if let Some(salt) = spec.str_option("salt")? {
+                redactor = redactor.with_salt(salt);
+            }
+            if let Some(prefix) = spec.str_option("prefix")? {
+                redactor = redactor.with_prefix(prefix.to_owned());
+            }
+
+            Ok(Box::new(redactor))
+        });
+
+        #[cfg(feature = "regex")]
+        let registry = registry.register("pattern", |spec| {
+            spec.check_options(&["common", "regex", "replacement"])?;
+            let mut redactor = crate::PatternRedactor::new();
+            if spec.bool_option("common")?.unwrap_or(false) {
+                redactor = redactor.with_common_patterns();
+            }
+
+            if let Some(pattern) = spec.str_option("regex")? {
+                let pattern =
+                    regex::Regex::new(pattern).map_err(|_e| spec.invalid_option("regex"))?;
+                let replacement = spec.str_option("replacement")?.unwrap_or("***");
+                redactor = redactor.with_pattern(pattern, replacement);
+            }
+
+            Ok(Box::new(redactor))
+        });
+
+        registry
+    }
+
+    /// Registers the kinds of redactors backed by a [`SimpleRedactor`].
+    fn with_simple_kinds(self) -> Self {
+        self.register("erase", |spec| {
+            spec.check_options(&["tag"])?;
+            Ok(simple(
+                spec,
+                SimpleRedactorMode::Erase,
+                SimpleRedactorMode::EraseAndTag,
+            )?)
+        })
+        .register("passthrough", |spec| {
+            spec.check_options(&["tag"])?;
+            Ok(simple(
+                spec,
+                SimpleRedactorMode::Passthrough,
+                SimpleRedactorMode::PassthroughAndTag,
+            )?)
+        })
+        .register("replace", |spec| {
+            spec.check_options(&["char", "bucketed", "tag"])?;
+            let c = spec.char_option("char")?.unwrap_or('*');
+            if spec.bool_option("bucketed")?.unwrap_or(false) {
+                Ok(simple(
+                    spec,
+                    SimpleRedactorMode::ReplaceBucketed(c),
+                    SimpleRedactorMode::ReplaceBucketedAndTag(c),
+                )?)
+            } else {
+                Ok(simple(
+                    spec,
+                    SimpleRedactorMode::Replace(c),
+                    SimpleRedactorMode::ReplaceAndTag(c),
+                )?)
+            }
+        })
+        .register("insert", |spec| {
+            spec.check_options(&["text", "tag"])?;
+            let text = required(spec, "text", spec.str_option("text")?)?;
+            Ok(simple(
+                spec,
+                SimpleRedactorMode::Insert(text.to_owned()),
+                SimpleRedactorMode::InsertAndTag(text.to_owned()),
+            )?)
+        })
+    }
+
+    /// Registers a factory for a kind of redactor, replacing any factory previously registered for that kind.
+    #[must_use]
+    pub fn register(
+        mut self,
+        kind: impl Into<String>,
+        factory: impl Fn(&RedactorSpec) -> Result<Box<dyn Redactor>, BuilderError>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        _ = self.factories.insert(kind.into(), Box::new(factory));
+        self
+    }
+
+    /// Creates a redactor from its specification.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuilderError::UnknownRedactor`] if no factory is registered for the kind of redactor,
+    /// or any error returned by the factory, such as [`BuilderError::InvalidRedactorOption`].
+    pub fn create(&self, spec: &RedactorSpec) -> Result<Box<dyn Redactor>, BuilderError> {
+        let factory = self
+            .factories
+            .get(spec.kind())
+            .ok_or_else(|| BuilderError::UnknownRedactor(spec.kind().to_owned()))?;
+
+        factory(spec)
+    }
+}
+
+/// Creates a simple redactor, using the tagging mode when the `tag` option is set.
+fn simple(
+    spec: &RedactorSpec,
+    mode: SimpleRedactorMode,
+    tagging_mode: SimpleRedactorMode,
+) -> Result<Box<SimpleRedactor>, BuilderError> {
+    let mode = if spec.bool_option("tag")?.unwrap_or(false) {
+        tagging_mode
+    } else {
+        mode
+    };
+
+    Ok(Box::new(SimpleRedactor::with_mode(mode)))
+}
+
+/// Checks that a required option is set.
+fn required<T>(spec: &RedactorSpec, name: &str, value: Option<T>) -> Result<T, BuilderError> {
+    value.ok_or_else(|| BuilderError::MissingRedactorOption {
+        redactor: spec.kind().to_owned(),
+        option: name.to_owned(),
+    })
+}
+
+impl Default for RedactorRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for RedactorRegistry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut kinds: Vec<_> = self.factories.keys().collect();
+        kinds.sort();
+        f.debug_list().entries(kinds).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DataClass;
+
+    fn redact(spec: &RedactorSpec, value: &str) -> Result<String, BuilderError> {
+        let redactor = RedactorRegistry::new().create(spec)?;
+        let mut output = String::new();
+        redactor.redact(&DataClass::new("tax", "class"), value, &mut |s| {
+            output.push_str(s);
+        });
+        Ok(output)
+    }
+
+    #[test]
+    fn builtin_kinds_should_be_configurable() {
+        assert_eq!(
+            redact(&RedactorSpec::new("erase"), "abc"),
+            Ok(String::new())
+        );
+        assert_eq!(
+            redact(&RedactorSpec::new("erase").with_option("tag", true), "abc"),
+            Ok("<tax/class:>".to_string())
+        );
+        assert_eq!(
+            redact(&RedactorSpec::new("passthrough"), "abc"),
+            Ok("abc".to_string())
+        );
+        assert_eq!(
+            redact(&RedactorSpec::new("replace"), "abc"),
+            Ok("***".to_string())
+        );
+        assert_eq!(
+            redact(
+                &RedactorSpec::new("replace")
+                    .with_option("char", "#")
+                    .with_option("bucketed", true),
+                "abc"
+            ),
+            Ok("####".to_string())
+        );
+        assert_eq!(
+            redact(&RedactorSpec::new("insert").with_option("text", "X"), "abc"),
+            Ok("X".to_string())
+        );
+        assert_eq!(
+            redact(
+                &RedactorSpec::new("partial").with_option("keep_first", 1_i64),
+                "abc"
+            ),
+            Ok("a**".to_string())
+        );
+        assert_eq!(
+            redact(
+                &RedactorSpec::new("partial").with_option("delimiters", "-"),
+                "a-b"
+            ),
+            Ok("*-*".to_string())
+        );
+        assert_eq!(
+            redact(
+                &RedactorSpec::new("truncate").with_option("max_chars", 2_i64),
+                "abc"
+            ),
+            Ok("ab".to_string())
+        );
+    }
+
+    #[test]
+    fn invalid_specs_should_be_reported() {
+        assert_eq!(
+            redact(&RedactorSpec::new("shred"), "abc"),
+            Err(BuilderError::UnknownRedactor("shred".to_string()))
+        );
+        assert_eq!(
+            redact(&RedactorSpec::new("insert"), "abc"),
+            Err(BuilderError::MissingRedactorOption {
+                redactor: "insert".to_string(),
+                option: "text".to_string(),
+            })
+        );
+        assert_eq!(
+            redact(
+                &RedactorSpec::new("replace").with_option("char", "##"),
+                "abc"
+            ),
+            Err(BuilderError::InvalidRedactorOption {
+                redactor: "replace".to_string(),
+                option: "char".to_string(),
+            })
+        );
+        assert_eq!(
+            redact(
+                &RedactorSpec::new("partial").with_option("keep_fist", 1_i64),
+                "abc"
+            ),
+            Err(BuilderError::UnknownRedactorOption {
+                redactor: "partial".to_string(),
+                option: "keep_fist".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn custom_kinds_should_be_registered() {
+        let registry = RedactorRegistry::new().register("erase", |_| {
+            Ok(Box::new(SimpleRedactor::with_mode(
+                SimpleRedactorMode::Insert("gone".to_string()),
+            )))
+        });
+
+        let redactor = registry.create(&RedactorSpec::new("erase")).unwrap();
+        let mut output = String::new();
+        redactor.redact(&DataClass::new("tax", "class"), "abc", &mut |s| {
+            output.push_str(s);
+        });
+        assert_eq!(output, "gone");
+        assert!(format!("{registry:?}").contains("\"partial\""));
+    }
+
+    #[test]
+    #[cfg(feature = "xxh3")]
+    fn xxh3_secret_should_be_validated() {
+        let spec = RedactorSpec::new("xxh3").with_option("secret", "short");
+        assert_eq!(redact(&spec, "abc"), Err(spec.invalid_option("secret")));
+
+        let spec = RedactorSpec::new("xxh3")
+            .with_option("secret", "s".repeat(192))
+            .with_option("per_class_keys", true);
+        assert_eq!(redact(&spec, "abc").unwrap().len(), 16);
+    }
+
+    #[test]
+    #[cfg(feature = "hmac")]
+    fn hmac_key_id_should_be_validated() {
+        let spec = RedactorSpec::new("hmac")
+            .with_option("key_id", "k:1")
+            .with_option("key", "secret");
+        assert_eq!(redact(&spec, "abc"), Err(spec.invalid_option("key_id")));
+
+        let spec = RedactorSpec::new("hmac")
+            .with_option("key_id", "k1")
+            .with_option("key", "secret");
+        assert!(redact(&spec, "abc").unwrap().starts_with("k1:"));
+    }
+
+    #[test]
+    #[cfg(feature = "hmac")]
+    fn tokenize_should_be_configurable() {
+        let spec = RedactorSpec::new("tokenize")
+            .with_option("pepper", "secret")
+            .with_option("prefix", "t_");
+        assert_eq!(redact(&spec, "abc").unwrap().len(), 34);
+
+        let spec = RedactorSpec::new("tokenize");
+        assert!(matches!(
+            redact(&spec, "abc"),
+            Err(BuilderError::MissingRedactorOption { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn pattern_should_be_configurable() {
+        let spec = RedactorSpec::new("pattern")
+            .with_option("common", true)
+            .with_option("regex", r"id-\d+");
+        assert_eq!(
+            redact(&spec, "id-42 wrote to a@b.com"),
+            Ok("*** wrote to [email]".to_string())
+        );
+
+        let spec = RedactorSpec::new("pattern").with_option("regex", "(");
+        assert_eq!(redact(&spec, "abc"), Err(spec.invalid_option("regex")));
+    }
+}
@@ -0,0 +1,316 @@
+use crate::{DataClass, RedactionContext, Redactor, SecretProvider, StreamingRedactor, TagFormat};
+use core::fmt::Debug;
+use std::io::{self, Read};
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// The function building a redactor from a secret.
+type Factory<R> = Box<dyn Fn(&[u8]) -> io::Result<R> + Send + Sync>;
+
+/// A redactor keyed by a secret fetched from a [`SecretProvider`], which can be reloaded at runtime.
+///
+/// Hashing redactors such as `HmacRedactor` and `xxH3Redactor` take their secret when they're created, which would otherwise
+/// require the secret to be available where the redaction engine is built and the engine to be rebuilt
+/// whenever the secret changes. This redactor instead fetches the secret from a provider, builds the
+/// inner redactor from it with a factory function, and does so again each time [`reload`](Self::reload)
+/// is called, so secrets can be kept out of the binary and rotated while the engine is in use.
+///
+/// To reload the secret of a redactor registered with an engine, wrap the redactor in an
+/// [`Arc`](std::sync::Arc) and register a clone of it, keeping the other clone to call `reload` on.
+///
+/// The factory reports secrets it can't build a redactor from, such as secrets of the wrong length, with an
+/// error of kind [`InvalidData`](io::ErrorKind::InvalidData), so a badly rotated secret fails the reload
+/// rather than panicking.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "xxh3")] {
+/// use data_privacy::{DataClass, Redactor, ReloadableRedactor, xxH3Redactor};
+/// use std::io;
+/// use std::sync::{Arc, Mutex};
+///
+/// let secret = Arc::new(Mutex::new(b"first secret".repeat(16)));
+/// let provider = {
+///     let secret = Arc::clone(&secret);
+///     move || Ok(secret.lock().unwrap().clone())
+/// };
+///
+/// let redactor = ReloadableRedactor::new(provider, |secret| {
+///     if !(136..=256).contains(&secret.len()) {
+///         return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid xxH3 secret"));
+///     }
+///     Ok(xxH3Redactor::with_secret(secret))
+/// })
+/// .unwrap();
+///
+/// let data_class = DataClass::new("corp", "email");
+/// let redact = |redactor: &ReloadableRedactor<xxH3Redactor>| {
+///     let mut output = String::new();
+///     redactor.redact(&data_class, "john@example.com", &mut |s| output.push_str(s));
+///     output
+/// };
+/// let first = redact(&redactor);
+///
+/// *secret.lock().unwrap() = b"second secret".repeat(16);
+/// redactor.reload().unwrap();
+/// let second = redact(&redactor);
+/// assert_ne!(first, second);
+///
+/// // a secret the redactor can't be built from is rejected, and the previous redactor is kept
+/// *secret.lock().unwrap() = b"too short".to_vec();
+/// assert_eq!(redactor.reload().unwrap_err().kind(), io::ErrorKind::InvalidData);
+/// assert_eq!(redact(&redactor), second);
+/// # }
+/// ```
+pub struct ReloadableRedactor<R> {
+    provider: Box<dyn SecretProvider>,
+    factory: Factory<R>,
+    current: RwLock<Arc<R>>,
+}
+
+impl<R: Redactor> ReloadableRedactor<R> {
+    /// Creates a new instance, fetching the secret from the provider and building the inner redactor with the factory.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error produced by the provider if the secret can't be fetched, or by the factory if the
+    /// redactor can't be built from it.
+    pub fn new(
+        provider: impl SecretProvider + 'static,
+        factory: impl Fn(&[u8]) -> io::Result<R> + Send + Sync + 'static,
+    ) -> io::Result<Self> {
+        let current = factory(&provider.secret()?)?;
+        Ok(Self {
+            provider: Box::new(provider),
+            factory: Box::new(factory),
+            current: RwLock::new(Arc::new(current)),
+        })
+    }
+
+    /// Fetches the secret from the provider again and replaces the inner redactor with one built from it.
+    ///
+    /// Redactions in progress complete with the previous redactor, while later ones use the new one.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error produced by the provider if the secret can't be fetched, or by the factory if the
+    /// redactor can't be built from it, in which case the previous redactor is kept.
+    pub fn reload(&self) -> io::Result<()> {
+        let redactor = (self.factory)(&self.provider.secret()?)?;
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(redactor);
+        Ok(())
+    }
+
+    /// Calls the function with the current inner redactor.
+    ///
+    /// The lock is released before the function is called, so slow outputs don't hold up reloads, and
+    /// outputs which reload the redactor don't deadlock.
+    fn with_current<T>(&self, f: impl FnOnce(&R) -> T) -> T {
+        let current = Arc::clone(&self.current.read().unwrap_or_else(PoisonError::into_inner));
+        f(&current)
+    }
+}
+
+impl<R: Redactor> Redactor for ReloadableRedactor<R> {
+    fn redact(&self, data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
+        self.with_current(|r| r.redact(data_class, value, output));
+    }
+
+    fn redact_ctx(
+        &self,
+        data_class: &DataClass,
+        value: &str,
+        context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) {
+        self.with_current(|r| r.redact_ctx(data_class, value, context, output));
+    }
+
+    fn redact_bytes(
+        &self,
+        data_class: &DataClass,
+        value: &[u8],
+        context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) {
+        self.with_current(|r| r.redact_bytes(data_class, value, context, output));
+    }
+
+    fn exact_len(&self) -> Option<usize> {
+        self.with_current(Redactor::exact_len)
+    }
+
+    fn len_hint(
+        &self,
+        data_class: &DataClass,
+        input_len: usize,
+        tag_format: &TagFormat,
+    ) -> Option<usize> {
+        self.with_current(|r| r.len_hint(data_class, input_len, tag_format))
+    }
+
+    fn as_streaming(&self) -> Option<&dyn StreamingRedactor> {
+        self.with_current(|r| r.as_streaming().is_some())
+            .then_some(self)
+    }
+
+    fn reveals_input(&self) -> bool {
+        self.with_current(Redactor::reveals_input)
+    }
+}
+
+impl<R: Redactor> StreamingRedactor for ReloadableRedactor<R> {
+    fn redact_reader(
+        &self,
+        data_class: &DataClass,
+        reader: &mut dyn Read,
+        output: &mut dyn FnMut(&str),
+    ) -> io::Result<()> {
+        self.redact_reader_ctx(data_class, reader, &RedactionContext::new(), output)
+    }
+
+    fn redact_reader_ctx(
+        &self,
+        data_class: &DataClass,
+        reader: &mut dyn Read,
+        context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) -> io::Result<()> {
+        self.with_current(|r| {
+            if let Some(streaming) = r.as_streaming() {
+                return streaming.redact_reader_ctx(data_class, reader, context, output);
+            }
+
+            // the inner redactor may have been replaced by one which doesn't stream since as_streaming was called
+            let mut value = String::new();
+            _ = reader.read_to_string(&mut value)?;
+            r.redact_ctx(data_class, &value, context, output);
+            Ok(())
+        })
+    }
+
+    fn is_passthrough(&self) -> bool {
+        self.with_current(|r| {
+            r.as_streaming()
+                .is_some_and(StreamingRedactor::is_passthrough)
+        })
+    }
+}
+
+impl<R> Debug for ReloadableRedactor<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ReloadableRedactor").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::CoreTaxonomy;
+    use crate::{SimpleRedactor, SimpleRedactorMode};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    fn redact_to_string(redactor: &dyn Redactor, value: &str) -> String {
+        let mut output = String::new();
+        redactor.redact(&CoreTaxonomy::Sensitive.data_class(), value, &mut |s| {
+            output.push_str(s);
+        });
+        output
+    }
+
+    #[test]
+    fn reload_should_rebuild_redactor_and_keep_it_on_failure() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = {
+            let calls = Arc::clone(&calls);
+            move || match calls.fetch_add(1, Ordering::Relaxed) {
+                1 => Err(io::Error::other("vault unavailable")),
+                n => Ok(vec![b'a' + u8::try_from(n).unwrap()]),
+            }
+        };
+
+        let redactor = ReloadableRedactor::new(provider, |secret| {
+            Ok(SimpleRedactor::with_mode(SimpleRedactorMode::Replace(
+                char::from(secret[0]),
+            )))
+        })
+        .unwrap();
+        assert_eq!(redact_to_string(&redactor, "xyz"), "aaa");
+
+        assert_eq!(
+            redactor.reload().unwrap_err().to_string(),
+            "vault unavailable"
+        );
+        assert_eq!(redact_to_string(&redactor, "xyz"), "aaa");
+
+        redactor.reload().unwrap();
+        assert_eq!(redact_to_string(&redactor, "xyz"), "ccc");
+    }
+
+    #[test]
+    fn reload_should_keep_redactor_when_factory_fails() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = {
+            let calls = Arc::clone(&calls);
+            move || match calls.fetch_add(1, Ordering::Relaxed) {
+                1 => Ok(Vec::new()),
+                _ => Ok(b"#".to_vec()),
+            }
+        };
+
+        let redactor = ReloadableRedactor::new(provider, |secret| {
+            let c = secret
+                .first()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "empty secret"))?;
+            Ok(SimpleRedactor::with_mode(SimpleRedactorMode::Replace(
+                char::from(*c),
+            )))
+        })
+        .unwrap();
+
+        assert_eq!(
+            redactor.reload().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert_eq!(redact_to_string(&redactor, "xyz"), "###");
+    }
+
+    #[test]
+    fn outputs_should_be_able_to_reload() {
+        let redactor =
+            ReloadableRedactor::new(|| Ok(Vec::new()), |_| Ok(SimpleRedactor::new())).unwrap();
+
+        let mut output = String::new();
+        redactor.redact(&CoreTaxonomy::Sensitive.data_class(), "abc", &mut |s| {
+            redactor.reload().unwrap();
+            output.push_str(s);
+        });
+        assert_eq!(output, "***");
+    }
+
+    #[test]
+    fn new_should_fail_without_secret() {
+        let result = ReloadableRedactor::new(
+            || Err(io::Error::from(io::ErrorKind::NotFound)),
+            |_| Ok(SimpleRedactor::new()),
+        );
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn streaming_should_use_inner_redactor() {
+        let redactor =
+            ReloadableRedactor::new(|| Ok(Vec::new()), |_| Ok(SimpleRedactor::new())).unwrap();
+        let streaming = redactor.as_streaming().unwrap();
+
+        let mut output = String::new();
+        streaming
+            .redact_reader(
+                &CoreTaxonomy::Sensitive.data_class(),
+                &mut &b"secret"[..],
+                &mut |s| output.push_str(s),
+            )
+            .unwrap();
+        assert_eq!(output, "******");
+    }
+}
@@ -0,0 +1,297 @@
+use crate::{DataClass, Redactor, StreamingRedactor, xxH3Redactor};
+use core::time::Duration;
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+use std::time::SystemTime;
+
+/// A redactor which hashes values with secrets that rotate over time.
+///
+/// Pseudonymization regimes such as the GDPR's often require hashing secrets to be rotated periodically,
+/// so that pseudonyms can't be correlated indefinitely. This redactor holds a set of secrets, each keyed
+/// by an epoch number, and hashes values with the [`xxH3Redactor`] of the active epoch. The output is
+/// prefixed with the epoch number, as in `42:8f0c6e3b1a9d2c47`, so values remain correlatable within an
+/// epoch and it's always known which secret produced a hash.
+///
+/// The active epoch is selected in one of three ways, in order of precedence:
+///
+/// * Explicitly, with [`with_active_epoch`](Self::with_active_epoch).
+/// * By time, with [`rotate_every`](Self::rotate_every), where the epoch is the number of whole periods
+///   elapsed since the Unix epoch.
+/// * Otherwise, the highest registered epoch is used.
+///
+/// If the selected epoch has no secret, the secret of the closest earlier epoch is used instead, or the
+/// earliest secret if every registered epoch is later, so that a late rollout of new secrets never stops
+/// redaction.
+///
+/// Like the [`xxH3Redactor`], this redactor only [streams](Redactor::as_streaming) values when the secrets
+/// of all its epochs have the recommended length of 192 bytes.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::{DataClass, Redactor, RotatingHashRedactor};
+///
+/// let redactor = RotatingHashRedactor::new(1, [1u8; 192])
+///     .add_epoch(2, [2u8; 192])
+///     .with_active_epoch(2);
+///
+/// let mut output = String::new();
+/// redactor.redact(&DataClass::new("corp", "email"), "john@example.com", &mut |s| output.push_str(s));
+/// assert!(output.starts_with("2:"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct RotatingHashRedactor {
+    epochs: BTreeMap<u64, xxH3Redactor>,
+    active_epoch: Option<u64>,
+    period: Option<Duration>,
+    per_class_keys: bool,
+}
+
+impl RotatingHashRedactor {
+    /// Creates a new instance with the secret of a first epoch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the secret isn't valid for an [`xxH3Redactor`].
+    #[must_use]
+    pub fn new(epoch: u64, secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            epochs: BTreeMap::new(),
+            active_epoch: None,
+            period: None,
+            per_class_keys: false,
+        }
+        .add_epoch(epoch, secret)
+    }
+
+    /// Adds the secret of another epoch.
+    ///
+    /// If a secret was already registered for the epoch, it is replaced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the secret isn't valid for an [`xxH3Redactor`].
+    #[must_use]
+    pub fn add_epoch(mut self, epoch: u64, secret: impl AsRef<[u8]>) -> Self {
+        let redactor = xxH3Redactor::with_secret(secret);
+        let redactor = if self.per_class_keys {
+            redactor.with_per_class_keys()
+        } else {
+            redactor
+        };
+
+        _ = self.epochs.insert(epoch, redactor);
+        self
+    }
+
+    /// Makes the given epoch the active one, regardless of time.
+    #[must_use]
+    pub const fn with_active_epoch(mut self, epoch: u64) -> Self {
+        self.active_epoch = Some(epoch);
+        self
+    }
+
+    /// Selects the active epoch by time, starting a new epoch every period.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the period is shorter than a second.
+    #[must_use]
+    pub fn rotate_every(mut self, period: Duration) -> Self {
+        assert!(
+            period.as_secs() > 0,
+            "Rotation period must be at least one second"
+        );
+        self.period = Some(period);
+        self
+    }
+
+    /// Derives a distinct secret for each data class from the secret of each epoch.
+    ///
+    /// See [`xxH3Redactor::with_per_class_keys`] for details.
+    #[must_use]
+    pub fn with_per_class_keys(mut self) -> Self {
+        self.per_class_keys = true;
+        self.epochs = self
+            .epochs
+            .into_iter()
+            .map(|(epoch, redactor)| (epoch, redactor.with_per_class_keys()))
+            .collect();
+        self
+    }
+
+    /// Returns the epoch whose secret is used at the given time.
+    #[must_use]
+    pub fn active_epoch_at(&self, time: SystemTime) -> u64 {
+        self.select(time).0
+    }
+
+    fn select(&self, time: SystemTime) -> (u64, &xxH3Redactor) {
+        let target = self.active_epoch.or_else(|| {
+            self.period.map(|period| {
+                let elapsed = time
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap_or_default();
+                u64::try_from(elapsed.as_nanos() / period.as_nanos()).unwrap_or(u64::MAX)
+            })
+        });
+
+        let selected = target.map_or_else(
+            || self.epochs.last_key_value(),
+            |target| {
+                self.epochs
+                    .range(..=target)
+                    .next_back()
+                    .or_else(|| self.epochs.first_key_value())
+            },
+        );
+
+        let (epoch, redactor) = selected.expect("there is always at least one epoch");
+        (*epoch, redactor)
+    }
+}
+
+impl Redactor for RotatingHashRedactor {
+    fn redact(&self, data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
+        let (epoch, redactor) = self.select(SystemTime::now());
+
+        let mut redacted = epoch.to_string();
+        redacted.push(':');
+        redactor.redact(data_class, value, &mut |s| redacted.push_str(s));
+        output(&redacted);
+    }
+
+    fn as_streaming(&self) -> Option<&dyn StreamingRedactor> {
+        self.epochs
+            .values()
+            .all(|redactor| redactor.as_streaming().is_some())
+            .then_some(self)
+    }
+
+    fn reveals_input(&self) -> bool {
+        false
+    }
+}
+
+impl StreamingRedactor for RotatingHashRedactor {
+    fn redact_reader(
+        &self,
+        data_class: &DataClass,
+        reader: &mut dyn Read,
+        output: &mut dyn FnMut(&str),
+    ) -> io::Result<()> {
+        let (epoch, redactor) = self.select(SystemTime::now());
+
+        let mut redacted = epoch.to_string();
+        redacted.push(':');
+        redactor.redact_reader(data_class, reader, &mut |s| redacted.push_str(s))?;
+        output(&redacted);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn secret(seed: u8) -> Vec<u8> {
+        (0..192_u8)
+            .map(|i| i.wrapping_mul(seed).wrapping_add(seed))
+            .collect()
+    }
+
+    fn redact(redactor: &RotatingHashRedactor, data_class: &DataClass, value: &str) -> String {
+        let mut output = String::new();
+        redactor.redact(data_class, value, &mut |s| output.push_str(s));
+        output
+    }
+
+    #[test]
+    fn redact_should_prefix_hash_with_epoch() {
+        let data_class = DataClass::new("test_taxonomy", "class");
+        let first = RotatingHashRedactor::new(1, secret(1)).add_epoch(2, secret(2));
+        let pinned = first.clone().with_active_epoch(1);
+
+        let output = redact(&first, &data_class, "value");
+        assert!(output.starts_with("2:"));
+        assert_eq!(output.len(), "2:".len() + 16);
+        assert_eq!(output, redact(&first, &data_class, "value"));
+
+        let pinned_output = redact(&pinned, &data_class, "value");
+        assert!(pinned_output.starts_with("1:"));
+        assert_ne!(output.strip_prefix("2:"), pinned_output.strip_prefix("1:"));
+
+        let mut streamed = String::new();
+        first
+            .redact_reader(&data_class, &mut &b"value"[..], &mut |s| {
+                streamed.push_str(s);
+            })
+            .unwrap();
+        assert_eq!(streamed, output);
+    }
+
+    #[test]
+    fn streaming_should_require_streaming_secrets() {
+        let redactor = RotatingHashRedactor::new(1, secret(1));
+        assert!(redactor.as_streaming().is_some());
+        assert!(redactor.add_epoch(2, [2u8; 136]).as_streaming().is_none());
+    }
+
+    #[test]
+    fn epoch_should_follow_time() {
+        let redactor = RotatingHashRedactor::new(100, secret(1))
+            .add_epoch(102, secret(2))
+            .rotate_every(DAY);
+        let at_day = |day: u32| SystemTime::UNIX_EPOCH + DAY * day;
+
+        assert_eq!(redactor.active_epoch_at(at_day(50)), 100);
+        assert_eq!(redactor.active_epoch_at(at_day(101)), 100);
+        assert_eq!(redactor.active_epoch_at(at_day(102)), 102);
+        assert_eq!(redactor.active_epoch_at(at_day(500)), 102);
+        assert_eq!(
+            redactor.with_active_epoch(100).active_epoch_at(at_day(500)),
+            100
+        );
+    }
+
+    #[test]
+    fn per_class_keys_should_apply_to_every_epoch() {
+        let redactor = RotatingHashRedactor::new(1, secret(1))
+            .with_per_class_keys()
+            .add_epoch(2, secret(2));
+        let class1 = DataClass::new("test_taxonomy", "class1");
+        let class2 = DataClass::new("test_taxonomy", "class2");
+
+        for epoch in [1, 2] {
+            let redactor = redactor.clone().with_active_epoch(epoch);
+            assert_ne!(
+                redact(&redactor, &class1, "value"),
+                redact(&redactor, &class2, "value")
+            );
+        }
+    }
+
+    #[test]
+    fn epoch_should_follow_fractional_periods() {
+        let redactor = RotatingHashRedactor::new(0, secret(1))
+            .add_epoch(2, secret(2))
+            .add_epoch(3, secret(3))
+            .rotate_every(Duration::from_millis(1500));
+        let at_secs = |secs: u64| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+
+        assert_eq!(redactor.active_epoch_at(at_secs(2)), 0);
+        assert_eq!(redactor.active_epoch_at(at_secs(3)), 2);
+        assert_eq!(redactor.active_epoch_at(at_secs(4)), 2);
+        assert_eq!(redactor.active_epoch_at(at_secs(5)), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Rotation period must be at least one second")]
+    fn rotate_every_should_reject_short_periods() {
+        _ = RotatingHashRedactor::new(1, secret(1)).rotate_every(Duration::from_millis(10));
+    }
+
+    crate::redactor_conformance_tests!(RotatingHashRedactor::new(1, secret(1)));
+}
@@ -0,0 +1,38 @@
+use std::io;
+
+/// A source of the secret keying a hashing redactor.
+///
+/// Providers let secrets be kept out of the binary and out of the code building the redaction engine,
+/// with the secret being fetched when a [`ReloadableRedactor`](crate::ReloadableRedactor) is created and
+/// each time it's [reloaded](crate::ReloadableRedactor::reload), which lets secrets be rotated without
+/// rebuilding the engine.
+///
+/// This crate provides [`EnvSecretProvider`](crate::EnvSecretProvider) and
+/// [`FileSecretProvider`](crate::FileSecretProvider), and the trait is implemented for closures, which
+/// lets secrets come from anywhere else, such as a key vault client.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::SecretProvider;
+///
+/// let provider = || Ok(b"a secret from a key vault".to_vec());
+/// assert_eq!(provider.secret().unwrap(), b"a secret from a key vault");
+/// ```
+pub trait SecretProvider: Send + Sync {
+    /// Fetches the current secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the secret can't be fetched.
+    fn secret(&self) -> io::Result<Vec<u8>>;
+}
+
+impl<F> SecretProvider for F
+where
+    F: Fn() -> io::Result<Vec<u8>> + Send + Sync,
+{
+    fn secret(&self) -> io::Result<Vec<u8>> {
+        self()
+    }
+}
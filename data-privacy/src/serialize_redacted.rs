@@ -0,0 +1,397 @@
+use crate::{DataClass, RedactionEngine};
+use core::cell::Cell;
+use core::fmt::{Debug, Display};
+use serde::ser::{Error, Impossible, Serialize, Serializer};
+
+thread_local! {
+    /// The engine redacting the classified values serialized on this thread, if any.
+    static CURRENT_ENGINE: Cell<*const RedactionEngine> = const { Cell::new(core::ptr::null()) };
+}
+
+/// Serializes a value while redacting every classified container it holds.
+///
+/// Classified containers normally serialize their payload in plain text, so they can be persisted and
+/// read back. When serialized within this wrapper, they instead serialize as a string produced by the
+/// [`RedactionEngine`] for their data class, so telemetry produced with serde never contains the
+/// sensitive payloads.
+///
+/// Payloads are converted to text before being redacted. Payloads which serialize as a single scalar
+/// value, such as strings, numbers, or an `Option` of one, are redacted based on that value. Any other
+/// payload is redacted as if it were empty.
+///
+/// You create instances of this type with [`RedactionEngine::to_serializable`].
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+/// use data_privacy::{RedactionEngineBuilder, SimpleRedactor};
+///
+/// #[derive(serde::Serialize)]
+/// struct Employee {
+///     name: Sensitive<String>,
+///     age: u32,
+/// }
+///
+/// let engine = RedactionEngineBuilder::new()
+///     .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+///     .build();
+///
+/// let employee = Employee { name: Sensitive::new("John".to_string()), age: 33 };
+/// let json = serde_json::to_string(&engine.to_serializable(&employee)).unwrap();
+/// assert_eq!(json, r#"{"name":"****","age":33}"#);
+/// ```
+pub struct SerializeRedacted<'a, T: ?Sized> {
+    engine: &'a RedactionEngine,
+    value: &'a T,
+}
+
+impl<'a, T: ?Sized> SerializeRedacted<'a, T> {
+    pub(crate) const fn new(engine: &'a RedactionEngine, value: &'a T) -> Self {
+        Self { engine, value }
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for SerializeRedacted<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        /// Restores the previous engine once serialization completes, even when unwinding.
+        struct Restore(*const RedactionEngine);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT_ENGINE.set(self.0);
+            }
+        }
+
+        let _restore = Restore(CURRENT_ENGINE.replace(self.engine));
+        self.value.serialize(serializer)
+    }
+}
+
+impl<T: ?Sized> Debug for SerializeRedacted<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SerializeRedacted").finish_non_exhaustive()
+    }
+}
+
+/// Serializes the payload of a classified container, redacting it when within a [`SerializeRedacted`].
+///
+/// # Errors
+///
+/// Returns any error produced by the serializer.
+pub fn serialize_classified<T, S>(
+    payload: &T,
+    data_class: &DataClass,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize + ?Sized,
+    S: Serializer,
+{
+    let engine = CURRENT_ENGINE.get();
+    if engine.is_null() {
+        return payload.serialize(serializer);
+    }
+
+    // SAFETY: The pointer is only set while a `SerializeRedacted` borrowing the engine is being serialized
+    // on this thread, and is restored before that borrow ends.
+    serialize_redacted_payload(unsafe { &*engine }, payload, data_class, serializer)
+}
+
+/// Serializes the redacted form of the payload of a classified container.
+fn serialize_redacted_payload<T, S>(
+    engine: &RedactionEngine,
+    payload: &T,
+    data_class: &DataClass,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize + ?Sized,
+    S: Serializer,
+{
+    let mut text = String::new();
+    if payload.serialize(ScalarWriter(&mut text)).is_err() {
+        text.clear();
+    }
+
+    let mut redacted = String::new();
+    engine.redact(data_class, &text, |s| redacted.push_str(s));
+    serializer.serialize_str(&redacted)
+}
+
+/// The error produced when serializing a value which isn't a scalar.
+#[derive(Debug)]
+struct NotScalar;
+
+impl Display for NotScalar {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("value is not a scalar")
+    }
+}
+
+impl core::error::Error for NotScalar {}
+
+impl Error for NotScalar {
+    fn custom<M: Display>(_msg: M) -> Self {
+        Self
+    }
+}
+
+/// A serializer which writes the text of a scalar value to a string.
+struct ScalarWriter<'a>(&'a mut String);
+
+impl ScalarWriter<'_> {
+    #[expect(
+        clippy::unnecessary_wraps,
+        reason = "Matches the serializer signatures"
+    )]
+    fn write(self, value: impl Display) -> Result<(), NotScalar> {
+        use core::fmt::Write;
+
+        _ = write!(self.0, "{value}");
+        Ok(())
+    }
+}
+
+impl Serializer for ScalarWriter<'_> {
+    type Ok = ();
+    type Error = NotScalar;
+    type SerializeSeq = Impossible<(), NotScalar>;
+    type SerializeTuple = Impossible<(), NotScalar>;
+    type SerializeTupleStruct = Impossible<(), NotScalar>;
+    type SerializeTupleVariant = Impossible<(), NotScalar>;
+    type SerializeMap = Impossible<(), NotScalar>;
+    type SerializeStruct = Impossible<(), NotScalar>;
+    type SerializeStructVariant = Impossible<(), NotScalar>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), NotScalar> {
+        self.write(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), NotScalar> {
+        self.write(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), NotScalar> {
+        self.write(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), NotScalar> {
+        self.write(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), NotScalar> {
+        self.write(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), NotScalar> {
+        self.write(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), NotScalar> {
+        self.write(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), NotScalar> {
+        self.write(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), NotScalar> {
+        self.write(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), NotScalar> {
+        self.write(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), NotScalar> {
+        self.write(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), NotScalar> {
+        self.write(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), NotScalar> {
+        self.write(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), NotScalar> {
+        self.write(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), NotScalar> {
+        self.0.push_str(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), NotScalar> {
+        self.write(String::from_utf8_lossy(v))
+    }
+
+    fn serialize_none(self) -> Result<(), NotScalar> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), NotScalar> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), NotScalar> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), NotScalar> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), NotScalar> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), NotScalar> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), NotScalar> {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, NotScalar> {
+        Err(NotScalar)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, NotScalar> {
+        Err(NotScalar)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, NotScalar> {
+        Err(NotScalar)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, NotScalar> {
+        Err(NotScalar)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, NotScalar> {
+        Err(NotScalar)
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, NotScalar> {
+        Err(NotScalar)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, NotScalar> {
+        Err(NotScalar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::{CoreTaxonomy, Insensitive, Sensitive, UnknownSensitivity};
+    use crate::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+    use std::collections::BTreeMap;
+
+    #[derive(serde::Serialize)]
+    struct Record {
+        name: Sensitive<String>,
+        nickname: Sensitive<Option<&'static str>>,
+        tags: Sensitive<Vec<u32>>,
+        team: Insensitive<&'static str>,
+        notes: UnknownSensitivity<&'static str>,
+        visits: BTreeMap<&'static str, Sensitive<u32>>,
+    }
+
+    fn create_engine() -> RedactionEngine {
+        RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::ReplaceAndTag('*')),
+            )
+            .add_class_redactor(
+                &CoreTaxonomy::Insensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+            )
+            .omit_class(&CoreTaxonomy::UnknownSensitivity.data_class())
+            .build()
+    }
+
+    fn create_record() -> Record {
+        Record {
+            name: Sensitive::new("John".to_string()),
+            nickname: Sensitive::new(Some("JJ")),
+            tags: Sensitive::new(vec![1, 2]),
+            team: Insensitive::new("ops"),
+            notes: UnknownSensitivity::new("hidden"),
+            visits: BTreeMap::from([("home", Sensitive::new(12))]),
+        }
+    }
+
+    #[test]
+    fn classified_values_should_be_redacted() {
+        let engine = create_engine();
+        let json = serde_json::to_string(&engine.to_serializable(&create_record())).unwrap();
+        assert_eq!(
+            json,
+            r#"{"name":"<core/sensitive:****>","nickname":"<core/sensitive:**>","tags":"<core/sensitive:>","team":"ops","notes":"","visits":{"home":"<core/sensitive:**>"}}"#
+        );
+    }
+
+    #[test]
+    fn plain_serialization_should_be_unaffected() {
+        let engine = create_engine();
+        _ = serde_json::to_string(&engine.to_serializable(&create_record())).unwrap();
+
+        let json = serde_json::to_string(&create_record()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"name":"John","nickname":"JJ","tags":[1,2],"team":"ops","notes":"hidden","visits":{"home":12}}"#
+        );
+    }
+
+    #[test]
+    fn debug_should_not_expose_value() {
+        let engine = create_engine();
+        let record = create_record();
+        assert_eq!(
+            format!("{:?}", engine.to_serializable(&record)),
+            "SerializeRedacted { .. }"
+        );
+    }
+}
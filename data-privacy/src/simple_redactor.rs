@@ -1,5 +1,11 @@
 use crate::DataClass;
-use crate::Redactor;
+use crate::{RedactionContext, Redactor, TagFormat};
+use alloc::borrow::Cow;
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+/// The name of the redactor, as it appears in tags which include it.
+const NAME: &str = "simple";
 
 /// Mode of operation for the `SimpleRedactor`.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -14,14 +20,31 @@ pub enum SimpleRedactorMode {
     Passthrough,
 
     /// Passes the original string through and tags it with the class id.
+    ///
+    /// Delimiters within the original string are escaped, as described in [`TagFormat`].
     PassthroughAndTag,
 
     /// Replaces the original string with a repeated character.
+    ///
+    /// The mask holds one character per unit of the original string, as counted by the redactor's
+    /// [`MaskLength`].
     Replace(char),
 
     /// Replaces the original string with a repeated character and tags it with the class id.
     ReplaceAndTag(char),
 
+    /// Replaces the original string with a repeated character, whose length reflects a coarse bucket
+    /// rather than the exact length of the original string.
+    ///
+    /// Strings of up to 8 units, as counted by the redactor's [`MaskLength`], produce a mask of 4 characters,
+    /// strings of up to 32 units produce a mask of 16 characters, and longer strings produce a mask of 32
+    /// characters. Empty strings produce no mask. This retains a rough sense of the size of the data for
+    /// debugging, without leaking its exact length.
+    ReplaceBucketed(char),
+
+    /// Replaces the original string with a length-bucketed repeated character and tags it with the class id.
+    ReplaceBucketedAndTag(char),
+
     /// Inserts a custom string in place of the original string.
     Insert(String),
 
@@ -29,80 +52,273 @@ pub enum SimpleRedactorMode {
     InsertAndTag(String),
 }
 
+/// How the length of the original string is measured by the masking modes of the `SimpleRedactor`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MaskLength {
+    /// Masks hold one character per byte of the UTF-8 encoding of the original string.
+    ///
+    /// Characters outside of the ASCII range take several bytes, so `héllo` produces a mask of 6
+    /// characters.
+    #[default]
+    Bytes,
+
+    /// Masks hold one character per character of the original string.
+    ///
+    /// This matches what readers see: `héllo` and `hello` both produce a mask of 5 characters.
+    Chars,
+}
+
+impl MaskLength {
+    /// Measures a string.
+    fn of(self, value: &str) -> usize {
+        match self {
+            Self::Chars => value.chars().count(),
+            Self::Bytes => value.len(),
+        }
+    }
+}
+
 /// A redactor that performs a variety of simple transformations on the input text.
+///
+/// The masking modes never split characters and are safe for any UTF-8 input: the mask is made of whole
+/// repetitions of the mask character, and its length only depends on the length of the original string,
+/// measured in bytes by default, or in characters with [`with_mask_length`](Self::with_mask_length).
 #[derive(Clone, Debug)]
 pub struct SimpleRedactor {
     mode: SimpleRedactorMode,
+    mask_length: MaskLength,
 }
 
 impl SimpleRedactor {
     /// Creates a new instance with the default mode of `SimpleRedactorMode::Replace('*')`.
     #[must_use]
     pub const fn new() -> Self {
-        Self {
-            mode: SimpleRedactorMode::Replace('*'),
-        }
+        Self::with_mode(SimpleRedactorMode::Replace('*'))
     }
 
     /// Creates a new instance with an explicit mode of operation.
     #[must_use]
     pub const fn with_mode(mode: SimpleRedactorMode) -> Self {
-        Self { mode }
+        Self {
+            mode,
+            mask_length: MaskLength::Bytes,
+        }
+    }
+
+    /// Sets how the length of the original string is measured when masking it.
+    #[must_use]
+    pub const fn with_mask_length(mut self, mask_length: MaskLength) -> Self {
+        self.mask_length = mask_length;
+        self
     }
 }
 
 impl Redactor for SimpleRedactor {
-    #[cfg_attr(test, mutants::skip)]
     fn redact(&self, data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
-        static ASTERISKS: &str = "********************************";
+        self.redact_ctx(data_class, value, &RedactionContext::new(), output);
+    }
 
+    #[cfg_attr(test, mutants::skip)]
+    fn redact_ctx(
+        &self,
+        data_class: &DataClass,
+        value: &str,
+        context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) {
+        let tag_format = context.tag_format();
         match &self.mode {
             SimpleRedactorMode::Erase => {
                 // nothing
             }
             SimpleRedactorMode::EraseAndTag => {
-                output(format!("<{data_class}:>").as_str());
+                tag_format.tag(data_class, NAME, "", output);
             }
             SimpleRedactorMode::Passthrough => {
                 output(value);
             }
             SimpleRedactorMode::PassthroughAndTag => {
-                output(format!("<{data_class}:{value}>").as_str());
+                tag_format.tag(data_class, NAME, value, output);
             }
 
-            #[expect(clippy::string_slice, reason = "No problem with UTF-8 here")]
             SimpleRedactorMode::Replace(c) => {
-                let len = value.len();
-                if *c == '*' && len < ASTERISKS.len() {
-                    output(&ASTERISKS[0..len]);
-                } else {
-                    output(c.to_string().repeat(len).as_str());
-                }
+                output(&mask(*c, self.mask_length.of(value)));
             }
-
-            #[expect(clippy::string_slice, reason = "No problem with UTF-8 here")]
             SimpleRedactorMode::ReplaceAndTag(c) => {
-                let len = value.len();
-                if *c == '*' && len < ASTERISKS.len() {
-                    output(format!("<{data_class}:{}>", &ASTERISKS[0..len]).as_str());
-                } else {
-                    output(
-                        format!("<{data_class}:{}>", (*c.to_string()).repeat(len).as_str())
-                            .as_str(),
-                    );
-                }
+                tag_format.tag(
+                    data_class,
+                    NAME,
+                    &mask(*c, self.mask_length.of(value)),
+                    output,
+                );
+            }
+            SimpleRedactorMode::ReplaceBucketed(c) => {
+                output(&mask(*c, bucketed_len(self.mask_length.of(value))));
+            }
+            SimpleRedactorMode::ReplaceBucketedAndTag(c) => {
+                tag_format.tag(
+                    data_class,
+                    NAME,
+                    &mask(*c, bucketed_len(self.mask_length.of(value))),
+                    output,
+                );
             }
             SimpleRedactorMode::Insert(s) => {
                 output(s.as_str());
             }
             SimpleRedactorMode::InsertAndTag(s) => {
-                output(format!("<{data_class}:{s}>").as_str());
+                tag_format.tag(data_class, NAME, s, output);
             }
         }
     }
 
     fn exact_len(&self) -> Option<usize> {
-        matches!(&self.mode, SimpleRedactorMode::Erase).then_some(0)
+        match &self.mode {
+            SimpleRedactorMode::Erase => Some(0),
+            SimpleRedactorMode::Insert(s) => Some(s.len()),
+            _ => None,
+        }
+    }
+
+    fn len_hint(
+        &self,
+        data_class: &DataClass,
+        input_len: usize,
+        tag_format: &TagFormat,
+    ) -> Option<usize> {
+        let tagged = |value_len| tag_format.tagged_len(data_class, NAME, value_len);
+        let tagged_mask =
+            |c: char, len: usize| tagged(len * tag_format.escaped_len(c.encode_utf8(&mut [0; 4])));
+
+        // masks are measured in bytes by default, and measuring them in characters gives the same
+        // length for ASCII values, while the length of tagged values which are passed through depends on
+        // how many of their characters are escaped
+        match &self.mode {
+            SimpleRedactorMode::Erase => Some(0),
+            SimpleRedactorMode::EraseAndTag => Some(tagged(0)),
+            SimpleRedactorMode::Passthrough => Some(input_len),
+            SimpleRedactorMode::PassthroughAndTag => None,
+            SimpleRedactorMode::Replace(c) => Some(input_len * c.len_utf8()),
+            SimpleRedactorMode::ReplaceAndTag(c) => Some(tagged_mask(*c, input_len)),
+            SimpleRedactorMode::ReplaceBucketed(c) => Some(bucketed_len(input_len) * c.len_utf8()),
+            SimpleRedactorMode::ReplaceBucketedAndTag(c) => {
+                Some(tagged_mask(*c, bucketed_len(input_len)))
+            }
+            SimpleRedactorMode::Insert(s) => Some(s.len()),
+            SimpleRedactorMode::InsertAndTag(s) => Some(tagged(tag_format.escaped_len(s))),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn as_streaming(&self) -> Option<&dyn crate::StreamingRedactor> {
+        Some(self)
+    }
+
+    fn reveals_input(&self) -> bool {
+        matches!(
+            self.mode,
+            SimpleRedactorMode::Passthrough | SimpleRedactorMode::PassthroughAndTag
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::StreamingRedactor for SimpleRedactor {
+    fn redact_reader(
+        &self,
+        data_class: &DataClass,
+        reader: &mut dyn std::io::Read,
+        output: &mut dyn FnMut(&str),
+    ) -> std::io::Result<()> {
+        self.redact_reader_ctx(data_class, reader, &RedactionContext::new(), output)
+    }
+
+    fn redact_reader_ctx(
+        &self,
+        data_class: &DataClass,
+        reader: &mut dyn std::io::Read,
+        context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) -> std::io::Result<()> {
+        use crate::streaming_redactor::read_str_chunks;
+
+        let tag_format = context.tag_format();
+        let tagged = matches!(
+            &self.mode,
+            SimpleRedactorMode::EraseAndTag
+                | SimpleRedactorMode::PassthroughAndTag
+                | SimpleRedactorMode::ReplaceAndTag(_)
+                | SimpleRedactorMode::ReplaceBucketedAndTag(_)
+                | SimpleRedactorMode::InsertAndTag(_)
+        );
+
+        if tagged {
+            output(&tag_format.header(data_class, NAME));
+        }
+
+        {
+            let mut emit = |s: &str| {
+                if tagged {
+                    tag_format.escape(s, output);
+                } else {
+                    output(s);
+                }
+            };
+
+            match &self.mode {
+                SimpleRedactorMode::Erase | SimpleRedactorMode::EraseAndTag => {
+                    read_str_chunks(reader, |_| {})?;
+                }
+                SimpleRedactorMode::Passthrough | SimpleRedactorMode::PassthroughAndTag => {
+                    read_str_chunks(reader, |chunk| emit(chunk))?;
+                }
+                SimpleRedactorMode::Replace(c) | SimpleRedactorMode::ReplaceAndTag(c) => {
+                    read_str_chunks(reader, |chunk| {
+                        emit(&mask(*c, self.mask_length.of(chunk)));
+                    })?;
+                }
+                SimpleRedactorMode::ReplaceBucketed(c)
+                | SimpleRedactorMode::ReplaceBucketedAndTag(c) => {
+                    let mut len = 0;
+                    read_str_chunks(reader, |chunk| len += self.mask_length.of(chunk))?;
+                    emit(&mask(*c, bucketed_len(len)));
+                }
+                SimpleRedactorMode::Insert(s) | SimpleRedactorMode::InsertAndTag(s) => {
+                    read_str_chunks(reader, |_| {})?;
+                    emit(s.as_str());
+                }
+            }
+        }
+
+        if tagged {
+            output(tag_format.suffix());
+        }
+
+        Ok(())
+    }
+
+    fn is_passthrough(&self) -> bool {
+        matches!(self.mode, SimpleRedactorMode::Passthrough)
+    }
+}
+
+/// Produces a string of `len` repetitions of a character, avoiding allocation for short runs of asterisks.
+fn mask(c: char, len: usize) -> Cow<'static, str> {
+    static ASTERISKS: &str = "********************************";
+
+    match ASTERISKS.get(..len) {
+        Some(asterisks) if c == '*' => Cow::Borrowed(asterisks),
+        _ => Cow::Owned(core::iter::repeat_n(c, len).collect()),
+    }
+}
+
+/// Maps an exact length to the length of the mask for its bucket.
+const fn bucketed_len(len: usize) -> usize {
+    match len {
+        0 => 0,
+        1..=8 => 4,
+        9..=32 => 16,
+        _ => 32,
     }
 }
 
@@ -115,6 +331,8 @@ impl Default for SimpleRedactor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, string::ToString};
 
     const TEST_CLASS_ID: DataClass = DataClass::new("test_taxonomy", "test_class");
     const TEST_VALUE: &str = "secret";
@@ -125,6 +343,94 @@ mod tests {
         output
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn streaming_should_match_redact() {
+        let value = "é€x[]\\".repeat(5000);
+        let context =
+            RedactionContext::new().with_tag_format(TagFormat::new().with_delimiters("[", "]"));
+        for mode in [
+            SimpleRedactorMode::Erase,
+            SimpleRedactorMode::EraseAndTag,
+            SimpleRedactorMode::Passthrough,
+            SimpleRedactorMode::PassthroughAndTag,
+            SimpleRedactorMode::Replace('#'),
+            SimpleRedactorMode::ReplaceAndTag('*'),
+            SimpleRedactorMode::ReplaceBucketed('*'),
+            SimpleRedactorMode::ReplaceBucketedAndTag('*'),
+            SimpleRedactorMode::Insert("x".to_string()),
+            SimpleRedactorMode::InsertAndTag("x".to_string()),
+        ] {
+            let redactor = SimpleRedactor::with_mode(mode);
+
+            let mut expected = String::new();
+            redactor.redact_ctx(&TEST_CLASS_ID, &value, &context, &mut |s| {
+                expected.push_str(s);
+            });
+
+            let mut streamed = String::new();
+            redactor
+                .as_streaming()
+                .unwrap()
+                .redact_reader_ctx(&TEST_CLASS_ID, &mut value.as_bytes(), &context, &mut |s| {
+                    streamed.push_str(s);
+                })
+                .unwrap();
+            assert_eq!(streamed, expected);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn masks_should_match_length_of_arbitrary_input() {
+        use crate::conformance::arbitrary_inputs;
+
+        let bytes = SimpleRedactor::with_mode(SimpleRedactorMode::Replace('é'));
+        let chars = bytes.clone().with_mask_length(MaskLength::Chars);
+        let bucketed = SimpleRedactor::with_mode(SimpleRedactorMode::ReplaceBucketed('*'))
+            .with_mask_length(MaskLength::Chars);
+
+        for input in arbitrary_inputs() {
+            let masked = redact_to_string(&chars, &TEST_CLASS_ID, &input);
+            assert!(masked.chars().all(|c| c == 'é'));
+            assert_eq!(masked.chars().count(), input.chars().count(), "{input:?}");
+
+            let masked = redact_to_string(&bytes, &TEST_CLASS_ID, &input);
+            assert_eq!(masked.chars().count(), input.len(), "{input:?}");
+
+            let masked = redact_to_string(&bucketed, &TEST_CLASS_ID, &input);
+            assert_eq!(
+                masked.len(),
+                bucketed_len(input.chars().count()),
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn masks_should_count_bytes_by_default() {
+        let redactor = SimpleRedactor::new();
+        assert_eq!(
+            redact_to_string(&redactor, &TEST_CLASS_ID, "héllo"),
+            "******"
+        );
+        assert_eq!(
+            redact_to_string(&redactor, &TEST_CLASS_ID, &"日".repeat(11)),
+            "*".repeat(33)
+        );
+
+        let redactor = redactor.with_mask_length(MaskLength::Chars);
+        assert_eq!(
+            redact_to_string(&redactor, &TEST_CLASS_ID, "héllo"),
+            "*****"
+        );
+        assert_eq!(redact_to_string(&redactor, &TEST_CLASS_ID, "🦀🦀"), "**");
+        assert_eq!(
+            redact_to_string(&redactor, &TEST_CLASS_ID, &"日".repeat(11)),
+            "*".repeat(11)
+        );
+    }
+
     #[test]
     fn new_should_create_default_redactor() {
         let redactor = SimpleRedactor::new();
@@ -201,6 +507,51 @@ mod tests {
         assert_eq!(result, format!("<{TEST_CLASS_ID}:######>"));
     }
 
+    #[test]
+    fn redact_should_replace_with_bucketed_length() {
+        let redactor = SimpleRedactor::with_mode(SimpleRedactorMode::ReplaceBucketed('*'));
+        assert_eq!(redact_to_string(&redactor, &TEST_CLASS_ID, ""), "");
+        assert_eq!(redact_to_string(&redactor, &TEST_CLASS_ID, "a"), "****");
+        assert_eq!(
+            redact_to_string(&redactor, &TEST_CLASS_ID, "abcdefgh"),
+            "****"
+        );
+        assert_eq!(
+            redact_to_string(&redactor, &TEST_CLASS_ID, "abcdefghi"),
+            "*".repeat(16)
+        );
+        assert_eq!(
+            redact_to_string(&redactor, &TEST_CLASS_ID, &"a".repeat(32)),
+            "*".repeat(16)
+        );
+        assert_eq!(
+            redact_to_string(&redactor, &TEST_CLASS_ID, &"a".repeat(33)),
+            "*".repeat(32)
+        );
+        assert_eq!(
+            redact_to_string(&redactor, &TEST_CLASS_ID, &"a".repeat(1000)),
+            "*".repeat(32)
+        );
+    }
+
+    #[test]
+    fn redact_should_replace_and_tag_with_bucketed_length() {
+        let redactor = SimpleRedactor::with_mode(SimpleRedactorMode::ReplaceBucketedAndTag('#'));
+        let result = redact_to_string(&redactor, &TEST_CLASS_ID, TEST_VALUE);
+        assert_eq!(result, format!("<{TEST_CLASS_ID}:####>"));
+    }
+
+    #[test]
+    fn redact_should_escape_tagged_values() {
+        let redactor = SimpleRedactor::with_mode(SimpleRedactorMode::PassthroughAndTag);
+        let result = redact_to_string(&redactor, &TEST_CLASS_ID, "a> leaked");
+        assert_eq!(result, format!("<{TEST_CLASS_ID}:a\\> leaked>"));
+
+        let redactor = SimpleRedactor::with_mode(SimpleRedactorMode::InsertAndTag(">".to_string()));
+        let result = redact_to_string(&redactor, &TEST_CLASS_ID, TEST_VALUE);
+        assert_eq!(result, format!("<{TEST_CLASS_ID}:\\>>"));
+    }
+
     #[test]
     fn redact_should_insert() {
         let redactor =
@@ -243,14 +594,70 @@ mod tests {
         let redactor = SimpleRedactor::with_mode(SimpleRedactorMode::ReplaceAndTag('*'));
         assert_eq!(redactor.exact_len(), None);
 
-        // Insert mode should return None as output length depends on the inserted string
+        // ReplaceBucketed mode should return None as output length depends on input length
+        let redactor = SimpleRedactor::with_mode(SimpleRedactorMode::ReplaceBucketed('*'));
+        assert_eq!(redactor.exact_len(), None);
+
+        // Insert mode should return the length of the inserted string
         let redactor =
             SimpleRedactor::with_mode(SimpleRedactorMode::Insert("replacement".to_string()));
-        assert_eq!(redactor.exact_len(), None);
+        assert_eq!(redactor.exact_len(), Some(11));
 
         // InsertAndTag mode should return None as output length depends on inserted string and data class
         let redactor =
             SimpleRedactor::with_mode(SimpleRedactorMode::InsertAndTag("replacement".to_string()));
         assert_eq!(redactor.exact_len(), None);
     }
+
+    #[test]
+    fn len_hint_should_match_output_len_for_all_modes() {
+        let formats = [
+            TagFormat::new(),
+            TagFormat::new()
+                .with_delimiters("[[", "]]")
+                .with_separator(" = ")
+                .include_redactor(true),
+            TagFormat::new()
+                .with_delimiters("\u{ab}", "\u{bb}")
+                .with_separator("\u{2192}"),
+        ];
+
+        for mode in [
+            SimpleRedactorMode::Erase,
+            SimpleRedactorMode::EraseAndTag,
+            SimpleRedactorMode::Passthrough,
+            SimpleRedactorMode::PassthroughAndTag,
+            SimpleRedactorMode::Replace('#'),
+            SimpleRedactorMode::ReplaceAndTag('€'),
+            SimpleRedactorMode::ReplaceBucketed('*'),
+            SimpleRedactorMode::ReplaceBucketedAndTag('€'),
+            SimpleRedactorMode::Insert("replacement".to_string()),
+            SimpleRedactorMode::InsertAndTag("replacement".to_string()),
+            SimpleRedactorMode::ReplaceAndTag('<'),
+            SimpleRedactorMode::ReplaceAndTag('['),
+            SimpleRedactorMode::ReplaceBucketedAndTag('\\'),
+            SimpleRedactorMode::ReplaceBucketedAndTag('\u{bb}'),
+            SimpleRedactorMode::InsertAndTag(">".to_string()),
+            SimpleRedactorMode::InsertAndTag("a\\b]]c\u{ab}".to_string()),
+        ] {
+            // the length of passed through values depends on how many of their characters are escaped
+            let passthrough_tagged = matches!(mode, SimpleRedactorMode::PassthroughAndTag);
+            let redactor = SimpleRedactor::with_mode(mode);
+            for format in &formats {
+                let context = RedactionContext::new().with_tag_format(format.clone());
+                for value in ["", TEST_VALUE, &"x".repeat(40), r"a<b\c>", "[[x]]\u{bb}"] {
+                    let mut output = String::new();
+                    redactor.redact_ctx(&TEST_CLASS_ID, value, &context, &mut |s| {
+                        output.push_str(s);
+                    });
+
+                    assert_eq!(
+                        redactor.len_hint(&TEST_CLASS_ID, value.len(), format),
+                        (!passthrough_tagged).then_some(output.len()),
+                        "{redactor:?} with {format:?} and {value:?}"
+                    );
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,178 @@
+/// Generates a redaction engine whose policy is fixed at compile time.
+///
+/// A [`RedactionEngine`](crate::RedactionEngine) is configured at runtime and looks up redactors in a hash map.
+/// For performance-critical services whose redaction policy never changes, this macro instead generates a
+/// unit struct which dispatches to its redactors with a `match` over the taxonomy and class names. The
+/// resulting engine has a `'static` lifetime, performs no hash lookups, and never allocates to dispatch.
+///
+/// The mapping is given as a list of `"taxonomy" / "class" => redactor` entries, followed by a mandatory
+/// `_ => redactor` entry for the fallback redactor. Each redactor expression is evaluated at compile time,
+/// so it must be a constant expression, such as [`SimpleRedactor::with_mode`](crate::SimpleRedactor::with_mode).
+///
+/// The generated type exposes `redact`, `display_redacted`, `debug_redacted`, and `exact_len` methods which
+/// mirror those of [`RedactionEngine`](crate::RedactionEngine), and also implements the [`Redactor`](crate::Redactor) trait.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::Sensitive;
+/// use data_privacy::{static_engine, SimpleRedactor, SimpleRedactorMode};
+///
+/// static_engine! {
+///     /// The redaction policy for production.
+///     pub struct ProductionEngine {
+///         "core" / "sensitive" => SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*')),
+///         "core" / "insensitive" => SimpleRedactor::with_mode(SimpleRedactorMode::Passthrough),
+///         _ => SimpleRedactor::with_mode(SimpleRedactorMode::Erase),
+///     }
+/// }
+///
+/// let mut output = String::new();
+/// ProductionEngine.display_redacted(&Sensitive::new("John"), |s| output.push_str(s));
+/// assert_eq!(output, "****");
+/// ```
+#[macro_export]
+macro_rules! static_engine {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($taxonomy:literal / $class:literal => $redactor:expr,)*
+            _ => $fallback:expr $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Default)]
+        $vis struct $name;
+
+        impl $name {
+            /// Redacts a string with an explicit data classification, sending the results to the output callback.
+            pub fn redact(
+                &self,
+                data_class: &$crate::DataClass,
+                value: impl ::core::convert::AsRef<str>,
+                mut output: impl FnMut(&str),
+            ) {
+                $crate::Redactor::redact(self, data_class, value.as_ref(), &mut output);
+            }
+
+            /// Redacts the output of a classified value's `Display` trait.
+            pub fn display_redacted<C, T>(&self, value: &C, output: impl FnMut(&str))
+            where
+                C: $crate::Classified<T>,
+                T: ::core::fmt::Display,
+            {
+                $crate::Classified::visit(value, |v| {
+                    $crate::__private::with_formatted(::core::format_args!("{v}"), |s| {
+                        self.redact(&$crate::Classified::data_class(value), s, output);
+                    });
+                });
+            }
+
+            /// Redacts the output of a classified value's `Debug` trait.
+            pub fn debug_redacted<C, T>(&self, value: &C, output: impl FnMut(&str))
+            where
+                C: $crate::Classified<T>,
+                T: ::core::fmt::Debug,
+            {
+                $crate::Classified::visit(value, |v| {
+                    $crate::__private::with_formatted(::core::format_args!("{v:?}"), |s| {
+                        self.redact(&$crate::Classified::data_class(value), s, output);
+                    });
+                });
+            }
+
+            /// The exact length of the redacted output for a data class, if it is a constant.
+            #[must_use]
+            pub fn exact_len(&self, data_class: &$crate::DataClass) -> ::core::option::Option<usize> {
+                match (data_class.taxonomy(), data_class.name()) {
+                    $(($taxonomy, $class) => $crate::Redactor::exact_len(&const { $redactor }),)*
+                    _ => $crate::Redactor::exact_len(&const { $fallback }),
+                }
+            }
+        }
+
+        impl $crate::Redactor for $name {
+            fn redact(&self, data_class: &$crate::DataClass, value: &str, output: &mut dyn FnMut(&str)) {
+                match (data_class.taxonomy(), data_class.name()) {
+                    $(($taxonomy, $class) => $crate::Redactor::redact(&const { $redactor }, data_class, value, output),)*
+                    _ => $crate::Redactor::redact(&const { $fallback }, data_class, value, output),
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core_taxonomy::{CoreTaxonomy, Insensitive, Sensitive, UnknownSensitivity};
+    use crate::{DataClass, Redactor, SimpleRedactor, SimpleRedactorMode};
+
+    static_engine! {
+        struct TestEngine {
+            "core" / "sensitive" => SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')),
+            "core" / "insensitive" => SimpleRedactor::with_mode(SimpleRedactorMode::PassthroughAndTag),
+            _ => SimpleRedactor::with_mode(SimpleRedactorMode::Erase),
+        }
+    }
+
+    static_engine! {
+        struct FallbackOnlyEngine {
+            _ => SimpleRedactor::with_mode(SimpleRedactorMode::Replace('*'))
+        }
+    }
+
+    static ENGINE: TestEngine = TestEngine;
+
+    #[test]
+    fn redact_should_dispatch_by_class() {
+        let mut output = String::new();
+        ENGINE.redact(&CoreTaxonomy::Sensitive.data_class(), "abc", |s| {
+            output.push_str(s);
+        });
+        assert_eq!(output, "###");
+
+        output.clear();
+        ENGINE.display_redacted(&Insensitive::new(42), |s| output.push_str(s));
+        assert_eq!(output, "<core/insensitive:42>");
+
+        output.clear();
+        ENGINE.debug_redacted(&Sensitive::new("ab"), |s| output.push_str(s));
+        assert_eq!(output, "####");
+
+        output.clear();
+        ENGINE.display_redacted(&UnknownSensitivity::new("abc"), |s| output.push_str(s));
+        assert_eq!(output, "");
+    }
+
+    #[test]
+    fn exact_len_should_dispatch_by_class() {
+        assert_eq!(
+            ENGINE.exact_len(&CoreTaxonomy::Sensitive.data_class()),
+            None
+        );
+        assert_eq!(ENGINE.exact_len(&DataClass::new("other", "class")), Some(0));
+    }
+
+    #[test]
+    fn engine_should_act_as_redactor() {
+        let redactor: &dyn Redactor = &FallbackOnlyEngine;
+        let mut output = String::new();
+        redactor.redact(&CoreTaxonomy::Sensitive.data_class(), "abc", &mut |s| {
+            output.push_str(s);
+        });
+        assert_eq!(output, "***");
+    }
+
+    #[test]
+    fn fallback_should_handle_all_classes() {
+        let engine = FallbackOnlyEngine;
+        let mut output = String::new();
+        engine.redact(&CoreTaxonomy::Insensitive.data_class(), "abc", |s| {
+            output.push_str(s);
+        });
+        engine.display_redacted(&Insensitive::new(12), |s| output.push_str(s));
+        engine.debug_redacted(&Sensitive::new("x"), |s| output.push_str(s));
+        assert_eq!(output, "********");
+        assert_eq!(engine.exact_len(&DataClass::new("any", "class")), None);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{DataClass, RedactionContext, Redactor};
+use std::io::{self, Read};
+
+/// The size of the chunks in which streaming redactors read their input.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// A redactor which can redact a value incrementally, as it is read from a reader.
+///
+/// Hashing redactors and [`SimpleRedactor`](crate::SimpleRedactor) implement this trait so that very large
+/// classified payloads, such as documents or request bodies, can be redacted without first materializing
+/// them as a single contiguous string. Memory usage stays bounded by the size of the chunks the input is
+/// read in, and redactors whose output depends on each chunk, such as passthrough or masking redactors,
+/// call the output function once per chunk. For a given sequence of bytes, the concatenated output is the
+/// same as that of [`Redactor::redact`] applied to the same bytes as a string.
+///
+/// A [`RedactionEngine`](crate::RedactionEngine) uses this trait through
+/// [`RedactionEngine::redact_reader`](crate::RedactionEngine::redact_reader), for redactors which expose it
+/// with [`Redactor::as_streaming`].
+///
+/// Values made of several chunks already in memory can be redacted by chaining them into a single reader
+/// with [`Read::chain`].
+pub trait StreamingRedactor: Redactor {
+    /// Redacts the bytes read from the reader until it's exhausted, and calls the output function with the redacted value.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by the reader, or an error of kind [`InvalidData`](io::ErrorKind::InvalidData)
+    /// if a redactor which works on text reads bytes which aren't valid UTF-8. In that case, the output
+    /// function may already have been called with part of the redacted value.
+    fn redact_reader(
+        &self,
+        data_class: &DataClass,
+        reader: &mut dyn Read,
+        output: &mut dyn FnMut(&str),
+    ) -> io::Result<()>;
+
+    /// Redacts the bytes read from the reader like [`redact_reader`](Self::redact_reader), with access
+    /// to the context of the call.
+    ///
+    /// A [`RedactionEngine`](crate::RedactionEngine) calls this method, passing the context given to
+    /// [`redact_reader_with_context`](crate::RedactionEngine::redact_reader_with_context), or an empty
+    /// context otherwise, like it does for [`Redactor::redact_ctx`]. Redactors which tag their output
+    /// should override it to use the context's [tag format](RedactionContext::tag_format). The default
+    /// implementation ignores the context and calls [`redact_reader`](Self::redact_reader).
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`redact_reader`](Self::redact_reader).
+    fn redact_reader_ctx(
+        &self,
+        data_class: &DataClass,
+        reader: &mut dyn Read,
+        context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) -> io::Result<()> {
+        _ = context;
+        self.redact_reader(data_class, reader, output)
+    }
+
+    /// Returns whether the redactor emits the bytes it reads unchanged, without tagging them.
+    ///
+    /// A [`RedactionEngine`](crate::RedactionEngine) uses this to report streamed values as
+    /// [passthroughs](crate::RedactionEvent::is_passthrough), since it doesn't keep them around for
+    /// comparison with the output. The default implementation returns `false`.
+    #[must_use]
+    fn is_passthrough(&self) -> bool {
+        false
+    }
+}
+
+/// Reads the reader until it's exhausted, passing each chunk of bytes read to the function.
+#[cfg(any(feature = "hmac", feature = "xxh3"))]
+pub fn read_chunks(reader: &mut dyn Read, mut f: impl FnMut(&[u8])) -> io::Result<()> {
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(len) => f(&buffer[..len]),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Reads the reader until it's exhausted, passing each chunk of text read to the function.
+///
+/// Characters split between reads are held back until they're complete, so every chunk is valid UTF-8.
+pub fn read_str_chunks(reader: &mut dyn Read, mut f: impl FnMut(&str)) -> io::Result<()> {
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    let mut pending = 0;
+    loop {
+        let filled = match reader.read(&mut buffer[pending..]) {
+            Ok(0) if pending == 0 => return Ok(()),
+            Ok(0) => return Err(invalid_utf8()),
+            Ok(len) => pending + len,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        let valid = match core::str::from_utf8(&buffer[..filled]) {
+            Ok(text) => text.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => return Err(invalid_utf8()),
+        };
+
+        // SAFETY: `from_utf8` checked that the bytes up to `valid` are valid UTF-8.
+        f(unsafe { core::str::from_utf8_unchecked(&buffer[..valid]) });
+
+        buffer.copy_within(valid..filled, 0);
+        pending = filled - valid;
+    }
+}
+
+fn invalid_utf8() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "stream did not contain valid UTF-8",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A reader which returns a single byte per read.
+    struct ByteReader<'a>(&'a [u8]);
+
+    impl Read for ByteReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let Some((first, rest)) = self.0.split_first() else {
+                return Ok(0);
+            };
+
+            buf[0] = *first;
+            self.0 = rest;
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn read_str_chunks_should_reassemble_split_characters() {
+        let mut text = String::new();
+        read_str_chunks(&mut ByteReader("héllo wörld €".as_bytes()), |chunk| {
+            text.push_str(chunk);
+        })
+        .unwrap();
+        assert_eq!(text, "héllo wörld €");
+    }
+
+    #[test]
+    fn read_str_chunks_should_reject_invalid_utf8() {
+        let err = read_str_chunks(&mut &[b'a', 0xff, b'b'][..], |_| {}).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let err = read_str_chunks(&mut &"€".as_bytes()[..2], |_| {}).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(any(feature = "hmac", feature = "xxh3"))]
+    #[test]
+    fn read_chunks_should_read_everything() {
+        let data = vec![7u8; CHUNK_SIZE * 2 + 1];
+        let mut len = 0;
+        read_chunks(&mut data.as_slice(), |chunk| len += chunk.len()).unwrap();
+        assert_eq!(len, data.len());
+    }
+}
@@ -0,0 +1,303 @@
+use crate::DataClass;
+use alloc::borrow::Cow;
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+/// The character which escapes delimiters within the values of tags.
+pub const ESCAPE: char = '\\';
+
+/// Controls the format of the tags produced by tagging redactors.
+///
+/// Tagging redactors, such as the tagging modes of [`SimpleRedactor`](crate::SimpleRedactor), wrap their
+/// output in a marker which identifies the data class of the value. The default format is
+/// `<taxonomy/class:value>`. Setting a format on the [`RedactionEngineBuilder`](crate::RedactionEngineBuilder)
+/// makes every tagging redactor in the engine produce the same markers, so they can be matched
+/// against a log schema.
+///
+/// A tag is made of a prefix, a header, a separator, the value, and a suffix. The header names the
+/// taxonomy and class, optionally followed by the name of the redactor, separated by `/`.
+///
+/// Within the value, backslashes and the first characters of the prefix and suffix are escaped with a
+/// backslash, so that a value can't end its tag early. With the default format, `a>b` is tagged as
+/// `<taxonomy/class:a\>b>`.
+///
+/// Every format can be read back: [`RedactionEngine::redact_tags`](crate::RedactionEngine::redact_tags)
+/// recognizes tags in the format of its engine.
+///
+/// # Example
+///
+#[cfg_attr(feature = "std", doc = "```rust")]
+#[cfg_attr(not(feature = "std"), doc = "```ignore")]
+/// use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+/// use data_privacy::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode, TagFormat};
+///
+/// let engine = RedactionEngineBuilder::new()
+///     .add_class_redactor(
+///         &CoreTaxonomy::Sensitive.data_class(),
+///         SimpleRedactor::with_mode(SimpleRedactorMode::ReplaceAndTag('*')),
+///     )
+///     .set_tag_format(
+///         TagFormat::new()
+///             .with_delimiters("[[", "]]")
+///             .with_separator("=")
+///             .include_redactor(true),
+///     )
+///     .build();
+///
+/// let mut output = String::new();
+/// engine.display_redacted(&Sensitive::new("John"), |s| output.push_str(s));
+/// assert_eq!(output, "[[core/sensitive/simple=****]]");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TagFormat {
+    prefix: Cow<'static, str>,
+    separator: Cow<'static, str>,
+    suffix: Cow<'static, str>,
+    include_redactor: bool,
+}
+
+impl TagFormat {
+    /// Creates the default format, which produces tags of the form `<taxonomy/class:value>`.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            prefix: Cow::Borrowed("<"),
+            separator: Cow::Borrowed(":"),
+            suffix: Cow::Borrowed(">"),
+            include_redactor: false,
+        }
+    }
+
+    /// Sets the strings which start and end each tag.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either string is empty or starts with a backslash.
+    #[must_use]
+    pub fn with_delimiters(
+        mut self,
+        prefix: impl Into<Cow<'static, str>>,
+        suffix: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        let (prefix, suffix) = (prefix.into(), suffix.into());
+        assert!(
+            [&prefix, &suffix]
+                .iter()
+                .all(|delimiter| !delimiter.is_empty() && !delimiter.starts_with(ESCAPE)),
+            "Tag delimiters must be non-empty and can't start with `\\`"
+        );
+
+        self.prefix = prefix;
+        self.suffix = suffix;
+        self
+    }
+
+    /// Sets the string which separates the header of each tag from its value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the separator is empty or starts with a character which can appear in the header, such as
+    /// an ASCII letter, digit, or `/`, as the end of the header couldn't be found otherwise.
+    #[must_use]
+    pub fn with_separator(mut self, separator: impl Into<Cow<'static, str>>) -> Self {
+        let separator = separator.into();
+        assert!(
+            separator.chars().next().is_some_and(|c| !is_header_char(c)),
+            "Tag separators must be non-empty and can't start with a character of the header"
+        );
+
+        self.separator = separator;
+        self
+    }
+
+    /// Sets whether the header of each tag includes the name of the redactor that produced it.
+    #[must_use]
+    pub const fn include_redactor(mut self, include: bool) -> Self {
+        self.include_redactor = include;
+        self
+    }
+
+    /// Formats a tag around a value and sends it to the output callback.
+    ///
+    /// Tagging redactors call this with the data class of the value and their own name.
+    pub fn tag(
+        &self,
+        data_class: &DataClass,
+        redactor_name: &str,
+        value: &str,
+        output: &mut dyn FnMut(&str),
+    ) {
+        let mut tag =
+            String::with_capacity(self.tagged_len(data_class, redactor_name, value.len()));
+        self.push_header(&mut tag, data_class, redactor_name);
+        self.escape(value, &mut |s| tag.push_str(s));
+        tag.push_str(&self.suffix);
+        output(&tag);
+    }
+
+    /// Returns the length of the tag [`tag`](Self::tag) formats around a value of the given length in bytes.
+    ///
+    /// The length is that of the value once escaped, as returned by [`escaped_len`](Self::escaped_len).
+    #[must_use]
+    pub fn tagged_len(
+        &self,
+        data_class: &DataClass,
+        redactor_name: &str,
+        value_len: usize,
+    ) -> usize {
+        let mut header_len = data_class.taxonomy().len() + 1 + data_class.name().len();
+        if self.include_redactor {
+            header_len += 1 + redactor_name.len();
+        }
+
+        self.prefix.len() + header_len + self.separator.len() + value_len + self.suffix.len()
+    }
+
+    /// Returns the length in bytes of a value once escaped within a tag.
+    #[must_use]
+    pub fn escaped_len(&self, value: &str) -> usize {
+        value.len() + value.chars().filter(|c| self.needs_escape(*c)).count()
+    }
+
+    /// Sends a value to the output callback, escaping the characters which could end its tag early.
+    pub(crate) fn escape(&self, value: &str, output: &mut dyn FnMut(&str)) {
+        let mut run_start = 0;
+        for (index, c) in value.char_indices() {
+            if self.needs_escape(c) {
+                if let Some(run) = value.get(run_start..index) {
+                    output(run);
+                }
+                output("\\");
+                run_start = index;
+            }
+        }
+
+        if let Some(run) = value.get(run_start..).filter(|run| !run.is_empty()) {
+            output(run);
+        }
+    }
+
+    /// Returns the start of a tag, up to where its value goes.
+    #[cfg(feature = "std")]
+    pub(crate) fn header(&self, data_class: &DataClass, redactor_name: &str) -> String {
+        let mut header = String::new();
+        self.push_header(&mut header, data_class, redactor_name);
+        header
+    }
+
+    /// Returns the string which starts each tag.
+    #[cfg(feature = "std")]
+    pub(crate) fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Returns the string which separates the header of each tag from its value.
+    #[cfg(feature = "std")]
+    pub(crate) fn separator(&self) -> &str {
+        &self.separator
+    }
+
+    /// Returns the string which ends each tag.
+    #[cfg(feature = "std")]
+    pub(crate) fn suffix(&self) -> &str {
+        &self.suffix
+    }
+
+    /// Returns whether a character is escaped within the values of tags.
+    fn needs_escape(&self, c: char) -> bool {
+        c == ESCAPE || self.prefix.starts_with(c) || self.suffix.starts_with(c)
+    }
+
+    fn push_header(&self, tag: &mut String, data_class: &DataClass, redactor_name: &str) {
+        tag.push_str(&self.prefix);
+        tag.push_str(data_class.taxonomy());
+        tag.push('/');
+        tag.push_str(data_class.name());
+        if self.include_redactor {
+            tag.push('/');
+            tag.push_str(redactor_name);
+        }
+        tag.push_str(&self.separator);
+    }
+}
+
+/// Returns whether a character can appear in the header of a tag.
+pub const fn is_header_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-' | '/')
+}
+
+impl Default for TagFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(format: &TagFormat, value: &str) -> String {
+        let mut output = String::new();
+        format.tag(&DataClass::new("tax", "class"), "simple", value, &mut |s| {
+            output.push_str(s);
+        });
+        output
+    }
+
+    #[test]
+    fn default_format_should_match_display() {
+        assert_eq!(tag(&TagFormat::default(), "abc"), "<tax/class:abc>");
+        assert_eq!(tag(&TagFormat::new(), ""), "<tax/class:>");
+    }
+
+    #[test]
+    fn redactor_name_should_be_configurable() {
+        let format = TagFormat::new().include_redactor(true);
+        assert_eq!(tag(&format, "abc"), "<tax/class/simple:abc>");
+    }
+
+    #[test]
+    fn delimiters_should_be_configurable() {
+        let format = TagFormat::new()
+            .with_delimiters("{{", "}}")
+            .with_separator(" = ");
+        assert_eq!(tag(&format, "abc"), "{{tax/class = abc}}");
+        assert_eq!(tag(&format, "}{x"), "{{tax/class = \\}\\{x}}");
+    }
+
+    #[test]
+    #[should_panic(expected = "Tag delimiters must be non-empty")]
+    fn empty_delimiters_should_be_rejected() {
+        _ = TagFormat::new().with_delimiters("", ">");
+    }
+
+    #[test]
+    #[should_panic(expected = "Tag separators must be non-empty")]
+    fn separators_in_the_header_should_be_rejected() {
+        _ = TagFormat::new().with_separator("/");
+    }
+
+    #[test]
+    fn values_should_be_escaped() {
+        let format = TagFormat::new();
+        assert_eq!(tag(&format, r"a>b<c\d"), r"<tax/class:a\>b\<c\\d>");
+        assert_eq!(tag(&format, ">"), r"<tax/class:\>>");
+    }
+
+    #[test]
+    fn tagged_len_should_match_escaped_values() {
+        let data_class = DataClass::new("tax", "class");
+        for format in [
+            TagFormat::new(),
+            TagFormat::new().with_delimiters("{{", "}}"),
+        ] {
+            for value in ["abc", r"a>b<c\d", "}{x", ""] {
+                assert_eq!(
+                    format.tagged_len(&data_class, "simple", format.escaped_len(value)),
+                    tag(&format, value).len(),
+                    "{format:?} and {value:?}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,486 @@
+use crate::TagFormat;
+use crate::tag_format::{ESCAPE, is_header_char};
+use alloc::borrow::Cow;
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+/// A piece of text produced while scanning for classification tags.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Segment<'a> {
+    /// Plain text outside of any tag.
+    Text(&'a str),
+
+    /// The taxonomy, class, and unescaped value of a tag.
+    Tag(&'a str, &'a str, Cow<'a, str>),
+}
+
+/// The header of a tag candidate, up to where its value starts.
+enum Header<'a> {
+    /// A complete header, with the taxonomy and class it names and its length in bytes.
+    Complete(&'a str, &'a str, usize),
+
+    /// The text ends partway through something that would be a header given more text.
+    Partial,
+
+    /// The text isn't the start of a tag.
+    Invalid,
+}
+
+/// Splits text into plain segments and classification tags in the given format, in order of appearance.
+///
+/// Tags are formatted like the tagging modes of [`SimpleRedactor`](crate::SimpleRedactor) format them,
+/// such as `<taxonomy/class:value>` with the default format. The header may name a redactor after the
+/// class, which is ignored. Backslashes and the first characters of the prefix and suffix are escaped
+/// within values with a backslash. Unescaped delimiters within the value are allowed so long as they are
+/// balanced.
+///
+/// Scanning fails closed: once a tag header has been seen, everything up to the end of its value is
+/// reported as the value of the tag, and a tag which is never terminated extends to the end of the
+/// text. Text is scanned in a single pass, in time linear in its length.
+#[expect(
+    clippy::string_slice,
+    reason = "Slices are split at the positions of characters, which are always character boundaries"
+)]
+pub fn scan_tags<'a>(text: &'a str, format: &TagFormat, mut on_segment: impl FnMut(Segment<'a>)) {
+    let mut text_start = 0;
+    let mut pos = 0;
+
+    while let Some(start) = find_prefix(text, pos, format) {
+        let Header::Complete(taxonomy, name, header_len) = parse_header(&text[start..], format)
+        else {
+            pos = next_char(text, start);
+            continue;
+        };
+
+        if start > text_start {
+            on_segment(Segment::Text(&text[text_start..start]));
+        }
+
+        let body = &text[start + header_len..];
+        let mut value = ValueState::default();
+        let (value_len, end_len) = value
+            .scan(body, format)
+            .map_or((body.len(), 0), |len| (len, format.suffix().len()));
+        on_segment(Segment::Tag(taxonomy, name, unescape(&body[..value_len])));
+
+        pos = start + header_len + value_len + end_len;
+        text_start = pos;
+    }
+
+    if text_start < text.len() {
+        on_segment(Segment::Text(&text[text_start..]));
+    }
+}
+
+/// Returns the position of the next candidate for the start of a tag, from the given position on.
+#[expect(
+    clippy::string_slice,
+    reason = "Positions are always those of characters, which are character boundaries"
+)]
+fn find_prefix(text: &str, pos: usize, format: &TagFormat) -> Option<usize> {
+    let first = format.prefix().chars().next()?;
+    text[pos..].find(first).map(|offset| pos + offset)
+}
+
+/// Returns the position of the character after the one at the given position.
+#[expect(
+    clippy::string_slice,
+    reason = "Positions are always those of characters, which are character boundaries"
+)]
+fn next_char(text: &str, pos: usize) -> usize {
+    pos + text[pos..].chars().next().map_or(1, char::len_utf8)
+}
+
+/// Parses the header of a tag at the start of text.
+#[expect(
+    clippy::string_slice,
+    reason = "Slices are split after ASCII characters, which are always character boundaries"
+)]
+fn parse_header<'a>(text: &'a str, format: &TagFormat) -> Header<'a> {
+    let Some(rest) = text.strip_prefix(format.prefix()) else {
+        return partial_or_invalid(text, format.prefix());
+    };
+
+    let taxonomy_len = identifier_len(rest);
+    match rest.as_bytes().get(taxonomy_len) {
+        None => return Header::Partial,
+        Some(b'/') if taxonomy_len > 0 => {}
+        Some(_) => return Header::Invalid,
+    }
+
+    let taxonomy = &rest[..taxonomy_len];
+    let rest = &rest[taxonomy_len + 1..];
+    let name_len = identifier_len(rest);
+    if name_len == 0 {
+        return if rest.is_empty() {
+            Header::Partial
+        } else {
+            Header::Invalid
+        };
+    }
+
+    let name = &rest[..name_len];
+    let mut rest = &rest[name_len..];
+    if let Some(redactor) = rest.strip_prefix('/') {
+        let redactor_len = identifier_len(redactor);
+        if redactor_len == 0 {
+            return if redactor.is_empty() {
+                Header::Partial
+            } else {
+                Header::Invalid
+            };
+        }
+        rest = &redactor[redactor_len..];
+    }
+
+    rest.strip_prefix(format.separator()).map_or_else(
+        || partial_or_invalid(rest, format.separator()),
+        |value| Header::Complete(taxonomy, name, text.len() - value.len()),
+    )
+}
+
+/// Returns whether text which doesn't start with the expected string may still do so given more text.
+fn partial_or_invalid<'a>(text: &str, expected: &str) -> Header<'a> {
+    if expected.starts_with(text) {
+        Header::Partial
+    } else {
+        Header::Invalid
+    }
+}
+
+/// The progress of a scan through the value of a tag.
+#[derive(Clone, Copy, Debug, Default)]
+struct ValueState {
+    depth: usize,
+    escaped: bool,
+}
+
+impl ValueState {
+    /// Scans more of a value, returning the length of the value if its end is in the text.
+    ///
+    /// Otherwise, returns the length of the text scanned, which stops short of the end of the text when
+    /// it ends partway through a delimiter.
+    #[expect(
+        clippy::string_slice,
+        reason = "Slices are split at the positions of characters, which are always character boundaries"
+    )]
+    fn scan(&mut self, text: &str, format: &TagFormat) -> Result<usize, usize> {
+        let mut skip_to = 0;
+        for (index, c) in text.char_indices() {
+            if index < skip_to {
+                continue;
+            }
+
+            if self.escaped {
+                self.escaped = false;
+                continue;
+            }
+
+            if c == ESCAPE {
+                self.escaped = true;
+                continue;
+            }
+
+            let rest = &text[index..];
+            for delimiter in [format.suffix(), format.prefix()] {
+                if delimiter.len() > rest.len() && delimiter.starts_with(rest) {
+                    return Err(index);
+                }
+            }
+
+            if rest.starts_with(format.suffix()) {
+                match self.depth.checked_sub(1) {
+                    Some(depth) => self.depth = depth,
+                    None => return Ok(index),
+                }
+                skip_to = index + format.suffix().len();
+            } else if rest.starts_with(format.prefix()) {
+                self.depth += 1;
+                skip_to = index + format.prefix().len();
+            }
+        }
+
+        Err(text.len())
+    }
+}
+
+/// Removes the escapes from the value of a tag, dropping a trailing lone backslash.
+fn unescape(value: &str) -> Cow<'_, str> {
+    if !value.contains(ESCAPE) {
+        return Cow::Borrowed(value);
+    }
+
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            ESCAPE => unescaped.extend(chars.next()),
+            _ => unescaped.push(c),
+        }
+    }
+
+    Cow::Owned(unescaped)
+}
+
+/// The progress of [`complete_len`] through text which arrives in pieces.
+#[cfg(feature = "futures")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ScanState {
+    /// Where scanning resumes.
+    resume: usize,
+
+    /// The progress through the value of the open tag the text starts with, if any.
+    value: Option<ValueState>,
+}
+
+#[cfg(feature = "futures")]
+impl ScanState {
+    /// Creates the state of a scan from the start of the text.
+    pub const fn new() -> Self {
+        Self {
+            resume: 0,
+            value: None,
+        }
+    }
+}
+
+/// Returns the length of the longest prefix of the text which doesn't end inside a tag in the given format.
+///
+/// A tag is still open when the text ends partway through its header, or before the end of its value.
+/// Streaming consumers hold back the text from that point until more of it arrives.
+///
+/// The caller is expected to consume the returned prefix of the text and to call this again with the
+/// rest of the text once more of it arrives, passing the same state, so that text is scanned only once.
+#[cfg(feature = "futures")]
+#[expect(
+    clippy::string_slice,
+    reason = "Slices are split at the positions of characters, which are always character boundaries"
+)]
+pub fn complete_len(text: &str, format: &TagFormat, state: &mut ScanState) -> usize {
+    let mut pos = state.resume;
+
+    if let Some(mut value) = state.value.take() {
+        match value.scan(&text[pos..], format) {
+            Ok(len) => pos += len + format.suffix().len(),
+            Err(len) => {
+                *state = ScanState {
+                    resume: pos + len,
+                    value: Some(value),
+                };
+                return 0;
+            }
+        }
+    }
+
+    while let Some(start) = find_prefix(text, pos, format) {
+        match parse_header(&text[start..], format) {
+            Header::Complete(.., header_len) => {
+                let mut value = ValueState::default();
+                match value.scan(&text[start + header_len..], format) {
+                    Ok(len) => pos = start + header_len + len + format.suffix().len(),
+                    Err(len) => {
+                        *state = ScanState {
+                            resume: header_len + len,
+                            value: Some(value),
+                        };
+                        return start;
+                    }
+                }
+            }
+            Header::Partial => {
+                *state = ScanState::default();
+                return start;
+            }
+            Header::Invalid => pos = next_char(text, start),
+        }
+    }
+
+    *state = ScanState::default();
+    text.len()
+}
+
+fn identifier_len(s: &str) -> usize {
+    s.chars()
+        .take_while(|&c| is_header_char(c) && c != '/')
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(text: &str) -> Vec<Segment<'_>> {
+        scan_with(text, &TagFormat::new())
+    }
+
+    fn scan_with<'a>(text: &'a str, format: &TagFormat) -> Vec<Segment<'a>> {
+        let mut segments = Vec::new();
+        scan_tags(text, format, |segment| segments.push(segment));
+        segments
+    }
+
+    #[test]
+    fn scan_tags_should_split_text_and_tags() {
+        assert_eq!(
+            scan("user <core/sensitive:John> logged in"),
+            vec![
+                Segment::Text("user "),
+                Segment::Tag("core", "sensitive", "John".into()),
+                Segment::Text(" logged in"),
+            ]
+        );
+
+        assert_eq!(
+            scan("<a/b:1><a/c:>"),
+            vec![
+                Segment::Tag("a", "b", "1".into()),
+                Segment::Tag("a", "c", "".into()),
+            ]
+        );
+
+        assert_eq!(
+            scan("1 < 2 and <not a/tag:x>"),
+            vec![Segment::Text("1 < 2 and <not a/tag:x>")]
+        );
+        assert!(scan("").is_empty());
+    }
+
+    #[test]
+    fn scan_tags_should_allow_balanced_brackets_in_values() {
+        assert_eq!(
+            scan("<a/b:Vec<u8>>!"),
+            vec![Segment::Tag("a", "b", "Vec<u8>".into()), Segment::Text("!"),]
+        );
+    }
+
+    #[test]
+    fn scan_tags_should_unescape_values() {
+        assert_eq!(
+            scan(r"<a/b:x\> leaked\<\\>!"),
+            vec![
+                Segment::Tag("a", "b", r"x> leaked<\".into()),
+                Segment::Text("!"),
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_tags_should_fail_closed_on_unterminated_tags() {
+        assert_eq!(
+            scan("ok <a/b:secret and more"),
+            vec![
+                Segment::Text("ok "),
+                Segment::Tag("a", "b", "secret and more".into()),
+            ]
+        );
+
+        // unbalanced brackets keep the tag open
+        assert_eq!(
+            scan("<a/b:x<y> secret"),
+            vec![Segment::Tag("a", "b", "x<y> secret".into())]
+        );
+
+        // a truncated escape is dropped
+        assert_eq!(
+            scan(r"<a/b:secret\"),
+            vec![Segment::Tag("a", "b", "secret".into())]
+        );
+    }
+
+    #[test]
+    fn scan_tags_should_use_the_tag_format() {
+        let format = TagFormat::new()
+            .with_delimiters("[[", "]]")
+            .with_separator(" = ");
+        assert_eq!(
+            scan_with(
+                r"a [[b]] [[c/d = x\]y [[z]]]] [[e/f/simple = g]] <c/d:h>",
+                &format
+            ),
+            vec![
+                Segment::Text("a [[b]] "),
+                Segment::Tag("c", "d", "x]y [[z]]".into()),
+                Segment::Text(" "),
+                Segment::Tag("e", "f", "g".into()),
+                Segment::Text(" <c/d:h>"),
+            ]
+        );
+
+        // a lone character of a delimiter doesn't end the tag
+        assert_eq!(
+            scan_with("[[c/d = x] y", &format),
+            vec![Segment::Tag("c", "d", "x] y".into())]
+        );
+    }
+
+    #[test]
+    fn scan_tags_should_handle_many_candidates() {
+        let text = "<a/".repeat(100_000);
+        assert_eq!(scan(&text), vec![Segment::Text(&text)]);
+
+        let text = format!("<a/b:{}", "<".repeat(100_000));
+        assert_eq!(scan(&text).len(), 1);
+    }
+
+    #[cfg(feature = "futures")]
+    fn complete_len(text: &str) -> usize {
+        super::complete_len(text, &TagFormat::new(), &mut ScanState::default())
+    }
+
+    #[cfg(feature = "futures")]
+    fn complete_pieces(pieces: &[&str], format: &TagFormat) -> String {
+        let mut state = ScanState::default();
+        let mut text = String::new();
+        let mut completed = String::new();
+
+        for piece in pieces {
+            text.push_str(piece);
+            let len = super::complete_len(&text, format, &mut state);
+            completed.extend(text.drain(..len));
+        }
+
+        assert!(text.is_empty());
+        completed
+    }
+
+    #[test]
+    #[cfg(feature = "futures")]
+    fn complete_len_should_stop_at_open_tags() {
+        assert_eq!(complete_len("plain text"), 10);
+        assert_eq!(complete_len("a <b/c:d> e"), 11);
+        assert_eq!(complete_len("a <"), 2);
+        assert_eq!(complete_len("a <core/sen"), 2);
+        assert_eq!(complete_len("a <core/sensitive:Jo"), 2);
+        assert_eq!(complete_len("a <b/c:Vec<u8>"), 2);
+        assert_eq!(complete_len(r"a <b/c:d\>"), 2);
+        assert_eq!(complete_len(r"a <b/c:d\"), 2);
+        assert_eq!(complete_len("1 < 2 <not a tag"), 16);
+        assert_eq!(complete_len("<b/c:d> <x y"), 12);
+    }
+
+    #[test]
+    #[cfg(feature = "futures")]
+    fn complete_len_should_resume_in_open_tags() {
+        let pieces = ["a <core/sen", "sitive:x<y", "> \\", ">> b", " <c/d:e> f"];
+        assert_eq!(
+            complete_pieces(&pieces, &TagFormat::new()),
+            "a <core/sensitive:x<y> \\>> b <c/d:e> f"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "futures")]
+    fn complete_len_should_resume_in_split_delimiters() {
+        let format = TagFormat::new().with_delimiters("[[", "]]");
+        let mut state = ScanState::default();
+        assert_eq!(super::complete_len("a [", &format, &mut state), 2);
+        let mut state = ScanState::default();
+        assert_eq!(super::complete_len("a [[c/d:x]", &format, &mut state), 2);
+
+        let pieces = ["a [", "[c/d:x]", "] b [", "[c/d:", "[[y]", "]]", "] c"];
+        assert_eq!(
+            complete_pieces(&pieces, &format),
+            "a [[c/d:x]] b [[c/d:[[y]]]] c"
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use crate::DataClass;
+use crate::data_class::{BuildClassHasher, ClassKey};
+use std::collections::HashMap;
+
+/// Translates data classes of one taxonomy into data classes of another.
+///
+/// Libraries typically classify their data using a general-purpose taxonomy, such as the
+/// [core taxonomy](crate::core_taxonomy), while applications define redaction rules in terms of
+/// their own taxonomy. Registering a mapper on a [`RedactionEngineBuilder`](crate::RedactionEngineBuilder)
+/// makes the engine translate each data class before looking up its redactor, so data classified by
+/// libraries follows the application's rules.
+///
+/// Translations are applied once: a data class that's mapped to another class which itself has a
+/// mapping isn't translated further.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::core_taxonomy::CoreTaxonomy;
+/// use data_privacy::{DataClass, RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode, TaxonomyMapper};
+///
+/// let customer_content = DataClass::new("contoso", "customer_content");
+/// let mapper = TaxonomyMapper::new()
+///     .add_mapping(&CoreTaxonomy::Sensitive.data_class(), &customer_content);
+///
+/// let engine = RedactionEngineBuilder::new()
+///     .add_class_redactor(&customer_content, SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')))
+///     .set_taxonomy_mapper(mapper)
+///     .build();
+///
+/// let output = engine.redact_to_string_as_class(&CoreTaxonomy::Sensitive.data_class(), "secret");
+/// assert_eq!(output, "######");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TaxonomyMapper {
+    classes: HashMap<DataClass, DataClass, BuildClassHasher>,
+}
+
+impl TaxonomyMapper {
+    /// Creates a new mapper with no mappings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a mapping from one data class to another.
+    ///
+    /// If a mapping was already registered for the source class, it is silently replaced.
+    #[must_use]
+    pub fn add_mapping(mut self, from: &DataClass, to: &DataClass) -> Self {
+        _ = self.classes.insert(from.clone(), to.clone());
+        self
+    }
+
+    /// Returns the data class a data class translates into, if it has a mapping.
+    #[must_use]
+    pub fn translate(&self, data_class: &DataClass) -> Option<&DataClass> {
+        self.classes.get(data_class)
+    }
+
+    /// Returns the data class a data class given by its taxonomy and class names translates into, if it has a mapping.
+    #[must_use]
+    pub(crate) fn translate_parts(&self, taxonomy: &str, name: &str) -> Option<&DataClass> {
+        self.classes.get(&(taxonomy, name) as &dyn ClassKey)
+    }
+
+    /// Returns whether the mapper has no mappings.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SENSITIVE: DataClass = DataClass::new("core", "sensitive");
+    const CUSTOMER_CONTENT: DataClass = DataClass::new("contoso", "customer_content");
+    const PUBLIC: DataClass = DataClass::new("contoso", "public");
+
+    #[test]
+    fn translate_should_follow_mappings() {
+        let mapper = TaxonomyMapper::new().add_mapping(&SENSITIVE, &CUSTOMER_CONTENT);
+
+        assert_eq!(mapper.translate(&SENSITIVE), Some(&CUSTOMER_CONTENT));
+        assert_eq!(
+            mapper.translate_parts("core", "sensitive"),
+            Some(&CUSTOMER_CONTENT)
+        );
+        assert_eq!(mapper.translate(&CUSTOMER_CONTENT), None);
+        assert!(!mapper.is_empty());
+    }
+
+    #[test]
+    fn translate_should_not_be_transitive() {
+        let mapper = TaxonomyMapper::new()
+            .add_mapping(&SENSITIVE, &CUSTOMER_CONTENT)
+            .add_mapping(&CUSTOMER_CONTENT, &PUBLIC);
+
+        assert_eq!(mapper.translate(&SENSITIVE), Some(&CUSTOMER_CONTENT));
+    }
+
+    #[test]
+    fn later_mappings_should_replace_earlier_ones() {
+        let mapper = TaxonomyMapper::new()
+            .add_mapping(&SENSITIVE, &CUSTOMER_CONTENT)
+            .add_mapping(&SENSITIVE, &PUBLIC);
+
+        assert_eq!(mapper.translate(&SENSITIVE), Some(&PUBLIC));
+        assert!(TaxonomyMapper::default().is_empty());
+    }
+}
@@ -0,0 +1,92 @@
+use crate::DataClass;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Stores the mapping from tokens back to the values they replace.
+///
+/// A [`TokenizingRedactor`](crate::TokenizingRedactor) hands every token it produces to its vault, along
+/// with the data class and original value, so that authorized tooling can later re-identify the data
+/// behind a token, for example while responding to an incident. Vaults are typically backed by a store
+/// with strict access controls, separate from the telemetry holding the tokens.
+///
+/// Vaults are invoked synchronously on the thread doing the redaction, so they should be quick and
+/// must not panic. The same token is stored each time its value is redacted, so vaults should treat
+/// repeated stores of a token as a no-op.
+pub trait TokenVault: Send + Sync {
+    /// Records the value replaced by a token.
+    fn store(&self, token: &str, data_class: &DataClass, value: &str);
+}
+
+/// A token vault which keeps the mapping in memory.
+///
+/// Clones of this vault share the same storage, so a clone can be given to a redactor while the original
+/// is kept around to resolve tokens.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryTokenVault {
+    entries: Arc<Mutex<HashMap<String, (DataClass, String)>>>,
+}
+
+impl MemoryTokenVault {
+    /// Creates a new empty vault.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the data class and original value replaced by a token, if the token is known.
+    #[must_use]
+    pub fn resolve(&self, token: &str) -> Option<(DataClass, String)> {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(token)
+            .cloned()
+    }
+
+    /// Returns the number of tokens in the vault.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Returns whether the vault is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl TokenVault for MemoryTokenVault {
+    fn store(&self, token: &str, data_class: &DataClass, value: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        if !entries.contains_key(token) {
+            _ = entries.insert(token.to_owned(), (data_class.clone(), value.to_owned()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vault_should_resolve_stored_tokens() {
+        let vault = MemoryTokenVault::new();
+        let clone = vault.clone();
+        assert!(vault.is_empty());
+
+        let data_class = DataClass::new("taxonomy", "class");
+        clone.store("tok_1", &data_class, "value");
+        clone.store("tok_1", &data_class, "value");
+
+        assert_eq!(vault.len(), 1);
+        assert_eq!(
+            vault.resolve("tok_1"),
+            Some((data_class, "value".to_string()))
+        );
+        assert_eq!(vault.resolve("tok_2"), None);
+    }
+}
@@ -0,0 +1,247 @@
+use crate::{DataClass, RedactionContext, Redactor, TokenVault};
+use core::fmt::Debug;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// The number of bytes of each digest included in a token.
+const TOKEN_LEN: usize = 16;
+
+/// A redactor that replaces values with opaque, deterministic tokens, optionally recording them in a vault.
+///
+/// Tokens are made of a prefix, `tok_` by default, followed by 32 hexadecimal characters derived from an
+/// HMAC-SHA256 digest of the value. The digest is keyed with a secret pepper, which should be kept
+/// out of the telemetry and its storage, and also covers an optional salt and the data class of the
+/// value, so the same value produces different tokens in different data classes.
+///
+/// Tokens are deterministic, so redacted values can still be correlated. Unlike a plain hash, the
+/// mapping from each token back to its value can be recorded in a [`TokenVault`], which lets authorized
+/// tooling re-identify data in a controlled way.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use data_privacy::{DataClass, MemoryTokenVault, Redactor, TokenizingRedactor};
+///
+/// let vault = MemoryTokenVault::new();
+/// let redactor = TokenizingRedactor::new(b"a secret pepper").with_vault(Arc::new(vault.clone()));
+///
+/// let data_class = DataClass::new("corp", "email");
+/// let mut token = String::new();
+/// redactor.redact(&data_class, "jdoe@contoso.com", &mut |s| token.push_str(s));
+///
+/// assert!(token.starts_with("tok_"));
+/// assert_eq!(vault.resolve(&token), Some((data_class, "jdoe@contoso.com".to_string())));
+/// ```
+#[derive(Clone)]
+pub struct TokenizingRedactor {
+    mac: Hmac<Sha256>,
+    salt: Vec<u8>,
+    prefix: Cow<'static, str>,
+    vault: Option<Arc<dyn TokenVault>>,
+    context_salt: Option<Cow<'static, str>>,
+}
+
+impl TokenizingRedactor {
+    /// Creates a new instance keyed with the given secret pepper.
+    #[must_use]
+    #[expect(
+        clippy::missing_panics_doc,
+        reason = "HMAC accepts keys of any length, so this can't panic"
+    )]
+    pub fn new(pepper: impl AsRef<[u8]>) -> Self {
+        Self {
+            mac: Hmac::<Sha256>::new_from_slice(pepper.as_ref())
+                .expect("HMAC accepts keys of any length"),
+            salt: Vec::new(),
+            prefix: Cow::Borrowed("tok_"),
+            vault: None,
+            context_salt: None,
+        }
+    }
+
+    /// Sets a salt mixed into every token, so that deployments sharing a pepper produce different tokens.
+    #[must_use]
+    pub fn with_salt(mut self, salt: impl AsRef<[u8]>) -> Self {
+        self.salt = salt.as_ref().to_vec();
+        self
+    }
+
+    /// Sets the prefix of every token.
+    #[must_use]
+    pub fn with_prefix(mut self, prefix: impl Into<Cow<'static, str>>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Mixes the value of a key of the [`RedactionContext`] into every token, in addition to the salt.
+    ///
+    /// This gives each tenant its own tokens when redacting with
+    /// [`RedactionEngine::redact_with_context`](crate::RedactionEngine::redact_with_context) and the
+    /// [tenant](RedactionContext::TENANT) key, without needing one engine per tenant. Redactions whose
+    /// context doesn't hold the key produce the same tokens as without this setting.
+    #[must_use]
+    pub fn with_context_salt(mut self, key: impl Into<Cow<'static, str>>) -> Self {
+        self.context_salt = Some(key.into());
+        self
+    }
+
+    /// Sets the vault recording the value behind each token.
+    #[must_use]
+    pub fn with_vault(mut self, vault: Arc<dyn TokenVault>) -> Self {
+        self.vault = Some(vault);
+        self
+    }
+}
+
+impl Redactor for TokenizingRedactor {
+    fn redact(&self, data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
+        self.redact_ctx(data_class, value, &RedactionContext::new(), output);
+    }
+
+    fn redact_ctx(
+        &self,
+        data_class: &DataClass,
+        value: &str,
+        context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) {
+        const HEX_LOWER_CHARS: &[u8; 16] = b"0123456789abcdef";
+
+        // each variable-length part is length-prefixed, so different parts can't produce the same input
+        let mut mac = self.mac.clone();
+        for part in [
+            self.salt.as_slice(),
+            data_class.taxonomy().as_bytes(),
+            data_class.name().as_bytes(),
+        ] {
+            mac.update(&(part.len() as u64).to_le_bytes());
+            mac.update(part);
+        }
+        if let Some(salt) = self.context_salt.as_ref().and_then(|key| context.get(key)) {
+            mac.update(&(salt.len() as u64).to_le_bytes());
+            mac.update(salt.as_bytes());
+        }
+        mac.update(value.as_bytes());
+        let digest = mac.finalize().into_bytes();
+
+        let mut token = String::with_capacity(self.prefix.len() + TOKEN_LEN * 2);
+        token.push_str(&self.prefix);
+        for byte in &digest[..TOKEN_LEN] {
+            token.push(char::from(HEX_LOWER_CHARS[usize::from(byte >> 4)]));
+            token.push(char::from(HEX_LOWER_CHARS[usize::from(byte & 0x0f)]));
+        }
+
+        if let Some(vault) = &self.vault {
+            vault.store(&token, data_class, value);
+        }
+
+        output(&token);
+    }
+
+    fn exact_len(&self) -> Option<usize> {
+        Some(self.prefix.len() + TOKEN_LEN * 2)
+    }
+
+    fn reveals_input(&self) -> bool {
+        false
+    }
+}
+
+impl Debug for TokenizingRedactor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TokenizingRedactor")
+            .field("prefix", &self.prefix)
+            .field("has_vault", &self.vault.is_some())
+            .field("context_salt", &self.context_salt)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryTokenVault;
+    use crate::core_taxonomy::CoreTaxonomy;
+
+    fn redact_to_string(
+        redactor: &TokenizingRedactor,
+        data_class: &DataClass,
+        value: &str,
+    ) -> String {
+        let mut output = String::new();
+        redactor.redact(data_class, value, &mut |s| output.push_str(s));
+        output
+    }
+
+    #[test]
+    fn tokens_should_be_deterministic_and_keyed() {
+        let sensitive = CoreTaxonomy::Sensitive.data_class();
+        let insensitive = CoreTaxonomy::Insensitive.data_class();
+        let redactor = TokenizingRedactor::new(b"pepper");
+
+        let token = redact_to_string(&redactor, &sensitive, "jdoe");
+        assert_eq!(token.len(), redactor.exact_len().unwrap());
+        assert!(token.starts_with("tok_"));
+        assert_eq!(token, redact_to_string(&redactor, &sensitive, "jdoe"));
+
+        for other in [
+            redact_to_string(&redactor, &sensitive, "jdoe2"),
+            redact_to_string(&redactor, &insensitive, "jdoe"),
+            redact_to_string(&TokenizingRedactor::new(b"other"), &sensitive, "jdoe"),
+            redact_to_string(&redactor.clone().with_salt(b"salt"), &sensitive, "jdoe"),
+        ] {
+            assert_ne!(token, other);
+        }
+    }
+
+    #[test]
+    fn context_salt_should_separate_tenants() {
+        let data_class = CoreTaxonomy::Sensitive.data_class();
+        let redactor =
+            TokenizingRedactor::new(b"pepper").with_context_salt(RedactionContext::TENANT);
+        let redact_for = |context: &RedactionContext| {
+            let mut output = String::new();
+            redactor.redact_ctx(&data_class, "jdoe", context, &mut |s| output.push_str(s));
+            output
+        };
+
+        let contoso = redact_for(&RedactionContext::new().with_value("tenant", "contoso"));
+        let fabrikam = redact_for(&RedactionContext::new().with_value("tenant", "fabrikam"));
+        let untenanted = redact_for(&RedactionContext::new().with_value("region", "eu"));
+
+        assert_ne!(contoso, fabrikam);
+        assert_eq!(
+            contoso,
+            redact_for(&RedactionContext::new().with_value("tenant", "contoso"))
+        );
+        assert_eq!(untenanted, redact_to_string(&redactor, &data_class, "jdoe"));
+    }
+
+    #[test]
+    fn vault_should_allow_reidentification() {
+        let vault = MemoryTokenVault::new();
+        let redactor = TokenizingRedactor::new(b"pepper")
+            .with_prefix("t-")
+            .with_vault(Arc::new(vault.clone()));
+
+        let data_class = CoreTaxonomy::Sensitive.data_class();
+        let token = redact_to_string(&redactor, &data_class, "jdoe");
+        assert!(token.starts_with("t-"));
+        assert_eq!(
+            vault.resolve(&token),
+            Some((data_class, "jdoe".to_string()))
+        );
+    }
+
+    #[test]
+    fn debug_should_not_expose_secrets() {
+        let redactor = TokenizingRedactor::new(b"pepper").with_salt(b"salt");
+        assert_eq!(
+            format!("{redactor:?}"),
+            r#"TokenizingRedactor { prefix: "tok_", has_vault: false, context_salt: None, .. }"#
+        );
+    }
+}
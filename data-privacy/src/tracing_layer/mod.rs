@@ -0,0 +1,58 @@
+//! Redaction of classified data recorded in `tracing` events and spans.
+//!
+//! Fields recorded with `tracing` are formatted by whichever layers consume the events, long after the
+//! call site has handed its values over. To keep classified data out of those layers, wrap classified
+//! values with [`redacted`] when recording them, and wrap the consuming layers in a [`RedactionLayer`].
+//! Whenever the wrapped layer formats a field, the value is redacted with the engine of the enclosing
+//! [`RedactionLayer`].
+//!
+//! Values formatted outside of a [`RedactionLayer`] are redacted with the
+//! [current redaction engine](crate::with_current_redaction_engine), which is the global engine unless
+//! overridden on the current thread, and erases all classified data unless configured otherwise, so
+//! classified data isn't leaked by layers which were left unwrapped.
+//!
+//! This module is available with the `tracing` feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use data_privacy::core_taxonomy::{CoreTaxonomy, Sensitive};
+//! use data_privacy::tracing_layer::{RedactionLayer, redacted};
+//! use data_privacy::{RedactionEngineBuilder, SimpleRedactor};
+//! use tracing_subscriber::Layer;
+//! use tracing_subscriber::layer::SubscriberExt;
+//! use tracing_subscriber::registry::LookupSpan;
+//!
+//! fn install<S>(subscriber: S, layer: impl Layer<S> + Send + Sync) -> impl tracing::Subscriber
+//! where
+//!     S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+//! {
+//!     let engine = RedactionEngineBuilder::new()
+//!         .add_class_redactor(&CoreTaxonomy::Sensitive.data_class(), SimpleRedactor::new())
+//!         .build()
+//!         .into_shared();
+//!
+//!     subscriber.with(RedactionLayer::new(engine, layer))
+//! }
+//!
+//! let user = Sensitive::new("John");
+//! tracing::info!(user = %redacted(&user), "user logged in");
+//! ```
+
+mod redacted_value;
+mod redaction_layer;
+
+pub use redacted_value::RedactedValue;
+pub use redaction_layer::RedactionLayer;
+
+use crate::Extract;
+
+/// Wraps a value so it's redacted when recorded as a field of a `tracing` event or span.
+///
+/// The returned value implements [`Display`](core::fmt::Display) and [`Debug`](core::fmt::Debug), both
+/// of which produce the redacted form of the value, so it can be recorded with either the `%` or the `?`
+/// sigil of the `tracing` macros.
+#[must_use]
+pub const fn redacted<T: Extract + ?Sized>(value: &T) -> RedactedValue<'_, T> {
+    RedactedValue::new(value)
+}
@@ -0,0 +1,40 @@
+use crate::{Extract, RedactionEngine, with_current_redaction_engine};
+use core::fmt::{Debug, Display, Formatter};
+
+/// A value which is redacted when formatted by a layer wrapped in a [`RedactionLayer`](super::RedactionLayer).
+///
+/// You create instances of this type with [`redacted`](super::redacted).
+pub struct RedactedValue<'a, T: ?Sized> {
+    value: &'a T,
+}
+
+impl<'a, T: ?Sized> RedactedValue<'a, T> {
+    pub(super) const fn new(value: &'a T) -> Self {
+        Self { value }
+    }
+}
+
+impl<T: Extract + ?Sized> RedactedValue<'_, T> {
+    fn write(&self, engine: &RedactionEngine, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut result = Ok(());
+        engine.extract_redacted(self.value, |s| {
+            if result.is_ok() {
+                result = f.write_str(s);
+            }
+        });
+
+        result
+    }
+}
+
+impl<T: Extract + ?Sized> Display for RedactedValue<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        with_current_redaction_engine(|engine| self.write(engine, f))
+    }
+}
+
+impl<T: Extract + ?Sized> Debug for RedactedValue<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
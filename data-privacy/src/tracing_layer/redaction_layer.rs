@@ -0,0 +1,189 @@
+use crate::{RedactionEngine, with_redaction_engine};
+use core::any::TypeId;
+use std::sync::Arc;
+use tracing::level_filters::LevelFilter;
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Dispatch, Event, Metadata, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+
+/// A layer which redacts the [`redacted`](super::redacted) values recorded in the events and spans it
+/// passes to its inner layer.
+///
+/// The inner layer is typically one which formats events, such as the `fmt` layer of `tracing-subscriber`.
+/// Every call made to the inner layer while it may format field values runs with this layer's engine as
+/// the current engine, so that the values are redacted with it.
+///
+/// See the [module documentation](super) for an example.
+#[derive(Debug)]
+pub struct RedactionLayer<L> {
+    engine: Arc<RedactionEngine>,
+    inner: L,
+}
+
+impl<L> RedactionLayer<L> {
+    /// Creates a new layer which redacts the values formatted by the given layer.
+    #[must_use]
+    pub const fn new(engine: Arc<RedactionEngine>, inner: L) -> Self {
+        Self { engine, inner }
+    }
+
+    /// Returns the engine used by this layer.
+    #[must_use]
+    pub const fn engine(&self) -> &Arc<RedactionEngine> {
+        &self.engine
+    }
+
+    /// Returns the inner layer.
+    #[must_use]
+    pub const fn inner(&self) -> &L {
+        &self.inner
+    }
+}
+
+impl<S, L> Layer<S> for RedactionLayer<L>
+where
+    S: Subscriber,
+    L: Layer<S>,
+{
+    fn on_register_dispatch(&self, subscriber: &Dispatch) {
+        self.inner.on_register_dispatch(subscriber);
+    }
+
+    fn on_layer(&mut self, subscriber: &mut S) {
+        self.inner.on_layer(subscriber);
+    }
+
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        self.inner.register_callsite(metadata)
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, ctx: Context<'_, S>) -> bool {
+        self.inner.enabled(metadata, ctx)
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        with_redaction_engine(&self.engine, || self.inner.on_new_span(attrs, id, ctx));
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        self.inner.max_level_hint()
+    }
+
+    fn on_record(&self, span: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        with_redaction_engine(&self.engine, || self.inner.on_record(span, values, ctx));
+    }
+
+    fn on_follows_from(&self, span: &Id, follows: &Id, ctx: Context<'_, S>) {
+        self.inner.on_follows_from(span, follows, ctx);
+    }
+
+    fn event_enabled(&self, event: &Event<'_>, ctx: Context<'_, S>) -> bool {
+        with_redaction_engine(&self.engine, || self.inner.event_enabled(event, ctx))
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        with_redaction_engine(&self.engine, || self.inner.on_event(event, ctx));
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_enter(id, ctx);
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        self.inner.on_exit(id, ctx);
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        self.inner.on_close(id, ctx);
+    }
+
+    fn on_id_change(&self, old: &Id, new: &Id, ctx: Context<'_, S>) {
+        self.inner.on_id_change(old, new, ctx);
+    }
+
+    unsafe fn downcast_raw(&self, id: TypeId) -> Option<*const ()> {
+        if id == TypeId::of::<Self>() {
+            Some(core::ptr::from_ref(self).cast())
+        } else {
+            // SAFETY: the inner layer upholds the same contract as this method.
+            unsafe { self.inner.downcast_raw(id) }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::{CoreTaxonomy, Sensitive};
+    use crate::tracing_layer::redacted;
+    use crate::{RedactionEngineBuilder, SimpleRedactor, SimpleRedactorMode};
+    use core::fmt::Debug;
+    use std::sync::Mutex;
+    use tracing::field::{Field, Visit};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[derive(Default)]
+    struct Recorder {
+        fields: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Visit for &Recorder {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.fields
+                .lock()
+                .unwrap()
+                .push(format!("{}={value:?}", field.name()));
+        }
+    }
+
+    impl<S: Subscriber> Layer<S> for Recorder {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+            attrs.record(&mut &*self);
+        }
+
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            event.record(&mut &*self);
+        }
+    }
+
+    fn create_engine() -> Arc<RedactionEngine> {
+        RedactionEngineBuilder::new()
+            .add_class_redactor(
+                &CoreTaxonomy::Sensitive.data_class(),
+                SimpleRedactor::with_mode(SimpleRedactorMode::Replace('#')),
+            )
+            .build()
+            .into_shared()
+    }
+
+    #[test]
+    fn layer_should_redact_wrapped_values() {
+        let recorder = Recorder::default();
+        let fields = Arc::clone(&recorder.fields);
+        let subscriber =
+            tracing_subscriber::registry().with(RedactionLayer::new(create_engine(), recorder));
+
+        let user = Sensitive::new("John");
+        tracing::subscriber::with_default(subscriber, || {
+            let _span = tracing::info_span!("request", user = ?redacted(&user)).entered();
+            tracing::info!(user = %redacted(&user), id = 42, "user logged in");
+        });
+
+        let fields = fields.lock().unwrap().clone();
+        assert_eq!(
+            fields,
+            ["user=####", "message=user logged in", "user=####", "id=42"]
+        );
+    }
+
+    #[test]
+    fn values_should_use_global_engine_outside_of_layer() {
+        // the global engine may or may not be installed by another test, but never passes data through
+        let user = Sensitive::new("John");
+        let text = redacted(&user).to_string();
+        assert!(!text.contains("John"));
+        assert_eq!(format!("{:?}", redacted(&user)), text);
+    }
+}
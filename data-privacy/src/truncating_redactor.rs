@@ -0,0 +1,74 @@
+use crate::{DataClass, Redactor};
+
+/// A redactor which keeps at most a given number of leading characters of a string.
+///
+/// Truncation bounds the size of values, but passes short values through unchanged, so it doesn't hide
+/// anything on its own. It's meant to be the first stage of a [`ChainedRedactor`](crate::ChainedRedactor),
+/// such as to truncate long values before hashing them.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::{DataClass, Redactor, TruncatingRedactor};
+///
+/// let redactor = TruncatingRedactor::new(4);
+///
+/// let mut output = String::new();
+/// redactor.redact(&DataClass::new("corp", "note"), "a long note", &mut |s| output.push_str(s));
+/// assert_eq!(output, "a lo");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TruncatingRedactor {
+    max_chars: usize,
+}
+
+impl TruncatingRedactor {
+    /// Creates a new instance which keeps at most `max_chars` characters.
+    #[must_use]
+    pub const fn new(max_chars: usize) -> Self {
+        Self { max_chars }
+    }
+}
+
+impl Redactor for TruncatingRedactor {
+    #[expect(clippy::string_slice, reason = "The index is at a character boundary")]
+    fn redact(&self, _data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
+        let end = value
+            .char_indices()
+            .nth(self.max_chars)
+            .map_or(value.len(), |(index, _)| index);
+
+        output(&value[..end]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core_taxonomy::CoreTaxonomy;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::String;
+
+    fn redact_to_string(redactor: &TruncatingRedactor, value: &str) -> String {
+        let mut output = String::new();
+        redactor.redact(&CoreTaxonomy::Sensitive.data_class(), value, &mut |s| {
+            output.push_str(s);
+        });
+        output
+    }
+
+    #[test]
+    fn redact_should_keep_leading_characters() {
+        let redactor = TruncatingRedactor::new(3);
+        assert_eq!(redact_to_string(&redactor, "secret"), "sec");
+        assert_eq!(redact_to_string(&redactor, "abc"), "abc");
+        assert_eq!(redact_to_string(&redactor, "ab"), "ab");
+        assert_eq!(redact_to_string(&TruncatingRedactor::new(0), "ab"), "");
+    }
+
+    #[test]
+    fn redact_should_handle_multibyte_characters() {
+        let redactor = TruncatingRedactor::new(2);
+        assert_eq!(redact_to_string(&redactor, "こんにちは"), "こん");
+    }
+}
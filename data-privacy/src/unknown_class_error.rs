@@ -0,0 +1,25 @@
+use core::fmt::Display;
+
+/// The error returned when parsing a taxonomy's data class from a name which doesn't belong to it.
+///
+/// Enums annotated with the [`taxonomy`](crate::taxonomy) attribute implement [`FromStr`](core::str::FromStr),
+/// accepting names of the form `taxonomy/class`. This error is returned for any other name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct UnknownClassError;
+
+impl UnknownClassError {
+    /// Creates a new instance.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Display for UnknownClassError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("unknown data class, expected `taxonomy/class` naming a class of the taxonomy")
+    }
+}
+
+impl core::error::Error for UnknownClassError {}
@@ -0,0 +1,120 @@
+use crate::DataClass;
+use crate::Redactor;
+use uuid::Uuid;
+use uuid::fmt::Hyphenated;
+
+/// A redactor that replaces the original string with a deterministic UUID.
+///
+/// Each value is mapped to a version 5 (SHA-1 based) UUID within a configurable namespace, so a given
+/// value always produces the same identifier. This is useful when redacted values flow into
+/// downstream systems that expect UUID-shaped identifiers.
+///
+/// Note that version 5 UUIDs aren't keyed, so anyone who knows the namespace can confirm a guess of the
+/// original value. Treat the namespace as a secret if that matters for the data being redacted.
+#[derive(Clone, Debug)]
+pub struct UuidRedactor {
+    namespace: Uuid,
+    per_class_namespaces: bool,
+}
+
+impl UuidRedactor {
+    /// Creates a new instance which generates identifiers within the given namespace.
+    #[must_use]
+    pub const fn with_namespace(namespace: [u8; 16]) -> Self {
+        Self {
+            namespace: Uuid::from_bytes(namespace),
+            per_class_namespaces: false,
+        }
+    }
+
+    /// Derives a distinct namespace for each data class from the configured namespace.
+    ///
+    /// As a result, identical values redacted as different data classes produce unrelated identifiers.
+    #[must_use]
+    pub const fn with_per_class_namespaces(mut self) -> Self {
+        self.per_class_namespaces = true;
+        self
+    }
+}
+
+impl Redactor for UuidRedactor {
+    fn redact(&self, data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
+        let namespace = if self.per_class_namespaces {
+            Uuid::new_v5(&self.namespace, data_class.to_string().as_bytes())
+        } else {
+            self.namespace
+        };
+
+        let mut buffer = [0u8; Hyphenated::LENGTH];
+        output(
+            Uuid::new_v5(&namespace, value.as_bytes())
+                .hyphenated()
+                .encode_lower(&mut buffer),
+        );
+    }
+
+    fn exact_len(&self) -> Option<usize> {
+        Some(Hyphenated::LENGTH)
+    }
+
+    fn reveals_input(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAMESPACE: [u8; 16] = [
+        0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30,
+        0xc8,
+    ];
+
+    fn redact_to_string(redactor: &UuidRedactor, data_class: &DataClass, value: &str) -> String {
+        let mut output = String::new();
+        redactor.redact(data_class, value, &mut |s| output.push_str(s));
+        output
+    }
+
+    #[test]
+    fn redact_should_produce_uuid_v5() {
+        let redactor = UuidRedactor::with_namespace(NAMESPACE);
+        let data_class = DataClass::new("test_taxonomy", "test_class");
+
+        // The namespace is the well-known DNS namespace, so this matches the standard UUIDv5 of "python.org".
+        let output = redact_to_string(&redactor, &data_class, "python.org");
+        assert_eq!(output, "886313e1-3b8a-5372-9b90-0c9aee199e5d");
+        assert_eq!(Some(output.len()), redactor.exact_len());
+    }
+
+    #[test]
+    fn redact_should_be_deterministic() {
+        let redactor = UuidRedactor::with_namespace(NAMESPACE);
+        let data_class = DataClass::new("test_taxonomy", "test_class");
+
+        let output1 = redact_to_string(&redactor, &data_class, "value");
+        let output2 = redact_to_string(&redactor, &data_class, "value");
+        let output3 = redact_to_string(&redactor, &data_class, "other");
+
+        assert_eq!(output1, output2);
+        assert_ne!(output1, output3);
+    }
+
+    #[test]
+    fn per_class_namespaces_should_separate_data_classes() {
+        let plain = UuidRedactor::with_namespace(NAMESPACE);
+        let redactor = UuidRedactor::with_namespace(NAMESPACE).with_per_class_namespaces();
+        let data_class1 = DataClass::new("test_taxonomy", "class1");
+        let data_class2 = DataClass::new("test_taxonomy", "class2");
+
+        assert_eq!(
+            redact_to_string(&plain, &data_class1, "value"),
+            redact_to_string(&plain, &data_class2, "value")
+        );
+        assert_ne!(
+            redact_to_string(&redactor, &data_class1, "value"),
+            redact_to_string(&redactor, &data_class2, "value")
+        );
+    }
+}
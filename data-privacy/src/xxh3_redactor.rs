@@ -1,21 +1,69 @@
-use crate::DataClass;
-use crate::Redactor;
-use xxhash_rust::xxh3::xxh3_64_with_secret;
-
-const REDACTED_LEN: usize = 16;
+use crate::hash_encoding::MAX_ENCODED_LEN;
+use crate::streaming_redactor::read_chunks;
+use crate::{DataClass, HashEncoding, RedactionContext};
+use crate::{Redactor, StreamingRedactor};
+use core::fmt::Debug;
+use hkdf::Hkdf;
+use sha2::Sha256;
+use std::borrow::Cow;
+use std::io::{self, Read};
+use xxhash_rust::xxh3::{Xxh3, xxh3_64_with_secret, xxh3_128_with_secret};
 
 /// A redactor that replaces the original string with the xxH3 hash of the string.
+///
+/// By default, the same secret is used for every data class, so a given value produces the same
+/// hash no matter how it is classified. Use [`with_per_class_keys`](Self::with_per_class_keys) to derive
+/// a distinct secret for each data class instead.
+///
+/// By default, the output is a 64-bit hash in lowercase hexadecimal, as in `8f0c6e3b1a9d2c47`. The output
+/// can be configured to trade log size against the risk of collisions between distinct values:
+///
+/// * [`with_128_bit_output`](Self::with_128_bit_output) produces 128-bit hashes, making collisions
+///   negligible even across billions of values.
+/// * [`with_encoding`](Self::with_encoding) encodes hashes more densely, in base32 or base64.
+/// * [`with_truncation`](Self::with_truncation) keeps only the beginning of the encoded hash.
+/// * [`with_prefix`](Self::with_prefix) starts the output with a fixed string, such as `xxh3:`, which
+///   identifies hashes in logs.
+///
+/// # Example
+///
+/// ```rust
+/// use data_privacy::{DataClass, HashEncoding, Redactor, xxH3Redactor};
+///
+/// let redactor = xxH3Redactor::with_secret([7u8; 192])
+///     .with_128_bit_output()
+///     .with_encoding(HashEncoding::Base64)
+///     .with_prefix("xxh3:");
+///
+/// let mut output = String::new();
+/// redactor.redact(&DataClass::new("corp", "email"), "john@example.com", &mut |s| output.push_str(s));
+/// assert!(output.starts_with("xxh3:"));
+/// assert_eq!(output.len(), redactor.exact_len().unwrap());
+/// ```
+///
+/// Large values can be hashed incrementally using the [`StreamingRedactor`] trait. This is only truly
+/// incremental with a secret of the recommended length of 192 bytes, so
+/// [`as_streaming`](Redactor::as_streaming) only returns the redactor for that length. When
+/// [`StreamingRedactor::redact_reader`] is called directly with a secret of another length, the value is
+/// read into memory before being hashed.
+#[derive(Clone)]
 #[expect(
     non_camel_case_types,
     reason = "Just following the naming conventions of xxHash, silly as they are"
 )]
-#[derive(Clone, Debug)]
 pub struct xxH3Redactor {
     secret: Box<[u8]>,
+    per_class_keys: bool,
+    wide: bool,
+    encoding: HashEncoding,
+    truncation: Option<usize>,
+    prefix: Cow<'static, str>,
 }
 
 const MIN_SECRET_LENGTH: usize = 136;
 const MAX_SECRET_LENGTH: usize = 256;
+const STREAMING_SECRET_LENGTH: usize = 192;
+const MIN_TRUNCATION: usize = 4;
 
 impl xxH3Redactor {
     /// Creates a new instance with a custom secret.
@@ -36,41 +84,206 @@ impl xxH3Redactor {
 
         Self {
             secret: Box::from(secret.as_ref()),
+            per_class_keys: false,
+            wide: false,
+            encoding: HashEncoding::Hex,
+            truncation: None,
+            prefix: Cow::Borrowed(""),
         }
     }
+
+    /// Derives a distinct secret for each data class from the base secret.
+    ///
+    /// Each class's secret is derived using HKDF-SHA256 over the base secret, with the
+    /// class's `taxonomy/name` identity as context. As a result, identical values redacted as
+    /// different data classes produce unrelated hashes, which prevents linking the values across
+    /// classes. For example, an email address used both as a user name and as contact information
+    /// isn't correlatable between the two uses.
+    ///
+    /// Secrets are derived for each value rather than cached, so memory use doesn't grow with the number of
+    /// data classes redacted, which may be chosen by whoever controls the input, as with
+    /// [`RedactionEngine::redact_tags`](crate::RedactionEngine::redact_tags).
+    #[must_use]
+    pub const fn with_per_class_keys(mut self) -> Self {
+        self.per_class_keys = true;
+        self
+    }
+
+    /// Produces 128-bit hashes rather than 64-bit ones.
+    ///
+    /// This doubles the length of the output, and makes collisions between distinct values negligible.
+    #[must_use]
+    pub const fn with_128_bit_output(mut self) -> Self {
+        self.wide = true;
+        self
+    }
+
+    /// Sets the encoding of the hashes, which is [`HashEncoding::Hex`] by default.
+    #[must_use]
+    pub const fn with_encoding(mut self, encoding: HashEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Keeps only the first `len` characters of the encoded hashes.
+    ///
+    /// Shorter hashes reduce the size of logs, at the cost of a greater risk of collisions. Each
+    /// character kept carries 4, 5, or 6 bits of the hash, depending on the encoding. Truncating to
+    /// more characters than the encoded hash holds has no effect.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is less than 4, as such short hashes collide too often to tell values apart.
+    #[must_use]
+    pub const fn with_truncation(mut self, len: usize) -> Self {
+        assert!(
+            len >= MIN_TRUNCATION,
+            "Hashes can't be truncated to fewer than 4 characters"
+        );
+        self.truncation = Some(len);
+        self
+    }
+
+    /// Starts the output with the given prefix, such as `xxh3:`.
+    #[must_use]
+    pub fn with_prefix(mut self, prefix: impl Into<Cow<'static, str>>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Returns the length of the encoded hash, after truncation.
+    fn encoded_len(&self) -> usize {
+        let len = self.encoding.encoded_len(if self.wide { 16 } else { 8 });
+        self.truncation
+            .map_or(len, |truncation| len.min(truncation))
+    }
+
+    /// Sends the prefix and the encoded hash to the output.
+    fn output_hash(&self, hash: u128, output: &mut dyn FnMut(&str)) {
+        let bytes = hash.to_be_bytes();
+        let bytes = if self.wide { &bytes[..] } else { &bytes[8..] };
+
+        let mut buffer = [0; MAX_ENCODED_LEN];
+        _ = self.encoding.encode(bytes, &mut buffer);
+
+        // SAFETY: The buffer is guaranteed to be valid UTF-8 because encodings only produce ASCII characters.
+        let encoded = unsafe { core::str::from_utf8_unchecked(&buffer[..self.encoded_len()]) };
+        if !self.prefix.is_empty() {
+            output(&self.prefix);
+        }
+
+        output(encoded);
+    }
+
+    fn digest(&self, value: &[u8], secret: &[u8]) -> u128 {
+        if self.wide {
+            xxh3_128_with_secret(value, secret)
+        } else {
+            u128::from(xxh3_64_with_secret(value, secret))
+        }
+    }
+
+    fn hash(&self, data_class: &DataClass, value: &[u8]) -> u128 {
+        self.digest(value, &self.class_secret(data_class))
+    }
+
+    /// Returns the secret used for the given data class, which is only copied when derived for the class.
+    fn class_secret(&self, data_class: &DataClass) -> Cow<'_, [u8]> {
+        if self.per_class_keys {
+            Cow::Owned(derive_class_secret(&self.secret, data_class))
+        } else {
+            Cow::Borrowed(&self.secret)
+        }
+    }
+
+    fn hash_reader(&self, data_class: &DataClass, reader: &mut dyn Read) -> io::Result<u128> {
+        let secret = self.class_secret(data_class);
+        let Ok(secret) = <[u8; STREAMING_SECRET_LENGTH]>::try_from(&*secret) else {
+            let mut value = Vec::new();
+            _ = reader.read_to_end(&mut value)?;
+            return Ok(self.digest(&value, &secret));
+        };
+
+        let mut hasher = Xxh3::with_secret(secret);
+        read_chunks(reader, |chunk| hasher.update(chunk))?;
+        Ok(if self.wide {
+            hasher.digest128()
+        } else {
+            u128::from(hasher.digest())
+        })
+    }
+}
+
+/// Derives a secret of the same length as the base secret, specific to the given data class.
+fn derive_class_secret(secret: &[u8], data_class: &DataClass) -> Vec<u8> {
+    let info = format!("{data_class}");
+    let mut derived = vec![0u8; secret.len()];
+
+    Hkdf::<Sha256>::new(None, secret)
+        .expand(info.as_bytes(), &mut derived)
+        .expect("secret length is well within HKDF-SHA256's output limit");
+
+    derived
+}
+
+impl Debug for xxH3Redactor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("xxH3Redactor")
+            .field("per_class_keys", &self.per_class_keys)
+            .field("wide", &self.wide)
+            .field("encoding", &self.encoding)
+            .field("truncation", &self.truncation)
+            .field("prefix", &self.prefix)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Redactor for xxH3Redactor {
-    fn redact(&self, _: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
-        let hash = xxh3_64_with_secret(value.as_bytes(), &self.secret);
-        let buffer = u64_to_hex_array(hash);
+    fn redact(&self, data_class: &DataClass, value: &str, output: &mut dyn FnMut(&str)) {
+        self.output_hash(self.hash(data_class, value.as_bytes()), output);
+    }
 
-        // SAFETY: The buffer is guaranteed to be valid UTF-8 because it only contains hex digits.
-        output(unsafe { core::str::from_utf8_unchecked(&buffer) });
+    fn redact_bytes(
+        &self,
+        data_class: &DataClass,
+        value: &[u8],
+        _context: &RedactionContext,
+        output: &mut dyn FnMut(&str),
+    ) {
+        self.output_hash(self.hash(data_class, value), output);
     }
 
     fn exact_len(&self) -> Option<usize> {
-        Some(REDACTED_LEN)
+        Some(self.prefix.len() + self.encoded_len())
     }
-}
 
-#[inline]
-fn u64_to_hex_array(mut value: u64) -> [u8; 16] {
-    static HEX_LOWER_CHARS: &[u8; 16] = b"0123456789abcdef";
+    fn as_streaming(&self) -> Option<&dyn StreamingRedactor> {
+        (self.secret.len() == STREAMING_SECRET_LENGTH).then_some(self)
+    }
 
-    let mut buffer = [0u8; REDACTED_LEN];
-    for e in buffer.iter_mut().rev() {
-        *e = HEX_LOWER_CHARS[(value & 0x0f) as usize];
-        value >>= 4;
+    fn reveals_input(&self) -> bool {
+        false
     }
+}
 
-    buffer
+impl StreamingRedactor for xxH3Redactor {
+    fn redact_reader(
+        &self,
+        data_class: &DataClass,
+        reader: &mut dyn Read,
+        output: &mut dyn FnMut(&str),
+    ) -> io::Result<()> {
+        self.output_hash(self.hash_reader(data_class, reader)?, output);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const REDACTED_LEN: usize = 16;
+
     fn get_test_redactor() -> xxH3Redactor {
         let mut secret: Vec<u8> = vec![0; 192];
         for i in 0u8..192u8 {
@@ -184,18 +397,82 @@ mod tests {
     }
 
     #[test]
-    fn test_u64_to_hex_array() {
-        let result = u64_to_hex_array(0x1234_5678_9abc_def0);
-        let expected = b"123456789abcdef0";
-        assert_eq!(result, *expected);
+    fn test_redact_bytes_hashes_raw_bytes() {
+        let redactor = get_test_redactor();
+        let data_class = DataClass::new("test_taxonomy", "test_class");
+
+        let mut text = String::new();
+        redactor.redact(&data_class, "abc", &mut |s| text.push_str(s));
+
+        let mut bytes = String::new();
+        redactor.redact_bytes(&data_class, b"abc", &RedactionContext::new(), &mut |s| {
+            bytes.push_str(s);
+        });
+        assert_eq!(bytes, text);
+
+        let mut binary = String::new();
+        redactor.redact_bytes(
+            &data_class,
+            &[0xff, 0x00],
+            &RedactionContext::new(),
+            &mut |s| {
+                binary.push_str(s);
+            },
+        );
+        assert_eq!(binary.len(), REDACTED_LEN);
+        assert_ne!(binary, bytes);
+    }
+
+    #[test]
+    fn test_output_options() {
+        let data_class = DataClass::new("test_taxonomy", "test_class");
+        let redact = |redactor: &xxH3Redactor| {
+            let mut output = String::new();
+            redactor.redact(&data_class, "test_input", &mut |s| output.push_str(s));
+            assert_eq!(Some(output.len()), redactor.exact_len());
+            output
+        };
+
+        let narrow = redact(&get_test_redactor());
+        let wide = redact(&get_test_redactor().with_128_bit_output());
+        assert_eq!(wide.len(), 32);
+        assert!(wide.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(wide.as_bytes()[16..], *narrow.as_bytes());
+
+        for (encoding, len) in [(HashEncoding::Base32, 13), (HashEncoding::Base64, 11)] {
+            let output = redact(&get_test_redactor().with_encoding(encoding));
+            assert_eq!(output.len(), len);
+            assert!(
+                output
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            );
+        }
 
-        let result = u64_to_hex_array(0);
-        let expected = b"0000000000000000";
-        assert_eq!(result, *expected);
+        let truncated = redact(&get_test_redactor().with_truncation(8));
+        assert_eq!(truncated.as_bytes(), &narrow.as_bytes()[..8]);
+        assert_eq!(redact(&get_test_redactor().with_truncation(100)), narrow);
 
-        let result = u64_to_hex_array(u64::MAX);
-        let expected = b"ffffffffffffffff";
-        assert_eq!(result, *expected);
+        let prefixed = get_test_redactor().with_prefix("xxh3:").with_truncation(4);
+        assert_eq!(
+            redact(&prefixed),
+            format!("xxh3:{}", narrow.get(..4).unwrap())
+        );
+        assert_eq!(redact(&prefixed.clone()), redact(&prefixed));
+
+        let streamed = get_test_redactor()
+            .with_128_bit_output()
+            .with_encoding(HashEncoding::Base32)
+            .with_prefix("h:");
+        let (direct, reader) = redact_both_ways(&streamed, &data_class, "test_input");
+        assert_eq!(direct, reader);
+        assert_eq!(direct.len(), 28);
+    }
+
+    #[test]
+    #[should_panic(expected = "fewer than 4 characters")]
+    fn test_short_truncation_panics() {
+        _ = get_test_redactor().with_truncation(3);
     }
 
     #[test]
@@ -248,4 +525,143 @@ mod tests {
         // The data_class parameter is ignored in the redaction process
         assert_eq!(output1, output2);
     }
+
+    #[test]
+    fn test_per_class_keys_separate_data_classes() {
+        let redactor = get_test_redactor().with_per_class_keys();
+        let data_class1 = DataClass::new("test_taxonomy", "class1");
+        let data_class2 = DataClass::new("test_taxonomy", "class2");
+        let input = "john@example.com";
+
+        let mut output1 = String::new();
+        let mut output1_again = String::new();
+        let mut output2 = String::new();
+
+        redactor.redact(&data_class1, input, &mut |s| output1.push_str(s));
+        redactor.redact(&data_class1, input, &mut |s| output1_again.push_str(s));
+        redactor.redact(&data_class2, input, &mut |s| output2.push_str(s));
+
+        assert_eq!(output1, output1_again);
+        assert_ne!(output1, output2);
+        assert_eq!(output2.len(), REDACTED_LEN);
+    }
+
+    #[test]
+    fn test_per_class_keys_differ_from_base_secret() {
+        let plain = get_test_redactor();
+        let derived = get_test_redactor().with_per_class_keys();
+        let data_class = DataClass::new("test_taxonomy", "class1");
+
+        let mut output1 = String::new();
+        let mut output2 = String::new();
+
+        plain.redact(&data_class, "input", &mut |s| output1.push_str(s));
+        derived.redact(&data_class, "input", &mut |s| output2.push_str(s));
+
+        assert_ne!(output1, output2);
+    }
+
+    #[test]
+    fn test_per_class_keys_survive_clone() {
+        let original = get_test_redactor().with_per_class_keys();
+        let cloned = original.clone();
+        let data_class = DataClass::new("test_taxonomy", "class1");
+
+        let mut output1 = String::new();
+        let mut output2 = String::new();
+
+        original.redact(&data_class, "input", &mut |s| output1.push_str(s));
+        cloned.redact(&data_class, "input", &mut |s| output2.push_str(s));
+
+        assert_eq!(output1, output2);
+        assert!(format!("{cloned:?}").contains("per_class_keys: true"));
+    }
+
+    fn redact_both_ways(
+        redactor: &xxH3Redactor,
+        data_class: &DataClass,
+        input: &str,
+    ) -> (String, String) {
+        let mut direct = String::new();
+        redactor.redact(data_class, input, &mut |s| direct.push_str(s));
+
+        let mut streamed = String::new();
+        redactor
+            .redact_reader(data_class, &mut input.as_bytes(), &mut |s| {
+                streamed.push_str(s);
+            })
+            .unwrap();
+
+        (direct, streamed)
+    }
+
+    #[test]
+    fn test_redact_reader_matches_redact() {
+        let data_class = DataClass::new("test_taxonomy", "class1");
+        let large = "0123456789abcdef".repeat(4096);
+
+        for redactor in [
+            get_test_redactor(),
+            get_test_redactor().with_per_class_keys(),
+            xxH3Redactor::with_secret(vec![0x42u8; 136]),
+        ] {
+            for input in ["", "short", &large] {
+                let (direct, streamed) = redact_both_ways(&redactor, &data_class, input);
+                assert_eq!(direct, streamed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_only_streaming_secrets_stream() {
+        assert!(get_test_redactor().as_streaming().is_some());
+        assert!(
+            get_test_redactor()
+                .with_per_class_keys()
+                .as_streaming()
+                .is_some()
+        );
+        assert!(
+            xxH3Redactor::with_secret(vec![0x42u8; 136])
+                .as_streaming()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_redact_reader_accepts_chunks() {
+        let redactor = get_test_redactor();
+        let data_class = DataClass::new("test_taxonomy", "class1");
+
+        let mut chained = b"hello ".chain(&b"world"[..]);
+        let mut streamed = String::new();
+        redactor
+            .redact_reader(&data_class, &mut chained, &mut |s| streamed.push_str(s))
+            .unwrap();
+
+        let (direct, _) = redact_both_ways(&redactor, &data_class, "hello world");
+        assert_eq!(direct, streamed);
+    }
+
+    #[test]
+    fn test_redact_reader_reports_errors() {
+        struct FailingReader;
+
+        impl Read for FailingReader {
+            fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("boom"))
+            }
+        }
+
+        let redactor = get_test_redactor();
+        let mut called = false;
+        let result = redactor.redact_reader(
+            &DataClass::new("test_taxonomy", "class1"),
+            &mut FailingReader,
+            &mut |_| called = true,
+        );
+
+        assert!(result.is_err());
+        assert!(!called);
+    }
 }
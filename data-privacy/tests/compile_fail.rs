@@ -0,0 +1,7 @@
+//! Checks the diagnostics produced by the `taxonomy` attribute for invalid taxonomies.
+
+#[test]
+fn taxonomy_rejections() {
+    let tests = trybuild::TestCases::new();
+    tests.compile_fail("tests/ui/taxonomy/*.rs");
+}
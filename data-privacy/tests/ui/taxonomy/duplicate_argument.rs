@@ -0,0 +1,8 @@
+use data_privacy::taxonomy;
+
+#[taxonomy(corp, serde = false, serde = true)]
+enum Taxonomy {
+    Email,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use data_privacy::taxonomy;
+
+#[taxonomy(corp, serde = false)]
+enum Taxonomy {
+    #[class(level = 1)]
+    #[class(level = 2)]
+    Email,
+}
+
+fn main() {}
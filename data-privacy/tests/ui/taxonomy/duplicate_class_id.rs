@@ -0,0 +1,11 @@
+use data_privacy::taxonomy;
+
+#[taxonomy(corp, serde = false)]
+enum Taxonomy {
+    #[class(id = 3)]
+    Email,
+    #[class(id = 3)]
+    Phone,
+}
+
+fn main() {}
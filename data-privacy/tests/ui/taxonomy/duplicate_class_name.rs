@@ -0,0 +1,13 @@
+use data_privacy::taxonomy;
+
+#[taxonomy(corp, serde = false)]
+enum Taxonomy {
+    #[class(name = "phone")]
+    Email,
+    Phone,
+    PhoneNumber,
+    #[class(name = "phone_number")]
+    Mobile,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use data_privacy::core_taxonomy::CoreTaxonomy;
+use data_privacy::taxonomy;
+
+#[taxonomy(core, serde = false, extends(CoreTaxonomy))]
+enum Taxonomy {
+    Sensitive,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use data_privacy::taxonomy;
+
+#[taxonomy(corp, zeroize = true, generic = true)]
+enum Secrets {
+    Password,
+}
+
+#[taxonomy(corp, generic = true)]
+enum Taxonomy {
+    #[class(upcast = data_privacy::core_taxonomy::Sensitive)]
+    Email,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use data_privacy::taxonomy;
+
+#[taxonomy(corp, serde = false)]
+enum Taxonomy<T> {
+    Email,
+    Phone(T),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use data_privacy::taxonomy;
+
+#[taxonomy(corp, serde = false)]
+enum Taxonomy {
+    #[class(level = 256)]
+    Email,
+    #[class(level = "high")]
+    Phone,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use data_privacy::taxonomy;
+
+#[taxonomy("corp")]
+enum Taxonomy {
+    Email,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use data_privacy::taxonomy;
+
+#[taxonomy(corp, serde = false)]
+enum Taxonomy {
+    #[class(retention_days = -1)]
+    Email,
+}
+
+fn main() {}
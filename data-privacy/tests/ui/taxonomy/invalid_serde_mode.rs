@@ -0,0 +1,8 @@
+use data_privacy::taxonomy;
+
+#[taxonomy(corp, serde = "both")]
+enum Taxonomy {
+    Email,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use data_privacy::taxonomy;
+
+#[taxonomy]
+enum Taxonomy {
+    Email,
+}
+
+fn main() {}
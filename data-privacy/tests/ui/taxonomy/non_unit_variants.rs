@@ -0,0 +1,10 @@
+use data_privacy::taxonomy;
+
+#[taxonomy(corp, serde = false)]
+enum Taxonomy {
+    Email(String),
+    Phone,
+    Address { street: String },
+}
+
+fn main() {}
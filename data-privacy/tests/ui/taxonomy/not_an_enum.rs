@@ -0,0 +1,8 @@
+use data_privacy::taxonomy;
+
+#[taxonomy(corp, serde = false)]
+struct Taxonomy {
+    email: String,
+}
+
+fn main() {}
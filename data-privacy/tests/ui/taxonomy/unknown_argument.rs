@@ -0,0 +1,8 @@
+use data_privacy::taxonomy;
+
+#[taxonomy(corp, verbose = true)]
+enum Taxonomy {
+    Email,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use data_privacy::taxonomy;
+
+#[taxonomy(corp, serde = false)]
+enum Taxonomy {
+    #[class(sensitivity = 3)]
+    Email,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use data_privacy::taxonomy;
+
+#[taxonomy(corp, serde = false)]
+enum Taxonomy {
+    #[class(never_log, upcast = "data_privacy::core_taxonomy::Sensitive")]
+    Password,
+}
+
+fn main() {}
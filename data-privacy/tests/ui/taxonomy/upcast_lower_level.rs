@@ -0,0 +1,11 @@
+use data_privacy::taxonomy;
+
+#[taxonomy(corp, serde = false)]
+enum Taxonomy {
+    #[class(level = 2, upcast = Internal)]
+    Email,
+    #[class(level = 1)]
+    Internal,
+}
+
+fn main() {}